@@ -0,0 +1,110 @@
+// This build script generates the tables used by this crate from the UCD in
+// the directory given by the RUCD_UCD_DIR environment variable. If it isn't
+// set, then the small excerpt of the UCD in ucd-fixture is used instead,
+// which only contains the handful of codepoints that this crate's tests
+// look up.
+
+extern crate ucd_generate;
+extern crate ucd_parse;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use ucd_generate::{Result, WriterBuilder};
+use ucd_generate::general_category;
+use ucd_parse::{CoreProperty, UnicodeData};
+
+macro_rules! eprintln {
+    ($($tt:tt)*) => {{
+        writeln!(&mut io::stderr(), $($tt)*).unwrap();
+    }}
+}
+
+/// The canonical name of every General_Category value, in the order of the
+/// `RUCD_GC_*` constants in `include/rucd.h`.
+const GENERAL_CATEGORIES: &'static [&'static str] = &[
+    "Uppercase_Letter", "Lowercase_Letter", "Titlecase_Letter",
+    "Modifier_Letter", "Other_Letter",
+    "Nonspacing_Mark", "Spacing_Mark", "Enclosing_Mark",
+    "Decimal_Number", "Letter_Number", "Other_Number",
+    "Connector_Punctuation", "Dash_Punctuation", "Open_Punctuation",
+    "Close_Punctuation", "Initial_Punctuation", "Final_Punctuation",
+    "Other_Punctuation",
+    "Math_Symbol", "Currency_Symbol", "Modifier_Symbol", "Other_Symbol",
+    "Space_Separator", "Line_Separator", "Paragraph_Separator",
+    "Control", "Format", "Surrogate", "Private_Use", "Unassigned",
+];
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=RUCD_UCD_DIR");
+    let ucd_dir = match env::var_os("RUCD_UCD_DIR") {
+        Some(ucd_dir) => PathBuf::from(ucd_dir),
+        None => {
+            println!("cargo:rerun-if-changed=ucd-fixture");
+            println!("cargo:warning=RUCD_UCD_DIR is not set, so tables are \
+                      generated from the UCD excerpt in ucd-fixture, which \
+                      covers only a few codepoints");
+            let manifest_dir = env::var_os("CARGO_MANIFEST_DIR").unwrap();
+            PathBuf::from(manifest_dir).join("ucd-fixture")
+        }
+    };
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    if let Err(err) = generate(&ucd_dir, &out_dir.join("tables.rs")) {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+fn generate(ucd_dir: &Path, out: &Path) -> Result<()> {
+    let mut wtr = WriterBuilder::new("tables")
+        .trie(true)
+        .front_coded(true)
+        .header(false)
+        .from_writer(File::create(out)?);
+
+    let bycat = general_category::tables_from_ucd_dir(ucd_dir, true)?;
+    let mut categories = BTreeMap::new();
+    for (name, set) in bycat {
+        let i = match GENERAL_CATEGORIES.iter().position(|&x| x == name) {
+            Some(i) => i as u64,
+            None => {
+                let msg = format!("unrecognized general category: {}", name);
+                return Err(ucd_generate::Error::Other(msg));
+            }
+        };
+        categories.extend(set.into_iter().map(|cp| (cp, i)));
+    }
+    wtr.ranges_to_unsigned_integer("general_category", &categories)?;
+
+    wtr.ranges("alphabetic", &alphabetic(ucd_dir)?)?;
+
+    // Names that are derived algorithmically, including the names of
+    // ideographs and Hangul syllables, aren't listed in UnicodeData.txt and
+    // are computed when they are looked up.
+    let rows: Vec<UnicodeData> = ucd_parse::parse(ucd_dir)?;
+    let mut names = BTreeMap::new();
+    for row in rows {
+        if !row.name.starts_with('<') {
+            names.insert(row.codepoint.value(), row.name.into_owned());
+        }
+    }
+    wtr.codepoint_to_string("names", &names)?;
+    Ok(())
+}
+
+/// Read the codepoints with the Alphabetic property from
+/// DerivedCoreProperties.txt.
+fn alphabetic(ucd_dir: &Path) -> Result<BTreeSet<u32>> {
+    let rows: Vec<CoreProperty> = ucd_parse::parse(ucd_dir)?;
+    let mut set = BTreeSet::new();
+    for row in rows {
+        if row.property == "Alphabetic" {
+            set.extend(row.codepoints.into_iter().map(|cp| cp.value()));
+        }
+    }
+    Ok(set)
+}
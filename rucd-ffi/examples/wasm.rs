@@ -0,0 +1,31 @@
+// An example of exposing lookups to JavaScript with wasm-bindgen.
+//
+// Build this example for the wasm32-unknown-unknown target and run the
+// wasm-bindgen CLI on the resulting .wasm file to produce the JavaScript
+// bindings. The generated tables don't need file I/O or lazy statics, so
+// nothing else is required.
+
+extern crate rucd;
+extern crate wasm_bindgen;
+
+use wasm_bindgen::prelude::*;
+
+/// Return the name of the given codepoint, or `undefined` if it has none.
+#[wasm_bindgen(js_name = charName)]
+pub fn char_name(cp: u32) -> Option<String> {
+    rucd::char_name(cp)
+}
+
+/// Return the General_Category of the given codepoint, as one of the
+/// `RUCD_GC_*` constants in `include/rucd.h`.
+#[wasm_bindgen(js_name = generalCategory)]
+pub fn general_category(cp: u32) -> u8 {
+    rucd::general_category(cp)
+}
+
+/// Return true if and only if the given codepoint has the Alphabetic
+/// property.
+#[wasm_bindgen(js_name = isAlphabetic)]
+pub fn is_alphabetic(cp: u32) -> bool {
+    rucd::is_alphabetic(cp)
+}
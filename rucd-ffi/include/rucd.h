@@ -0,0 +1,72 @@
+#ifndef RUCD_H
+#define RUCD_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Values of the General_Category property, as returned by
+ * rucd_general_category.
+ */
+#define RUCD_GC_UPPERCASE_LETTER 0
+#define RUCD_GC_LOWERCASE_LETTER 1
+#define RUCD_GC_TITLECASE_LETTER 2
+#define RUCD_GC_MODIFIER_LETTER 3
+#define RUCD_GC_OTHER_LETTER 4
+#define RUCD_GC_NONSPACING_MARK 5
+#define RUCD_GC_SPACING_MARK 6
+#define RUCD_GC_ENCLOSING_MARK 7
+#define RUCD_GC_DECIMAL_NUMBER 8
+#define RUCD_GC_LETTER_NUMBER 9
+#define RUCD_GC_OTHER_NUMBER 10
+#define RUCD_GC_CONNECTOR_PUNCTUATION 11
+#define RUCD_GC_DASH_PUNCTUATION 12
+#define RUCD_GC_OPEN_PUNCTUATION 13
+#define RUCD_GC_CLOSE_PUNCTUATION 14
+#define RUCD_GC_INITIAL_PUNCTUATION 15
+#define RUCD_GC_FINAL_PUNCTUATION 16
+#define RUCD_GC_OTHER_PUNCTUATION 17
+#define RUCD_GC_MATH_SYMBOL 18
+#define RUCD_GC_CURRENCY_SYMBOL 19
+#define RUCD_GC_MODIFIER_SYMBOL 20
+#define RUCD_GC_OTHER_SYMBOL 21
+#define RUCD_GC_SPACE_SEPARATOR 22
+#define RUCD_GC_LINE_SEPARATOR 23
+#define RUCD_GC_PARAGRAPH_SEPARATOR 24
+#define RUCD_GC_CONTROL 25
+#define RUCD_GC_FORMAT 26
+#define RUCD_GC_SURROGATE 27
+#define RUCD_GC_PRIVATE_USE 28
+#define RUCD_GC_UNASSIGNED 29
+
+/*
+ * Return the General_Category of the given codepoint, as one of the
+ * RUCD_GC_* constants.
+ */
+uint8_t rucd_general_category(uint32_t cp);
+
+/*
+ * Return true if and only if the given codepoint has the Alphabetic property.
+ */
+bool rucd_is_alphabetic(uint32_t cp);
+
+/*
+ * Write the name of the given codepoint to buf.
+ *
+ * This behaves like snprintf: at most len - 1 bytes of the name are written,
+ * followed by a NUL byte, and the length of the full name (excluding the NUL
+ * byte) is returned. If len is 0, then nothing is written and buf may be
+ * NULL. If the codepoint has no name, then 0 is returned.
+ */
+size_t rucd_char_name(uint32_t cp, char *buf, size_t len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
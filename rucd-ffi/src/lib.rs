@@ -0,0 +1,162 @@
+/*!
+A C interface to lookups in the Unicode character database.
+
+This crate builds a shared and a static library exposing a small set of
+lookup functions with a C ABI, declared in `include/rucd.h`. The tables
+backing these functions are generated by `ucd-generate` when the crate is
+built, from the UCD in the directory given by the `RUCD_UCD_DIR` environment
+variable:
+
+```text
+$ RUCD_UCD_DIR=/path/to/ucd cargo build --release
+```
+
+When `RUCD_UCD_DIR` isn't set, the tables are generated from the small
+excerpt of the UCD in `ucd-fixture` instead, with a warning. This is enough
+to run this crate's tests, but every codepoint outside of the excerpt is
+unassigned.
+
+Every function accepts any `uint32_t`. Values that aren't codepoints are
+treated as unassigned codepoints.
+
+The same lookups are available to Rust code as safe functions, which is how
+the `wasm` example exposes them to JavaScript with `wasm-bindgen`:
+
+```text
+$ RUCD_UCD_DIR=/path/to/ucd cargo build --release \
+    --target wasm32-unknown-unknown --example wasm
+```
+*/
+
+#![deny(missing_docs)]
+
+extern crate ucd_trie;
+extern crate ucd_util;
+
+use std::cmp;
+use std::ptr;
+
+#[allow(dead_code)]
+mod tables {
+    include!(concat!(env!("OUT_DIR"), "/tables.rs"));
+}
+
+/// The value of `RUCD_GC_UNASSIGNED` in `include/rucd.h`.
+pub const GC_UNASSIGNED: u8 = 29;
+
+/// Return the General_Category of the given codepoint, as one of the
+/// `RUCD_GC_*` constants in `include/rucd.h`.
+pub fn general_category(cp: u32) -> u8 {
+    tables::GENERAL_CATEGORY.get_u32(cp).unwrap_or(GC_UNASSIGNED)
+}
+
+/// Return true if and only if the given codepoint has the Alphabetic
+/// property.
+pub fn is_alphabetic(cp: u32) -> bool {
+    tables::ALPHABETIC.contains_u32(cp)
+}
+
+/// Return the name of the given codepoint, if it has one.
+pub fn char_name(cp: u32) -> Option<String> {
+    if let Some(name) = ucd_util::hangul_name(cp) {
+        return Some(name);
+    }
+    if let Some(name) = ucd_util::ideograph_name(cp) {
+        return Some(name);
+    }
+    tables::NAMES_CODEPOINTS
+        .binary_search(&cp)
+        .ok()
+        .map(|i| tables::NAMES.get(i))
+}
+
+/// Return the General_Category of the given codepoint, as one of the
+/// `RUCD_GC_*` constants.
+#[no_mangle]
+pub extern "C" fn rucd_general_category(cp: u32) -> u8 {
+    general_category(cp)
+}
+
+/// Return true if and only if the given codepoint has the Alphabetic
+/// property.
+#[no_mangle]
+pub extern "C" fn rucd_is_alphabetic(cp: u32) -> bool {
+    is_alphabetic(cp)
+}
+
+/// Write the name of the given codepoint to `buf`.
+///
+/// This behaves like `snprintf`: at most `len - 1` bytes of the name are
+/// written, followed by a `NUL` byte, and the length of the full name
+/// (excluding the `NUL` byte) is returned. If `len` is `0`, then nothing is
+/// written and `buf` may be `NULL`. If the codepoint has no name, then `0`
+/// is returned.
+#[no_mangle]
+pub unsafe extern "C" fn rucd_char_name(
+    cp: u32,
+    buf: *mut u8,
+    len: usize,
+) -> usize {
+    let name = match char_name(cp) {
+        None => return 0,
+        Some(name) => name,
+    };
+    if len > 0 {
+        let n = cmp::min(name.len(), len - 1);
+        ptr::copy_nonoverlapping(name.as_ptr(), buf, n);
+        *buf.offset(n as isize) = 0;
+    }
+    name.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+
+    use super::{
+        rucd_char_name, rucd_general_category, rucd_is_alphabetic,
+        GC_UNASSIGNED,
+    };
+
+    fn name(cp: u32, len: usize) -> (usize, String) {
+        let mut buf = vec![0xFF; len];
+        let n = unsafe { rucd_char_name(cp, buf.as_mut_ptr(), len) };
+        let end = buf.iter().position(|&b| b == 0).unwrap_or(0);
+        (n, String::from_utf8(buf[..end].to_vec()).unwrap())
+    }
+
+    #[test]
+    fn general_category() {
+        // Uppercase_Letter and Lowercase_Letter.
+        assert_eq!(0, rucd_general_category('A' as u32));
+        assert_eq!(1, rucd_general_category('a' as u32));
+        assert_eq!(GC_UNASSIGNED, rucd_general_category(0x110000));
+    }
+
+    #[test]
+    fn alphabetic() {
+        assert!(rucd_is_alphabetic('A' as u32));
+        assert!(!rucd_is_alphabetic(' ' as u32));
+        assert!(!rucd_is_alphabetic(0x110000));
+    }
+
+    #[test]
+    fn char_name() {
+        let a = "LATIN CAPITAL LETTER A".to_string();
+        assert_eq!(name('A' as u32, 64), (22, a));
+        let ga = "HANGUL SYLLABLE GA".to_string();
+        assert_eq!(name(0xAC00, 64), (18, ga));
+        let cjk = "CJK UNIFIED IDEOGRAPH-3400".to_string();
+        assert_eq!(name(0x3400, 64), (26, cjk));
+        assert_eq!(name(0, 64), (0, "".into()));
+        assert_eq!(name(0x110000, 64), (0, "".into()));
+    }
+
+    #[test]
+    fn char_name_truncated() {
+        assert_eq!(name('A' as u32, 6), (22, "LATIN".into()));
+        assert_eq!(name('A' as u32, 1), (22, "".into()));
+        let n = unsafe { rucd_char_name('A' as u32, ptr::null_mut(), 0) };
+        assert_eq!(n, 22);
+    }
+}
@@ -0,0 +1,231 @@
+/*!
+Procedural macros for embedding Unicode tables at compile time.
+
+The `ucd_table!` macro runs the same pipeline as the `ucd-generate` command
+line tool while a crate is being compiled, and expands to the items that
+`ucd-generate` would have emitted. This removes the need to generate tables
+and commit them to a repository for projects that vendor the UCD.
+
+```ignore
+#[macro_use]
+extern crate rucd_macros;
+
+ucd_table!(general_category, "ucd");
+
+fn main() {
+    assert!(UPPERCASE_LETTER.iter().any(|&(s, e)| s <= 0x41 && 0x41 <= e));
+}
+```
+
+The first argument is the name of the table. Only a subset of the
+`ucd-generate` subcommands is supported so far:
+
+* `general_category`, which emits one slice of codepoint ranges for each
+  General_Category value.
+* `jamo_short_name`, which emits a `JAMO_SHORT_NAME` slice mapping
+  codepoints to their Jamo_Short_Name.
+
+Any other name is reported with a `compile_error!` that lists the supported
+tables. Other tables can still be generated with the `ucd-generate` command
+line tool.
+
+The second argument is the path to a directory containing the UCD. Relative
+paths are resolved relative to the root of the crate using the macro.
+
+Stable Rust has no way for a procedural macro to tell Cargo which files it
+read, so the expansion also contains a single unnamed constant that includes
+each UCD file read by the macro with `include_bytes!`. This makes the crate
+be recompiled whenever one of those files changes. The constant is never
+used, so none of its data ends up in the compiled crate.
+*/
+
+extern crate proc_macro;
+extern crate ucd_generate;
+extern crate ucd_parse;
+
+use std::cell::RefCell;
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use proc_macro::{TokenStream, TokenTree};
+use ucd_generate::{Error, Result, WriterBuilder};
+use ucd_generate::{general_category, jamo_short_name};
+use ucd_parse::{
+    JamoShortName, PropertyAlias, PropertyValueAlias, UcdFile, UnicodeData,
+};
+
+macro_rules! err {
+    ($($tt:tt)*) => {
+        Err(Error::Other(format!($($tt)*)))
+    }
+}
+
+#[cfg(test)]
+mod temp_dir;
+
+/// The names of the tables that `ucd_table!` can generate.
+const TABLES: &'static [&'static str] = &[
+    "general_category",
+    "jamo_short_name",
+];
+
+/// Expand to the items of the given table, generated from the UCD in the
+/// given directory.
+///
+/// See the crate documentation for details.
+#[proc_macro]
+pub fn ucd_table(input: TokenStream) -> TokenStream {
+    let source = parse_args(input).and_then(|(table, dir)| {
+        generate(&table, &ucd_dir(&dir))
+    });
+    expand(source).parse().unwrap()
+}
+
+/// Return the source code that `ucd_table!` expands to, which is either the
+/// generated table or a `compile_error!` describing why it couldn't be
+/// generated.
+fn expand(source: Result<String>) -> String {
+    match source {
+        Ok(source) => source,
+        Err(err) => {
+            let msg = format!("ucd_table!: {}", err);
+            format!("compile_error!({:?});", msg)
+        }
+    }
+}
+
+/// Parse the arguments to `ucd_table!`, which are a table name and a string
+/// literal containing the path to the UCD.
+fn parse_args(input: TokenStream) -> Result<(String, String)> {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    match tokens.as_slice() {
+        [TokenTree::Ident(ref table), TokenTree::Punct(ref comma),
+         TokenTree::Literal(ref dir)] if comma.as_char() == ',' => {
+            Ok((table.to_string(), unquote(&dir.to_string())?))
+        }
+        _ => err!("expected a table name and a path, e.g., \
+                   ucd_table!(general_category, \"ucd\")"),
+    }
+}
+
+/// Return the contents of the given string literal. Escape sequences are not
+/// supported.
+fn unquote(lit: &str) -> Result<String> {
+    if lit.len() < 2 || !lit.starts_with('"') || !lit.ends_with('"') {
+        return err!("expected a string literal, but got {}", lit);
+    }
+    let s = &lit[1..lit.len() - 1];
+    if s.contains('\\') {
+        return err!("escape sequences are not supported in {}", lit);
+    }
+    Ok(s.to_string())
+}
+
+/// Resolve the given UCD directory relative to the crate being compiled.
+fn ucd_dir(dir: &str) -> PathBuf {
+    match env::var_os("CARGO_MANIFEST_DIR") {
+        None => PathBuf::from(dir),
+        Some(root) => Path::new(&root).join(dir),
+    }
+}
+
+/// Generate the Rust source code for the given table.
+fn generate(table: &str, ucd_dir: &Path) -> Result<String> {
+    if !TABLES.contains(&table) {
+        return err!("unsupported table `{}`, expected one of: {}",
+                    table, TABLES.join(", "));
+    }
+    let buf = Buffer::default();
+    let mut wtr = WriterBuilder::new(table)
+        .header(false)
+        .from_writer(buf.clone());
+    let files = match table {
+        "general_category" => {
+            let bycat = general_category::tables_from_ucd_dir(ucd_dir, true)?;
+            for (name, set) in &bycat {
+                wtr.ranges(name, set)?;
+            }
+            vec![
+                UnicodeData::file_path(ucd_dir),
+                PropertyAlias::file_path(ucd_dir),
+                PropertyValueAlias::file_path(ucd_dir),
+            ]
+        }
+        "jamo_short_name" => {
+            let map = jamo_short_name::table(ucd_dir)?;
+            wtr.codepoint_to_string("jamo_short_name", &map)?;
+            vec![JamoShortName::file_path(ucd_dir)]
+        }
+        _ => unreachable!(),
+    };
+    drop(wtr);
+
+    let mut source = String::from_utf8(buf.0.borrow().clone()).unwrap();
+    // Including each file that was read makes Cargo rebuild the crate using
+    // this macro whenever one of them changes.
+    source.push_str("const _: &'static [&'static [u8]] = &[\n");
+    for path in files {
+        let path = match path.to_str() {
+            Some(path) => path.to_string(),
+            None => return err!("invalid UTF-8 in path {:?}", path),
+        };
+        source.push_str(&format!("  include_bytes!({:?}),\n", path));
+    }
+    source.push_str("];\n");
+    Ok(source)
+}
+
+/// A writer whose contents can be read after it has been given to a
+/// `Writer`.
+#[derive(Clone, Default)]
+struct Buffer(Rc<RefCell<Vec<u8>>>);
+
+impl io::Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use temp_dir::TempDir;
+
+    use super::{expand, generate, unquote};
+
+    #[test]
+    fn unquote_literal() {
+        assert_eq!(unquote("\"ucd\"").unwrap(), "ucd");
+        assert!(unquote("ucd").is_err());
+        assert!(unquote("\"a\\\\b\"").is_err());
+    }
+
+    #[test]
+    fn jamo_short_name() {
+        let dir = TempDir::new("rucd-macros-jamo-short-name");
+        dir.write("Jamo.txt", b"1100; G # HANGUL CHOSEONG KIYEOK\n");
+
+        let source = generate("jamo_short_name", dir.path()).unwrap();
+        assert!(source.starts_with(
+            "\npub const JAMO_SHORT_NAME: &'static [(u32, &'static str)] \
+             = &[\n  (4352, \"G\"),\n];\n"));
+        let jamo = dir.path().join("Jamo.txt");
+        assert!(source.ends_with(&format!(
+            "const _: &'static [&'static [u8]] = &[\n  \
+             include_bytes!({:?}),\n];\n", jamo.to_str().unwrap())));
+    }
+
+    #[test]
+    fn unsupported_table() {
+        let dir = TempDir::new("rucd-macros-unsupported-table");
+        assert_eq!(
+            expand(generate("script", dir.path())),
+            "compile_error!(\"ucd_table!: unsupported table `script`, \
+             expected one of: general_category, jamo_short_name\");");
+    }
+}
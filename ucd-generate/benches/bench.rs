@@ -5,11 +5,14 @@ extern crate fst;
 #[macro_use]
 extern crate lazy_static;
 extern crate test;
+extern crate ucd_trie;
 
 use std::cmp::Ordering;
 
 use byteorder::{ByteOrder, BigEndian as BE};
+use fst::{Set, SetBuilder};
 use test::Bencher;
+use ucd_trie::TrieSet;
 
 mod tables;
 
@@ -129,3 +132,150 @@ fn jamo_short_name_slice_linear(b: &mut Bencher) {
         assert_eq!(found.1, name);
     });
 }
+
+// The benchmarks below compare the slice, FST and trie representations of a
+// single codepoint set on a few different distributions of input. Each
+// iteration looks up every codepoint in a fixed sequence of queries.
+
+lazy_static! {
+    static ref OTHER_LETTER: SetTables = SetTables::new("Other_Letter");
+    static ref ASCII_HEAVY: Vec<u32> = queries(90, 0);
+    static ref CJK_HEAVY: Vec<u32> = queries(10, 80);
+    static ref RANDOM: Vec<u32> = queries(0, 0);
+}
+
+/// Every representation of a single codepoint set.
+struct SetTables {
+    slice: Vec<(u32, u32)>,
+    fst: Set,
+    trie: TrieSet,
+}
+
+impl SetTables {
+    /// Build every representation of the set of codepoints with the given
+    /// General_Category value.
+    fn new(category: &str) -> SetTables {
+        let names = tables::slice::general_category::GENERAL_CATEGORY_ENUM;
+        let value = names.iter().position(|&x| x == category).unwrap() as u8;
+
+        let mut slice = vec![];
+        let mut codepoints = vec![];
+        for &(s, e, v) in tables::slice::general_category::GENERAL_CATEGORY {
+            if v == value {
+                slice.push((s, e));
+                codepoints.extend(s..e + 1);
+            }
+        }
+        let mut builder = SetBuilder::memory();
+        builder.extend_iter(codepoints.iter().cloned().map(u32_key)).unwrap();
+        let fst = Set::from_bytes(builder.into_inner().unwrap()).unwrap();
+        let trie = TrieSet::from_codepoints(&codepoints);
+        SetTables { slice: slice, fst: fst, trie: trie }
+    }
+
+    fn slice_contains(&self, cp: u32) -> bool {
+        self.slice.binary_search_by(|&(s, e)| {
+            if s > cp {
+                Ordering::Greater
+            } else if e < cp {
+                Ordering::Less
+            } else {
+                Ordering::Equal
+            }
+        }).is_ok()
+    }
+}
+
+/// Generate a deterministic sequence of codepoint queries.
+///
+/// `ascii` is the percentage of queries drawn from ASCII and `cjk` is the
+/// percentage drawn from the CJK Unified Ideographs block. All remaining
+/// queries are drawn uniformly from the entire codepoint space.
+fn queries(ascii: u32, cjk: u32) -> Vec<u32> {
+    // A simple xorshift generator. We don't need good randomness, just
+    // something that's the same on every run.
+    let mut state: u32 = 2463534242;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state
+    };
+    let mut queries = Vec::with_capacity(1000);
+    for _ in 0..1000 {
+        let pct = next() % 100;
+        let cp =
+            if pct < ascii {
+                next() % 0x80
+            } else if pct < ascii + cjk {
+                0x4E00 + (next() % (0x9FFF - 0x4E00 + 1))
+            } else {
+                next() % 0x110000
+            };
+        queries.push(cp);
+    }
+    queries
+}
+
+fn bench_lookups<F: Fn(u32) -> bool>(
+    b: &mut Bencher,
+    queries: &[u32],
+    contains: F,
+) {
+    b.iter(|| {
+        let mut found = 0;
+        for &cp in queries {
+            if contains(cp) {
+                found += 1;
+            }
+        }
+        test::black_box(found)
+    });
+}
+
+#[bench]
+fn other_letter_slice_ascii(b: &mut Bencher) {
+    bench_lookups(b, &ASCII_HEAVY, |cp| OTHER_LETTER.slice_contains(cp));
+}
+
+#[bench]
+fn other_letter_slice_cjk(b: &mut Bencher) {
+    bench_lookups(b, &CJK_HEAVY, |cp| OTHER_LETTER.slice_contains(cp));
+}
+
+#[bench]
+fn other_letter_slice_random(b: &mut Bencher) {
+    bench_lookups(b, &RANDOM, |cp| OTHER_LETTER.slice_contains(cp));
+}
+
+#[bench]
+fn other_letter_fst_ascii(b: &mut Bencher) {
+    bench_lookups(b, &ASCII_HEAVY, |cp| {
+        OTHER_LETTER.fst.contains(u32_key(cp))
+    });
+}
+
+#[bench]
+fn other_letter_fst_cjk(b: &mut Bencher) {
+    bench_lookups(b, &CJK_HEAVY, |cp| OTHER_LETTER.fst.contains(u32_key(cp)));
+}
+
+#[bench]
+fn other_letter_fst_random(b: &mut Bencher) {
+    bench_lookups(b, &RANDOM, |cp| OTHER_LETTER.fst.contains(u32_key(cp)));
+}
+
+#[bench]
+fn other_letter_trie_ascii(b: &mut Bencher) {
+    bench_lookups(b, &ASCII_HEAVY, |cp| OTHER_LETTER.trie.contains_u32(cp));
+}
+
+#[bench]
+fn other_letter_trie_cjk(b: &mut Bencher) {
+    bench_lookups(b, &CJK_HEAVY, |cp| OTHER_LETTER.trie.contains_u32(cp));
+}
+
+#[bench]
+fn other_letter_trie_random(b: &mut Bencher) {
+    bench_lookups(b, &RANDOM, |cp| OTHER_LETTER.trie.contains_u32(cp));
+}
@@ -34,6 +34,87 @@ permits fast searching while simultaneously compressing the table.
 
 Project home page: https://github.com/BurntSushi/rucd";
 
+const ABOUT_BIDI_BRACKETS: &'static str = "\
+bidi-brackets emits the Bidi_Paired_Bracket property from BidiBrackets.txt as
+a map from each bracket to the bracket it pairs with, along with one table of
+Unicode codepoint ranges for each Bidi_Paired_Bracket_Type value, i.e., OPEN
+and CLOSE. These are the properties used by rule BD16 of the Unicode
+Bidirectional Algorithm (UAX #9) to identify bracket pairs. Note that BD16
+also matches brackets that are canonically equivalent to a pair, such as
+U+2329 and U+3009, which these tables don't account for.
+
+With --enum, a single table that maps each bracket to its
+Bidi_Paired_Bracket_Type value is emitted instead of OPEN and CLOSE.
+
+When emitted as an FST table, the map corresponds to a map from a Unicode
+codepoint (encoded as a big-endian u32) to a u64, where the u64 is the paired
+bracket.
+";
+
+const ABOUT_BIDI_CLASS: &'static str = "\
+bidi-class produces one table of Unicode codepoint ranges for each Bidi_Class
+value. Every codepoint is in exactly one table. Unassigned codepoints have
+their default value, e.g., Right_To_Left or Arabic_Letter in the blocks
+reserved for right-to-left scripts, and Left_To_Right elsewhere.
+
+By default, the values are read from UnicodeData.txt. With --derived, the
+values are read from extracted/DerivedBidiClass.txt instead. Either way, the
+defaults are taken from the @missing lines of extracted/DerivedBidiClass.txt.
+
+With --enum, a single table is emitted instead, which maps each codepoint to
+its Bidi_Class value.
+";
+
+const ABOUT_BIDI_MIRRORING_GLYPH: &'static str = "\
+bidi-mirroring-glyph emits the Bidi_Mirroring_Glyph property from
+BidiMirroring.txt as a map from codepoint to codepoint, along with a table of
+Unicode codepoint ranges named BIDI_MIRRORED that contains every codepoint
+with the Bidi_Mirrored property in UnicodeData.txt. Mirrored codepoints
+without a mirroring glyph are in the latter table only.
+
+When emitted as an FST table, the map corresponds to a map from a Unicode
+codepoint (encoded as a big-endian u32) to a u64, where the u64 is the
+mirroring glyph.
+";
+
+const ABOUT_CASE_FOLDING_SIMPLE: &'static str = "\
+case-folding-simple emits a map from codepoint to its simple case folding,
+using the mappings in CaseFolding.txt with status C or S. Codepoints that
+fold to themselves are omitted.
+
+When emitted as an FST table, the FST corresponds to a map from a Unicode
+codepoint (encoded as a big-endian u32) to a u64, where the u64 is the
+codepoint it folds to.
+
+With --circular, the map instead forms a cycle through each set of
+codepoints that are equivalent under simple case folding. Each codepoint in
+such a set maps to the next largest codepoint in the set, and the largest
+maps to the smallest. Following the map from a codepoint until returning to
+it yields every codepoint that is equivalent to it, which is what case
+insensitive matching in regular expressions needs.
+";
+
+const ABOUT_CASE_MAPPING: &'static str = "\
+case-mapping emits the simple uppercase, lowercase and titlecase mappings in
+UnicodeData.txt as three maps from codepoint to codepoint, named UPPERCASE,
+LOWERCASE and TITLECASE. Codepoints that map to themselves are omitted. When
+the titlecase mapping of a codepoint is empty, its uppercase mapping is used.
+
+With --deltas, each map instead associates ranges of codepoints with the
+signed difference between the codepoint mapped to and the codepoint. Since
+long runs of codepoints share the same difference, these tables are much
+smaller. When emitted as an FST table, each difference is stored as the u64
+with the same two's complement representation.
+";
+
+const ABOUT_DOWNLOAD: &'static str = "\
+download fetches the UCD.zip and Unihan.zip archives for a release of the UCD
+from unicode.org. The archives are saved in a directory named after the
+version, which is created inside the given directory. The path to that
+directory is printed on stdout, and it can be passed as the UCD directory to
+any other command. Archives that already exist aren't downloaded again.
+";
+
 const ABOUT_GENERAL_CATEGORY: &'static str = "\
 general-category produces one table of Unicode codepoint ranges for each
 possible General_Category value.
@@ -52,12 +133,96 @@ bytes (up to 3).
 Since the table is so small, the slice table is faster to search.
 ";
 
+const ABOUT_JOINING_TYPE: &'static str = "\
+joining-type produces one table of Unicode codepoint ranges for each
+Joining_Type value. Every codepoint is in exactly one table.
+
+By default, the values are read from ArabicShaping.txt. Codepoints that
+aren't listed there are Transparent if their General_Category in
+UnicodeData.txt is Mn, Me or Cf, and Non_Joining otherwise. With --derived,
+the values are read from extracted/DerivedJoiningType.txt instead, and the
+default is taken from its @missing lines.
+
+With --enum, a single table is emitted instead, which maps each codepoint to
+its Joining_Type value.
+";
+
+const ABOUT_LINE_BREAK: &'static str = "\
+line-break produces one table of Unicode codepoint ranges for each
+Line_Break value listed in LineBreak.txt, for use by implementations of the
+line breaking algorithm in UAX #14. The tables are named after the long
+value aliases, e.g., ALPHABETIC rather than AL.
+
+With --enum, a single table is emitted instead, which maps each codepoint to
+its Line_Break value.
+";
+
 const ABOUT_NAMES: &'static str = "\
 names emits a table of all character names in the UCD, including aliases and
 names that are algorithmically generated such as Hangul syllables and
 ideographs.
 ";
 
+const ABOUT_PROPERTY_BOOL: &'static str = "\
+property-bool produces one table of Unicode codepoint ranges for each binary
+property listed in PropList.txt, DerivedCoreProperties.txt and, if it exists,
+emoji/emoji-data.txt. By default, a table is emitted for every property.
+Use --include and --exclude to select specific properties.
+";
+
+const ABOUT_REPL: &'static str = "\
+repl parses the UCD once and then starts an interactive session for querying
+codepoints, names and properties. Type 'help' in the session for a list of
+commands.
+";
+
+const ABOUT_SCRIPT_EXTENSION: &'static str = "\
+script-extension produces one table of Unicode codepoint ranges for each
+script. Each table contains every codepoint whose Script_Extensions property
+includes that script, which is the set matched by \\p{scx=...} in regular
+expressions. Codepoints that aren't listed in ScriptExtensions.txt are
+included in the table of their Script property value.
+";
+
+const ABOUT_SENTENCE_BREAK: &'static str = "\
+sentence-break produces one table of Unicode codepoint ranges for each
+Sentence_Break value listed in auxiliary/SentenceBreakProperty.txt. Codepoints
+that aren't listed have the value Other, which has no table.
+
+With --enum, a single table is emitted instead, which maps each codepoint to
+its Sentence_Break value.
+";
+
+const ABOUT_SPECIAL_CASING: &'static str = "\
+special-casing emits the full uppercase, lowercase and titlecase mappings as
+three maps from codepoint to a sequence of up to 3 codepoints, named
+UPPERCASE, LOWERCASE and TITLECASE. These are the simple mappings in
+UnicodeData.txt, overridden by the unconditional mappings in
+SpecialCasing.txt. Codepoints that map to themselves are omitted.
+
+Conditional mappings, which depend on context or language, are excluded by
+default. With --conditional, they are emitted in additional tables tagged
+with their conditions, e.g., LOWERCASE_FINAL_SIGMA or LOWERCASE_TR_AFTER_I.
+Each of these contains only the mappings that differ from the unconditional
+table.
+
+When emitted as an FST table, the FST corresponds to a map from a Unicode
+codepoint (encoded as a big-endian u32) to a u64, where each codepoint in
+the sequence occupies 21 bits, starting with the least significant bits.
+";
+
+const ABOUT_SIZE_REPORT: &'static str = "\
+size-report builds the codepoint set for a single property value in each of
+the slice, FST, trie and bitset representations. For each
+representation, it prints the number of bytes used and the average time taken
+by a single membership test. This is useful for deciding which output format
+to use for a particular table.
+
+The property is General_Category by default. Bidi_Class, Joining_Type,
+Script_Extensions and every binary property, such as Alphabetic, may be given
+with --property instead. The value of a binary property is either Yes or No.
+";
+
 const ABOUT_TEST_UNICODE_DATA: &'static str = "\
 test-unicode-data parses the UCD's UnicodeData.txt file and emits its contents
 on stdout. The purpose of this command is to diff the output with the input and
@@ -84,11 +249,154 @@ pub fn app() -> App<'static, 'static> {
         .long("fst-dir")
         .help("Emit the table as a FST in Rust source codeto stdout.")
         .takes_value(true);
+    let flag_no_lazy_static = Arg::with_name("no-lazy-static")
+        .long("no-lazy-static")
+        .requires("fst-dir")
+        .help("Emit each FST as a function that returns it, instead of as \
+               a lazy_static. The generated code then doesn't depend on \
+               the lazy_static crate.");
+    let flag_cache_dir = Arg::with_name("cache-dir")
+        .long("cache-dir")
+        .help("Cache parsed UCD files in this directory. Subsequent runs \
+               with the same cache directory load the cache instead of \
+               parsing the UCD again, unless the UCD files have changed.")
+        .takes_value(true);
+    let flag_front_coded = Arg::with_name("front-coded")
+        .long("front-coded")
+        .conflicts_with("fst-dir")
+        .help("Compress the strings in the emitted slice table with front \
+               coding. This emits a FrontCodedStrings value, which can \
+               decode and search the strings, along with a slice of the \
+               values associated with each string.");
     let ucd_dir = Arg::with_name("ucd-dir")
         .required(true)
         .help("Directory containing the Unicode character database files.");
 
     // Subcommands.
+    let cmd_bidi_brackets = SubCommand::with_name("bidi-brackets")
+        .author(crate_authors!())
+        .version(crate_version!())
+        .template(TEMPLATE_SUB)
+        .about("Create the Bidi_Paired_Bracket and bracket type tables.")
+        .before_help(ABOUT_BIDI_BRACKETS)
+        .arg(ucd_dir.clone())
+        .arg(flag_fst_dir.clone())
+        .arg(flag_no_lazy_static.clone())
+        .arg(flag_name("BIDI_PAIRED_BRACKET"))
+        .arg(flag_chars.clone())
+        .arg(Arg::with_name("enum")
+            .long("enum")
+            .help("Emit a single table that maps codepoints to \
+                   Bidi_Paired_Bracket_Type values."))
+        .arg(Arg::with_name("trie")
+            .long("trie")
+            .conflicts_with("fst-dir")
+            .help("Emit each table as a trie from the ucd-trie crate, \
+                   which supports fast lookups in constant time."));
+
+    let cmd_bidi_class = SubCommand::with_name("bidi-class")
+        .author(crate_authors!())
+        .version(crate_version!())
+        .template(TEMPLATE_SUB)
+        .about("Create the Bidi_Class property tables.")
+        .before_help(ABOUT_BIDI_CLASS)
+        .arg(ucd_dir.clone())
+        .arg(flag_cache_dir.clone())
+        .arg(flag_fst_dir.clone())
+        .arg(flag_no_lazy_static.clone())
+        .arg(flag_name("BIDI_CLASS"))
+        .arg(flag_chars.clone())
+        .arg(Arg::with_name("derived")
+            .long("derived")
+            .conflicts_with("cache-dir")
+            .help("Read extracted/DerivedBidiClass.txt instead of \
+                   UnicodeData.txt."))
+        .arg(Arg::with_name("enum")
+            .long("enum")
+            .help("Emit a single table that maps codepoints to Bidi_Class \
+                   values."))
+        .arg(Arg::with_name("trie")
+            .long("trie")
+            .conflicts_with("fst-dir")
+            .help("Emit each table as a trie from the ucd-trie crate, \
+                   which supports fast lookups in constant time."));
+
+    let cmd_bidi_mirroring_glyph =
+        SubCommand::with_name("bidi-mirroring-glyph")
+        .author(crate_authors!())
+        .version(crate_version!())
+        .template(TEMPLATE_SUB)
+        .about("Create the Bidi_Mirroring_Glyph and Bidi_Mirrored tables.")
+        .before_help(ABOUT_BIDI_MIRRORING_GLYPH)
+        .arg(ucd_dir.clone())
+        .arg(flag_cache_dir.clone())
+        .arg(flag_fst_dir.clone())
+        .arg(flag_no_lazy_static.clone())
+        .arg(flag_name("BIDI_MIRRORING_GLYPH"))
+        .arg(flag_chars.clone())
+        .arg(Arg::with_name("trie")
+            .long("trie")
+            .conflicts_with("fst-dir")
+            .help("Emit each table as a trie from the ucd-trie crate, \
+                   which supports fast lookups in constant time."));
+
+    let cmd_case_folding_simple = SubCommand::with_name("case-folding-simple")
+        .author(crate_authors!())
+        .version(crate_version!())
+        .template(TEMPLATE_SUB)
+        .about("Create a simple case folding table.")
+        .before_help(ABOUT_CASE_FOLDING_SIMPLE)
+        .arg(ucd_dir.clone())
+        .arg(flag_fst_dir.clone())
+        .arg(flag_no_lazy_static.clone())
+        .arg(flag_chars.clone())
+        .arg(flag_name("CASE_FOLDING_SIMPLE"))
+        .arg(Arg::with_name("circular")
+            .long("circular")
+            .help("Emit a circular map through each set of equivalent \
+                   codepoints, instead of a map to the folded codepoint."))
+        .arg(Arg::with_name("trie")
+            .long("trie")
+            .conflicts_with("fst-dir")
+            .help("Emit the table as a trie from the ucd-trie crate, \
+                   which supports fast lookups in constant time."));
+
+    let cmd_case_mapping = SubCommand::with_name("case-mapping")
+        .author(crate_authors!())
+        .version(crate_version!())
+        .template(TEMPLATE_SUB)
+        .about("Create the simple case mapping tables.")
+        .before_help(ABOUT_CASE_MAPPING)
+        .arg(ucd_dir.clone())
+        .arg(flag_cache_dir.clone())
+        .arg(flag_fst_dir.clone())
+        .arg(flag_no_lazy_static.clone())
+        .arg(flag_chars.clone())
+        .arg(Arg::with_name("deltas")
+            .long("deltas")
+            .help("Map each codepoint to the difference between the \
+                   codepoint it maps to and itself."))
+        .arg(Arg::with_name("trie")
+            .long("trie")
+            .conflicts_with("fst-dir")
+            .help("Emit each table as a trie from the ucd-trie crate, \
+                   which supports fast lookups in constant time."));
+
+    let cmd_download = SubCommand::with_name("download")
+        .author(crate_authors!())
+        .version(crate_version!())
+        .template(TEMPLATE_SUB)
+        .about("Download a release of the UCD.")
+        .before_help(ABOUT_DOWNLOAD)
+        .arg(Arg::with_name("version")
+            .required(true)
+            .help("The version of Unicode to download, e.g., 15.1.0."))
+        .arg(Arg::with_name("dir")
+            .long("dir")
+            .help("The directory to download the UCD into.")
+            .takes_value(true)
+            .default_value("."));
+
     let cmd_general_category = SubCommand::with_name("general-category")
         .author(crate_authors!())
         .version(crate_version!())
@@ -96,7 +404,9 @@ pub fn app() -> App<'static, 'static> {
         .about("Create the General_Category property tables.")
         .before_help(ABOUT_GENERAL_CATEGORY)
         .arg(ucd_dir.clone())
+        .arg(flag_cache_dir.clone())
         .arg(flag_fst_dir.clone())
+        .arg(flag_no_lazy_static.clone())
         .arg(flag_name("GENERAL_CATEGORY"))
         .arg(flag_chars.clone())
         .arg(Arg::with_name("enum")
@@ -104,7 +414,20 @@ pub fn app() -> App<'static, 'static> {
             .help("Emit a single table that maps codepoints to categories."))
         .arg(Arg::with_name("no-unassigned")
             .long("no-unassigned")
-            .help("Don't emit the Unassigned general category."));
+            .help("Don't emit the Unassigned general category."))
+        .arg(Arg::with_name("share-complements")
+            .long("share-complements")
+            .help("When a table is identical to a previously emitted \
+                   table, emit it as an alias to the previous table. When \
+                   a table is the complement of a previously emitted \
+                   table, also emit NAME_COMPLEMENT, an alias to the \
+                   previous table, which contains every codepoint not in \
+                   NAME. Complements only apply to slice tables."))
+        .arg(Arg::with_name("trie")
+            .long("trie")
+            .conflicts_with("fst-dir")
+            .help("Emit each table as a trie from the ucd-trie crate, \
+                   which supports fast lookups in constant time."));
     let cmd_jamo_short_name = SubCommand::with_name("jamo-short-name")
         .author(crate_authors!())
         .version(crate_version!())
@@ -113,8 +436,58 @@ pub fn app() -> App<'static, 'static> {
         .before_help(ABOUT_JAMO_SHORT_NAME)
         .arg(ucd_dir.clone())
         .arg(flag_fst_dir.clone())
+        .arg(flag_no_lazy_static.clone())
         .arg(flag_chars.clone())
+        .arg(flag_front_coded.clone())
         .arg(flag_name("JAMO_SHORT_NAME"));
+    let cmd_joining_type = SubCommand::with_name("joining-type")
+        .author(crate_authors!())
+        .version(crate_version!())
+        .template(TEMPLATE_SUB)
+        .about("Create the Joining_Type property tables.")
+        .before_help(ABOUT_JOINING_TYPE)
+        .arg(ucd_dir.clone())
+        .arg(flag_cache_dir.clone())
+        .arg(flag_fst_dir.clone())
+        .arg(flag_no_lazy_static.clone())
+        .arg(flag_name("JOINING_TYPE"))
+        .arg(flag_chars.clone())
+        .arg(Arg::with_name("derived")
+            .long("derived")
+            .conflicts_with("cache-dir")
+            .help("Read extracted/DerivedJoiningType.txt instead of \
+                   ArabicShaping.txt and UnicodeData.txt."))
+        .arg(Arg::with_name("enum")
+            .long("enum")
+            .help("Emit a single table that maps codepoints to \
+                   Joining_Type values."))
+        .arg(Arg::with_name("trie")
+            .long("trie")
+            .conflicts_with("fst-dir")
+            .help("Emit each table as a trie from the ucd-trie crate, \
+                   which supports fast lookups in constant time."));
+
+    let cmd_line_break = SubCommand::with_name("line-break")
+        .author(crate_authors!())
+        .version(crate_version!())
+        .template(TEMPLATE_SUB)
+        .about("Create the Line_Break property tables.")
+        .before_help(ABOUT_LINE_BREAK)
+        .arg(ucd_dir.clone())
+        .arg(flag_fst_dir.clone())
+        .arg(flag_no_lazy_static.clone())
+        .arg(flag_name("LINE_BREAK"))
+        .arg(flag_chars.clone())
+        .arg(Arg::with_name("enum")
+            .long("enum")
+            .help("Emit a single table that maps codepoints to Line_Break \
+                   values."))
+        .arg(Arg::with_name("trie")
+            .long("trie")
+            .conflicts_with("fst-dir")
+            .help("Emit each table as a trie from the ucd-trie crate, \
+                   which supports fast lookups in constant time."));
+
     let cmd_names = SubCommand::with_name("names")
         .author(crate_authors!())
         .version(crate_version!())
@@ -122,8 +495,11 @@ pub fn app() -> App<'static, 'static> {
         .about("Create a mapping from character name to codepoint.")
         .before_help(ABOUT_NAMES)
         .arg(ucd_dir.clone())
+        .arg(flag_cache_dir.clone())
         .arg(flag_fst_dir.clone())
+        .arg(flag_no_lazy_static.clone())
         .arg(flag_chars.clone().conflicts_with("tagged"))
+        .arg(flag_front_coded.clone())
         .arg(flag_name("NAMES"))
         .arg(Arg::with_name("no-aliases")
             .long("no-aliases")
@@ -149,13 +525,121 @@ pub fn app() -> App<'static, 'static> {
             .long("normalize")
             .help("Normalize all character names according to UAX44-LM2."));
 
+    let cmd_property_bool = SubCommand::with_name("property-bool")
+        .author(crate_authors!())
+        .version(crate_version!())
+        .template(TEMPLATE_SUB)
+        .about("Create boolean property tables.")
+        .before_help(ABOUT_PROPERTY_BOOL)
+        .arg(ucd_dir.clone())
+        .arg(flag_fst_dir.clone())
+        .arg(flag_no_lazy_static.clone())
+        .arg(flag_chars.clone())
+        .arg(Arg::with_name("include")
+            .long("include")
+            .takes_value(true)
+            .use_delimiter(true)
+            .help("A comma separated list of properties to emit. When \
+                   absent, every property is emitted."))
+        .arg(Arg::with_name("exclude")
+            .long("exclude")
+            .takes_value(true)
+            .use_delimiter(true)
+            .help("A comma separated list of properties to omit."))
+        .arg(Arg::with_name("trie")
+            .long("trie")
+            .conflicts_with("fst-dir")
+            .help("Emit each table as a trie from the ucd-trie crate, \
+                   which supports fast lookups in constant time."));
+
+    let cmd_repl = SubCommand::with_name("repl")
+        .author(crate_authors!())
+        .version(crate_version!())
+        .template(TEMPLATE_SUB)
+        .about("Interactively explore the Unicode character database.")
+        .before_help(ABOUT_REPL)
+        .arg(ucd_dir.clone())
+        .arg(flag_cache_dir.clone());
+
+    let cmd_script_extension = SubCommand::with_name("script-extension")
+        .author(crate_authors!())
+        .version(crate_version!())
+        .template(TEMPLATE_SUB)
+        .about("Create the Script_Extensions property tables.")
+        .before_help(ABOUT_SCRIPT_EXTENSION)
+        .arg(ucd_dir.clone())
+        .arg(flag_fst_dir.clone())
+        .arg(flag_no_lazy_static.clone())
+        .arg(flag_chars.clone())
+        .arg(Arg::with_name("trie")
+            .long("trie")
+            .conflicts_with("fst-dir")
+            .help("Emit each table as a trie from the ucd-trie crate, \
+                   which supports fast lookups in constant time."));
+
+    let cmd_sentence_break = SubCommand::with_name("sentence-break")
+        .author(crate_authors!())
+        .version(crate_version!())
+        .template(TEMPLATE_SUB)
+        .about("Create the Sentence_Break property tables.")
+        .before_help(ABOUT_SENTENCE_BREAK)
+        .arg(ucd_dir.clone())
+        .arg(flag_fst_dir.clone())
+        .arg(flag_no_lazy_static.clone())
+        .arg(flag_name("SENTENCE_BREAK"))
+        .arg(flag_chars.clone())
+        .arg(Arg::with_name("enum")
+            .long("enum")
+            .help("Emit a single table that maps codepoints to \
+                   Sentence_Break values."))
+        .arg(Arg::with_name("trie")
+            .long("trie")
+            .conflicts_with("fst-dir")
+            .help("Emit each table as a trie from the ucd-trie crate, \
+                   which supports fast lookups in constant time."));
+
+    let cmd_special_casing = SubCommand::with_name("special-casing")
+        .author(crate_authors!())
+        .version(crate_version!())
+        .template(TEMPLATE_SUB)
+        .about("Create the full case mapping tables.")
+        .before_help(ABOUT_SPECIAL_CASING)
+        .arg(ucd_dir.clone())
+        .arg(flag_cache_dir.clone())
+        .arg(flag_fst_dir.clone())
+        .arg(flag_no_lazy_static.clone())
+        .arg(flag_chars.clone())
+        .arg(Arg::with_name("conditional")
+            .long("conditional")
+            .help("Also emit the conditional mappings, in one table per \
+                   set of conditions."));
+
+    let cmd_size_report = SubCommand::with_name("size-report")
+        .author(crate_authors!())
+        .version(crate_version!())
+        .template(TEMPLATE_SUB)
+        .about("Compare the size and speed of each output format.")
+        .before_help(ABOUT_SIZE_REPORT)
+        .arg(ucd_dir.clone())
+        .arg(flag_cache_dir.clone())
+        .arg(Arg::with_name("property")
+            .long("property")
+            .help("The property of the table to report on.")
+            .takes_value(true)
+            .default_value("gc"))
+        .arg(Arg::with_name("table")
+            .required(true)
+            .help("The property value of the table to report on, e.g., Lu \
+                   or Uppercase_Letter."));
+
     let cmd_test_unicode_data = SubCommand::with_name("test-unicode-data")
         .author(crate_authors!())
         .version(crate_version!())
         .template(TEMPLATE_SUB)
         .about("Test the UnicodeData.txt parser.")
         .before_help(ABOUT_TEST_UNICODE_DATA)
-        .arg(ucd_dir.clone());
+        .arg(ucd_dir.clone())
+        .arg(flag_cache_dir.clone());
 
     // The actual App.
     App::new("ucd-generate")
@@ -165,8 +649,22 @@ pub fn app() -> App<'static, 'static> {
         .template(TEMPLATE)
         .max_term_width(100)
         .setting(AppSettings::UnifiedHelpMessage)
+        .subcommand(cmd_bidi_brackets)
+        .subcommand(cmd_bidi_class)
+        .subcommand(cmd_bidi_mirroring_glyph)
+        .subcommand(cmd_case_folding_simple)
+        .subcommand(cmd_case_mapping)
+        .subcommand(cmd_download)
         .subcommand(cmd_general_category)
         .subcommand(cmd_jamo_short_name)
+        .subcommand(cmd_joining_type)
+        .subcommand(cmd_line_break)
         .subcommand(cmd_names)
+        .subcommand(cmd_property_bool)
+        .subcommand(cmd_repl)
+        .subcommand(cmd_script_extension)
+        .subcommand(cmd_sentence_break)
+        .subcommand(cmd_size_report)
+        .subcommand(cmd_special_casing)
         .subcommand(cmd_test_unicode_data)
 }
@@ -1,10 +1,11 @@
-use std::ffi::OsStr;
 use std::ops;
+use std::path::Path;
 
 use clap;
+use ucd_parse::UcdDirectory;
 
-use error::Result;
-use writer::{Writer, WriterBuilder};
+use ucd_generate::error::Result;
+use ucd_generate::writer::{Writer, WriterBuilder};
 
 /// Wraps clap matches and provides convenient accessors to various parameters.
 pub struct ArgMatches<'a>(&'a clap::ArgMatches<'a>);
@@ -19,18 +20,26 @@ impl<'a> ArgMatches<'a> {
         ArgMatches(matches)
     }
 
-    pub fn ucd_dir(&self) -> Result<&OsStr> {
+    pub fn ucd_dir(&self) -> Result<UcdDirectory> {
         match self.value_of_os("ucd-dir") {
-            Some(x) => Ok(x),
+            Some(x) => Ok(UcdDirectory::open(x)?),
             None => err!("missing UCD directory"),
         }
     }
 
+    pub fn cache_dir(&self) -> Option<&Path> {
+        self.value_of_os("cache-dir").map(Path::new)
+    }
+
     pub fn writer(&self, name: &str) -> Result<Writer> {
         let mut builder = WriterBuilder::new(name);
         builder
             .columns(79)
-            .char_literals(self.is_present("chars"));
+            .char_literals(self.is_present("chars"))
+            .share_complements(self.is_present("share-complements"))
+            .front_coded(self.is_present("front-coded"))
+            .trie(self.is_present("trie"))
+            .lazy_static(!self.is_present("no-lazy-static"));
         match self.value_of_os("fst-dir") {
             None => Ok(builder.from_stdout()),
             Some(x) => builder.from_fst_dir(x),
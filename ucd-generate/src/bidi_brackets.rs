@@ -0,0 +1,58 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use ucd_parse::BidiBracket;
+
+/// Build a map from each bracket to the bracket it pairs with, i.e., the
+/// Bidi_Paired_Bracket property, using rows already parsed from
+/// BidiBrackets.txt.
+///
+/// Codepoints that aren't brackets aren't in the map.
+pub fn pairs(rows: &[BidiBracket]) -> BTreeMap<u32, u32> {
+    rows.iter()
+        .map(|row| (row.codepoint.value(), row.pair.value()))
+        .collect()
+}
+
+/// Build a map from each Bidi_Paired_Bracket_Type value to the set of
+/// codepoints with that value, using rows already parsed from
+/// BidiBrackets.txt.
+///
+/// The keys of the map are the long names of each value, i.e., `Open` and
+/// `Close`. The `None` value, which every other codepoint has, is omitted.
+pub fn types(rows: &[BidiBracket]) -> BTreeMap<String, BTreeSet<u32>> {
+    let mut bytype: BTreeMap<String, BTreeSet<u32>> = BTreeMap::new();
+    for row in rows {
+        bytype.entry(row.bracket_type.long_name().to_string())
+            .or_insert(BTreeSet::new())
+            .insert(row.codepoint.value());
+    }
+    bytype
+}
+
+#[cfg(test)]
+mod tests {
+    use ucd_parse::{BidiBracket, UcdFile};
+
+    #[test]
+    fn pairs_and_types() {
+        let rows: Vec<BidiBracket> = BidiBracket::from_data("\
+0028; 0029; o # LEFT PARENTHESIS
+0029; 0028; c # RIGHT PARENTHESIS
+005B; 005D; o # LEFT SQUARE BRACKET
+005D; 005B; c # RIGHT SQUARE BRACKET
+").collect::<Result<_, _>>().unwrap();
+
+        let map = super::pairs(&rows);
+        assert_eq!(map.len(), 4);
+        assert_eq!(map[&0x28], 0x29);
+        assert_eq!(map[&0x5D], 0x5B);
+
+        let bytype = super::types(&rows);
+        let get = |t: &str| -> Vec<u32> {
+            bytype[t].iter().cloned().collect()
+        };
+        assert_eq!(bytype.len(), 2);
+        assert_eq!(get("Open"), vec![0x28, 0x5B]);
+        assert_eq!(get("Close"), vec![0x29, 0x5D]);
+    }
+}
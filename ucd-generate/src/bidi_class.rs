@@ -0,0 +1,139 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use ucd_parse::{
+    self, DerivedBidiClass, MissingValue, UnicodeData, UnicodeDataRanges,
+};
+
+use error::Result;
+use property_enum;
+use util::PropertyValues;
+
+/// Build a map from each Bidi_Class value to the set of codepoints with
+/// that value, using the UCD in the given directory.
+///
+/// If `derived` is true, then the values are read from
+/// extracted/DerivedBidiClass.txt. Otherwise, they are read from
+/// UnicodeData.txt. Either way, the defaults for unlisted codepoints are
+/// taken from the `# @missing:` lines of extracted/DerivedBidiClass.txt.
+pub fn tables_from_ucd_dir<P: AsRef<Path>>(
+    ucd_dir: P,
+    derived: bool,
+) -> Result<BTreeMap<String, BTreeSet<u32>>> {
+    let propvals = PropertyValues::from_ucd_dir(&ucd_dir)?;
+    let missing = ucd_parse::parse_missing_lines::<_, DerivedBidiClass>(
+        &ucd_dir)?;
+    if derived {
+        let rows = ucd_parse::parse(&ucd_dir)?;
+        derived_tables(&propvals, rows, missing)
+    } else {
+        let rows = ucd_parse::parse(&ucd_dir)?;
+        tables(&propvals, rows, missing)
+    }
+}
+
+/// Build a map from each Bidi_Class value to the set of codepoints with
+/// that value, using rows already parsed from UnicodeData.txt and
+/// `# @missing:` lines already parsed from extracted/DerivedBidiClass.txt.
+///
+/// The keys of the map are the canonical names of each value, as given by
+/// `propvals`. Every codepoint is in exactly one set. Codepoints that
+/// aren't listed in UnicodeData.txt have the default value given by
+/// `missing`, which is `Right_To_Left` or `Arabic_Letter` in blocks
+/// reserved for right-to-left scripts, and `Left_To_Right` in most other
+/// places.
+pub fn tables(
+    propvals: &PropertyValues,
+    rows: Vec<UnicodeData<'static>>,
+    missing: Vec<MissingValue>,
+) -> Result<BTreeMap<String, BTreeSet<u32>>> {
+    let ranges: Vec<_> = UnicodeDataRanges::new(rows).collect();
+    property_enum::tables_with_defaults(
+        propvals,
+        "bc",
+        missing.iter().map(|m| (m.codepoints, &*m.value)),
+        ranges.iter().map(|&(range, ref row)| (range, &*row.bidi_class)),
+    )
+}
+
+/// Build a map from each Bidi_Class value to the set of codepoints with
+/// that value, using rows and `# @missing:` lines already parsed from
+/// extracted/DerivedBidiClass.txt.
+///
+/// This is like `tables`, except the defaults for unlisted codepoints are
+/// taken from `missing` instead.
+pub fn derived_tables(
+    propvals: &PropertyValues,
+    rows: Vec<DerivedBidiClass>,
+    missing: Vec<MissingValue>,
+) -> Result<BTreeMap<String, BTreeSet<u32>>> {
+    property_enum::tables_with_defaults(
+        propvals,
+        "bc",
+        missing.iter().map(|m| (m.codepoints, &*m.value)),
+        rows.iter().map(|row| (row.codepoints, &*row.value)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use ucd_parse::{DerivedBidiClass, MissingValue, UcdFile, UnicodeData};
+
+    use util::{self, PropertyValues};
+
+    const ALIASES: &'static str = "\
+bc ; AL ; Arabic_Letter
+bc ; BN ; Boundary_Neutral
+bc ; ET ; European_Terminator
+bc ; L ; Left_To_Right
+bc ; NSM ; Nonspacing_Mark
+bc ; R ; Right_To_Left
+";
+
+    fn propvals() -> PropertyValues {
+        util::property_values("bc ; Bidi_Class\n", ALIASES)
+    }
+
+    #[test]
+    fn missing_defaults() {
+        let rows: Vec<UnicodeData> = UnicodeData::from_data("\
+0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;
+05D0;HEBREW LETTER ALEF;Lo;0;R;;;;;N;;;;;
+0610;ARABIC SIGN SALLALLAHOU ALAYHE WASSALLAM;Mn;230;NSM;;;;;N;;;;;
+").collect::<Result<_, _>>().unwrap();
+        let missing: Vec<MissingValue> = vec![
+            "# @missing: 0000..10FFFF; Left_To_Right".parse().unwrap(),
+            "# @missing: 0590..05FF; Right_To_Left".parse().unwrap(),
+            "# @missing: 0600..07BF; Arabic_Letter".parse().unwrap(),
+        ];
+        let bybc = super::tables(&propvals(), rows, missing).unwrap();
+
+        assert_eq!(bybc.len(), 4);
+        assert_eq!(bybc["Right_To_Left"].len(), 0x70);
+        assert_eq!(bybc["Arabic_Letter"].len(), 0x1C0 - 1);
+        assert_eq!(bybc["Nonspacing_Mark"].len(), 1);
+        assert!(bybc["Left_To_Right"].contains(&0x0041));
+        assert!(bybc["Left_To_Right"].contains(&0x0800));
+        assert!(!bybc["Left_To_Right"].contains(&0x05D1));
+    }
+
+    #[test]
+    fn derived_defaults() {
+        let rows: Vec<DerivedBidiClass> = DerivedBidiClass::from_data("\
+0041..005A    ; L
+05C8..05CF    ; R
+").collect::<Result<_, _>>().unwrap();
+        let missing: Vec<MissingValue> = vec![
+            "# @missing: 0000..07FF; Left_To_Right".parse().unwrap(),
+            "# @missing: 0600..07BF; Arabic_Letter".parse().unwrap(),
+        ];
+        let bybc = super::derived_tables(&propvals(), rows, missing)
+            .unwrap();
+
+        assert_eq!(bybc.len(), 3);
+        assert_eq!(bybc["Right_To_Left"].len(), 8);
+        assert_eq!(bybc["Arabic_Letter"].len(), 0x1C0);
+        assert!(bybc["Left_To_Right"].contains(&0x05D0));
+        assert!(!bybc["Left_To_Right"].contains(&0x0800));
+    }
+}
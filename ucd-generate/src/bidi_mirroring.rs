@@ -0,0 +1,67 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use ucd_parse::{self, BidiMirroring, UnicodeData, UnicodeDataRanges};
+
+use error::Result;
+
+/// Build a map from codepoint to its Bidi_Mirroring_Glyph, using the
+/// BidiMirroring.txt file in the given UCD directory.
+pub fn glyphs_from_ucd_dir<P: AsRef<Path>>(
+    ucd_dir: P,
+) -> Result<BTreeMap<u32, u32>> {
+    let rows: Vec<BidiMirroring> = ucd_parse::parse(ucd_dir)?;
+    Ok(glyphs(&rows))
+}
+
+/// Build a map from codepoint to its Bidi_Mirroring_Glyph, using rows
+/// already parsed from BidiMirroring.txt.
+///
+/// Codepoints without a mirroring glyph aren't in the map.
+pub fn glyphs(rows: &[BidiMirroring]) -> BTreeMap<u32, u32> {
+    rows.iter()
+        .map(|row| (row.codepoint.value(), row.mirror.value()))
+        .collect()
+}
+
+/// Build the set of codepoints with the Bidi_Mirrored property, using rows
+/// already parsed from UnicodeData.txt.
+///
+/// This is a superset of the keys of the map returned by `glyphs`, since
+/// some mirrored characters, such as U+2201 COMPLEMENT, have no character
+/// whose glyph is their mirror image.
+pub fn mirrored(rows: Vec<UnicodeData<'static>>) -> BTreeSet<u32> {
+    let mut set = BTreeSet::new();
+    for (range, row) in UnicodeDataRanges::new(rows) {
+        if row.bidi_mirrored {
+            set.extend(range.start.value()..(range.end.value() + 1));
+        }
+    }
+    set
+}
+
+#[cfg(test)]
+mod tests {
+    use ucd_parse::{BidiMirroring, UcdFile, UnicodeData};
+
+    #[test]
+    fn glyphs_and_mirrored() {
+        let rows: Vec<BidiMirroring> = BidiMirroring::from_data("\
+0028; 0029 # LEFT PARENTHESIS
+0029; 0028 # RIGHT PARENTHESIS
+2208; 220B # ELEMENT OF
+").collect::<Result<_, _>>().unwrap();
+        let map = super::glyphs(&rows);
+        assert_eq!(map.len(), 3);
+        assert_eq!(map[&0x28], 0x29);
+        assert_eq!(map[&0x2208], 0x220B);
+
+        let rows: Vec<UnicodeData> = UnicodeData::from_data("\
+0028;LEFT PARENTHESIS;Ps;0;ON;;;;;Y;OPENING PARENTHESIS;;;;
+0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;
+2201;COMPLEMENT;Sm;0;ON;;;;;Y;;;;;
+").collect::<Result<_, _>>().unwrap();
+        let set = super::mirrored(rows);
+        assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![0x28, 0x2201]);
+    }
+}
@@ -0,0 +1,351 @@
+// This module provides a binary cache of parsed UCD files.
+//
+// Parsing UnicodeData.txt (and to a lesser extent, the other files) is a
+// significant fraction of the time taken by most subcommands. When a cache
+// directory is given, the parsed records are written to a binary file in that
+// directory on first use. Subsequent invocations load the records from that
+// file instead of parsing the UCD again, as long as the source file has not
+// changed.
+
+use std::borrow::Cow;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use byteorder::{ByteOrder, LittleEndian as LE};
+use ucd_parse::{
+    self, Codepoint, NameAlias, NameAliasLabel, UcdFile, UnicodeData,
+    UnicodeDataDecomposition,
+};
+
+use ucd_generate::error::Result;
+
+/// The bytes at the start of every cache file. This should be changed
+/// whenever the encoding of any record changes.
+const MAGIC: &'static [u8] = b"ucd-generate-cache-1\n";
+
+/// Parse all records of a particular UCD file.
+///
+/// If `cache_dir` is given, then the records are loaded from a cache in that
+/// directory when possible. If the cache is missing or out of date, then the
+/// records are parsed from the UCD and the cache is refreshed.
+pub fn parse<D: Cached + Send>(
+    ucd_dir: &Path,
+    cache_dir: Option<&Path>,
+) -> Result<Vec<D>> {
+    let cache_dir = match cache_dir {
+        None => return parse_all(ucd_dir),
+        Some(cache_dir) => cache_dir,
+    };
+    let source = D::file_path(ucd_dir);
+    let header = header(&source)?;
+    let cache_path = cache_file_path::<D>(cache_dir);
+    if let Some(records) = read_cache(&cache_path, &header)? {
+        return Ok(records);
+    }
+
+    let records: Vec<D> = parse_all(ucd_dir)?;
+    write_cache(cache_dir, &cache_path, &header, &records)?;
+    Ok(records)
+}
+
+/// Parse all records of a particular UCD file without using the cache.
+///
+/// The records are parsed in parallel when the `parallel` feature is enabled.
+#[cfg(feature = "parallel")]
+fn parse_all<D: UcdFile + Send>(ucd_dir: &Path) -> Result<Vec<D>> {
+    Ok(ucd_parse::parse_parallel(ucd_dir)?)
+}
+
+#[cfg(not(feature = "parallel"))]
+fn parse_all<D: UcdFile>(ucd_dir: &Path) -> Result<Vec<D>> {
+    Ok(ucd_parse::parse(ucd_dir)?)
+}
+
+/// A record type that can be stored in a cache file.
+pub trait Cached: UcdFile {
+    /// Append a binary encoding of this record to `buf`.
+    fn encode(&self, buf: &mut Vec<u8>);
+
+    /// Decode a single record from the front of the given decoder.
+    fn decode(dec: &mut Decoder) -> Result<Self>;
+}
+
+impl Cached for UnicodeData<'static> {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        encode_u32(buf, self.codepoint.value());
+        encode_str(buf, &self.name);
+        encode_str(buf, &self.general_category);
+        buf.push(self.canonical_combining_class);
+        encode_str(buf, &self.bidi_class);
+        match self.decomposition.tag {
+            None => encode_str(buf, ""),
+            Some(ref tag) => encode_str(buf, &tag.to_string()),
+        }
+        encode_u32(buf, self.decomposition.len as u32);
+        for cp in self.decomposition.mapping() {
+            encode_u32(buf, cp.value());
+        }
+        encode_opt_u8(buf, self.numeric_type_decimal);
+        encode_opt_u8(buf, self.numeric_type_digit);
+        match self.numeric_type_numeric {
+            None => encode_str(buf, ""),
+            Some(ref n) => encode_str(buf, &n.to_string()),
+        }
+        buf.push(self.bidi_mirrored as u8);
+        encode_str(buf, &self.unicode1_name);
+        encode_str(buf, &self.iso_comment);
+        encode_opt_codepoint(buf, self.simple_uppercase_mapping);
+        encode_opt_codepoint(buf, self.simple_lowercase_mapping);
+        encode_opt_codepoint(buf, self.simple_titlecase_mapping);
+    }
+
+    fn decode(dec: &mut Decoder) -> Result<UnicodeData<'static>> {
+        let mut x = UnicodeData::default();
+        x.codepoint = dec.codepoint()?;
+        x.name = Cow::Owned(dec.string()?);
+        x.general_category = Cow::Owned(dec.string()?);
+        x.canonical_combining_class = dec.u8()?;
+        x.bidi_class = Cow::Owned(dec.string()?);
+        let tag = dec.string()?;
+        let tag = if tag.is_empty() { None } else { Some(tag.parse()?) };
+        let mut mapping = vec![];
+        for _ in 0..dec.u32()? {
+            mapping.push(dec.codepoint()?);
+        }
+        x.decomposition = UnicodeDataDecomposition::new(tag, &mapping)?;
+        x.numeric_type_decimal = dec.opt_u8()?;
+        x.numeric_type_digit = dec.opt_u8()?;
+        let numeric = dec.string()?;
+        if !numeric.is_empty() {
+            x.numeric_type_numeric = Some(numeric.parse()?);
+        }
+        x.bidi_mirrored = dec.u8()? == 1;
+        x.unicode1_name = Cow::Owned(dec.string()?);
+        x.iso_comment = Cow::Owned(dec.string()?);
+        x.simple_uppercase_mapping = dec.opt_codepoint()?;
+        x.simple_lowercase_mapping = dec.opt_codepoint()?;
+        x.simple_titlecase_mapping = dec.opt_codepoint()?;
+        // This interns repeated values such as the general category.
+        Ok(x.into_owned())
+    }
+}
+
+impl Cached for NameAlias<'static> {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        use ucd_parse::NameAliasLabel::*;
+
+        encode_u32(buf, self.codepoint.value());
+        encode_str(buf, &self.alias);
+        buf.push(match self.label {
+            Correction => 0,
+            Control => 1,
+            Alternate => 2,
+            Figment => 3,
+            Abbreviation => 4,
+        });
+    }
+
+    fn decode(dec: &mut Decoder) -> Result<NameAlias<'static>> {
+        use ucd_parse::NameAliasLabel::*;
+
+        let codepoint = dec.codepoint()?;
+        let alias = Cow::Owned(dec.string()?);
+        let label: NameAliasLabel = match dec.u8()? {
+            0 => Correction,
+            1 => Control,
+            2 => Alternate,
+            3 => Figment,
+            4 => Abbreviation,
+            n => return err!("invalid name alias label in cache: {}", n),
+        };
+        Ok(NameAlias { codepoint: codepoint, alias: alias, label: label })
+    }
+}
+
+/// A cursor over the encoded records in a cache file.
+pub struct Decoder<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        if self.buf.len() < n {
+            return err!("unexpected end of cache file");
+        }
+        let (bytes, rest) = self.buf.split_at(n);
+        self.buf = rest;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(LE::read_u32(self.take(4)?))
+    }
+
+    fn codepoint(&mut self) -> Result<Codepoint> {
+        Ok(Codepoint::from_u32(self.u32()?)?)
+    }
+
+    fn opt_u8(&mut self) -> Result<Option<u8>> {
+        match self.u8()? {
+            0 => Ok(None),
+            _ => Ok(Some(self.u8()?)),
+        }
+    }
+
+    fn opt_codepoint(&mut self) -> Result<Option<Codepoint>> {
+        match self.u8()? {
+            0 => Ok(None),
+            _ => Ok(Some(self.codepoint()?)),
+        }
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let len = self.u32()? as usize;
+        match String::from_utf8(self.take(len)?.to_vec()) {
+            Ok(s) => Ok(s),
+            Err(err) => err!("invalid UTF-8 in cache file: {}", err),
+        }
+    }
+}
+
+fn encode_u32(buf: &mut Vec<u8>, n: u32) {
+    let mut bytes = [0; 4];
+    LE::write_u32(&mut bytes, n);
+    buf.extend_from_slice(&bytes);
+}
+
+fn encode_str(buf: &mut Vec<u8>, s: &str) {
+    encode_u32(buf, s.len() as u32);
+    buf.extend_from_slice(s.as_bytes());
+}
+
+fn encode_opt_u8(buf: &mut Vec<u8>, n: Option<u8>) {
+    match n {
+        None => buf.push(0),
+        Some(n) => {
+            buf.push(1);
+            buf.push(n);
+        }
+    }
+}
+
+fn encode_opt_codepoint(buf: &mut Vec<u8>, cp: Option<Codepoint>) {
+    match cp {
+        None => buf.push(0),
+        Some(cp) => {
+            buf.push(1);
+            encode_u32(buf, cp.value());
+        }
+    }
+}
+
+/// Build the header of a cache file for the given UCD source file.
+///
+/// The header records the size and modification time of the source file, so
+/// that a stale cache is never used.
+fn header(source: &Path) -> Result<Vec<u8>> {
+    let md = fs::metadata(source)?;
+    let mtime = match md.modified()?.duration_since(UNIX_EPOCH) {
+        Ok(dur) => dur,
+        Err(err) => return err!("invalid mtime for {:?}: {}", source, err),
+    };
+    let mut header = MAGIC.to_vec();
+    encode_str(&mut header, &source.to_string_lossy());
+    encode_u32(&mut header, (md.len() >> 32) as u32);
+    encode_u32(&mut header, md.len() as u32);
+    encode_u32(&mut header, (mtime.as_secs() >> 32) as u32);
+    encode_u32(&mut header, mtime.as_secs() as u32);
+    encode_u32(&mut header, mtime.subsec_nanos());
+    Ok(header)
+}
+
+/// Return the path of the cache file for the given record type.
+fn cache_file_path<D: UcdFile>(cache_dir: &Path) -> PathBuf {
+    let name = D::relative_file_path().to_string_lossy().replace('/', "-");
+    cache_dir.join(format!("{}.cache", name))
+}
+
+/// Read all records from the given cache file.
+///
+/// If the cache file doesn't exist or if its header doesn't match the header
+/// given, then `None` is returned.
+fn read_cache<D: Cached>(
+    cache_path: &Path,
+    header: &[u8],
+) -> Result<Option<Vec<D>>> {
+    let mut buf = vec![];
+    match File::open(cache_path) {
+        Ok(mut file) => { file.read_to_end(&mut buf)?; }
+        Err(_) => return Ok(None),
+    }
+    if !buf.starts_with(header) {
+        return Ok(None);
+    }
+    let mut dec = Decoder { buf: &buf[header.len()..] };
+    let mut records = Vec::with_capacity(dec.u32()? as usize);
+    while !dec.buf.is_empty() {
+        records.push(D::decode(&mut dec)?);
+    }
+    Ok(Some(records))
+}
+
+/// Write the given records to a cache file.
+///
+/// The cache is written to a temporary file first and then renamed, so that
+/// a partially written cache is never read.
+fn write_cache<D: Cached>(
+    cache_dir: &Path,
+    cache_path: &Path,
+    header: &[u8],
+    records: &[D],
+) -> Result<()> {
+    fs::create_dir_all(cache_dir)?;
+    let mut buf = header.to_vec();
+    encode_u32(&mut buf, records.len() as u32);
+    for record in records {
+        record.encode(&mut buf);
+    }
+    let tmp_path = cache_path.with_extension("cache.tmp");
+    File::create(&tmp_path)?.write_all(&buf)?;
+    fs::rename(&tmp_path, cache_path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use ucd_parse::{NameAlias, UnicodeData};
+
+    use super::{Cached, Decoder};
+
+    fn roundtrip<D: Cached>(line: &str) {
+        let record: D = line.parse().unwrap();
+        let mut buf = vec![];
+        record.encode(&mut buf);
+        let mut dec = Decoder { buf: &buf };
+        assert_eq!(record, D::decode(&mut dec).unwrap());
+        assert!(dec.buf.is_empty());
+    }
+
+    #[test]
+    fn unicode_data() {
+        roundtrip::<UnicodeData>(
+            "00BC;VULGAR FRACTION ONE QUARTER;No;0;ON;\
+             <fraction> 0031 2044 0034;;;1/4;N;FRACTION ONE QUARTER;;;;");
+        roundtrip::<UnicodeData>(
+            "0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;");
+        roundtrip::<UnicodeData>(
+            "0037;DIGIT SEVEN;Nd;0;EN;;7;7;7;N;;;;;");
+    }
+
+    #[test]
+    fn name_alias() {
+        roundtrip::<NameAlias>("E01EF;VS256;abbreviation");
+        roundtrip::<NameAlias>("0081;HIGH OCTET PRESET;figment");
+    }
+}
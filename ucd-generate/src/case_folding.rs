@@ -0,0 +1,104 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use ucd_parse::{self, CaseFold, CaseStatus};
+
+use error::Result;
+
+/// Build a map from codepoint to its simple case folding, using the
+/// CaseFolding.txt file in the given UCD directory.
+pub fn simple_from_ucd_dir<P: AsRef<Path>>(
+    ucd_dir: P,
+) -> Result<BTreeMap<u32, u32>> {
+    let rows: Vec<CaseFold> = ucd_parse::parse(ucd_dir)?;
+    Ok(simple(&rows))
+}
+
+/// Build a map from codepoint to its simple case folding, using rows already
+/// parsed from CaseFolding.txt.
+///
+/// Only mappings with a status of `C` (common) or `S` (simple) are used.
+/// Codepoints that fold to themselves aren't in the map.
+pub fn simple(rows: &[CaseFold]) -> BTreeMap<u32, u32> {
+    let mut map = BTreeMap::new();
+    for row in rows {
+        match row.status {
+            CaseStatus::Common | CaseStatus::Simple => {
+                map.insert(row.codepoint.value(), row.mapping[0].value());
+            }
+            CaseStatus::Full | CaseStatus::Special => {}
+        }
+    }
+    map
+}
+
+/// Convert a simple case folding map into its circular representation.
+///
+/// Codepoints that fold to the same codepoint form an equivalence class, or
+/// orbit, under simple case folding. In the map returned, each codepoint in
+/// an orbit (including the codepoint they all fold to) maps to the next
+/// largest codepoint in its orbit, and the largest maps to the smallest. So
+/// every codepoint that is equivalent to a codepoint `cp` under simple case
+/// folding can be found by following the map from `cp` until it returns to
+/// `cp`. This is the representation used for case insensitive matching in
+/// regular expression engines.
+pub fn circular(fold: &BTreeMap<u32, u32>) -> BTreeMap<u32, u32> {
+    let mut orbits: BTreeMap<u32, BTreeSet<u32>> = BTreeMap::new();
+    for (&cp, &folded) in fold {
+        let orbit = orbits.entry(folded).or_insert(BTreeSet::new());
+        orbit.insert(cp);
+        orbit.insert(folded);
+    }
+
+    let mut map = BTreeMap::new();
+    for orbit in orbits.values() {
+        let orbit: Vec<u32> = orbit.iter().cloned().collect();
+        for (i, &cp) in orbit.iter().enumerate() {
+            map.insert(cp, orbit[(i + 1) % orbit.len()]);
+        }
+    }
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use ucd_parse::{CaseFold, UcdFile};
+
+    // A small excerpt of CaseFolding.txt.
+    const CASE_FOLDING: &'static str = "\
+0041; C; 0061; # LATIN CAPITAL LETTER A
+004B; C; 006B; # LATIN CAPITAL LETTER K
+00DF; F; 0073 0073; # LATIN SMALL LETTER SHARP S
+0049; T; 0131; # LATIN CAPITAL LETTER I
+1E9E; F; 0073 0073; # LATIN CAPITAL LETTER SHARP S
+1E9E; S; 00DF; # LATIN CAPITAL LETTER SHARP S
+212A; C; 006B; # KELVIN SIGN
+";
+
+    fn rows() -> Vec<CaseFold> {
+        CaseFold::from_data(CASE_FOLDING)
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn simple() {
+        let fold = super::simple(&rows());
+        let pairs: Vec<(u32, u32)> =
+            fold.iter().map(|(&k, &v)| (k, v)).collect();
+        assert_eq!(pairs, vec![
+            (0x41, 0x61), (0x4B, 0x6B), (0x1E9E, 0xDF), (0x212A, 0x6B),
+        ]);
+    }
+
+    #[test]
+    fn circular() {
+        let orbits = super::circular(&super::simple(&rows()));
+        let pairs: Vec<(u32, u32)> =
+            orbits.iter().map(|(&k, &v)| (k, v)).collect();
+        assert_eq!(pairs, vec![
+            (0x41, 0x61), (0x4B, 0x6B), (0x61, 0x41), (0x6B, 0x212A),
+            (0xDF, 0x1E9E), (0x1E9E, 0xDF), (0x212A, 0x4B),
+        ]);
+    }
+}
@@ -0,0 +1,271 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use ucd_parse::{self, Codepoint, SpecialCaseMapping, UnicodeData};
+
+use error::Result;
+
+/// The simple case mappings of every codepoint, as given by UnicodeData.txt.
+///
+/// Each map only contains codepoints whose mapping differs from the
+/// codepoint itself.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SimpleCaseMaps {
+    /// The Simple_Uppercase_Mapping property.
+    pub upper: BTreeMap<u32, u32>,
+    /// The Simple_Lowercase_Mapping property.
+    pub lower: BTreeMap<u32, u32>,
+    /// The Simple_Titlecase_Mapping property.
+    pub title: BTreeMap<u32, u32>,
+}
+
+/// The full case mappings of every codepoint, which may map a codepoint to
+/// zero or more codepoints.
+///
+/// Each map only contains codepoints whose mapping differs from the
+/// codepoint itself.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FullCaseMaps {
+    /// The Uppercase_Mapping property.
+    pub upper: BTreeMap<u32, Vec<u32>>,
+    /// The Lowercase_Mapping property.
+    pub lower: BTreeMap<u32, Vec<u32>>,
+    /// The Titlecase_Mapping property.
+    pub title: BTreeMap<u32, Vec<u32>>,
+}
+
+/// Build the simple case mappings using the UnicodeData.txt file in the
+/// given UCD directory.
+pub fn simple_from_ucd_dir<P: AsRef<Path>>(
+    ucd_dir: P,
+) -> Result<SimpleCaseMaps> {
+    let rows: Vec<UnicodeData> = ucd_parse::parse(ucd_dir)?;
+    Ok(simple(&rows))
+}
+
+/// Build the simple case mappings using rows already parsed from
+/// UnicodeData.txt.
+///
+/// When the titlecase mapping of a row is empty, its uppercase mapping is
+/// used instead, as specified by UAX #44.
+pub fn simple(rows: &[UnicodeData]) -> SimpleCaseMaps {
+    let mut maps = SimpleCaseMaps::default();
+    for row in rows {
+        let cp = row.codepoint.value();
+        let upper = row.simple_uppercase_mapping.map(|cp| cp.value());
+        let lower = row.simple_lowercase_mapping.map(|cp| cp.value());
+        let title = row.simple_titlecase_mapping.map(|cp| cp.value());
+        insert(&mut maps.upper, cp, upper);
+        insert(&mut maps.lower, cp, lower);
+        insert(&mut maps.title, cp, title.or(upper));
+    }
+    maps
+}
+
+/// Build the full case mappings, excluding conditional mappings, using the
+/// UnicodeData.txt and SpecialCasing.txt files in the given UCD directory.
+pub fn full_from_ucd_dir<P: AsRef<Path>>(
+    ucd_dir: P,
+) -> Result<FullCaseMaps> {
+    let rows: Vec<UnicodeData> = ucd_parse::parse(&ucd_dir)?;
+    let special: Vec<SpecialCaseMapping> = ucd_parse::parse(&ucd_dir)?;
+    Ok(full(&simple(&rows), &special))
+}
+
+/// Build the full case mappings by merging the given simple case mappings
+/// with the unconditional mappings in SpecialCasing.txt, which take
+/// precedence. Conditional mappings are ignored.
+pub fn full(
+    simple: &SimpleCaseMaps,
+    special: &[SpecialCaseMapping],
+) -> FullCaseMaps {
+    let expand = |map: &BTreeMap<u32, u32>| -> BTreeMap<u32, Vec<u32>> {
+        map.iter().map(|(&k, &v)| (k, vec![v])).collect()
+    };
+    let mut maps = FullCaseMaps {
+        upper: expand(&simple.upper),
+        lower: expand(&simple.lower),
+        title: expand(&simple.title),
+    };
+    for row in special.iter().filter(|row| !row.is_conditional()) {
+        let cp = row.codepoint.value();
+        override_mapping(&mut maps.upper, cp, &row.uppercase);
+        override_mapping(&mut maps.lower, cp, &row.lowercase);
+        override_mapping(&mut maps.title, cp, &row.titlecase);
+    }
+    maps
+}
+
+/// Group the conditional mappings in SpecialCasing.txt by their conditions.
+///
+/// The key of each group is its list of conditions joined by `_`, e.g.,
+/// `Final_Sigma` or `tr_After_I`. A group only contains the mappings that
+/// differ from the given full case mappings, which should not include any
+/// conditional mappings.
+pub fn conditional(
+    full: &FullCaseMaps,
+    special: &[SpecialCaseMapping],
+) -> BTreeMap<String, FullCaseMaps> {
+    let mut groups: BTreeMap<String, FullCaseMaps> = BTreeMap::new();
+    for row in special.iter().filter(|row| row.is_conditional()) {
+        let cp = row.codepoint.value();
+        let group = groups
+            .entry(row.conditions.join("_"))
+            .or_insert(FullCaseMaps::default());
+        add_conditional(&mut group.upper, &full.upper, cp, &row.uppercase);
+        add_conditional(&mut group.lower, &full.lower, cp, &row.lowercase);
+        add_conditional(&mut group.title, &full.title, cp, &row.titlecase);
+    }
+    groups
+}
+
+/// Convert a map from codepoint to codepoint into a map from codepoint to
+/// the difference between the codepoint mapped to and the codepoint.
+///
+/// Runs of consecutive codepoints often have the same difference, e.g.,
+/// every ASCII uppercase letter maps to its lowercase letter by adding `32`,
+/// so a table of differences compresses far better when it is stored as
+/// ranges of values.
+pub fn deltas(map: &BTreeMap<u32, u32>) -> BTreeMap<u32, i64> {
+    map.iter().map(|(&k, &v)| (k, v as i64 - k as i64)).collect()
+}
+
+fn insert(map: &mut BTreeMap<u32, u32>, cp: u32, mapped: Option<u32>) {
+    if let Some(mapped) = mapped {
+        if mapped != cp {
+            map.insert(cp, mapped);
+        }
+    }
+}
+
+/// Replace the mapping of the given codepoint. If the new mapping is the
+/// codepoint itself, then the codepoint is removed from the map.
+fn override_mapping(
+    map: &mut BTreeMap<u32, Vec<u32>>,
+    cp: u32,
+    mapping: &[Codepoint],
+) {
+    let mapping: Vec<u32> = mapping.iter().map(|cp| cp.value()).collect();
+    if mapping == [cp] {
+        map.remove(&cp);
+    } else {
+        map.insert(cp, mapping);
+    }
+}
+
+/// Add the given conditional mapping of a codepoint to `map`, unless it is
+/// the same as the codepoint's mapping in `base`.
+fn add_conditional(
+    map: &mut BTreeMap<u32, Vec<u32>>,
+    base: &BTreeMap<u32, Vec<u32>>,
+    cp: u32,
+    mapping: &[Codepoint],
+) {
+    let mapping: Vec<u32> = mapping.iter().map(|cp| cp.value()).collect();
+    let unconditional = base.get(&cp).cloned().unwrap_or(vec![cp]);
+    if mapping != unconditional {
+        map.insert(cp, mapping);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use ucd_parse::{SpecialCaseMapping, UcdFile, UnicodeData};
+
+    // A small excerpt of UnicodeData.txt. The titlecase field of U+0061 is
+    // left empty, so that it exercises the fallback to the uppercase
+    // mapping.
+    const UNICODE_DATA: &'static str = "\
+0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;
+0061;LATIN SMALL LETTER A;Ll;0;L;;;;;N;;;0041;;
+01C4;LATIN CAPITAL LETTER DZ WITH CARON;Lu;0;L;<compat> 0044 017D;;;;N;;;;01C6;01C5
+01C5;LATIN CAPITAL LETTER D WITH SMALL LETTER Z WITH CARON;Lt;0;L;<compat> 0044 017E;;;;N;;;01C4;01C6;01C5
+01C6;LATIN SMALL LETTER DZ WITH CARON;Ll;0;L;<compat> 0064 017E;;;;N;;;01C4;;01C5
+10D0;GEORGIAN LETTER AN;Ll;0;L;;;;;N;;;1C90;;10D0
+";
+
+    fn rows() -> Vec<UnicodeData<'static>> {
+        UnicodeData::from_data(UNICODE_DATA)
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    fn pairs<V: Clone>(map: &BTreeMap<u32, V>) -> Vec<(u32, V)> {
+        map.iter().map(|(&k, v)| (k, v.clone())).collect()
+    }
+
+    #[test]
+    fn simple() {
+        let maps = super::simple(&rows());
+        assert_eq!(pairs(&maps.upper), vec![
+            (0x61, 0x41), (0x1C5, 0x1C4), (0x1C6, 0x1C4), (0x10D0, 0x1C90),
+        ]);
+        assert_eq!(pairs(&maps.lower), vec![
+            (0x41, 0x61), (0x1C4, 0x1C6), (0x1C5, 0x1C6),
+        ]);
+        // U+10D0 has an explicit titlecase mapping to itself, while U+0061
+        // falls back to its uppercase mapping.
+        assert_eq!(pairs(&maps.title), vec![
+            (0x61, 0x41), (0x1C4, 0x1C5), (0x1C6, 0x1C5),
+        ]);
+    }
+
+    // A small excerpt of SpecialCasing.txt.
+    const SPECIAL_CASING: &'static str = "\
+00DF; 00DF; 0053 0073; 0053 0053; # LATIN SMALL LETTER SHARP S
+01C5; 01C6; 01C5; 01C4; # LATIN CAPITAL LETTER D WITH SMALL LETTER Z WITH CARON
+03A3; 03C2; 03A3; 03A3; Final_Sigma; # GREEK CAPITAL LETTER SIGMA
+0307; ; 0307; 0307; tr After_I; # COMBINING DOT ABOVE
+0049; 0131; 0049; 0049; tr; # LATIN CAPITAL LETTER I
+";
+
+    fn special() -> Vec<SpecialCaseMapping> {
+        SpecialCaseMapping::from_data(SPECIAL_CASING)
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn full() {
+        let maps = super::full(&super::simple(&rows()), &special());
+        assert_eq!(maps.upper[&0xDF], vec![0x53, 0x53]);
+        assert_eq!(maps.title[&0xDF], vec![0x53, 0x73]);
+        assert!(!maps.lower.contains_key(&0xDF));
+        assert_eq!(maps.upper[&0x61], vec![0x41]);
+        assert_eq!(maps.lower[&0x1C5], vec![0x1C6]);
+        assert!(!maps.title.contains_key(&0x1C5));
+        // Conditional mappings are excluded.
+        assert!(!maps.lower.contains_key(&0x49));
+        assert!(!maps.lower.contains_key(&0x307));
+    }
+
+    #[test]
+    fn conditional() {
+        let special = special();
+        let full = super::full(&super::simple(&rows()), &special);
+        let groups = super::conditional(&full, &special);
+        assert_eq!(
+            groups.keys().collect::<Vec<_>>(),
+            vec!["Final_Sigma", "tr", "tr_After_I"]);
+        assert_eq!(pairs(&groups["Final_Sigma"].lower), vec![
+            (0x3A3, vec![0x3C2]),
+        ]);
+        assert!(groups["Final_Sigma"].upper.is_empty());
+        assert_eq!(pairs(&groups["tr"].lower), vec![(0x49, vec![0x131])]);
+        assert_eq!(pairs(&groups["tr_After_I"].lower), vec![(0x307, vec![])]);
+        assert!(groups["tr_After_I"].title.is_empty());
+    }
+
+    #[test]
+    fn deltas() {
+        let maps = super::simple(&rows());
+        assert_eq!(pairs(&super::deltas(&maps.lower)), vec![
+            (0x41, 32), (0x1C4, 2), (0x1C5, 1),
+        ]);
+        assert_eq!(pairs(&super::deltas(&maps.title)), vec![
+            (0x61, -32), (0x1C4, 1), (0x1C6, -1),
+        ]);
+    }
+}
@@ -0,0 +1,26 @@
+use std::collections::BTreeSet;
+
+use ucd_parse::{self, BidiBracket};
+
+use ucd_generate::bidi_brackets;
+use ucd_generate::error::Result;
+
+use args::ArgMatches;
+
+pub fn command(args: ArgMatches) -> Result<()> {
+    let dir = args.ucd_dir()?;
+    let rows: Vec<BidiBracket> = ucd_parse::parse(&dir)?;
+    let pairs = bidi_brackets::pairs(&rows);
+    let bytype = bidi_brackets::types(&rows);
+
+    let mut wtr = args.writer("bidi_brackets")?;
+    wtr.codepoint_to_codepoint(args.name(), &pairs)?;
+    if args.is_present("enum") {
+        wtr.ranges_to_enum("bidi_paired_bracket_type", &bytype)?;
+    } else {
+        let tables: Vec<(&str, &BTreeSet<u32>)> =
+            bytype.iter().map(|(name, set)| (&**name, set)).collect();
+        wtr.ranges_many(&tables)?;
+    }
+    Ok(())
+}
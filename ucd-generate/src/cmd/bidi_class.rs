@@ -0,0 +1,33 @@
+use std::collections::BTreeSet;
+
+use ucd_parse::{self, DerivedBidiClass};
+
+use ucd_generate::bidi_class;
+use ucd_generate::error::Result;
+use ucd_generate::util::PropertyValues;
+
+use args::ArgMatches;
+use cache;
+
+pub fn command(args: ArgMatches) -> Result<()> {
+    let dir = args.ucd_dir()?;
+    let bybc = if args.is_present("derived") {
+        bidi_class::tables_from_ucd_dir(&dir, true)?
+    } else {
+        let propvals = PropertyValues::from_ucd_dir(&dir)?;
+        let rows = cache::parse(dir.as_ref(), args.cache_dir())?;
+        let missing = ucd_parse::parse_missing_lines::<_, DerivedBidiClass>(
+            &dir)?;
+        bidi_class::tables(&propvals, rows, missing)?
+    };
+
+    let mut wtr = args.writer("bidi_class")?;
+    if args.is_present("enum") {
+        wtr.ranges_to_enum("bidi_class", &bybc)?;
+    } else {
+        let tables: Vec<(&str, &BTreeSet<u32>)> =
+            bybc.iter().map(|(name, set)| (&**name, set)).collect();
+        wtr.ranges_many(&tables)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use ucd_generate::bidi_mirroring;
+use ucd_generate::error::Result;
+
+use args::ArgMatches;
+use cache;
+
+pub fn command(args: ArgMatches) -> Result<()> {
+    let dir = args.ucd_dir()?;
+    let glyphs = bidi_mirroring::glyphs_from_ucd_dir(&dir)?;
+    let rows = cache::parse(dir.as_ref(), args.cache_dir())?;
+    let mirrored = bidi_mirroring::mirrored(rows);
+
+    let mut wtr = args.writer("bidi_mirroring_glyph")?;
+    wtr.codepoint_to_codepoint(args.name(), &glyphs)?;
+    wtr.ranges("BIDI_MIRRORED", &mirrored)?;
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use ucd_generate::case_folding;
+use ucd_generate::error::Result;
+
+use args::ArgMatches;
+
+pub fn command_simple(args: ArgMatches) -> Result<()> {
+    let dir = args.ucd_dir()?;
+    let mut map = case_folding::simple_from_ucd_dir(&dir)?;
+    if args.is_present("circular") {
+        map = case_folding::circular(&map);
+    }
+
+    let mut wtr = args.writer("case_folding_simple")?;
+    wtr.codepoint_to_codepoint(args.name(), &map)?;
+    Ok(())
+}
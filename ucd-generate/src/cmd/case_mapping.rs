@@ -0,0 +1,62 @@
+use std::collections::BTreeMap;
+
+use ucd_parse::{self, SpecialCaseMapping, UnicodeData};
+
+use ucd_generate::case_mapping::{self, FullCaseMaps};
+use ucd_generate::error::Result;
+
+use args::ArgMatches;
+use cache;
+
+pub fn command(args: ArgMatches) -> Result<()> {
+    let dir = args.ucd_dir()?;
+    let rows: Vec<UnicodeData> = cache::parse(dir.as_ref(), args.cache_dir())?;
+    let maps = case_mapping::simple(&rows);
+    let tables: [(&str, &BTreeMap<u32, u32>); 3] = [
+        ("uppercase", &maps.upper),
+        ("lowercase", &maps.lower),
+        ("titlecase", &maps.title),
+    ];
+
+    let mut wtr = args.writer("case_mapping")?;
+    for &(name, map) in &tables {
+        if args.is_present("deltas") {
+            wtr.ranges_to_signed_integer(name, &case_mapping::deltas(map))?;
+        } else {
+            wtr.codepoint_to_codepoint(name, map)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn command_full(args: ArgMatches) -> Result<()> {
+    let dir = args.ucd_dir()?;
+    let rows: Vec<UnicodeData> = cache::parse(dir.as_ref(), args.cache_dir())?;
+    let special: Vec<SpecialCaseMapping> = ucd_parse::parse(&dir)?;
+    let full = case_mapping::full(&case_mapping::simple(&rows), &special);
+
+    let mut wtr = args.writer("special_casing")?;
+    for &(name, map) in &full_tables(&full) {
+        wtr.codepoint_to_codepoints(name, map)?;
+    }
+    if args.is_present("conditional") {
+        let groups = case_mapping::conditional(&full, &special);
+        for (conditions, maps) in &groups {
+            for &(name, map) in &full_tables(maps) {
+                if !map.is_empty() {
+                    let name = format!("{}_{}", name, conditions);
+                    wtr.codepoint_to_codepoints(&name, map)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn full_tables(maps: &FullCaseMaps) -> [(&str, &BTreeMap<u32, Vec<u32>>); 3] {
+    [
+        ("uppercase", &maps.upper),
+        ("lowercase", &maps.lower),
+        ("titlecase", &maps.title),
+    ]
+}
@@ -0,0 +1,15 @@
+use ucd_generate::error::Result;
+use ucd_parse::{self, UnicodeVersion};
+
+use args::ArgMatches;
+
+pub fn command(args: ArgMatches) -> Result<()> {
+    let version: UnicodeVersion = match args.value_of("version") {
+        Some(version) => version.parse()?,
+        None => return err!("missing Unicode version"),
+    };
+    let dir = args.value_of_os("dir").expect("the download directory");
+    let ucd_dir = ucd_parse::fetch(version, dir)?;
+    println!("{}", ucd_dir.path().display());
+    Ok(())
+}
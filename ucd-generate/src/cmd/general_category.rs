@@ -0,0 +1,27 @@
+use std::collections::BTreeSet;
+
+use ucd_generate::error::Result;
+use ucd_generate::general_category;
+use ucd_generate::util::PropertyValues;
+
+use args::ArgMatches;
+use cache;
+
+pub fn command(args: ArgMatches) -> Result<()> {
+    let dir = args.ucd_dir()?;
+    let propvals = PropertyValues::from_ucd_dir(&dir)?;
+    let unexpanded = cache::parse(dir.as_ref(), args.cache_dir())?;
+    let bycat = general_category::tables(
+        &propvals, unexpanded, !args.is_present("no-unassigned"))?;
+
+    let mut wtr = args.writer("general_category")?;
+    if args.is_present("enum") {
+        wtr.ranges_to_enum("general_category", &bycat)?;
+    } else {
+        let tables: Vec<(&str, &BTreeSet<u32>)> =
+            bycat.iter().map(|(name, set)| (&**name, set)).collect();
+        wtr.ranges_many(&tables)?;
+    }
+
+    Ok(())
+}
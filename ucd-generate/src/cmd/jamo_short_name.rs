@@ -0,0 +1,13 @@
+use ucd_generate::error::Result;
+use ucd_generate::jamo_short_name;
+
+use args::ArgMatches;
+
+pub fn command(args: ArgMatches) -> Result<()> {
+    let dir = args.ucd_dir()?;
+    let map = jamo_short_name::table(dir)?;
+
+    let mut wtr = args.writer("jamo_short_name")?;
+    wtr.codepoint_to_string(args.name(), &map)?;
+    Ok(())
+}
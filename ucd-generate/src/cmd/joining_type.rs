@@ -0,0 +1,32 @@
+use std::collections::BTreeSet;
+
+use ucd_parse::{self, ArabicShaping};
+
+use ucd_generate::error::Result;
+use ucd_generate::joining_type;
+use ucd_generate::util::PropertyValues;
+
+use args::ArgMatches;
+use cache;
+
+pub fn command(args: ArgMatches) -> Result<()> {
+    let dir = args.ucd_dir()?;
+    let byjt = if args.is_present("derived") {
+        joining_type::tables_from_ucd_dir(&dir, true)?
+    } else {
+        let propvals = PropertyValues::from_ucd_dir(&dir)?;
+        let shaping: Vec<ArabicShaping> = ucd_parse::parse(&dir)?;
+        let rows = cache::parse(dir.as_ref(), args.cache_dir())?;
+        joining_type::tables(&propvals, &shaping, rows)?
+    };
+
+    let mut wtr = args.writer("joining_type")?;
+    if args.is_present("enum") {
+        wtr.ranges_to_enum("joining_type", &byjt)?;
+    } else {
+        let tables: Vec<(&str, &BTreeSet<u32>)> =
+            byjt.iter().map(|(name, set)| (&**name, set)).collect();
+        wtr.ranges_many(&tables)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,18 @@
+pub mod bidi_brackets;
+pub mod bidi_class;
+pub mod bidi_mirroring;
+pub mod case_folding;
+pub mod case_mapping;
+#[cfg(feature = "fetch")]
+pub mod download;
+pub mod general_category;
+pub mod jamo_short_name;
+pub mod joining_type;
+pub mod line_break;
+pub mod names;
+pub mod property_bool;
+#[cfg(feature = "repl")]
+pub mod repl;
+pub mod script_extension;
+pub mod sentence_break;
+pub mod size_report;
@@ -0,0 +1,51 @@
+use std::collections::BTreeMap;
+
+use ucd_generate::error::Result;
+use ucd_generate::names::{aliases_by_codepoint, names_to_codepoint};
+use ucd_parse::UnicodeData;
+use ucd_util;
+
+use args::ArgMatches;
+use cache;
+
+pub fn command(args: ArgMatches) -> Result<()> {
+    let dir = args.ucd_dir()?;
+    let cache_dir = args.cache_dir();
+    let data = cache::parse(dir.as_ref(), cache_dir)?
+        .into_iter()
+        .map(|row: UnicodeData| (row.codepoint, row))
+        .collect();
+    let aliases =
+        if args.is_present("no-aliases") {
+            None
+        } else {
+            Some(aliases_by_codepoint(cache::parse(dir.as_ref(), cache_dir)?))
+        };
+    let mut names = names_to_codepoint(
+        &data,
+        &aliases,
+        !args.is_present("no-ideograph"),
+        !args.is_present("no-hangul"));
+    if args.is_present("normalize") {
+        names = names.into_iter().map(|(mut name, tagged)| {
+            ucd_util::character_name_normalize(&mut name);
+            (name, tagged)
+        }).collect();
+    }
+
+    let mut wtr = args.writer("names")?;
+    if args.is_present("tagged") {
+        let mut map = BTreeMap::new();
+        for (name, (tag, cp)) in names {
+            map.insert(name, tag.with_codepoint(cp));
+        }
+        wtr.string_to_u64(args.name(), &map)?;
+    } else {
+        let mut map = BTreeMap::new();
+        for (name, (_, cp)) in names {
+            map.insert(name, cp);
+        }
+        wtr.string_to_codepoint(args.name(), &map)?;
+    }
+    Ok(())
+}
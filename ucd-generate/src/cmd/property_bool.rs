@@ -0,0 +1,26 @@
+use std::collections::BTreeSet;
+
+use ucd_generate::error::Result;
+use ucd_generate::property_bool;
+use ucd_generate::util::PropertyNames;
+
+use args::ArgMatches;
+
+pub fn command(args: ArgMatches) -> Result<()> {
+    let dir = args.ucd_dir()?;
+    let names = PropertyNames::from_ucd_dir(&dir)?;
+    let byprop = property_bool::tables_from_ucd_dir(&dir)?;
+
+    let include: Option<Vec<&str>> =
+        args.values_of("include").map(|names| names.collect());
+    let exclude: Vec<&str> =
+        args.values_of("exclude").map_or(vec![], |names| names.collect());
+    let byprop = property_bool::select(
+        &names, byprop, include.as_ref().map(|x| &**x), &exclude)?;
+
+    let mut wtr = args.writer("property_bool")?;
+    let tables: Vec<(&str, &BTreeSet<u32>)> =
+        byprop.iter().map(|(name, set)| (&**name, set)).collect();
+    wtr.ranges_many(&tables)?;
+    Ok(())
+}
@@ -0,0 +1,283 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::Path;
+
+use regex::RegexBuilder;
+use rustyline::Editor;
+use rustyline::error::ReadlineError;
+use ucd_generate::error::Result;
+use ucd_generate::names::{
+    NameTag, aliases_by_codepoint, names_to_codepoint,
+};
+use ucd_generate::util::PropertyValues;
+use ucd_parse::{
+    Codepoint, CodepointRange, NameAlias, UnicodeData, UnicodeDataIndex,
+    UnicodeDataRanges,
+};
+use ucd_util;
+
+use args::ArgMatches;
+use cache;
+
+const HELP: &'static str = "\
+Commands:
+    <codepoint>      Show the properties of a codepoint, e.g., U+1F600.
+    char <c>         Show the properties of the given character literal.
+    name <name>      Look up a codepoint by its name or alias. Names are
+                     matched according to UAX44-LM2.
+    search <regex>   List every codepoint whose name or alias matches the
+                     given regex. Matching is case insensitive.
+    gc <value>       List the codepoint ranges with the given
+                     General_Category.
+    help             Show this message.
+    quit             Exit.";
+
+pub fn command(args: ArgMatches) -> Result<()> {
+    let dir = args.ucd_dir()?;
+    let db = Database::from_ucd_dir(dir, args.cache_dir())?;
+
+    let mut editor = Editor::<()>::new();
+    let mut stdout = io::stdout();
+    loop {
+        let line = match editor.readline("ucd> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(err) => return err!("failed to read line: {}", err),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line);
+        if line == "quit" || line == "exit" {
+            break;
+        }
+        // Errors from a single query are reported without leaving the REPL.
+        if let Err(err) = db.eval(&mut stdout, line) {
+            if err.is_broken_pipe() {
+                return Err(err);
+            }
+            writeln!(stdout, "error: {}", err)?;
+        }
+        stdout.flush()?;
+    }
+    Ok(())
+}
+
+/// All of the UCD data that is queryable from the REPL.
+///
+/// Everything is parsed exactly once when the REPL starts.
+struct Database {
+    /// An index of UnicodeData.txt, used to look up the row of a single
+    /// codepoint.
+    index: UnicodeDataIndex,
+    /// The General_Category of every row in UnicodeData.txt, with each pair
+    /// of `First`/`Last` rows combined into a single range.
+    categories: Vec<(CodepointRange, String)>,
+    /// All aliases from NameAliases.txt.
+    aliases: BTreeMap<Codepoint, Vec<NameAlias<'static>>>,
+    /// A map from every name (including aliases and algorithmically
+    /// generated names) to its codepoint.
+    names: BTreeMap<String, (NameTag, u32)>,
+    /// The same as `names`, except every key is normalized according to
+    /// UAX44-LM2.
+    normalized_names: BTreeMap<String, u32>,
+    /// Canonical property values, used to resolve user supplied values.
+    propvals: PropertyValues,
+}
+
+impl Database {
+    fn from_ucd_dir<P: AsRef<Path>>(
+        dir: P,
+        cache_dir: Option<&Path>,
+    ) -> Result<Database> {
+        let dir = dir.as_ref();
+        let rows: Vec<UnicodeData<'static>> = cache::parse(dir, cache_dir)?;
+        let aliases = aliases_by_codepoint(cache::parse(dir, cache_dir)?);
+
+        let data = rows.into_iter().map(|row| (row.codepoint, row)).collect();
+        let names =
+            names_to_codepoint(&data, &Some(aliases.clone()), true, true);
+        let rows = data.into_iter().map(|(_, row)| row);
+        let categories = UnicodeDataRanges::new(rows)
+            .map(|(range, row)| (range, row.general_category.into_owned()))
+            .collect();
+        let mut normalized_names = BTreeMap::new();
+        for (name, &(_, cp)) in &names {
+            let mut name = name.clone();
+            ucd_util::character_name_normalize(&mut name);
+            normalized_names.insert(name, cp);
+        }
+        Ok(Database {
+            index: UnicodeDataIndex::from_dir(dir)?,
+            categories: categories,
+            aliases: aliases,
+            names: names,
+            normalized_names: normalized_names,
+            propvals: PropertyValues::from_ucd_dir(dir)?,
+        })
+    }
+
+    /// Evaluate a single line of input and write the results to `wtr`.
+    fn eval<W: Write>(&self, mut wtr: W, line: &str) -> Result<()> {
+        let (cmd, arg) = match line.find(char::is_whitespace) {
+            None => (line, ""),
+            Some(i) => (&line[..i], line[i..].trim()),
+        };
+        match cmd {
+            "help" => {
+                writeln!(wtr, "{}", HELP)?;
+            }
+            "char" => {
+                let mut chars = arg.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => {
+                        let cp = Codepoint::from_u32(c as u32)?;
+                        self.write_codepoint(&mut wtr, cp)?;
+                    }
+                    _ => return err!("expected exactly one character"),
+                }
+            }
+            "name" => {
+                let mut key = arg.to_string();
+                ucd_util::character_name_normalize(&mut key);
+                match self.normalized_names.get(&key) {
+                    None => return err!("no codepoint named {:?}", arg),
+                    Some(&cp) => {
+                        let cp = Codepoint::from_u32(cp)?;
+                        self.write_codepoint(&mut wtr, cp)?;
+                    }
+                }
+            }
+            "search" => {
+                let re = match RegexBuilder::new(arg)
+                    .case_insensitive(true)
+                    .build()
+                {
+                    Ok(re) => re,
+                    Err(err) => return err!("{}", err),
+                };
+                for (name, &(_, cp)) in &self.names {
+                    if re.is_match(name) {
+                        writeln!(wtr, "U+{:04X} {}", cp, name)?;
+                    }
+                }
+            }
+            "gc" => {
+                let gc = self.propvals.canonical("gc", arg)?;
+                let mut ranges: Vec<(u32, u32)> = vec![];
+                for &(range, ref value) in &self.categories {
+                    if self.propvals.canonical("gc", value)? != gc {
+                        continue;
+                    }
+                    let (start, end) =
+                        (range.start.value(), range.end.value());
+                    match ranges.last_mut() {
+                        Some(&mut (_, ref mut last)) if *last + 1 == start => {
+                            *last = end;
+                            continue;
+                        }
+                        _ => {}
+                    }
+                    ranges.push((start, end));
+                }
+                let mut count = 0;
+                for &(start, end) in &ranges {
+                    count += end - start + 1;
+                    if start == end {
+                        writeln!(wtr, "U+{:04X}", start)?;
+                    } else {
+                        writeln!(wtr, "U+{:04X}..U+{:04X}", start, end)?;
+                    }
+                }
+                writeln!(wtr, "{} codepoints with General_Category={}",
+                         count, gc)?;
+            }
+            _ => {
+                let cp: Codepoint = match line.parse() {
+                    Ok(cp) => cp,
+                    Err(_) => return err!(
+                        "unrecognized command or codepoint: {:?}", line),
+                };
+                self.write_codepoint(&mut wtr, cp)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write a summary of all known properties for the given codepoint.
+    fn write_codepoint<W: Write>(
+        &self,
+        mut wtr: W,
+        cp: Codepoint,
+    ) -> Result<()> {
+        let row = match self.index.get(cp)? {
+            None => {
+                writeln!(wtr, "U+{} is unassigned", cp)?;
+                return Ok(());
+            }
+            Some(row) => row,
+        };
+        match self.name(cp, &row) {
+            None => writeln!(wtr, "U+{}", cp)?,
+            Some(name) => writeln!(wtr, "U+{} {}", cp, name)?,
+        }
+        if let Some(c) = cp.scalar() {
+            if !c.is_control() {
+                writeln!(wtr, "  Character: {}", c)?;
+            }
+        }
+        let gc = self.propvals.canonical("gc", &row.general_category)?;
+        writeln!(wtr, "  General_Category: {} ({})",
+                 gc, row.general_category)?;
+        writeln!(wtr, "  Canonical_Combining_Class: {}",
+                 row.canonical_combining_class)?;
+        writeln!(wtr, "  Bidi_Class: {}", row.bidi_class)?;
+        writeln!(wtr, "  Bidi_Mirrored: {}",
+                 if row.bidi_mirrored { "Yes" } else { "No" })?;
+        if row.decomposition.mapping() != &[row.codepoint] {
+            writeln!(wtr, "  Decomposition_Mapping: {}", row.decomposition)?;
+        }
+        if let Some(n) = row.numeric_type_decimal {
+            writeln!(wtr, "  Numeric_Value: {} (Decimal)", n)?;
+        } else if let Some(n) = row.numeric_type_digit {
+            writeln!(wtr, "  Numeric_Value: {} (Digit)", n)?;
+        } else if let Some(n) = row.numeric_type_numeric {
+            writeln!(wtr, "  Numeric_Value: {} (Numeric)", n)?;
+        }
+        if !row.unicode1_name.is_empty() {
+            writeln!(wtr, "  Unicode_1_Name: {}", row.unicode1_name)?;
+        }
+        if let Some(m) = row.simple_uppercase_mapping {
+            writeln!(wtr, "  Simple_Uppercase_Mapping: U+{}", m)?;
+        }
+        if let Some(m) = row.simple_lowercase_mapping {
+            writeln!(wtr, "  Simple_Lowercase_Mapping: U+{}", m)?;
+        }
+        if let Some(m) = row.simple_titlecase_mapping {
+            writeln!(wtr, "  Simple_Titlecase_Mapping: U+{}", m)?;
+        }
+        if let Some(aliases) = self.aliases.get(&cp) {
+            for alias in aliases {
+                writeln!(wtr, "  Name_Alias: {} ({:?})",
+                         alias.alias, alias.label)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the name of the given codepoint, if it has one.
+    fn name(&self, cp: Codepoint, row: &UnicodeData) -> Option<String> {
+        if let Some(name) = ucd_util::hangul_name(cp.value()) {
+            return Some(name);
+        }
+        if let Some(name) = ucd_util::ideograph_name(cp.value()) {
+            return Some(name);
+        }
+        if !row.name.is_empty() && !row.name.starts_with('<') {
+            return Some(row.name.clone().into_owned());
+        }
+        None
+    }
+}
@@ -0,0 +1,17 @@
+use std::collections::BTreeSet;
+
+use ucd_generate::error::Result;
+use ucd_generate::script_extension;
+
+use args::ArgMatches;
+
+pub fn command(args: ArgMatches) -> Result<()> {
+    let dir = args.ucd_dir()?;
+    let byscript = script_extension::tables_from_ucd_dir(&dir)?;
+
+    let mut wtr = args.writer("script_extension")?;
+    let tables: Vec<(&str, &BTreeSet<u32>)> =
+        byscript.iter().map(|(name, set)| (&**name, set)).collect();
+    wtr.ranges_many(&tables)?;
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use std::collections::BTreeSet;
+
+use ucd_parse::{self, SentenceBreak};
+
+use ucd_generate::error::Result;
+use ucd_generate::property_enum;
+use ucd_generate::util::PropertyValues;
+
+use args::ArgMatches;
+
+pub fn command(args: ArgMatches) -> Result<()> {
+    let dir = args.ucd_dir()?;
+    let propvals = PropertyValues::from_ucd_dir(&dir)?;
+    let rows: Vec<SentenceBreak> = ucd_parse::parse(&dir)?;
+    let byval = property_enum::tables(
+        &propvals,
+        "SB",
+        rows.iter().map(|row| (row.codepoints, &*row.value)),
+    )?;
+
+    let mut wtr = args.writer("sentence_break")?;
+    if args.is_present("enum") {
+        wtr.ranges_to_enum("sentence_break", &byval)?;
+    } else {
+        let tables: Vec<(&str, &BTreeSet<u32>)> =
+            byval.iter().map(|(name, set)| (&**name, set)).collect();
+        wtr.ranges_many(&tables)?;
+    }
+    Ok(())
+}
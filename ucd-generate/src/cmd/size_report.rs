@@ -0,0 +1,188 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Instant;
+
+use fst::{Set, SetBuilder};
+use ucd_generate::error::Result;
+use ucd_generate::util::{self, PropertyNames, PropertyValues};
+use ucd_generate::writer::u32_key;
+use ucd_generate::{
+    bidi_class, general_category, joining_type, property_bool,
+    script_extension,
+};
+use ucd_trie::TrieSet;
+
+use args::ArgMatches;
+use cache;
+
+pub fn command(args: ArgMatches) -> Result<()> {
+    let dir = args.ucd_dir()?;
+    let names = PropertyNames::from_ucd_dir(&dir)?;
+    let propvals = PropertyValues::from_ucd_dir(&dir)?;
+    let property = names.canonical(args.value_of("property").unwrap())?;
+    let table = match args.value_of("table") {
+        Some(table) => table,
+        None => return err!("missing table name"),
+    };
+    // Each enumerated property is mapped to the property whose aliases
+    // name its values, since Script_Extensions uses those of Script.
+    let (byval, values_of): (BTreeMap<String, BTreeSet<u32>>, &str) =
+        match property {
+            "General_Category" => {
+                let rows = cache::parse(dir.as_ref(), args.cache_dir())?;
+                (general_category::tables(&propvals, rows, true)?, "gc")
+            }
+            "Bidi_Class" => {
+                (bidi_class::tables_from_ucd_dir(&dir, false)?, "bc")
+            }
+            "Joining_Type" => {
+                (joining_type::tables_from_ucd_dir(&dir, false)?, "jt")
+            }
+            "Script_Extensions" => {
+                (script_extension::tables_from_ucd_dir(&dir)?, "sc")
+            }
+            _ => return binary_command(&dir, &propvals, property, table),
+        };
+    let value = propvals.canonical(values_of, table)?;
+    let set = byval.get(value).cloned().unwrap_or(BTreeSet::new());
+    report(&format!("{}={}", property, value), &set)
+}
+
+/// Report on the set of codepoints with the given value of a binary
+/// property, such as `Alphabetic=Yes`.
+fn binary_command<P: AsRef<Path>>(
+    dir: P,
+    propvals: &PropertyValues,
+    property: &str,
+    table: &str,
+) -> Result<()> {
+    let mut byprop = property_bool::tables_from_ucd_dir(&dir)?;
+    let yes = match byprop.remove(property) {
+        None => return err!("unsupported property: {}", property),
+        Some(yes) => yes,
+    };
+    let value = propvals.canonical(property, table)?;
+    let set = match value {
+        "Yes" => yes,
+        _ => (0..(0x10FFFF + 1)).filter(|cp| !yes.contains(cp)).collect(),
+    };
+    report(&format!("{}={}", property, value), &set)
+}
+
+/// Write the size and lookup speed of each representation of `set`.
+fn report(label: &str, set: &BTreeSet<u32>) -> Result<()> {
+    let reports = vec![
+        slice_report(set),
+        fst_report(set)?,
+        trie_report(set),
+        bitset_report(set),
+    ];
+    let mut stdout = io::stdout();
+    writeln!(stdout, "{} ({} codepoints)", label, set.len())?;
+    writeln!(stdout, "")?;
+    writeln!(stdout, "{:<8} {:>12} {:>14}", "format", "bytes", "ns/lookup")?;
+    for r in &reports {
+        writeln!(
+            stdout,
+            "{:<8} {:>12} {:>14.2}",
+            r.format, r.bytes, r.nanos_per_lookup)?;
+    }
+    Ok(())
+}
+
+/// The size and lookup speed of a single representation of a codepoint set.
+#[derive(Debug)]
+struct Report {
+    /// The name of the representation.
+    format: &'static str,
+    /// The number of bytes used by the representation.
+    bytes: usize,
+    /// The average time, in nanoseconds, that a single membership test took.
+    nanos_per_lookup: f64,
+}
+
+fn slice_report(set: &BTreeSet<u32>) -> Report {
+    let ranges = util::to_ranges(set.iter().cloned());
+    let elapsed = measure(set.len(), |cp| {
+        ranges.binary_search_by(|&(s, e)| {
+            if s > cp {
+                Ordering::Greater
+            } else if e < cp {
+                Ordering::Less
+            } else {
+                Ordering::Equal
+            }
+        }).is_ok()
+    });
+    Report {
+        format: "slice",
+        bytes: ranges.len() * 8,
+        nanos_per_lookup: elapsed,
+    }
+}
+
+fn fst_report(set: &BTreeSet<u32>) -> Result<Report> {
+    let mut builder = SetBuilder::memory();
+    builder.extend_iter(set.iter().cloned().map(u32_key))?;
+    let fst = Set::from_bytes(builder.into_inner()?)?;
+    let elapsed = measure(set.len(), |cp| fst.contains(u32_key(cp)));
+    Ok(Report {
+        format: "fst",
+        bytes: fst.as_fst().size(),
+        nanos_per_lookup: elapsed,
+    })
+}
+
+fn trie_report(set: &BTreeSet<u32>) -> Report {
+    let codepoints: Vec<u32> = set.iter().cloned().collect();
+    let trie = TrieSet::from_codepoints(&codepoints);
+    let elapsed = measure(set.len(), |cp| trie.contains_u32(cp));
+    Report {
+        format: "trie",
+        bytes: trie.memory_usage(),
+        nanos_per_lookup: elapsed,
+    }
+}
+
+fn bitset_report(set: &BTreeSet<u32>) -> Report {
+    let mut bits = vec![0u64; 0x110000 / 64];
+    for &cp in set {
+        bits[cp as usize / 64] |= 1 << (cp % 64);
+    }
+    let elapsed = measure(set.len(), |cp| {
+        (bits[cp as usize / 64] >> (cp % 64)) & 1 == 1
+    });
+    Report {
+        format: "bitset",
+        bytes: bits.len() * 8,
+        nanos_per_lookup: elapsed,
+    }
+}
+
+/// Run a membership test on every codepoint and return the average number
+/// of nanoseconds taken by each test.
+///
+/// This panics if the number of codepoints found is not equal to `expected`,
+/// which also guarantees that the lookups cannot be optimized away.
+fn measure<F: FnMut(u32) -> bool>(expected: usize, mut contains: F) -> f64 {
+    const ITERS: u32 = 3;
+
+    let start = Instant::now();
+    let mut found = 0;
+    for _ in 0..ITERS {
+        for cp in 0..(0x10FFFF + 1) {
+            if contains(cp) {
+                found += 1;
+            }
+        }
+    }
+    let elapsed = start.elapsed();
+    assert_eq!(found, expected * ITERS as usize);
+
+    let nanos =
+        (elapsed.as_secs() as f64 * 1_000_000_000.0)
+        + elapsed.subsec_nanos() as f64;
+    nanos / (ITERS as f64 * 0x110000 as f64)
+}
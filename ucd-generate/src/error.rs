@@ -6,17 +6,27 @@ use std::result;
 use fst;
 use clap;
 use ucd_parse;
+use ucd_util;
 
+/// A type alias for handling errors throughout ucd-generate.
 pub type Result<T> = result::Result<T, Error>;
 
+/// An error that can occur while reading the UCD or writing tables.
+///
+/// Errors from parsing the UCD and building FSTs are converted to `Other`
+/// with a descriptive message.
 #[derive(Debug)]
 pub enum Error {
+    /// An I/O error, e.g., from writing a table.
     Io(io::Error),
+    /// An error from parsing command line arguments.
     Clap(clap::Error),
+    /// Any other error, described by its message.
     Other(String),
 }
 
 impl Error {
+    /// Returns true if this is an I/O error caused by a broken pipe.
     pub fn is_broken_pipe(&self) -> bool {
         match *self {
             Error::Io(ref e) if e.kind() == io::ErrorKind::BrokenPipe => true,
@@ -76,3 +86,9 @@ impl From<ucd_parse::Error> for Error {
         Error::Other(err.to_string())
     }
 }
+
+impl From<ucd_util::ParseUnicodeVersionError> for Error {
+    fn from(err: ucd_util::ParseUnicodeVersionError) -> Error {
+        Error::Other(err.to_string())
+    }
+}
@@ -1,34 +1,53 @@
 use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
 
-use ucd_parse::{self, UnicodeDataExpander};
+use ucd_parse::{self, UnicodeData, UnicodeDataRanges};
 
-use args::ArgMatches;
 use error::Result;
 use util::PropertyValues;
 
-pub fn command(args: ArgMatches) -> Result<()> {
-    let dir = args.ucd_dir()?;
-    let propvals = PropertyValues::from_ucd_dir(&dir)?;
-    let unexpanded = ucd_parse::parse(&dir)?;
-
-    // Expand all of our UnicodeData rows. This results in one big list of
-    // all assigned codepoints.
-    let rows: Vec<_> = UnicodeDataExpander::new(unexpanded).collect();
+/// Build a map from each General_Category value to the set of codepoints
+/// with that value, using the UCD in the given directory.
+///
+/// If `unassigned` is true, then the map includes the `Unassigned` value,
+/// which contains every codepoint not listed in UnicodeData.txt.
+pub fn tables_from_ucd_dir<P: AsRef<Path>>(
+    ucd_dir: P,
+    unassigned: bool,
+) -> Result<BTreeMap<String, BTreeSet<u32>>> {
+    let propvals = PropertyValues::from_ucd_dir(&ucd_dir)?;
+    let rows = ucd_parse::parse(&ucd_dir)?;
+    tables(&propvals, rows, unassigned)
+}
 
-    // Collect each general category into an ordered set.
+/// Build a map from each General_Category value to the set of codepoints
+/// with that value, using rows already parsed from UnicodeData.txt.
+///
+/// The keys of the map are the canonical names of each value, as given by
+/// `propvals`. If `unassigned` is true, then the map includes the
+/// `Unassigned` value, which contains every codepoint not in `rows`.
+pub fn tables(
+    propvals: &PropertyValues,
+    rows: Vec<UnicodeData<'static>>,
+    unassigned: bool,
+) -> Result<BTreeMap<String, BTreeSet<u32>>> {
+    // Collect each general category into an ordered set. Rows that
+    // correspond to ranges of codepoints are grouped together, so that the
+    // general category of each range only needs to be resolved once.
     let mut bycat: BTreeMap<String, BTreeSet<u32>> = BTreeMap::new();
     let mut assigned = BTreeSet::new();
-    for row in rows {
-        assigned.insert(row.codepoint.value());
+    for (range, row) in UnicodeDataRanges::new(rows) {
+        let range = range.start.value()..(range.end.value() + 1);
+        assigned.extend(range.clone());
         let gc = propvals
             .canonical("gc", &row.general_category)?
             .to_string();
         bycat.entry(gc)
             .or_insert(BTreeSet::new())
-            .insert(row.codepoint.value());
+            .extend(range);
     }
     // As a special case, collect all unassigned codepoints.
-    if !args.is_present("no-unassigned") {
+    if unassigned {
         let unassigned_name = propvals
             .canonical("gc", "unassigned")?
             .to_string();
@@ -39,15 +58,96 @@ pub fn command(args: ArgMatches) -> Result<()> {
             }
         }
     }
+    Ok(bycat)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::env;
+
+    use ucd_parse::{UcdFile, UnicodeData};
+    use ucd_trie::{TrieMap, TrieSet};
 
-    let mut wtr = args.writer("general_category")?;
-    if args.is_present("enum") {
-        wtr.ranges_to_enum("general_category", &bycat)?;
-    } else {
-        for (name, set) in bycat {
-            wtr.ranges(&name, &set)?;
+    use util;
+
+    fn compare_range(start: u32, end: u32, cp: u32) -> Ordering {
+        if end < cp {
+            Ordering::Less
+        } else if start > cp {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
         }
     }
 
-    Ok(())
+    fn slice_contains(slice: &[(u32, u32)], cp: u32) -> bool {
+        slice.binary_search_by(|&(s, e)| compare_range(s, e, cp)).is_ok()
+    }
+
+    fn slice_get(slice: &[(u32, u32, u64)], cp: u32) -> Option<u64> {
+        slice.binary_search_by(|&(s, e, _)| compare_range(s, e, cp))
+            .ok()
+            .map(|i| slice[i].2)
+    }
+
+    /// Compare the trie representation of every General_Category table
+    /// against its slice representation.
+    fn assert_trie_matches_slice(bycat: &BTreeMap<String, BTreeSet<u32>>) {
+        let mut pairs = vec![];
+        for (i, set) in bycat.values().enumerate() {
+            let codepoints: Vec<u32> = set.iter().cloned().collect();
+            let slice = util::to_ranges(codepoints.iter().cloned());
+            let trie = TrieSet::from_codepoints(&codepoints);
+            for cp in 0..0x110000 {
+                let expected = slice_contains(&slice, cp);
+                assert_eq!(expected, trie.contains_u32(cp), "{:X}", cp);
+            }
+            pairs.extend(codepoints.into_iter().map(|cp| (cp, i as u64)));
+        }
+
+        let slice = util::to_range_values(pairs.iter().cloned());
+        let trie = TrieMap::from_codepoints(&pairs);
+        for cp in 0..0x110000 {
+            assert_eq!(slice_get(&slice, cp), trie.get_u32(cp), "{:X}", cp);
+        }
+    }
+
+    #[test]
+    fn trie_matches_slice() {
+        let propvals = util::property_values("\
+gc ; General_Category
+", "\
+gc ; Cc ; Control
+gc ; Ll ; Lowercase_Letter
+gc ; Lo ; Other_Letter
+gc ; Lu ; Uppercase_Letter
+gc ; Zs ; Space_Separator
+");
+        let rows: Vec<UnicodeData> = UnicodeData::from_data("\
+0000;<control>;Cc;0;BN;;;;;N;NULL;;;;
+001F;<control>;Cc;0;S;;;;;N;INFORMATION SEPARATOR ONE;;;;
+0020;SPACE;Zs;0;WS;;;;;N;;;;;
+0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;
+0061;LATIN SMALL LETTER A;Ll;0;L;;;;;N;;;0041;;0041
+AC00;<Hangul Syllable, First>;Lo;0;L;;;;;N;;;;;
+D7A3;<Hangul Syllable, Last>;Lo;0;L;;;;;N;;;;;
+20000;<CJK Ideograph Extension B, First>;Lo;0;L;;;;;N;;;;;
+2A6D6;<CJK Ideograph Extension B, Last>;Lo;0;L;;;;;N;;;;;
+").collect::<Result<_, _>>().unwrap();
+        let bycat = super::tables(&propvals, rows, false).unwrap();
+        assert_eq!(bycat["Other_Letter"].len(), 11172 + 42711);
+        assert_trie_matches_slice(&bycat);
+    }
+
+    /// Like `trie_matches_slice`, but uses the UCD in the directory given
+    /// by the `UCD_DIR` environment variable.
+    #[test]
+    #[ignore = "requires the UCD in the directory given by UCD_DIR"]
+    fn trie_matches_slice_ucd() {
+        let dir = env::var_os("UCD_DIR").expect("UCD_DIR must be set");
+        let bycat = super::tables_from_ucd_dir(dir, true).unwrap();
+        assert_trie_matches_slice(&bycat);
+    }
 }
@@ -1,19 +1,18 @@
 use std::collections::BTreeMap;
+use std::path::Path;
 
 use ucd_parse::{self, JamoShortName};
 
-use args::ArgMatches;
 use error::Result;
 
-pub fn command(args: ArgMatches) -> Result<()> {
-    let dir = args.ucd_dir()?;
-    let jamo_map = ucd_parse::parse_by_codepoint::<_, JamoShortName>(dir)?;
+/// Build a map from codepoint to its Jamo_Short_Name property value, using
+/// the Jamo.txt file in the given UCD directory.
+pub fn table<P: AsRef<Path>>(ucd_dir: P) -> Result<BTreeMap<u32, String>> {
+    let jamo_map = ucd_parse::parse_by_codepoint::<_, JamoShortName>(ucd_dir)?;
 
-    let mut wtr = args.writer("jamo_short_name")?;
     let mut map = BTreeMap::new();
     for (cp, jamo) in jamo_map {
         map.insert(cp.value(), jamo.name.into_owned());
     }
-    wtr.codepoint_to_string(args.name(), &map)?;
-    Ok(())
+    Ok(map)
 }
@@ -0,0 +1,180 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use ucd_parse::{
+    self, ArabicShaping, CodepointRange, DerivedJoiningType, MissingValue,
+    UnicodeData, UnicodeDataRanges,
+};
+
+use error::Result;
+use property_enum;
+use util::PropertyValues;
+
+/// Build a map from each Joining_Type value to the set of codepoints with
+/// that value, using the UCD in the given directory.
+///
+/// If `derived` is true, then the values are read from
+/// extracted/DerivedJoiningType.txt. Otherwise, they are read from
+/// ArabicShaping.txt and UnicodeData.txt.
+pub fn tables_from_ucd_dir<P: AsRef<Path>>(
+    ucd_dir: P,
+    derived: bool,
+) -> Result<BTreeMap<String, BTreeSet<u32>>> {
+    let propvals = PropertyValues::from_ucd_dir(&ucd_dir)?;
+    if derived {
+        let rows = ucd_parse::parse(&ucd_dir)?;
+        let missing = ucd_parse::parse_missing_lines::<_, DerivedJoiningType>(
+            &ucd_dir)?;
+        derived_tables(&propvals, rows, missing)
+    } else {
+        let shaping: Vec<ArabicShaping> = ucd_parse::parse(&ucd_dir)?;
+        let rows = ucd_parse::parse(&ucd_dir)?;
+        tables(&propvals, &shaping, rows)
+    }
+}
+
+/// Build a map from each Joining_Type value to the set of codepoints with
+/// that value, using rows already parsed from ArabicShaping.txt and
+/// UnicodeData.txt.
+///
+/// The keys of the map are the canonical names of each value, as given by
+/// `propvals`. Every codepoint is in exactly one set. Codepoints that
+/// aren't listed in ArabicShaping.txt are `Transparent` if their general
+/// category is `Mn`, `Me` or `Cf`, and `Non_Joining` otherwise. Only the
+/// general category is read from `unicode_data`.
+pub fn tables(
+    propvals: &PropertyValues,
+    shaping: &[ArabicShaping],
+    unicode_data: Vec<UnicodeData<'static>>,
+) -> Result<BTreeMap<String, BTreeSet<u32>>> {
+    let rows = shaping.iter().map(|row| {
+        let jt = row.joining_type.abbreviation();
+        (CodepointRange::single(row.codepoint), jt)
+    });
+    let defaults = defaults(unicode_data)?;
+    property_enum::tables_with_defaults(propvals, "jt", defaults, rows)
+}
+
+/// Return the default Joining_Type values of codepoints that aren't listed
+/// in ArabicShaping.txt, as ranges of codepoints in order of increasing
+/// precedence, using rows already parsed from UnicodeData.txt.
+pub fn defaults(
+    unicode_data: Vec<UnicodeData<'static>>,
+) -> Result<Vec<(CodepointRange, &'static str)>> {
+    let all: CodepointRange = "0000..10FFFF".parse()?;
+    let mut defaults = vec![(all, "U")];
+    for (range, row) in UnicodeDataRanges::new(unicode_data) {
+        match &*row.general_category {
+            "Mn" | "Me" | "Cf" => defaults.push((range, "T")),
+            _ => {}
+        }
+    }
+    Ok(defaults)
+}
+
+/// Build a map from each Joining_Type value to the set of codepoints with
+/// that value, using rows and `# @missing:` lines already parsed from
+/// extracted/DerivedJoiningType.txt.
+///
+/// This is like `tables`, except the defaults for unlisted codepoints are
+/// taken from `missing` instead. Transparent codepoints are listed
+/// explicitly in DerivedJoiningType.txt, so no general categories are
+/// needed.
+pub fn derived_tables(
+    propvals: &PropertyValues,
+    rows: Vec<DerivedJoiningType>,
+    missing: Vec<MissingValue>,
+) -> Result<BTreeMap<String, BTreeSet<u32>>> {
+    property_enum::tables_with_defaults(
+        propvals,
+        "jt",
+        missing.iter().map(|m| (m.codepoints, &*m.value)),
+        rows.iter().map(|row| (row.codepoints, &*row.value)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use ucd_parse::{
+        ArabicShaping, CodepointRange, DerivedJoiningType, MissingValue,
+        UcdFile, UnicodeData,
+    };
+
+    use util::{self, PropertyValues};
+
+    const ALIASES: &'static str = "\
+jt ; C ; Join_Causing
+jt ; D ; Dual_Joining
+jt ; L ; Left_Joining
+jt ; R ; Right_Joining
+jt ; T ; Transparent
+jt ; U ; Non_Joining
+";
+
+    fn propvals() -> PropertyValues {
+        util::property_values("jt ; Joining_Type\n", ALIASES)
+    }
+
+    #[test]
+    fn defaults() {
+        let rows: Vec<UnicodeData> = UnicodeData::from_data("\
+0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;
+0300;COMBINING GRAVE ACCENT;Mn;230;NSM;;;;;N;NON-SPACING GRAVE;;;;
+0627;ARABIC LETTER ALEF;Lo;0;AL;;;;;N;;;;;
+200B;ZERO WIDTH SPACE;Cf;0;BN;;;;;N;;;;;
+").collect::<Result<_, _>>().unwrap();
+        let defaults = super::defaults(rows).unwrap();
+        let ranges: Vec<(CodepointRange, &str)> = vec![
+            ("0000..10FFFF".parse().unwrap(), "U"),
+            ("0300".parse().unwrap(), "T"),
+            ("200B".parse().unwrap(), "T"),
+        ];
+        assert_eq!(defaults, ranges);
+    }
+
+    #[test]
+    fn arabic_shaping_overrides_defaults() {
+        let shaping: Vec<ArabicShaping> = ArabicShaping::from_data("\
+0627; ALEF; R; ALEF
+0628; BEH; D; BEH
+200D; ZERO WIDTH JOINER; C; No_Joining_Group
+").collect::<Result<_, _>>().unwrap();
+        let rows = shaping.iter().map(|row| {
+            let jt = row.joining_type.abbreviation();
+            (CodepointRange::single(row.codepoint), jt)
+        });
+        let defaults: Vec<(CodepointRange, &str)> = vec![
+            ("0000..00FF".parse().unwrap(), "U"),
+            ("200D".parse().unwrap(), "T"),
+        ];
+        let byjt = ::property_enum::tables_with_defaults(
+            &propvals(), "jt", defaults, rows).unwrap();
+
+        let get = |jt: &str| -> Vec<u32> {
+            byjt[jt].iter().cloned().collect()
+        };
+        assert_eq!(byjt.len(), 4);
+        assert_eq!(get("Right_Joining"), vec![0x0627]);
+        assert_eq!(get("Dual_Joining"), vec![0x0628]);
+        assert_eq!(get("Join_Causing"), vec![0x200D]);
+        assert_eq!(byjt["Non_Joining"].len(), 0x100);
+    }
+
+    #[test]
+    fn derived_defaults() {
+        let rows: Vec<DerivedJoiningType> = DerivedJoiningType::from_data("\
+0300..036F    ; T
+0628          ; D
+").collect::<Result<_, _>>().unwrap();
+        let missing: Vec<MissingValue> = vec![
+            "# @missing: 0000..07FF; Non_Joining".parse().unwrap(),
+        ];
+        let byjt = super::derived_tables(&propvals(), rows, missing)
+            .unwrap();
+
+        assert_eq!(byjt.len(), 3);
+        assert_eq!(byjt["Transparent"].len(), 0x70);
+        assert_eq!(byjt["Dual_Joining"].len(), 1);
+        assert_eq!(byjt["Non_Joining"].len(), 0x800 - 0x71);
+    }
+}
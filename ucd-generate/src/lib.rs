@@ -0,0 +1,96 @@
+/*!
+A library for generating packed representations of the Unicode character
+database.
+
+This is the library behind the `ucd-generate` command line tool. It exposes
+the construction of each property's tables from a UCD directory, along with
+the `Writer` used to emit those tables as Rust source code. This permits
+generating tables from a build script without shelling out to the binary.
+The default features of this crate only enable subcommands of the binary, so
+crates that depend on the library should disable them.
+
+For example, a build script might write the General_Category tables to
+`OUT_DIR` like so:
+
+```no_run
+extern crate ucd_generate;
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fs::File;
+use std::path::Path;
+
+use ucd_generate::WriterBuilder;
+use ucd_generate::general_category;
+
+# fn example() -> ucd_generate::Result<()> {
+let bycat = general_category::tables_from_ucd_dir("ucd", true)?;
+let out = Path::new(&env::var("OUT_DIR").unwrap()).join("gc.rs");
+let mut wtr = WriterBuilder::new("general_category")
+    .char_literals(true)
+    .header(false)
+    .from_writer(File::create(out)?);
+let tables: Vec<(&str, &BTreeSet<u32>)> =
+    bycat.iter().map(|(name, set)| (&**name, set)).collect();
+wtr.ranges_many(&tables)?;
+# Ok(()) }
+# fn main() { example().unwrap(); }
+```
+
+See the `writer` module for the kinds of tables that can be written and the
+stability guarantees of the generated code.
+*/
+
+#![deny(missing_docs)]
+
+extern crate byteorder;
+extern crate clap;
+extern crate fst;
+extern crate rayon;
+extern crate ucd_parse;
+extern crate ucd_trie;
+extern crate ucd_util;
+
+pub use error::{Error, Result};
+pub use table::{Table, TableSource, TableVisitor};
+pub use writer::{Writer, WriterBuilder};
+
+macro_rules! err {
+    ($($tt:tt)*) => {
+        Err(::error::Error::Other(format!($($tt)*)))
+    }
+}
+
+/// The error type used throughout this crate.
+pub mod error;
+pub mod table;
+/// Property name normalization and conversions between sets and ranges.
+pub mod util;
+pub mod writer;
+
+/// Table construction for the Bidi_Paired_Bracket and
+/// Bidi_Paired_Bracket_Type properties.
+pub mod bidi_brackets;
+/// Table construction for the Bidi_Class property.
+pub mod bidi_class;
+/// Table construction for the Bidi_Mirroring_Glyph and Bidi_Mirrored
+/// properties.
+pub mod bidi_mirroring;
+/// Table construction for case folding.
+pub mod case_folding;
+/// Table construction for simple and full case mappings.
+pub mod case_mapping;
+/// Table construction for the General_Category property.
+pub mod general_category;
+/// Table construction for the Jamo_Short_Name property.
+pub mod jamo_short_name;
+/// Table construction for the Joining_Type property.
+pub mod joining_type;
+/// Table construction for character names.
+pub mod names;
+/// Table construction for binary properties.
+pub mod property_bool;
+/// Table construction for enumerated properties.
+pub mod property_enum;
+/// Table construction for the Script_Extensions property.
+pub mod script_extension;
@@ -2,16 +2,23 @@ extern crate byteorder;
 #[macro_use]
 extern crate clap;
 extern crate fst;
+#[cfg(feature = "repl")]
+extern crate regex;
+#[cfg(feature = "repl")]
+extern crate rustyline;
+extern crate ucd_generate;
 extern crate ucd_parse;
+extern crate ucd_trie;
 extern crate ucd_util;
 
 use std::io::{self, Write};
 use std::process;
 
-use ucd_parse::{UcdFile, UnicodeData};
+use ucd_parse::UnicodeData;
+
+use ucd_generate::error::Result;
 
 use args::ArgMatches;
-use error::Result;
 
 macro_rules! eprintln {
     ($($tt:tt)*) => {{
@@ -22,19 +29,14 @@ macro_rules! eprintln {
 
 macro_rules! err {
     ($($tt:tt)*) => {
-        Err(::error::Error::Other(format!($($tt)*)))
+        Err(::ucd_generate::error::Error::Other(format!($($tt)*)))
     }
 }
 
 mod app;
 mod args;
-mod error;
-mod util;
-mod writer;
-
-mod general_category;
-mod jamo_short_name;
-mod names;
+mod cache;
+mod cmd;
 
 fn main() {
     if let Err(err) = run() {
@@ -49,14 +51,66 @@ fn main() {
 fn run() -> Result<()> {
     let matches = app::app().get_matches();
     match matches.subcommand() {
+        ("bidi-brackets", Some(m)) => {
+            cmd::bidi_brackets::command(ArgMatches::new(m))
+        }
+        ("bidi-class", Some(m)) => {
+            cmd::bidi_class::command(ArgMatches::new(m))
+        }
+        ("bidi-mirroring-glyph", Some(m)) => {
+            cmd::bidi_mirroring::command(ArgMatches::new(m))
+        }
+        ("case-folding-simple", Some(m)) => {
+            cmd::case_folding::command_simple(ArgMatches::new(m))
+        }
+        ("case-mapping", Some(m)) => {
+            cmd::case_mapping::command(ArgMatches::new(m))
+        }
+        #[cfg(feature = "fetch")]
+        ("download", Some(m)) => {
+            cmd::download::command(ArgMatches::new(m))
+        }
+        #[cfg(not(feature = "fetch"))]
+        ("download", Some(_)) => {
+            err!("the download command requires the fetch feature")
+        }
         ("general-category", Some(m)) => {
-            general_category::command(ArgMatches::new(m))
+            cmd::general_category::command(ArgMatches::new(m))
         }
         ("jamo-short-name", Some(m)) => {
-            jamo_short_name::command(ArgMatches::new(m))
+            cmd::jamo_short_name::command(ArgMatches::new(m))
+        }
+        ("joining-type", Some(m)) => {
+            cmd::joining_type::command(ArgMatches::new(m))
+        }
+        ("line-break", Some(m)) => {
+            cmd::line_break::command(ArgMatches::new(m))
         }
         ("names", Some(m)) => {
-            names::command(ArgMatches::new(m))
+            cmd::names::command(ArgMatches::new(m))
+        }
+        ("property-bool", Some(m)) => {
+            cmd::property_bool::command(ArgMatches::new(m))
+        }
+        #[cfg(feature = "repl")]
+        ("repl", Some(m)) => {
+            cmd::repl::command(ArgMatches::new(m))
+        }
+        #[cfg(not(feature = "repl"))]
+        ("repl", Some(_)) => {
+            err!("the repl command requires the repl feature")
+        }
+        ("script-extension", Some(m)) => {
+            cmd::script_extension::command(ArgMatches::new(m))
+        }
+        ("sentence-break", Some(m)) => {
+            cmd::sentence_break::command(ArgMatches::new(m))
+        }
+        ("special-casing", Some(m)) => {
+            cmd::case_mapping::command_full(ArgMatches::new(m))
+        }
+        ("size-report", Some(m)) => {
+            cmd::size_report::command(ArgMatches::new(m))
         }
         ("test-unicode-data", Some(m)) => {
             cmd_test_unicode_data(ArgMatches::new(m))
@@ -72,9 +126,9 @@ fn run() -> Result<()> {
 
 fn cmd_test_unicode_data(args: ArgMatches) -> Result<()> {
     let dir = args.ucd_dir()?;
+    let rows: Vec<UnicodeData> = cache::parse(dir.as_ref(), args.cache_dir())?;
     let mut stdout = io::stdout();
-    for result in UnicodeData::from_dir(dir)? {
-        let x: UnicodeData = result?;
+    for x in rows {
         writeln!(stdout, "{}", x)?;
     }
     Ok(())
@@ -1,55 +1,14 @@
 use std::collections::BTreeMap;
 
-use ucd_parse::{self, Codepoint, UnicodeData, NameAlias};
+use ucd_parse::{Codepoint, UnicodeData, NameAlias};
 use ucd_util;
 
-use args::ArgMatches;
-use error::Result;
-
-pub fn command(args: ArgMatches) -> Result<()> {
-    let dir = args.ucd_dir()?;
-    let data = ucd_parse::parse_by_codepoint(&dir)?;
-    let aliases =
-        if args.is_present("no-aliases") {
-            None
-        } else {
-            Some(ucd_parse::parse_many_by_codepoint(&dir)?)
-        };
-    let mut names = names_to_codepoint(
-        &data,
-        &aliases,
-        !args.is_present("no-ideograph"),
-        !args.is_present("no-hangul"));
-    if args.is_present("normalize") {
-        names = names.into_iter().map(|(mut name, tagged)| {
-            ucd_util::character_name_normalize(&mut name);
-            (name, tagged)
-        }).collect();
-    }
-
-    let mut wtr = args.writer("names")?;
-    if args.is_present("tagged") {
-        let mut map = BTreeMap::new();
-        for (name, (tag, cp)) in names {
-            map.insert(name, tag.with_codepoint(cp));
-        }
-        wtr.string_to_u64(args.name(), &map)?;
-    } else {
-        let mut map = BTreeMap::new();
-        for (name, (_, cp)) in names {
-            map.insert(name, cp);
-        }
-        wtr.string_to_codepoint(args.name(), &map)?;
-    }
-    Ok(())
-}
-
 /// A tag indicating how the name of a codepoint was found.
 ///
 /// When a name has both an algorithmically generated name and an
 /// explicit/alias name, then the algorithmically generated tag is preferred.
 #[derive(Debug)]
-enum NameTag {
+pub enum NameTag {
     /// The name is listed explicitly in UnicodeData.txt.
     Explicit,
     /// The name was taken from NameAliases.txt.
@@ -61,7 +20,12 @@ enum NameTag {
 }
 
 impl NameTag {
-    fn with_codepoint(&self, cp: u32) -> u64 {
+    /// Return the given codepoint tagged with this tag.
+    ///
+    /// The lower 32 bits of the value returned are the codepoint. Bits 33,
+    /// 34, 35 and 36 are set when the tag is `Explicit`, `Alias`, `Hangul`
+    /// and `Ideograph`, respectively.
+    pub fn with_codepoint(&self, cp: u32) -> u64 {
         use self::NameTag::*;
         match *self {
             Explicit => (1<<33) | (cp as u64),
@@ -72,12 +36,24 @@ impl NameTag {
     }
 }
 
+/// Group name aliases by the codepoint they belong to, preserving the order
+/// in which they appear in NameAliases.txt.
+pub fn aliases_by_codepoint(
+    aliases: Vec<NameAlias<'static>>,
+) -> BTreeMap<Codepoint, Vec<NameAlias<'static>>> {
+    let mut map: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for alias in aliases {
+        map.entry(alias.codepoint).or_insert(vec![]).push(alias);
+    }
+    map
+}
+
 /// Build one big map in memory from every possible name of a character to its
 /// corresponding codepoint. One codepoint may be pointed to by multiple names.
 ///
 /// The return value maps each name to its corresponding codepoint, along with
 /// a tag associated with how that mapping was generated.
-fn names_to_codepoint(
+pub fn names_to_codepoint(
     data: &BTreeMap<Codepoint, UnicodeData<'static>>,
     aliases: &Option<BTreeMap<Codepoint, Vec<NameAlias<'static>>>>,
     ideograph: bool,
@@ -0,0 +1,145 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use ucd_parse::{
+    self, CodepointRange, CoreProperty, EmojiProperty, PropList,
+    UcdDirectory,
+};
+
+use error::Result;
+use util::PropertyNames;
+
+/// Build a map from each binary property to the set of codepoints with that
+/// property, using the UCD in the given directory.
+///
+/// The properties are read from PropList.txt, DerivedCoreProperties.txt and,
+/// if it exists, emoji/emoji-data.txt. Rows of DerivedCoreProperties.txt
+/// that assign a value to an enumerated property are skipped.
+pub fn tables_from_ucd_dir<P: AsRef<Path>>(
+    ucd_dir: P,
+) -> Result<BTreeMap<String, BTreeSet<u32>>> {
+    let dir = UcdDirectory::open(&ucd_dir)?;
+    let names = PropertyNames::from_ucd_dir(&ucd_dir)?;
+    let proplist: Vec<PropList> = ucd_parse::parse(&ucd_dir)?;
+    let core: Vec<CoreProperty> = ucd_parse::parse(&ucd_dir)?;
+    let emoji: Vec<EmojiProperty> = if dir.has::<EmojiProperty>() {
+        ucd_parse::parse(&ucd_dir)?
+    } else {
+        vec![]
+    };
+
+    let rows = proplist.iter()
+        .map(|row| (row.codepoints, &*row.property))
+        .chain(core.iter()
+            .filter(|row| row.is_binary())
+            .map(|row| (row.codepoints, &*row.property)))
+        .chain(emoji.iter().map(|row| (row.codepoints, &*row.property)));
+    tables(&names, rows)
+}
+
+/// Build a map from each binary property to the set of codepoints with that
+/// property, using rows that each state that a range of codepoints has a
+/// property.
+///
+/// The keys of the map are the canonical names of each property, as given
+/// by `names`.
+pub fn tables<'a, I>(
+    names: &PropertyNames,
+    rows: I,
+) -> Result<BTreeMap<String, BTreeSet<u32>>>
+    where I: IntoIterator<Item=(CodepointRange, &'a str)>
+{
+    let mut byprop: BTreeMap<String, BTreeSet<u32>> = BTreeMap::new();
+    for (codepoints, property) in rows {
+        let property = names.canonical(property)?.to_string();
+        byprop.entry(property)
+            .or_insert(BTreeSet::new())
+            .extend(codepoints.into_iter().map(|cp| cp.value()));
+    }
+    Ok(byprop)
+}
+
+/// Select a subset of the tables returned by `tables`.
+///
+/// If `include` is given, then only the properties it names are kept.
+/// Properties named by `exclude` are always dropped. Properties may be named
+/// by any of their aliases, e.g., `WSpace` for `White_Space`. If a name
+/// doesn't correspond to a property, then an error is returned.
+pub fn select(
+    names: &PropertyNames,
+    byprop: BTreeMap<String, BTreeSet<u32>>,
+    include: Option<&[&str]>,
+    exclude: &[&str],
+) -> Result<BTreeMap<String, BTreeSet<u32>>> {
+    let canonical = |list: &[&str]| -> Result<BTreeSet<String>> {
+        let mut set = BTreeSet::new();
+        for name in list {
+            set.insert(names.canonical(name)?.to_string());
+        }
+        Ok(set)
+    };
+    let include = match include {
+        None => None,
+        Some(list) => Some(canonical(list)?),
+    };
+    let exclude = canonical(exclude)?;
+    Ok(byprop
+        .into_iter()
+        .filter(|&(ref name, _)| {
+            include.as_ref().map_or(true, |set| set.contains(name))
+            && !exclude.contains(name)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use ucd_parse::CodepointRange;
+
+    use util::{self, PropertyNames};
+
+    const ALIASES: &'static str = "\
+Alpha ; Alphabetic
+Dash ; Dash
+EPres ; Emoji_Presentation
+WSpace ; White_Space ; space
+";
+
+    fn names() -> PropertyNames {
+        util::property_names(ALIASES)
+    }
+
+    fn range(start: u32, end: u32) -> CodepointRange {
+        format!("{:04X}..{:04X}", start, end).parse().unwrap()
+    }
+
+    #[test]
+    fn tables_and_select() {
+        let names = names();
+        let rows = vec![
+            (range(0x09, 0x0D), "White_Space"),
+            (range(0x20, 0x20), "WSpace"),
+            (range(0x2D, 0x2D), "Dash"),
+            (range(0x41, 0x5A), "Alphabetic"),
+        ];
+        let byprop = super::tables(&names, rows).unwrap();
+        assert_eq!(byprop.len(), 3);
+        assert_eq!(byprop["White_Space"].len(), 6);
+        assert!(super::tables(&names, vec![(range(0, 0), "Nope")]).is_err());
+
+        let all = super::select(&names, byprop.clone(), None, &[]).unwrap();
+        assert_eq!(all, byprop);
+
+        let some = super::select(
+            &names, byprop.clone(), Some(&["space", "Alpha"]), &["Alpha"],
+        ).unwrap();
+        assert_eq!(some.keys().collect::<Vec<_>>(), vec!["White_Space"]);
+
+        let some = super::select(&names, byprop.clone(), None, &["Dash"])
+            .unwrap();
+        assert_eq!(some.len(), 2);
+        assert!(!some.contains_key("Dash"));
+
+        assert!(super::select(&names, byprop, Some(&["Nope"]), &[]).is_err());
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use ucd_parse::CodepointRange;
+
+use error::Result;
+use util::PropertyValues;
+
+/// Build a map from each value of an enumerated property to the set of
+/// codepoints with that value, using rows that each assign a value to a
+/// range of codepoints.
+///
+/// `property` is any alias of the property, e.g., `SB` for
+/// `Sentence_Break`. The keys of the map are the canonical names of each
+/// value, as given by `propvals`. Values that don't belong to the property
+/// result in an error.
+pub fn tables<'a, I>(
+    propvals: &PropertyValues,
+    property: &str,
+    rows: I,
+) -> Result<BTreeMap<String, BTreeSet<u32>>>
+    where I: IntoIterator<Item=(CodepointRange, &'a str)>
+{
+    let mut byval: BTreeMap<String, BTreeSet<u32>> = BTreeMap::new();
+    for (codepoints, value) in rows {
+        let value = propvals.canonical(property, value)?.to_string();
+        byval.entry(value)
+            .or_insert(BTreeSet::new())
+            .extend(codepoints.into_iter().map(|cp| cp.value()));
+    }
+    Ok(byval)
+}
+
+/// Like `tables`, except every codepoint that isn't in `rows` has a default
+/// value from `defaults`, such as those given by `# @missing:` lines.
+///
+/// When more than one default applies to a codepoint, the last one takes
+/// precedence. Codepoints that are in neither `rows` nor `defaults` are
+/// omitted.
+pub fn tables_with_defaults<'a, D, I>(
+    propvals: &PropertyValues,
+    property: &str,
+    defaults: D,
+    rows: I,
+) -> Result<BTreeMap<String, BTreeSet<u32>>>
+    where D: IntoIterator<Item=(CodepointRange, &'a str)>,
+          I: IntoIterator<Item=(CodepointRange, &'a str)>
+{
+    let mut values: Vec<Option<&str>> = vec![None; 0x110000];
+    for (codepoints, value) in defaults.into_iter().chain(rows) {
+        for cp in codepoints {
+            values[cp.value() as usize] = Some(value);
+        }
+    }
+    let mut byval: BTreeMap<String, BTreeSet<u32>> = BTreeMap::new();
+    let mut canonical: BTreeMap<&str, String> = BTreeMap::new();
+    for (cp, value) in values.into_iter().enumerate() {
+        let value = match value {
+            None => continue,
+            Some(value) => value,
+        };
+        if !canonical.contains_key(value) {
+            let name = propvals.canonical(property, value)?.to_string();
+            canonical.insert(value, name);
+        }
+        byval.entry(canonical[value].clone())
+            .or_insert(BTreeSet::new())
+            .insert(cp as u32);
+    }
+    Ok(byval)
+}
+
+#[cfg(test)]
+mod tests {
+    use ucd_parse::CodepointRange;
+
+    use util::{self, PropertyValues};
+
+    const ALIASES: &'static str = "\
+SB ; AT ; ATerm
+SB ; LO ; Lower
+SB ; UP ; Upper
+SB ; XX ; Other
+";
+
+    fn range(start: u32, end: u32) -> CodepointRange {
+        format!("{:04X}..{:04X}", start, end).parse().unwrap()
+    }
+
+    fn propvals() -> PropertyValues {
+        util::property_values("SB ; Sentence_Break\n", ALIASES)
+    }
+
+    #[test]
+    fn tables() {
+        let propvals = propvals();
+
+        let rows = vec![
+            (range(0x2E, 0x2E), "ATerm"),
+            (range(0x41, 0x5A), "Upper"),
+            (range(0x61, 0x7A), "LO"),
+            (range(0xC0, 0xD6), "Upper"),
+        ];
+        let byval = super::tables(&propvals, "SB", rows).unwrap();
+        assert_eq!(
+            byval.keys().collect::<Vec<_>>(),
+            vec!["ATerm", "Lower", "Upper"]);
+        assert_eq!(byval["Lower"].len(), 26);
+        assert_eq!(byval["Upper"].len(), 49);
+
+        let rows = vec![(range(0x20, 0x20), "Sp")];
+        assert!(super::tables(&propvals, "SB", rows).is_err());
+    }
+
+    #[test]
+    fn tables_with_defaults() {
+        let propvals = propvals();
+        let defaults = vec![
+            (range(0x0000, 0x00FF), "Other"),
+            (range(0x0041, 0x007A), "Upper"),
+        ];
+        let rows = vec![
+            (range(0x2E, 0x2E), "ATerm"),
+            (range(0x61, 0x7A), "LO"),
+        ];
+        let byval = super::tables_with_defaults(
+            &propvals, "SB", defaults, rows).unwrap();
+        assert_eq!(
+            byval.keys().collect::<Vec<_>>(),
+            vec!["ATerm", "Lower", "Other", "Upper"]);
+        assert_eq!(byval["Upper"].len(), 32);
+        assert_eq!(byval["Lower"].len(), 26);
+        assert_eq!(byval["Other"].len(), 0x100 - 59);
+        assert!(!byval["Other"].contains(&0x2E));
+        assert!(!byval["Other"].contains(&0x100));
+    }
+}
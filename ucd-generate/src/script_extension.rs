@@ -0,0 +1,101 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use ucd_parse::{self, ScriptExtensions, Scripts};
+
+use error::Result;
+use util::PropertyValues;
+
+/// Build a map from each script to the set of codepoints whose
+/// Script_Extensions property contains that script, using the UCD in the
+/// given directory.
+pub fn tables_from_ucd_dir<P: AsRef<Path>>(
+    ucd_dir: P,
+) -> Result<BTreeMap<String, BTreeSet<u32>>> {
+    let propvals = PropertyValues::from_ucd_dir(&ucd_dir)?;
+    let scripts = ucd_parse::parse(&ucd_dir)?;
+    let extensions = ucd_parse::parse(&ucd_dir)?;
+    tables(&propvals, scripts, extensions)
+}
+
+/// Build a map from each script to the set of codepoints whose
+/// Script_Extensions property contains that script, using rows already
+/// parsed from Scripts.txt and ScriptExtensions.txt.
+///
+/// The keys of the map are the canonical names of each script, as given by
+/// `propvals`. Codepoints that aren't listed in ScriptExtensions.txt have
+/// their Script value as their only extension, so every script contains at
+/// least the codepoints that are listed under it in Scripts.txt and have
+/// no explicit extensions.
+pub fn tables(
+    propvals: &PropertyValues,
+    scripts: Vec<Scripts>,
+    extensions: Vec<ScriptExtensions>,
+) -> Result<BTreeMap<String, BTreeSet<u32>>> {
+    let mut byscript: BTreeMap<String, BTreeSet<u32>> = BTreeMap::new();
+    let mut extended = BTreeSet::new();
+    for row in extensions {
+        let range = row.codepoints.start.value()
+            ..(row.codepoints.end.value() + 1);
+        extended.extend(range.clone());
+        for script in &row.scripts {
+            let sc = propvals.canonical("sc", script)?.to_string();
+            byscript.entry(sc)
+                .or_insert(BTreeSet::new())
+                .extend(range.clone());
+        }
+    }
+    for row in scripts {
+        let sc = propvals.canonical("sc", &row.value)?.to_string();
+        let set = byscript.entry(sc).or_insert(BTreeSet::new());
+        for cp in row.codepoints {
+            if !extended.contains(&cp.value()) {
+                set.insert(cp.value());
+            }
+        }
+    }
+    Ok(byscript)
+}
+
+#[cfg(test)]
+mod tests {
+    use ucd_parse::{ScriptExtensions, Scripts, UcdFile};
+
+    use util;
+
+    const ALIASES: &'static str = "\
+sc ; Arab ; Arabic
+sc ; Syrc ; Syriac
+sc ; Thaa ; Thaana
+sc ; Zyyy ; Common
+";
+
+    #[test]
+    fn extensions_override_script() {
+        let propvals = util::property_values(
+            "sc ; Script\nscx ; Script_Extensions\n", ALIASES);
+
+        let scripts: Vec<Scripts> = Scripts::from_data("\
+0041..005A    ; Common
+060C          ; Common
+0620..063F    ; Arabic
+0710          ; Syriac
+").collect::<Result<_, _>>().unwrap();
+        let extensions: Vec<ScriptExtensions> =
+            ScriptExtensions::from_data("060C ; Arab Syrc Thaa\n")
+                .collect::<Result<_, _>>()
+                .unwrap();
+        let byscript = super::tables(&propvals, scripts, extensions).unwrap();
+
+        let get = |sc: &str| -> Vec<u32> {
+            byscript[sc].iter().cloned().collect()
+        };
+        assert_eq!(byscript.len(), 4);
+        assert_eq!(get("Common").len(), 26);
+        assert!(!byscript["Common"].contains(&0x060C));
+        assert_eq!(get("Arabic")[0], 0x060C);
+        assert_eq!(get("Arabic").len(), 33);
+        assert_eq!(get("Syriac"), vec![0x060C, 0x0710]);
+        assert_eq!(get("Thaana"), vec![0x060C]);
+    }
+}
@@ -0,0 +1,95 @@
+/*!
+Generating tables from arbitrary data.
+
+The `Writer` doesn't depend on the UCD: each of its methods accepts plain
+sets and maps. This module provides a way to describe a whole file of
+tables independently of any particular writer, by implementing
+`TableSource`. A source calls a `TableVisitor` once for each table, and a
+`Writer` is a visitor that writes each table it's given.
+
+This makes it possible to generate tables from data that isn't in the UCD
+(for example, a registry of private use characters), and to combine such
+tables with tables built from the UCD in a single file.
+*/
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use error::Result;
+use writer::Writer;
+
+/// A single table of Unicode data, borrowed from its source.
+///
+/// Each variant corresponds to a method on `Writer`, which documents the
+/// items emitted for that kind of table.
+#[derive(Clone, Copy, Debug)]
+pub enum Table<'a> {
+    /// A set of codepoints. See `Writer::ranges`.
+    Ranges(&'a BTreeSet<u32>),
+    /// A map from an enumeration value to the set of codepoints with that
+    /// value. See `Writer::ranges_to_enum`.
+    Enum(&'a BTreeMap<String, BTreeSet<u32>>),
+    /// A map from codepoint to integer. See
+    /// `Writer::ranges_to_unsigned_integer`.
+    UnsignedInteger(&'a BTreeMap<u32, u64>),
+    /// A map from codepoint to codepoint. See
+    /// `Writer::codepoint_to_codepoint`.
+    CodepointToCodepoint(&'a BTreeMap<u32, u32>),
+    /// A map from codepoint to a sequence of codepoints. See
+    /// `Writer::codepoint_to_codepoints`.
+    CodepointToCodepoints(&'a BTreeMap<u32, Vec<u32>>),
+    /// A map from codepoint to string. See `Writer::codepoint_to_string`.
+    CodepointToString(&'a BTreeMap<u32, String>),
+    /// A map from string to codepoint. See `Writer::string_to_codepoint`.
+    StringToCodepoint(&'a BTreeMap<String, u32>),
+    /// A map from string to integer. See `Writer::string_to_u64`.
+    StringToU64(&'a BTreeMap<String, u64>),
+}
+
+/// A visitor of named tables.
+pub trait TableVisitor {
+    /// Visit a single table with the given name.
+    ///
+    /// If this returns an error, then the source calling it should stop and
+    /// return that error.
+    fn visit(&mut self, name: &str, table: Table) -> Result<()>;
+}
+
+/// A source of named tables.
+///
+/// This is implemented for any closure with the signature of `visit`, so
+/// that a callback can be used as a source directly.
+pub trait TableSource {
+    /// Call the given visitor for each table in this source, in order.
+    fn visit(&self, visitor: &mut TableVisitor) -> Result<()>;
+}
+
+impl<F> TableSource for F where F: Fn(&mut TableVisitor) -> Result<()> {
+    fn visit(&self, visitor: &mut TableVisitor) -> Result<()> {
+        self(visitor)
+    }
+}
+
+impl TableVisitor for Writer {
+    fn visit(&mut self, name: &str, table: Table) -> Result<()> {
+        match table {
+            Table::Ranges(set) => self.ranges(name, set),
+            Table::Enum(map) => self.ranges_to_enum(name, map),
+            Table::UnsignedInteger(map) => {
+                self.ranges_to_unsigned_integer(name, map)
+            }
+            Table::CodepointToCodepoint(map) => {
+                self.codepoint_to_codepoint(name, map)
+            }
+            Table::CodepointToCodepoints(map) => {
+                self.codepoint_to_codepoints(name, map)
+            }
+            Table::CodepointToString(map) => {
+                self.codepoint_to_string(name, map)
+            }
+            Table::StringToCodepoint(map) => {
+                self.string_to_codepoint(name, map)
+            }
+            Table::StringToU64(map) => self.string_to_u64(name, map),
+        }
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 use std::path::Path;
 use std::str;
 
-use ucd_parse::{PropertyAlias, PropertyValueAlias};
+use ucd_parse::{self, PropertyAlias, PropertyValueAlias};
 use ucd_util;
 
 use error::Result;
@@ -15,12 +15,20 @@ use error::Result;
 pub struct PropertyNames(BTreeMap<String, String>);
 
 impl PropertyNames {
+    /// Build the map of property names from PropertyAliases.txt in the
+    /// given UCD directory.
     pub fn from_ucd_dir<P: AsRef<Path>>(ucd_dir: P) -> Result<PropertyNames> {
-        use ucd_parse::UcdFile;
+        let aliases: Vec<PropertyAlias> = ucd_parse::parse(ucd_dir)?;
+        Ok(PropertyNames::from_aliases(aliases))
+    }
 
+    /// Build the map of property names from rows already parsed from
+    /// PropertyAliases.txt.
+    pub fn from_aliases<'a, I>(aliases: I) -> PropertyNames
+        where I: IntoIterator<Item=PropertyAlias<'a>>
+    {
         let mut map = BTreeMap::new();
-        for result in PropertyAlias::from_dir(ucd_dir)? {
-            let a = result?;
+        for a in aliases {
             let canon = a.long.to_string();
             let make_key = |mut value| {
                 ucd_util::symbolic_name_normalize(&mut value);
@@ -33,7 +41,7 @@ impl PropertyNames {
             map.insert(make_key(a.abbreviation.into_owned()), canon.clone());
             map.insert(make_key(a.long.into_owned()), canon);
         }
-        Ok(PropertyNames(map))
+        PropertyNames(map)
     }
 
     /// Return the "canonical" or "long" property name for the given property
@@ -59,13 +67,25 @@ pub struct PropertyValues {
 }
 
 impl PropertyValues {
+    /// Build the map of property values from PropertyValueAliases.txt in the
+    /// given UCD directory.
     pub fn from_ucd_dir<P: AsRef<Path>>(ucd_dir: P) -> Result<PropertyValues> {
-        use ucd_parse::UcdFile;
-
         let props = PropertyNames::from_ucd_dir(&ucd_dir)?;
+        let aliases: Vec<PropertyValueAlias> = ucd_parse::parse(ucd_dir)?;
+        PropertyValues::from_aliases(props, aliases)
+    }
+
+    /// Build the map of property values from rows already parsed from
+    /// PropertyValueAliases.txt. The property of each row must be in
+    /// `props`.
+    pub fn from_aliases<'a, I>(
+        props: PropertyNames,
+        aliases: I,
+    ) -> Result<PropertyValues>
+        where I: IntoIterator<Item=PropertyValueAlias<'a>>
+    {
         let mut outer_map = BTreeMap::new();
-        for result in PropertyValueAlias::from_dir(ucd_dir)? {
-            let a = result?;
+        for a in aliases {
             let prop = props.canonical(&a.property)?.to_string();
             let canon = a.long.to_string();
             let make_key = |mut value| {
@@ -178,3 +198,45 @@ pub fn range_value_add(
     }
     ranges.push((codepoint, codepoint, value));
 }
+
+/// Return the complement of the given sorted ranges with respect to all
+/// codepoints.
+pub fn complement_ranges(ranges: &[(u32, u32)]) -> Vec<(u32, u32)> {
+    let mut complement = vec![];
+    let mut next = 0;
+    for &(start, end) in ranges {
+        if start > next {
+            complement.push((next, start - 1));
+        }
+        next = end + 1;
+    }
+    if next <= 0x10FFFF {
+        complement.push((next, 0x10FFFF));
+    }
+    complement
+}
+
+/// Build the map of property names from the contents of
+/// PropertyAliases.txt, for use in tests.
+#[cfg(test)]
+pub fn property_names(aliases: &str) -> PropertyNames {
+    use ucd_parse::UcdFile;
+
+    let rows: Vec<PropertyAlias> = PropertyAlias::from_data(aliases)
+        .collect::<::std::result::Result<_, _>>()
+        .unwrap();
+    PropertyNames::from_aliases(rows)
+}
+
+/// Build the map of property values from the contents of
+/// PropertyAliases.txt and PropertyValueAliases.txt, for use in tests.
+#[cfg(test)]
+pub fn property_values(aliases: &str, value_aliases: &str) -> PropertyValues {
+    use ucd_parse::UcdFile;
+
+    let rows: Vec<PropertyValueAlias> =
+        PropertyValueAlias::from_data(value_aliases)
+            .collect::<::std::result::Result<_, _>>()
+            .unwrap();
+    PropertyValues::from_aliases(property_names(aliases), rows).unwrap()
+}
@@ -1,40 +1,75 @@
-#![allow(dead_code)]
-
-// BREADCRUMBS:
-//
-// Move all output decisions into the Writer. Basic idea is that we specify
-// common data structures and do the FST conversion inside the writer.
-//
-// Remove --raw-fst and make --rust-fst always emit FSTs to separate files
-// They use much less space on disk, i.e., by avoiding encoding bytes in a
-// byte literal.
-//
-// Problem: --rust-slice writes directly to a single file and it make sense
-// to emit it to stdout. But --rust-fst wants to write at least two files:
-// one for Rust source and another for the FST. An obvious solution is to leave
-// --rust-slice untouched but require a directory argument for --rust-fst.
-// It's a bit incongruous, which is unfortunate, but probably makes the most
-// sense. However, if --rust-slice is always the default (which seems
-// reasonable), then we could just remove --rust-slice altogether and that,
-// I think, removes some of the incongruity.
+/*!
+Writing Unicode tables as Rust source code.
+
+A `Writer` is created from a `WriterBuilder`, which fixes the output format
+for every table written. Each method on `Writer` corresponds to a kind of
+table:
+
+* `ranges` and `ranges_many` write sets of codepoints.
+* `ranges_to_enum` writes a map from codepoint to one of a fixed set of
+  string values.
+* `ranges_to_unsigned_integer` and `ranges_to_signed_integer` write a map
+  from codepoint to an integer.
+* `codepoint_to_codepoint` writes a map from codepoint to codepoint.
+* `codepoint_to_codepoints` writes a map from codepoint to a short sequence
+  of codepoints.
+* `codepoint_to_string` writes a map from codepoint to string.
+* `string_to_codepoint` writes a map from string to codepoint.
+* `string_to_u64` writes a map from string to integer.
+
+Alternatively, tables can be written by passing a `TableSource` to
+`Writer::source`. See the `table` module for details.
+
+By default, each table is emitted as a `pub const` sorted slice. If an FST
+directory is given, then each table is instead emitted as a `lazy_static`
+FST (as provided by the `fst` crate) that is loaded from a file written to
+that directory. If tries are enabled, then sets and maps of codepoints are
+instead emitted as tries from the `ucd-trie` crate. Other options on
+`WriterBuilder` change the representation of slice tables and are
+documented there.
+
+The `ucd-table` crate implements common lookup traits for each of these
+representations, so that code using a table doesn't need to change when its
+representation does.
+
+The name given to each method is converted to an upper case Rust constant
+name. The Rust items emitted for a given table kind and set of options,
+including their names and types, are part of this crate's public API.
+Changing them is a breaking change. The formatting of the emitted source
+(whitespace, line breaks and the header comment) is not.
+
+Every method returns an error if writing fails. Methods that encode strings
+also return an error if a string can't be represented in the output format;
+the conditions are documented on each method. When a method returns an
+error, the output may contain a partially written table.
+*/
 
 use std::ascii;
 use std::char;
 use std::collections::{BTreeMap, BTreeSet};
 use std::env;
+use std::fmt;
 use std::fs::File;
+use std::hash::Hash;
 use std::io::{self, Write};
+use std::mem;
 use std::path::{Path, PathBuf};
 use std::str;
 
 use byteorder::{ByteOrder, BigEndian as BE};
-use fst::{Map, MapBuilder, Set, SetBuilder};
-use fst::raw::Fst;
+use fst::{MapBuilder, SetBuilder};
+use rayon::prelude::*;
 use ucd_parse::Codepoint;
+use ucd_trie::{TrieMap, TrieSet, TrieSetSlice};
 
 use error::Result;
+use table::TableSource;
 use util;
 
+/// A builder for configuring the output format of a `Writer`.
+///
+/// The defaults are to emit sorted slices of `u32` codepoints, wrapped at
+/// 79 columns.
 #[derive(Clone, Debug)]
 pub struct WriterBuilder(WriterOptions);
 
@@ -44,27 +79,44 @@ struct WriterOptions {
     columns: u64,
     char_literals: bool,
     fst_dir: Option<PathBuf>,
+    share_complements: bool,
+    front_coded: bool,
+    trie: bool,
+    header: bool,
+    lazy_static: bool,
 }
 
 impl WriterBuilder {
-    /// Create a new builder Unicode writers.
+    /// Create a new builder for Unicode writers.
     ///
     /// The name given corresponds to the Rust module name to use when
-    /// applicable.
+    /// applicable. In particular, `from_fst_dir` writes Rust source code to
+    /// `{name}.rs` in the FST directory.
     pub fn new(name: &str) -> WriterBuilder {
         WriterBuilder(WriterOptions {
             name: name.to_string(),
             columns: 79,
             char_literals: false,
             fst_dir: None,
+            share_complements: false,
+            front_coded: false,
+            trie: false,
+            header: true,
+            lazy_static: true,
         })
     }
 
-    /// Create a new Unicode writer from this builder's configuration.
+    /// Create a new Unicode writer from this builder's configuration that
+    /// writes Rust source code to the given writer.
+    ///
+    /// The writer given is buffered internally, and is flushed after each
+    /// table is written.
     pub fn from_writer<W: io::Write + 'static>(&self, wtr: W) -> Writer {
         Writer {
-            wtr: LineWriter::new(Box::new(wtr)),
+            wtr: LineWriter::new(Box::new(io::BufWriter::new(wtr))),
             wrote_header: false,
+            wrote_front_coded: false,
+            range_tables: BTreeMap::new(),
             opts: self.0.clone(),
         }
     }
@@ -75,14 +127,21 @@ impl WriterBuilder {
     }
 
     /// Create a new Unicode writer that writes FSTs to a directory.
+    ///
+    /// Both the Rust source code and the FST files are written to the given
+    /// directory, which must already exist. This returns an error if the
+    /// Rust source file could not be created.
     pub fn from_fst_dir<P: AsRef<Path>>(&self, fst_dir: P) -> Result<Writer> {
         let mut opts = self.0.clone();
         opts.fst_dir = Some(fst_dir.as_ref().to_path_buf());
         let mut fpath = fst_dir.as_ref().join(rust_module_name(&opts.name));
         fpath.set_extension("rs");
+        let file = io::BufWriter::new(File::create(fpath)?);
         Ok(Writer {
-            wtr: LineWriter::new(Box::new(File::create(fpath)?)),
+            wtr: LineWriter::new(Box::new(file)),
             wrote_header: false,
+            wrote_front_coded: false,
+            range_tables: BTreeMap::new(),
             opts: opts,
         })
     }
@@ -105,9 +164,12 @@ impl WriterBuilder {
 
     /// Emit codepoints as a finite state transducer.
     ///
-    /// The directory given is where both the Rust source file and the FST
-    /// files are written. The Rust source file includes the FSTs using the
-    /// `include_bytes!` macro.
+    /// The directory given is where the FST files are written. The Rust
+    /// source file includes the FSTs using the `include_bytes!` macro, so it
+    /// is usually written to the same directory. `from_fst_dir` does this
+    /// automatically.
+    ///
+    /// The generated code requires the `fst` and `lazy_static` crates.
     pub fn fst_dir<P: AsRef<Path>>(
         &mut self,
         fst_dir: Option<P>,
@@ -115,19 +177,117 @@ impl WriterBuilder {
         self.0.fst_dir = fst_dir.map(|p| p.as_ref().to_path_buf());
         self
     }
+
+    /// Share storage between sets of codepoints written by `ranges`.
+    ///
+    /// When writing a set of codepoints that is identical to a set that was
+    /// previously written, emit an alias to the previous table instead of
+    /// writing a new table. When writing a set of codepoints that is the
+    /// complement of a set that was previously written, also emit a
+    /// `{NAME}_COMPLEMENT` alias to the previous table. `{NAME}` itself is
+    /// still written in full, so callers that need both a set and its
+    /// complement get the latter for free. Complements are only shared
+    /// between slice tables.
+    ///
+    /// This changes the items emitted, so it is disabled by default.
+    pub fn share_complements(&mut self, yes: bool) -> &mut WriterBuilder {
+        self.0.share_complements = yes;
+        self
+    }
+
+    /// When writing a slice table whose keys or values are strings, compress
+    /// the strings with front coding instead of emitting a string literal
+    /// for every entry.
+    ///
+    /// Front coded tables are emitted as a `FrontCodedStrings` value along
+    /// with a slice of the other half of each entry. The definition of
+    /// `FrontCodedStrings`, which knows how to decode the strings, is emitted
+    /// once per file. This has no effect on FST tables.
+    pub fn front_coded(&mut self, yes: bool) -> &mut WriterBuilder {
+        self.0.front_coded = yes;
+        self
+    }
+
+    /// Emit sets of codepoints as a `ucd_trie::TrieSetSlice` and maps from
+    /// codepoints to integers as a `ucd_trie::TrieMapSlice`, instead of as
+    /// slices of ranges.
+    ///
+    /// The generated code requires the `ucd-trie` crate. This applies to
+    /// `ranges`, `ranges_to_enum` and `ranges_to_unsigned_integer`, and has
+    /// no effect when writing FSTs.
+    pub fn trie(&mut self, yes: bool) -> &mut WriterBuilder {
+        self.0.trie = yes;
+        self
+    }
+
+    /// Whether to write a header before the first table. This is enabled
+    /// by default.
+    ///
+    /// The header contains an inner `allow(dead_code)` attribute, which
+    /// can't appear in a file that is included with the `include!` macro.
+    /// Disable the header when writing tables from a build script.
+    pub fn header(&mut self, yes: bool) -> &mut WriterBuilder {
+        self.0.header = yes;
+        self
+    }
+
+    /// When writing FSTs, emit each FST as a static initialized with the
+    /// `lazy_static!` macro. This is enabled by default.
+    ///
+    /// When disabled, each FST is instead emitted as a `{NAME}_BYTES`
+    /// constant along with a function, named after the table in lower case,
+    /// that returns the FST. Constructing an FST from its bytes is cheap
+    /// and doesn't allocate. This removes the dependency on the
+    /// `lazy_static` crate, which is useful for targets like
+    /// `wasm32-unknown-unknown`.
+    pub fn lazy_static(&mut self, yes: bool) -> &mut WriterBuilder {
+        self.0.lazy_static = yes;
+        self
+    }
+}
+
+/// How a single table of codepoint ranges is written.
+enum RangeTable {
+    /// The table is written out in full.
+    New(Vec<(u32, u32)>),
+    /// The table is an alias of the named table, which is identical.
+    Alias(String),
+    /// The table is written out in full, and its complement is the named
+    /// table.
+    Complement(Vec<(u32, u32)>, String),
 }
 
 /// A writer of various kinds of Unicode data.
 ///
 /// A writer takes as input various forms of Unicode data and writes that data
-/// in a number of different output formats.
+/// in a number of different output formats. The first table written is
+/// preceded by a header, which includes an `allow(dead_code)` attribute and
+/// the command used to generate the file.
 pub struct Writer {
     wtr: LineWriter<Box<io::Write + 'static>>,
     wrote_header: bool,
+    /// Whether the definition of `FrontCodedStrings` has been written.
+    wrote_front_coded: bool,
+    /// A map from every codepoint set written by `ranges` to the name of the
+    /// table it was written to. This is used to share storage between
+    /// identical (or complementary) sets when `share_complements` is
+    /// enabled.
+    range_tables: BTreeMap<Vec<(u32, u32)>, String>,
     opts: WriterOptions,
 }
 
 impl Writer {
+    /// Write every table in the given source, in order.
+    ///
+    /// This stops at the first error returned by writing a table, or by the
+    /// source itself.
+    pub fn source<S: TableSource + ?Sized>(
+        &mut self,
+        source: &S,
+    ) -> Result<()> {
+        source.visit(self)
+    }
+
     /// Write a sorted sequence of codepoints.
     ///
     /// Note that the specific representation of ranges may differ with the
@@ -135,28 +295,118 @@ impl Writer {
     /// straight-forward slice of sorted codepoint ranges is emitted. But if
     /// the output format is an FST or similar, then all codepoints are
     /// explicitly represented.
+    ///
+    /// If `share_complements` is enabled and the same set of codepoints was
+    /// previously written by this writer, then the new table is emitted as
+    /// an alias of the previous one.
     pub fn ranges(
         &mut self,
         name: &str,
         codepoints: &BTreeSet<u32>,
     ) -> Result<()> {
-        self.header()?;
-        self.separator()?;
+        self.ranges_many(&[(name, codepoints)])
+    }
 
-        let name = rust_const_name(name);
-        if self.opts.fst_dir.is_some() {
-            let mut builder = SetBuilder::memory();
-            builder.extend_iter(codepoints.iter().cloned().map(u32_key))?;
-            let set = Set::from_bytes(builder.into_inner()?)?;
-            self.fst(&name, set.as_fst(), false)?;
-        } else {
-            let ranges = util::to_ranges(codepoints.iter().cloned());
-            self.ranges_slice(&name, &ranges)?;
+    /// Write many sorted sequences of codepoints, in the order given.
+    ///
+    /// This emits exactly what calling `ranges` on each table in turn would
+    /// emit, except that the tables are built in parallel. This matters most
+    /// when writing FSTs, since building an FST is far more expensive than
+    /// writing a slice.
+    pub fn ranges_many(
+        &mut self,
+        tables: &[(&str, &BTreeSet<u32>)],
+    ) -> Result<()> {
+        let all_ranges: Vec<Vec<(u32, u32)>> = tables
+            .par_iter()
+            .map(|&(_, set)| util::to_ranges(set.iter().cloned()))
+            .collect();
+
+        // Deciding which tables can share storage depends on which tables
+        // came before, so this must be done sequentially.
+        let mut kinds = vec![];
+        for (&(name, _), ranges) in tables.iter().zip(all_ranges) {
+            let name = rust_const_name(name);
+            let kind = self.range_table_kind(&name, ranges);
+            kinds.push((name, kind));
+        }
+
+        if let Some(ref fst_dir) = self.opts.fst_dir {
+            kinds
+                .par_iter()
+                .zip(tables.par_iter())
+                .map(|(&(ref name, ref kind), &(_, set))| {
+                    match *kind {
+                        RangeTable::New(_) => {
+                            let file = fst_file(fst_dir, name)?;
+                            let mut builder = SetBuilder::new(file)?;
+                            builder.extend_iter(
+                                set.iter().cloned().map(u32_key))?;
+                            builder.finish()?;
+                            Ok(())
+                        }
+                        _ => Ok(()),
+                    }
+                })
+                .collect::<Result<()>>()?;
+        }
+
+        for ((name, kind), &(_, set)) in kinds.into_iter().zip(tables) {
+            self.header()?;
+            self.separator()?;
+            match kind {
+                RangeTable::New(ranges) => {
+                    if self.opts.fst_dir.is_some() {
+                        self.fst_static(&name, &fst_file_name(&name), false)?;
+                    } else if self.opts.trie {
+                        self.ranges_trie(&name, set)?;
+                    } else {
+                        self.ranges_slice(&name, &ranges)?;
+                    }
+                }
+                RangeTable::Alias(original) => {
+                    self.ranges_alias(&name, &original)?;
+                }
+                RangeTable::Complement(ranges, original) => {
+                    self.ranges_slice(&name, &ranges)?;
+                    let ty = self.rust_codepoint_type();
+                    writeln!(
+                        self.wtr,
+                        "pub const {}_COMPLEMENT: &'static [({}, {})] = {};",
+                        name, ty, ty, original)?;
+                }
+            }
+            self.wtr.flush()?;
         }
-        self.wtr.flush()?;
         Ok(())
     }
 
+    /// Determine how the given ranges should be written, and remember them
+    /// so that later tables can share their storage.
+    fn range_table_kind(
+        &mut self,
+        name: &str,
+        ranges: Vec<(u32, u32)>,
+    ) -> RangeTable {
+        if !self.opts.share_complements {
+            return RangeTable::New(ranges);
+        }
+        if let Some(original) = self.range_tables.get(&ranges) {
+            return RangeTable::Alias(original.clone());
+        }
+        let slice = self.opts.fst_dir.is_none() && !self.opts.trie;
+        if slice {
+            let complement = util::complement_ranges(&ranges);
+            if let Some(original) = self.range_tables.get(&complement) {
+                let original = original.clone();
+                self.range_tables.insert(ranges.clone(), name.to_string());
+                return RangeTable::Complement(ranges, original);
+            }
+        }
+        self.range_tables.insert(ranges.clone(), name.to_string());
+        RangeTable::New(ranges)
+    }
+
     fn ranges_slice(
         &mut self,
         name: &str,
@@ -170,19 +420,55 @@ impl Writer {
         for &(start, end) in table {
             let range = (self.rust_codepoint(start), self.rust_codepoint(end));
             if let (Some(start), Some(end)) = range {
-                self.wtr.write_str(&format!("({}, {}), ", start, end))?;
+                self.wtr.write_item(format_args!("({}, {}), ", start, end))?;
             }
         }
         writeln!(self.wtr, "];")?;
         Ok(())
     }
 
+    fn ranges_trie(&mut self, name: &str, set: &BTreeSet<u32>) -> Result<()> {
+        let codepoints: Vec<u32> = set.iter().cloned().collect();
+        let trie = TrieSet::from_codepoints(&codepoints);
+        writeln!(
+            self.wtr,
+            "pub const {}: &'static ::ucd_trie::TrieSetSlice<'static> =",
+            name)?;
+        writeln!(self.wtr, "  &::ucd_trie::TrieSetSlice {{")?;
+        self.trie_set_levels("  ", trie.as_slice())?;
+        writeln!(self.wtr, "}};")?;
+        Ok(())
+    }
+
+    /// Write a table that shares its storage with a table of the same type
+    /// that was previously written.
+    fn ranges_alias(&mut self, name: &str, original: &str) -> Result<()> {
+        if self.opts.fst_dir.is_some() {
+            self.fst_static(name, &fst_file_name(original), false)
+        } else if self.opts.trie {
+            writeln!(
+                self.wtr,
+                "pub const {}: &'static ::ucd_trie::TrieSetSlice<'static> = \
+                 {};",
+                name, original)?;
+            Ok(())
+        } else {
+            let ty = self.rust_codepoint_type();
+            writeln!(
+                self.wtr,
+                "pub const {}: &'static [({}, {})] = {};",
+                name, ty, ty, original)?;
+            Ok(())
+        }
+    }
+
     /// Write a map that associates codepoint ranges to a single value in an
     /// enumeration. This usually emits two items: a map from codepoint range
     /// to index and a map from index to one of the enum variants.
     ///
     /// The given map should be a map from the enum variant value to the set
-    /// of codepoints that have that value.
+    /// of codepoints that have that value. The index of each variant is its
+    /// position in the map's sorted order.
     pub fn ranges_to_enum(
         &mut self,
         name: &str,
@@ -196,7 +482,7 @@ impl Writer {
             "pub const {}_ENUM: &'static [&'static str] = &[",
             rust_const_name(name))?;
         for variant in enum_map.keys() {
-            self.wtr.write_str(&format!("{:?}, ", variant))?;
+            self.wtr.write_item(format_args!("{:?}, ", variant))?;
         }
         writeln!(self.wtr, "];")?;
 
@@ -223,12 +509,16 @@ impl Writer {
 
         let name = rust_const_name(name);
         if self.opts.fst_dir.is_some() {
-            let mut builder = MapBuilder::memory();
+            let fst_dir = self.opts.fst_dir.as_ref().unwrap();
+            let file = fst_file(fst_dir, &name)?;
+            let mut builder = MapBuilder::new(file)?;
             for (&k, &v) in map {
                 builder.insert(u32_key(k), v)?;
             }
-            let map = Map::from_bytes(builder.into_inner()?)?;
-            self.fst(&name, map.as_fst(), true)?;
+            builder.finish()?;
+            self.fst_static(&name, &fst_file_name(&name), true)?;
+        } else if self.opts.trie {
+            self.ranges_to_unsigned_integer_trie(&name, map)?;
         } else {
             let ranges = util::to_range_values(
                 map.iter().map(|(&k, &v)| (k, v)));
@@ -256,14 +546,260 @@ impl Writer {
         for &(start, end, num) in table {
             let range = (self.rust_codepoint(start), self.rust_codepoint(end));
             if let (Some(start), Some(end)) = range {
-                let src = format!("({}, {}, {}), ", start, end, num);
-                self.wtr.write_str(&src)?;
+                self.wtr.write_item(
+                    format_args!("({}, {}, {}), ", start, end, num))?;
             }
         }
         writeln!(self.wtr, "];")?;
         Ok(())
     }
 
+    fn ranges_to_unsigned_integer_trie(
+        &mut self,
+        name: &str,
+        map: &BTreeMap<u32, u64>,
+    ) -> Result<()> {
+        let num_ty = match map.values().max() {
+            None => "u8",
+            Some(&max_num) => smallest_unsigned_type(max_num),
+        };
+        let pairs: Vec<(u32, u64)> =
+            map.iter().map(|(&k, &v)| (k, v)).collect();
+        self.integer_trie(name, num_ty, &pairs)
+    }
+
+    /// Write a `TrieMapSlice` of the given codepoint and integer pairs, with
+    /// values of the given Rust type.
+    fn integer_trie<V>(
+        &mut self,
+        name: &str,
+        num_ty: &str,
+        pairs: &[(u32, V)],
+    ) -> Result<()>
+        where V: Copy + Default + Eq + Hash + fmt::Display + fmt::UpperHex
+    {
+        let trie = TrieMap::from_codepoints(pairs);
+        let trie = trie.as_slice();
+
+        writeln!(
+            self.wtr,
+            "pub const {}: &'static ::ucd_trie::TrieMapSlice<'static, {}> =",
+            name, num_ty)?;
+        writeln!(self.wtr, "  &::ucd_trie::TrieMapSlice {{")?;
+        writeln!(self.wtr, "  keys: ::ucd_trie::TrieSetSlice {{")?;
+        self.trie_set_levels("    ", trie.keys)?;
+        writeln!(self.wtr, "  }},")?;
+        self.trie_level("  ", "tree1_level1", trie.tree1_level1, false)?;
+        self.trie_level("  ", "tree2_level1", trie.tree2_level1, false)?;
+        self.trie_level("  ", "tree2_level2", trie.tree2_level2, false)?;
+        self.trie_level("  ", "tree3_level1", trie.tree3_level1, false)?;
+        self.trie_level("  ", "tree3_level2", trie.tree3_level2, false)?;
+        self.trie_level("  ", "tree3_level3", trie.tree3_level3, false)?;
+        writeln!(self.wtr, "}};")?;
+        Ok(())
+    }
+
+    /// Write the fields of a `TrieSetSlice`, each indented by `indent`.
+    fn trie_set_levels(
+        &mut self,
+        indent: &str,
+        trie: TrieSetSlice,
+    ) -> Result<()> {
+        self.trie_level(indent, "tree1_level1", trie.tree1_level1, true)?;
+        self.trie_level(indent, "tree2_level1", trie.tree2_level1, false)?;
+        self.trie_level(indent, "tree2_level2", trie.tree2_level2, true)?;
+        self.trie_level(indent, "tree3_level1", trie.tree3_level1, false)?;
+        self.trie_level(indent, "tree3_level2", trie.tree3_level2, false)?;
+        self.trie_level(indent, "tree3_level3", trie.tree3_level3, true)?;
+        Ok(())
+    }
+
+    /// Write a single level of a trie as a struct field.
+    ///
+    /// Levels of bitsets are written in hexadecimal.
+    fn trie_level<T: fmt::Display + fmt::UpperHex>(
+        &mut self,
+        indent: &str,
+        field: &str,
+        level: &[T],
+        hex: bool,
+    ) -> Result<()> {
+        writeln!(self.wtr, "{}{}: &[", indent, field)?;
+        self.wtr.indent(&format!("{}  ", indent));
+        for n in level {
+            if hex {
+                self.wtr.write_item(format_args!("0x{:X}, ", n))?;
+            } else {
+                self.wtr.write_item(format_args!("{}, ", n))?;
+            }
+        }
+        self.wtr.flush_line()?;
+        self.wtr.indent("  ");
+        writeln!(self.wtr, "{}],", indent)?;
+        Ok(())
+    }
+
+    /// Write a map that associates ranges of codepoints with an arbitrary
+    /// signed integer.
+    ///
+    /// The smallest numeric type is used when applicable. When the output
+    /// format is an FST, then each integer is stored as the `u64` with the
+    /// same two's complement representation, i.e., `n as u64`, and should
+    /// be converted back with `v as i64`.
+    pub fn ranges_to_signed_integer(
+        &mut self,
+        name: &str,
+        map: &BTreeMap<u32, i64>,
+    ) -> Result<()> {
+        self.header()?;
+        self.separator()?;
+
+        let name = rust_const_name(name);
+        let num_ty = smallest_signed_type(
+            map.values().cloned().min().unwrap_or(0),
+            map.values().cloned().max().unwrap_or(0));
+        if self.opts.fst_dir.is_some() {
+            let fst_dir = self.opts.fst_dir.as_ref().unwrap();
+            let file = fst_file(fst_dir, &name)?;
+            let mut builder = MapBuilder::new(file)?;
+            for (&k, &v) in map {
+                builder.insert(u32_key(k), v as u64)?;
+            }
+            builder.finish()?;
+            self.fst_static(&name, &fst_file_name(&name), true)?;
+        } else if self.opts.trie {
+            let pairs: Vec<(u32, i64)> =
+                map.iter().map(|(&k, &v)| (k, v)).collect();
+            self.integer_trie(&name, num_ty, &pairs)?;
+        } else {
+            // Ranges of values are computed on the two's complement
+            // representation, which preserves equality.
+            let ranges = util::to_range_values(
+                map.iter().map(|(&k, &v)| (k, v as u64)));
+            let cp_ty = self.rust_codepoint_type();
+            writeln!(
+                self.wtr,
+                "pub const {}: &'static [({}, {}, {})] = &[",
+                name, cp_ty, cp_ty, num_ty)?;
+            for (start, end, num) in ranges {
+                let range =
+                    (self.rust_codepoint(start), self.rust_codepoint(end));
+                if let (Some(start), Some(end)) = range {
+                    self.wtr.write_item(format_args!(
+                        "({}, {}, {}), ", start, end, num as i64))?;
+                }
+            }
+            writeln!(self.wtr, "];")?;
+        }
+        self.wtr.flush()?;
+        Ok(())
+    }
+
+    /// Write a map that associates codepoints to other codepoints.
+    ///
+    /// When the output format is a slice, a sorted slice of pairs is
+    /// emitted. If char literals are enabled, then pairs in which either
+    /// codepoint is a surrogate are dropped. When the output format is an
+    /// FST, then the FST map emitted is from codepoint to u64, where the u64
+    /// is the codepoint mapped to. When the output format is a trie, then a
+    /// `TrieMapSlice` with `u32` values is emitted.
+    pub fn codepoint_to_codepoint(
+        &mut self,
+        name: &str,
+        map: &BTreeMap<u32, u32>,
+    ) -> Result<()> {
+        self.header()?;
+        self.separator()?;
+
+        let name = rust_const_name(name);
+        if self.opts.fst_dir.is_some() {
+            let fst_dir = self.opts.fst_dir.as_ref().unwrap();
+            let file = fst_file(fst_dir, &name)?;
+            let mut builder = MapBuilder::new(file)?;
+            for (&k, &v) in map {
+                builder.insert(u32_key(k), v as u64)?;
+            }
+            builder.finish()?;
+            self.fst_static(&name, &fst_file_name(&name), true)?;
+        } else if self.opts.trie {
+            let map: BTreeMap<u32, u64> =
+                map.iter().map(|(&k, &v)| (k, v as u64)).collect();
+            self.ranges_to_unsigned_integer_trie(&name, &map)?;
+        } else {
+            let ty = self.rust_codepoint_type();
+            writeln!(
+                self.wtr,
+                "pub const {}: &'static [({}, {})] = &[", name, ty, ty)?;
+            for (&k, &v) in map {
+                let pair = (self.rust_codepoint(k), self.rust_codepoint(v));
+                if let (Some(k), Some(v)) = pair {
+                    self.wtr.write_item(format_args!("({}, {}), ", k, v))?;
+                }
+            }
+            writeln!(self.wtr, "];")?;
+        }
+        self.wtr.flush()?;
+        Ok(())
+    }
+
+    /// Write a map that associates codepoints to sequences of codepoints.
+    ///
+    /// When the output format is a slice, a sorted slice of pairs of a
+    /// codepoint and a slice of codepoints is emitted. If char literals are
+    /// enabled, then pairs containing a surrogate are dropped.
+    ///
+    /// When the output format is an FST, then the FST map emitted is from
+    /// codepoint to u64, where the sequence is encoded into the u64. Each
+    /// codepoint in the sequence occupies 21 bits, starting with the least
+    /// significant bits, and the end of the sequence is delimited by a zero
+    /// codepoint. If a sequence has more than 3 codepoints or contains
+    /// `U+0000`, then an error is returned. Tries are not supported, so a
+    /// slice is emitted when they are enabled.
+    pub fn codepoint_to_codepoints(
+        &mut self,
+        name: &str,
+        map: &BTreeMap<u32, Vec<u32>>,
+    ) -> Result<()> {
+        self.header()?;
+        self.separator()?;
+
+        let name = rust_const_name(name);
+        if self.opts.fst_dir.is_some() {
+            let fst_dir = self.opts.fst_dir.as_ref().unwrap();
+            let file = fst_file(fst_dir, &name)?;
+            let mut builder = MapBuilder::new(file)?;
+            for (&k, v) in map {
+                builder.insert(u32_key(k), pack_codepoints(v)?)?;
+            }
+            builder.finish()?;
+            self.fst_static(&name, &fst_file_name(&name), true)?;
+        } else {
+            let ty = self.rust_codepoint_type();
+            writeln!(
+                self.wtr,
+                "pub const {}: &'static [({}, &'static [{}])] = &[",
+                name, ty, ty)?;
+            'PAIRS: for (&k, v) in map {
+                let k = match self.rust_codepoint(k) {
+                    None => continue,
+                    Some(k) => k,
+                };
+                let mut seq = vec![];
+                for &cp in v {
+                    match self.rust_codepoint(cp) {
+                        None => continue 'PAIRS,
+                        Some(cp) => seq.push(cp.to_string()),
+                    }
+                }
+                self.wtr.write_item(
+                    format_args!("({}, &[{}]), ", k, seq.join(", ")))?;
+            }
+            writeln!(self.wtr, "];")?;
+        }
+        self.wtr.flush()?;
+        Ok(())
+    }
+
     /// Write a map that associates codepoints to strings.
     ///
     /// When the output format is an FST, then the FST map emitted is from
@@ -272,6 +808,10 @@ impl Writer {
     /// string. The end of a string is delimited by the zero byte. If a string
     /// is more than 8 bytes or contains a `NUL` byte, then an error is
     /// returned.
+    ///
+    /// When the output format is front coded, then an error is returned if
+    /// a string longer than 255 bytes starts a block or shares more than 255
+    /// bytes with the previous string.
     pub fn codepoint_to_string(
         &mut self,
         name: &str,
@@ -282,17 +822,23 @@ impl Writer {
 
         let name = rust_const_name(name);
         if self.opts.fst_dir.is_some() {
-            let mut builder = MapBuilder::memory();
+            let fst_dir = self.opts.fst_dir.as_ref().unwrap();
+            let file = fst_file(fst_dir, &name)?;
+            let mut builder = MapBuilder::new(file)?;
             for (&k, v) in map {
                 let v = pack_str(v)?;
                 builder.insert(u32_key(k), v)?;
             }
-            let map = Map::from_bytes(builder.into_inner()?)?;
-            self.fst(&name, map.as_fst(), true)?;
+            builder.finish()?;
+            self.fst_static(&name, &fst_file_name(&name), true)?;
         } else {
             let table: Vec<(u32, &str)> =
                 map.iter().map(|(&k, v)| (k, &**v)).collect();
-            self.codepoint_to_string_slice(&name, &table)?;
+            if self.opts.front_coded {
+                self.codepoint_to_string_front_coded(&name, &table)?;
+            } else {
+                self.codepoint_to_string_slice(&name, &table)?;
+            }
         }
         self.wtr.flush()?;
         Ok(())
@@ -310,14 +856,41 @@ impl Writer {
             name, ty)?;
         for &(cp, ref s) in table {
             if let Some(cp) = self.rust_codepoint(cp) {
-                self.wtr.write_str(&format!("({}, {:?}), ", cp, s))?;
+                self.wtr.write_item(format_args!("({}, {:?}), ", cp, s))?;
             }
         }
         writeln!(self.wtr, "];")?;
         Ok(())
     }
 
+    fn codepoint_to_string_front_coded(
+        &mut self,
+        name: &str,
+        table: &[(u32, &str)],
+    ) -> Result<()> {
+        let table: Vec<(RustCodepoint, &str)> = table
+            .iter()
+            .filter_map(|&(cp, s)| self.rust_codepoint(cp).map(|cp| (cp, s)))
+            .collect();
+        let strings: Vec<&str> = table.iter().map(|&(_, s)| s).collect();
+        self.front_coded_strings(name, &strings)?;
+
+        let ty = self.rust_codepoint_type();
+        writeln!(
+            self.wtr,
+            "pub const {}_CODEPOINTS: &'static [{}] = &[", name, ty)?;
+        for &(cp, _) in &table {
+            self.wtr.write_item(format_args!("{}, ", cp))?;
+        }
+        writeln!(self.wtr, "];")?;
+        Ok(())
+    }
+
     /// Write a map that associates strings to codepoints.
+    ///
+    /// When the output format is front coded, then an error is returned if
+    /// a string longer than 255 bytes starts a block or shares more than 255
+    /// bytes with the previous string.
     pub fn string_to_codepoint(
         &mut self,
         name: &str,
@@ -328,16 +901,22 @@ impl Writer {
 
         let name = rust_const_name(name);
         if self.opts.fst_dir.is_some() {
-            let mut builder = MapBuilder::memory();
+            let fst_dir = self.opts.fst_dir.as_ref().unwrap();
+            let file = fst_file(fst_dir, &name)?;
+            let mut builder = MapBuilder::new(file)?;
             for (k, &v) in map {
                 builder.insert(k.as_bytes(), v as u64)?;
             }
-            let map = Map::from_bytes(builder.into_inner()?)?;
-            self.fst(&name, map.as_fst(), true)?;
+            builder.finish()?;
+            self.fst_static(&name, &fst_file_name(&name), true)?;
         } else {
             let table: Vec<(&str, u32)> =
                 map.iter().map(|(k, &v)| (&**k, v)).collect();
-            self.string_to_codepoint_slice(&name, &table)?;
+            if self.opts.front_coded {
+                self.string_to_codepoint_front_coded(&name, &table)?;
+            } else {
+                self.string_to_codepoint_slice(&name, &table)?;
+            }
         }
         self.wtr.flush()?;
         Ok(())
@@ -355,14 +934,41 @@ impl Writer {
             name, ty)?;
         for &(ref s, cp) in table {
             if let Some(cp) = self.rust_codepoint(cp) {
-                self.wtr.write_str(&format!("({:?}, {}), ", s, cp))?;
+                self.wtr.write_item(format_args!("({:?}, {}), ", s, cp))?;
             }
         }
         writeln!(self.wtr, "];")?;
         Ok(())
     }
 
+    fn string_to_codepoint_front_coded(
+        &mut self,
+        name: &str,
+        table: &[(&str, u32)],
+    ) -> Result<()> {
+        let table: Vec<(&str, RustCodepoint)> = table
+            .iter()
+            .filter_map(|&(s, cp)| self.rust_codepoint(cp).map(|cp| (s, cp)))
+            .collect();
+        let strings: Vec<&str> = table.iter().map(|&(s, _)| s).collect();
+        self.front_coded_strings(name, &strings)?;
+
+        let ty = self.rust_codepoint_type();
+        writeln!(
+            self.wtr,
+            "pub const {}_CODEPOINTS: &'static [{}] = &[", name, ty)?;
+        for &(_, cp) in &table {
+            self.wtr.write_item(format_args!("{}, ", cp))?;
+        }
+        writeln!(self.wtr, "];")?;
+        Ok(())
+    }
+
     /// Write a map that associates strings to `u64` values.
+    ///
+    /// When the output format is front coded, then an error is returned if
+    /// a string longer than 255 bytes starts a block or shares more than 255
+    /// bytes with the previous string.
     pub fn string_to_u64(
         &mut self,
         name: &str,
@@ -373,16 +979,22 @@ impl Writer {
 
         let name = rust_const_name(name);
         if self.opts.fst_dir.is_some() {
-            let mut builder = MapBuilder::memory();
+            let fst_dir = self.opts.fst_dir.as_ref().unwrap();
+            let file = fst_file(fst_dir, &name)?;
+            let mut builder = MapBuilder::new(file)?;
             for (k, &v) in map {
                 builder.insert(k.as_bytes(), v)?;
             }
-            let map = Map::from_bytes(builder.into_inner()?)?;
-            self.fst(&name, map.as_fst(), true)?;
+            builder.finish()?;
+            self.fst_static(&name, &fst_file_name(&name), true)?;
         } else {
             let table: Vec<(&str, u64)> =
                 map.iter().map(|(k, &v)| (&**k, v)).collect();
-            self.string_to_u64_slice(&name, &table)?;
+            if self.opts.front_coded {
+                self.string_to_u64_front_coded(&name, &table)?;
+            } else {
+                self.string_to_u64_slice(&name, &table)?;
+            }
         }
         self.wtr.flush()?;
         Ok(())
@@ -398,24 +1010,103 @@ impl Writer {
             "pub const {}: &'static [(&'static str, u64)] = &[",
             name)?;
         for &(ref s, n) in table {
-            self.wtr.write_str(&format!("({:?}, {}), ", s, n))?;
+            self.wtr.write_item(format_args!("({:?}, {}), ", s, n))?;
+        }
+        writeln!(self.wtr, "];")?;
+        Ok(())
+    }
+
+    fn string_to_u64_front_coded(
+        &mut self,
+        name: &str,
+        table: &[(&str, u64)],
+    ) -> Result<()> {
+        let strings: Vec<&str> = table.iter().map(|&(s, _)| s).collect();
+        self.front_coded_strings(name, &strings)?;
+
+        writeln!(
+            self.wtr, "pub const {}_VALUES: &'static [u64] = &[", name)?;
+        for &(_, n) in table {
+            self.wtr.write_item(format_args!("{}, ", n))?;
         }
         writeln!(self.wtr, "];")?;
         Ok(())
     }
 
-    fn fst(
+    /// Write the given strings as a front coded table, preceded by the
+    /// definition of `FrontCodedStrings` if it hasn't been written yet.
+    fn front_coded_strings(
+        &mut self,
+        name: &str,
+        strings: &[&str],
+    ) -> Result<()> {
+        if !self.wrote_front_coded {
+            self.wtr.write_all(FRONT_CODED_STRINGS.trim_left().as_bytes())?;
+            self.separator()?;
+            self.wrote_front_coded = true;
+        }
+        let (data, blocks) = front_code(strings, FRONT_CODED_BLOCK_LEN)?;
+        writeln!(
+            self.wtr,
+            "pub const {}: FrontCodedStrings = FrontCodedStrings {{", name)?;
+        writeln!(self.wtr, "  block_len: {},", FRONT_CODED_BLOCK_LEN)?;
+        writeln!(self.wtr, "  len: {},", strings.len())?;
+        writeln!(self.wtr, "  blocks: &[")?;
+        self.wtr.indent("    ");
+        for offset in blocks {
+            self.wtr.write_item(format_args!("{}, ", offset))?;
+        }
+        self.wtr.flush_line()?;
+        writeln!(self.wtr, "  ],")?;
+        writeln!(self.wtr, "  data: b\"\\")?;
+        let mut line = String::new();
+        for &b in &data {
+            let escaped = escape_input(b);
+            if line.len() + escaped.len() > self.opts.columns as usize - 6 {
+                writeln!(self.wtr, "    {}\\", line)?;
+                line.clear();
+            }
+            // Whitespace following a line continuation is skipped, so a
+            // space at the start of a line must be escaped.
+            if line.is_empty() && b == b' ' {
+                line.push_str("\\x20");
+            } else {
+                line.push_str(&escaped);
+            }
+        }
+        writeln!(self.wtr, "    {}\",", line)?;
+        self.wtr.indent("  ");
+        writeln!(self.wtr, "}};")?;
+        Ok(())
+    }
+
+    /// Write a lazily initialized static that loads an FST from the given
+    /// file, which must be in the FST directory. If lazy statics are
+    /// disabled, then write a function that loads the FST instead.
+    fn fst_static(
         &mut self,
         const_name: &str,
-        fst: &Fst,
+        fst_file_name: &str,
         map: bool,
     ) -> Result<()> {
-        let fst_dir = self.opts.fst_dir.as_ref().unwrap();
-        let fst_file_name = format!("{}.fst", rust_module_name(const_name));
-        let fst_file_path = fst_dir.join(&fst_file_name);
-        File::create(fst_file_path)?.write_all(&fst.to_vec())?;
-
         let ty = if map { "Map" } else { "Set" };
+        if !self.opts.lazy_static {
+            writeln!(
+                self.wtr,
+                "pub const {}_BYTES: &'static [u8] =", const_name)?;
+            writeln!(self.wtr, "  include_bytes!({:?});", fst_file_name)?;
+            writeln!(self.wtr, "")?;
+            writeln!(
+                self.wtr,
+                "pub fn {}() -> ::fst::{} {{",
+                rust_module_name(const_name), ty)?;
+            writeln!(
+                self.wtr,
+                "  ::fst::{}::from(::fst::raw::Fst::from_static_slice(", ty)?;
+            writeln!(self.wtr, "    {}_BYTES).unwrap())", const_name)?;
+            writeln!(self.wtr, "}}")?;
+            return Ok(());
+        }
         writeln!(self.wtr, "lazy_static! {{")?;
         writeln!(
             self.wtr,
@@ -431,7 +1122,7 @@ impl Writer {
     }
 
     fn header(&mut self) -> Result<()> {
-        if self.wrote_header {
+        if self.wrote_header || !self.opts.header {
             return Ok(());
         }
         let mut argv = vec![];
@@ -467,11 +1158,11 @@ impl Writer {
     /// The source code returned is either a u32 literal or a char literal,
     /// depending on the configuration. If the configuration demands a char
     /// literal and the given codepoint is a surrogate, then return None.
-    fn rust_codepoint(&self, cp: u32) -> Option<String> {
+    fn rust_codepoint(&self, cp: u32) -> Option<RustCodepoint> {
         if self.opts.char_literals {
-            char::from_u32(cp).map(|c| format!("{:?}", c))
+            char::from_u32(cp).map(RustCodepoint::Char)
         } else {
-            Some(cp.to_string())
+            Some(RustCodepoint::U32(cp))
         }
     }
 
@@ -486,10 +1177,33 @@ impl Writer {
     }
 }
 
+/// A codepoint that is written as Rust source code.
+///
+/// This avoids allocating a string for every codepoint in a table.
+#[derive(Clone, Copy, Debug)]
+enum RustCodepoint {
+    /// A `char` literal.
+    Char(char),
+    /// A `u32` literal.
+    U32(u32),
+}
+
+impl fmt::Display for RustCodepoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RustCodepoint::Char(c) => write!(f, "{:?}", c),
+            RustCodepoint::U32(n) => write!(f, "{}", n),
+        }
+    }
+}
+
 #[derive(Debug)]
 struct LineWriter<W> {
     wtr: W,
     line: String,
+    /// A buffer that is reused for formatting each item written by
+    /// `write_item`.
+    scratch: String,
     columns: usize,
     indent: String,
 }
@@ -499,6 +1213,7 @@ impl<W: io::Write> LineWriter<W> {
         LineWriter {
             wtr: wtr,
             line: String::new(),
+            scratch: String::new(),
             columns: 79,
             indent: "  ".to_string(),
         }
@@ -515,6 +1230,19 @@ impl<W: io::Write> LineWriter<W> {
         Ok(())
     }
 
+    /// Format a single item and write it with `write_str`, without
+    /// allocating a new string.
+    fn write_item(&mut self, args: fmt::Arguments) -> io::Result<()> {
+        use std::fmt::Write;
+
+        let mut scratch = mem::replace(&mut self.scratch, String::new());
+        scratch.clear();
+        scratch.write_fmt(args).unwrap();
+        let result = self.write_str(&scratch);
+        self.scratch = scratch;
+        result
+    }
+
     fn indent(&mut self, s: &str) {
         self.indent = s.to_string();
     }
@@ -542,6 +1270,141 @@ impl<W: io::Write> io::Write for LineWriter<W> {
     }
 }
 
+/// The number of strings in each block of a front coded table. The first
+/// string in each block is stored in full.
+const FRONT_CODED_BLOCK_LEN: usize = 16;
+
+/// The definition of the type used to represent front coded tables in
+/// generated code.
+const FRONT_CODED_STRINGS: &'static str = r#"
+/// A table of strings compressed with front coding.
+///
+/// Strings are split into blocks. The first string in each block is stored in
+/// full, and each subsequent string is stored as the length of the prefix it
+/// shares with the previous string followed by the rest of its bytes.
+pub struct FrontCodedStrings {
+  block_len: usize,
+  len: usize,
+  blocks: &'static [u32],
+  data: &'static [u8],
+}
+
+impl FrontCodedStrings {
+  /// Return the number of strings in this table.
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Return the string at the given index.
+  pub fn get(&self, i: usize) -> String {
+    let mut buf = vec![];
+    let mut pos = self.blocks[i / self.block_len] as usize;
+    for _ in 0..(i % self.block_len) + 1 {
+      pos = self.decode_next(pos, &mut buf);
+    }
+    String::from_utf8(buf).unwrap()
+  }
+
+  /// Return the index of the given string, if it is in this table.
+  ///
+  /// This only works if the strings in this table are sorted.
+  pub fn find(&self, key: &str) -> Option<usize> {
+    let key = key.as_bytes();
+    let (mut lo, mut hi) = (0, self.blocks.len());
+    while lo < hi {
+      let mid = (lo + hi) / 2;
+      let pos = self.blocks[mid] as usize;
+      let first = &self.data[pos + 2..pos + 2 + self.data[pos + 1] as usize];
+      if first <= key {
+        lo = mid + 1;
+      } else {
+        hi = mid;
+      }
+    }
+    if lo == 0 {
+      return None;
+    }
+    let block = lo - 1;
+    let mut buf = vec![];
+    let mut pos = self.blocks[block] as usize;
+    let start = block * self.block_len;
+    for i in start..::std::cmp::min(start + self.block_len, self.len) {
+      pos = self.decode_next(pos, &mut buf);
+      if &*buf == key {
+        return Some(i);
+      }
+    }
+    None
+  }
+
+  fn decode_next(&self, pos: usize, buf: &mut Vec<u8>) -> usize {
+    let prefix = self.data[pos] as usize;
+    let suffix = self.data[pos + 1] as usize;
+    buf.truncate(prefix);
+    buf.extend_from_slice(&self.data[pos + 2..pos + 2 + suffix]);
+    pos + 2 + suffix
+  }
+}
+"#;
+
+/// Encode the given strings with front coding.
+///
+/// This returns the encoded bytes along with the offset of each block of
+/// `block_len` strings. The lengths of the prefix that each string shares
+/// with the previous string and of the rest of the string are each stored in
+/// a single byte, so if either is longer than 255 bytes, then an error is
+/// returned. In particular, the first string in each block can't be longer
+/// than 255 bytes.
+fn front_code(
+    strings: &[&str],
+    block_len: usize,
+) -> Result<(Vec<u8>, Vec<u32>)> {
+    let mut data = vec![];
+    let mut blocks = vec![];
+    let mut prev: &[u8] = &[];
+    for (i, string) in strings.iter().enumerate() {
+        let s = string.as_bytes();
+        let prefix =
+            if i % block_len == 0 {
+                blocks.push(data.len() as u32);
+                0
+            } else {
+                prev.iter().zip(s).take_while(|&(a, b)| a == b).count()
+            };
+        let suffix = s.len() - prefix;
+        if prefix > 255 {
+            return err!(
+                "cannot front code {:?} (shared prefix of {} bytes is \
+                 too long)", string, prefix);
+        }
+        if suffix > 255 {
+            return err!(
+                "cannot front code {:?} (suffix of {} bytes is too long)",
+                string, suffix);
+        }
+        data.push(prefix as u8);
+        data.push(suffix as u8);
+        data.extend_from_slice(&s[prefix..]);
+        prev = s;
+    }
+    Ok((data, blocks))
+}
+
+/// Return the name of the file that the FST for the given constant is written
+/// to, relative to the FST directory.
+fn fst_file_name(const_name: &str) -> String {
+    format!("{}.fst", rust_module_name(const_name))
+}
+
+/// Create the file that the FST for the given constant is written to.
+///
+/// FSTs are streamed directly to this file as they are built, which keeps
+/// memory usage bounded even for very large tables.
+fn fst_file(fst_dir: &Path, const_name: &str) -> Result<io::BufWriter<File>> {
+    let file = File::create(fst_dir.join(fst_file_name(const_name)))?;
+    Ok(io::BufWriter::new(file))
+}
+
 /// Return the given byte as its escaped string form.
 fn escape_input(b: u8) -> String {
     String::from_utf8(ascii::escape_default(b).collect::<Vec<_>>()).unwrap()
@@ -603,6 +1466,26 @@ fn pack_str(s: &str) -> Result<u64> {
     Ok(value)
 }
 
+/// Convert the given sequence of codepoints into a u64, where the least
+/// significant 21 bits of the u64 are the first codepoint.
+///
+/// If the sequence contains `U+0000` or has more than 3 codepoints, then an
+/// error is returned.
+fn pack_codepoints(codepoints: &[u32]) -> Result<u64> {
+    if codepoints.len() > 3 {
+        return err!("cannot encode codepoints {:?} (too long)", codepoints);
+    }
+    if codepoints.contains(&0) {
+        return err!(
+            "cannot encode codepoints {:?} (contains NUL)", codepoints);
+    }
+    let mut value = 0;
+    for (i, &cp) in codepoints.iter().enumerate() {
+        value |= (cp as u64) << (21 * i as u64);
+    }
+    Ok(value)
+}
+
 /// Return a string representing the smallest unsigned integer type for the
 /// given value.
 fn smallest_unsigned_type(n: u64) -> &'static str {
@@ -617,9 +1500,66 @@ fn smallest_unsigned_type(n: u64) -> &'static str {
     }
 }
 
+/// Return a string representing the smallest signed integer type that can
+/// represent every value between the given minimum and maximum.
+fn smallest_signed_type(min: i64, max: i64) -> &'static str {
+    use std::{i16, i32, i8};
+
+    if min >= i8::MIN as i64 && max <= i8::MAX as i64 {
+        "i8"
+    } else if min >= i16::MIN as i64 && max <= i16::MAX as i64 {
+        "i16"
+    } else if min >= i32::MIN as i64 && max <= i32::MAX as i64 {
+        "i32"
+    } else {
+        "i64"
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::pack_str;
+    use std::cell::RefCell;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::{self, Read};
+    use std::rc::Rc;
+
+    use table::{Table, TableVisitor};
+
+    use super::{
+        WriterBuilder, front_code, pack_codepoints, pack_str,
+        smallest_signed_type,
+    };
+
+    /// A writer whose contents can be inspected after it has been given to a
+    /// `Writer`.
+    #[derive(Clone, Default)]
+    struct Buffer(Rc<RefCell<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn write_ranges(
+        builder: &WriterBuilder,
+        tables: &[(&str, &[u32])],
+    ) -> String {
+        let buf = Buffer::default();
+        let mut wtr = builder.from_writer(buf.clone());
+        for &(name, codepoints) in tables {
+            let set: BTreeSet<u32> = codepoints.iter().cloned().collect();
+            wtr.ranges(name, &set).unwrap();
+        }
+        let out = buf.0.borrow();
+        String::from_utf8(out.clone()).unwrap()
+    }
 
     fn unpack_str(mut encoded: u64) -> String {
         let mut value = String::new();
@@ -641,4 +1581,282 @@ mod tests {
         assert!(pack_str("ABCDEFGHI").is_err());
         assert!(pack_str("AB\x00CD").is_err());
     }
+
+    #[test]
+    fn ranges_identical() {
+        let tables: &[(&str, &[u32])] = &[
+            ("a", &[1, 2, 3, 10]),
+            ("b", &[1, 2, 3, 10]),
+        ];
+
+        let out = write_ranges(&WriterBuilder::new("test"), tables);
+        assert!(out.contains("pub const B: &'static [(u32, u32)] = &[\n  \
+                              (1, 3), (10, 10),\n];"));
+
+        let out = write_ranges(
+            WriterBuilder::new("test").share_complements(true), tables);
+        assert!(out.contains("pub const A: &'static [(u32, u32)] = &[\n  \
+                              (1, 3), (10, 10),\n];"));
+        assert!(out.contains("pub const B: &'static [(u32, u32)] = A;"));
+    }
+
+    #[test]
+    fn ranges_complement() {
+        let rest: Vec<u32> = (2..0x110000).collect();
+        let tables: &[(&str, &[u32])] = &[("a", &[0, 1]), ("b", &rest)];
+
+        let out = write_ranges(&WriterBuilder::new("test"), tables);
+        assert!(out.contains("pub const B: &'static [(u32, u32)] = &["));
+
+        let out = write_ranges(
+            WriterBuilder::new("test").share_complements(true), tables);
+        assert!(out.contains("pub const B: &'static [(u32, u32)] = &[\n  \
+                              (2, 1114111),\n];"));
+        assert!(out.contains(
+            "pub const B_COMPLEMENT: &'static [(u32, u32)] = A;"));
+    }
+
+    #[test]
+    fn no_header() {
+        let out = write_ranges(WriterBuilder::new("test").header(false), &[
+            ("a", &[1]),
+        ]);
+        assert_eq!(out, "\npub const A: &'static [(u32, u32)] = &[\n  \
+                         (1, 1),\n];\n");
+    }
+
+    #[test]
+    fn fst_no_lazy_static() {
+        let dir = env::temp_dir().join("ucd-generate-fst-no-lazy-static");
+        fs::create_dir_all(&dir).unwrap();
+        let mut wtr = WriterBuilder::new("test")
+            .lazy_static(false)
+            .from_fst_dir(&dir)
+            .unwrap();
+        let set: BTreeSet<u32> = vec![1, 2, 3].into_iter().collect();
+        wtr.ranges("a", &set).unwrap();
+        drop(wtr);
+
+        let mut out = String::new();
+        File::open(dir.join("test.rs")).unwrap()
+            .read_to_string(&mut out).unwrap();
+        assert!(out.contains("pub const A_BYTES: &'static [u8] =\n  \
+                              include_bytes!(\"a.fst\");"));
+        assert!(out.contains("pub fn a() -> ::fst::Set {"));
+        assert!(!out.contains("lazy_static"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn ranges_trie() {
+        let out = write_ranges(
+            WriterBuilder::new("test").trie(true).share_complements(true), &[
+                ("a", &[0, 1, 0x10FFFF]),
+                ("b", &[0, 1, 0x10FFFF]),
+            ]);
+        assert!(out.contains(
+            "pub const A: &'static ::ucd_trie::TrieSetSlice<'static> =\n  \
+             &::ucd_trie::TrieSetSlice {\n  tree1_level1: &[\n    0x3, 0x0,"));
+        assert!(out.contains(
+            "pub const B: &'static ::ucd_trie::TrieSetSlice<'static> = A;"));
+    }
+
+    #[test]
+    fn table_source() {
+        let set: BTreeSet<u32> = vec![1, 2, 3].into_iter().collect();
+        let mut map = BTreeMap::new();
+        map.insert(0xE000, "PUA".to_string());
+
+        let buf = Buffer::default();
+        let mut wtr = WriterBuilder::new("test").from_writer(buf.clone());
+        wtr.source(&|v: &mut TableVisitor| {
+            v.visit("custom", Table::Ranges(&set))?;
+            v.visit("names", Table::CodepointToString(&map))
+        }).unwrap();
+        let out = String::from_utf8(buf.0.borrow().clone()).unwrap();
+        assert!(out.contains("pub const CUSTOM: &'static [(u32, u32)] = &[\n  \
+                              (1, 3),\n];"));
+        assert!(out.contains("pub const NAMES: &'static [(u32, &'static str)] \
+                              = &[\n  (57344, \"PUA\"),\n];"));
+    }
+
+    #[test]
+    fn codepoint_to_codepoint() {
+        let mut map = BTreeMap::new();
+        map.insert(0x41, 0x61);
+        map.insert(0xD800, 0x61);
+        map.insert(0x1E9E, 0xDF);
+
+        let buf = Buffer::default();
+        let mut wtr = WriterBuilder::new("test")
+            .char_literals(true)
+            .from_writer(buf.clone());
+        wtr.codepoint_to_codepoint("fold", &map).unwrap();
+        let out = String::from_utf8(buf.0.borrow().clone()).unwrap();
+        assert!(out.contains("pub const FOLD: &'static [(char, char)] = &[\n  \
+                              ('A', 'a'), ('\u{1e9e}', '\u{df}'),\n];"));
+    }
+
+    #[test]
+    fn ranges_to_signed_integer() {
+        let mut map = BTreeMap::new();
+        for cp in 0x41..0x44 {
+            map.insert(cp, 32);
+        }
+        map.insert(0x61, -32);
+        map.insert(0x1E9E, -7615);
+
+        let buf = Buffer::default();
+        let mut wtr = WriterBuilder::new("test").from_writer(buf.clone());
+        wtr.ranges_to_signed_integer("delta", &map).unwrap();
+        let out = String::from_utf8(buf.0.borrow().clone()).unwrap();
+        assert!(out.contains("pub const DELTA: &'static [(u32, u32, i16)] = \
+                              &[\n  (65, 67, 32), (97, 97, -32), \
+                              (7838, 7838, -7615),\n];"));
+
+        assert_eq!(smallest_signed_type(-128, 127), "i8");
+        assert_eq!(smallest_signed_type(-129, 0), "i16");
+        assert_eq!(smallest_signed_type(0, 1 << 31), "i64");
+    }
+
+    #[test]
+    fn codepoint_to_codepoints() {
+        let mut map = BTreeMap::new();
+        map.insert(0xDF, vec![0x53, 0x53]);
+        map.insert(0x307, vec![]);
+        map.insert(0xD800, vec![0x41]);
+        map.insert(0x41, vec![0xD800]);
+
+        let buf = Buffer::default();
+        let mut wtr = WriterBuilder::new("test")
+            .char_literals(true)
+            .from_writer(buf.clone());
+        wtr.codepoint_to_codepoints("upper", &map).unwrap();
+        let out = String::from_utf8(buf.0.borrow().clone()).unwrap();
+        assert!(out.contains("pub const UPPER: &'static [(char, &'static \
+                              [char])] = &[\n  ('ß', &['S', 'S']), \
+                              ('\\u{307}', &[]),\n];"));
+
+        assert_eq!(pack_codepoints(&[]).unwrap(), 0);
+        assert_eq!(
+            pack_codepoints(&[0x46, 0x66, 0x10FFFF]).unwrap(),
+            0x46 | (0x66 << 21) | (0x10FFFF << 42));
+        assert!(pack_codepoints(&[0x46, 0x66, 0x69, 0x69]).is_err());
+        assert!(pack_codepoints(&[0x46, 0]).is_err());
+    }
+
+    #[test]
+    fn front_coded() {
+        let strings = &["A", "AB", "ABC", "ABD", "B", "BA", "C"];
+        let (data, blocks) = front_code(strings, 3).unwrap();
+        assert_eq!(blocks, vec![0, 9, 20]);
+        assert_eq!(data, b"\
+            \x00\x01A\x01\x01B\x02\x01C\
+            \x00\x03ABD\x00\x01B\x01\x01A\
+            \x00\x01C".to_vec());
+
+        assert!(front_code(&[&"A".repeat(256)], 3).is_err());
+    }
+
+    /// A decoder for front coded tables that works the same way as the
+    /// `get` and `find` methods of `FRONT_CODED_STRINGS`.
+    struct FrontCodedStrings {
+        block_len: usize,
+        len: usize,
+        blocks: Vec<u32>,
+        data: Vec<u8>,
+    }
+
+    impl FrontCodedStrings {
+        fn new(strings: &[&str], block_len: usize) -> FrontCodedStrings {
+            let (data, blocks) = front_code(strings, block_len).unwrap();
+            FrontCodedStrings {
+                block_len: block_len,
+                len: strings.len(),
+                blocks: blocks,
+                data: data,
+            }
+        }
+
+        fn get(&self, i: usize) -> String {
+            let mut buf = vec![];
+            let mut pos = self.blocks[i / self.block_len] as usize;
+            for _ in 0..(i % self.block_len) + 1 {
+                pos = self.decode_next(pos, &mut buf);
+            }
+            String::from_utf8(buf).unwrap()
+        }
+
+        fn find(&self, key: &str) -> Option<usize> {
+            let key = key.as_bytes();
+            let (mut lo, mut hi) = (0, self.blocks.len());
+            while lo < hi {
+                let mid = (lo + hi) / 2;
+                let pos = self.blocks[mid] as usize;
+                let end = pos + 2 + self.data[pos + 1] as usize;
+                if &self.data[pos + 2..end] <= key {
+                    lo = mid + 1;
+                } else {
+                    hi = mid;
+                }
+            }
+            if lo == 0 {
+                return None;
+            }
+            let block = lo - 1;
+            let mut buf = vec![];
+            let mut pos = self.blocks[block] as usize;
+            let start = block * self.block_len;
+            for i in start..::std::cmp::min(start + self.block_len, self.len) {
+                pos = self.decode_next(pos, &mut buf);
+                if &*buf == key {
+                    return Some(i);
+                }
+            }
+            None
+        }
+
+        fn decode_next(&self, pos: usize, buf: &mut Vec<u8>) -> usize {
+            let prefix = self.data[pos] as usize;
+            let suffix = self.data[pos + 1] as usize;
+            buf.truncate(prefix);
+            buf.extend_from_slice(&self.data[pos + 2..pos + 2 + suffix]);
+            pos + 2 + suffix
+        }
+    }
+
+    #[test]
+    fn front_coded_roundtrip() {
+        let long1 = "D".repeat(200);
+        let long2 = format!("{}{}", long1, "E".repeat(55));
+        let strings = &[
+            "", "A", "AB", "ABC", "ABD", "B", "BA", "BAA", "BB", "C",
+            "CAT", "CATS", "D", &long1, &long2, "E",
+        ];
+        for &block_len in &[1, 2, 3, 16] {
+            let table = FrontCodedStrings::new(strings, block_len);
+            for (i, s) in strings.iter().enumerate() {
+                assert_eq!(table.get(i), *s);
+                assert_eq!(table.find(s), Some(i));
+            }
+            assert_eq!(table.find("AA"), None);
+            assert_eq!(table.find("CA"), None);
+            assert_eq!(table.find("F"), None);
+        }
+    }
+
+    #[test]
+    fn front_coded_too_long() {
+        let long1 = "A".repeat(200);
+        let long2 = "A".repeat(400);
+        let long3 = "A".repeat(401);
+        // The suffix of the first string in a block is the whole string.
+        assert!(front_code(&[&long1, &long2], 1).is_err());
+        let table = FrontCodedStrings::new(&[&long1, &long2], 2);
+        assert_eq!(table.get(1), long2);
+        assert_eq!(table.find(&long2), Some(1));
+        // The prefix shared with the previous string doesn't fit in a byte.
+        let err = front_code(&[&long1, &long2, &long3], 3).unwrap_err();
+        assert!(err.to_string().contains("shared prefix of 400 bytes"));
+    }
 }
@@ -0,0 +1,157 @@
+use std::borrow::Cow;
+use std::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+use std::str::FromStr;
+
+use common::{
+    UcdFile, UcdFileByCodepoint, Codepoint, is_hex_codepoint, split_fields,
+};
+use error::Error;
+
+property_value_enum! {
+    /// The `Joining_Type` property of a codepoint.
+    ///
+    /// Codepoints that aren't listed in `ArabicShaping.txt` have the value
+    /// `Transparent` if their general category is `Mn`, `Me` or `Cf`, and
+    /// `NonJoining` otherwise.
+    pub enum JoiningType ("joining type") {
+        JoinCausing, "C", "Join_Causing";
+        DualJoining, "D", "Dual_Joining";
+        LeftJoining, "L", "Left_Joining";
+        RightJoining, "R", "Right_Joining";
+        Transparent, "T", "Transparent";
+        NonJoining, "U", "Non_Joining";
+    }
+}
+
+impl Default for JoiningType {
+    fn default() -> JoiningType {
+        JoiningType::NonJoining
+    }
+}
+
+/// A single row in the `ArabicShaping.txt` file.
+///
+/// The `ArabicShaping.txt` file defines the `Joining_Type` and
+/// `Joining_Group` properties, which are used for cursive joining in
+/// scripts such as Arabic and Syriac.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ArabicShaping<'a> {
+    /// The codepoint corresponding to this row.
+    pub codepoint: Codepoint,
+    /// A schematic name for the codepoint, which is derived from its
+    /// character name and is only informative.
+    pub schematic_name: Cow<'a, str>,
+    /// The joining type of this codepoint.
+    pub joining_type: JoiningType,
+    /// The joining group of this codepoint, e.g., `BEH` or
+    /// `No_Joining_Group`.
+    pub joining_group: Cow<'a, str>,
+}
+
+impl UcdFile for ArabicShaping<'static> {
+    #[cfg(feature = "std")]
+    fn relative_file_path() -> &'static Path {
+        Path::new("ArabicShaping.txt")
+    }
+}
+
+impl UcdFileByCodepoint for ArabicShaping<'static> {
+    fn codepoint(&self) -> Codepoint {
+        self.codepoint
+    }
+}
+
+impl<'a> ArabicShaping<'a> {
+    /// Convert this record into an owned value such that it no longer
+    /// borrows from the original line that it was parsed from.
+    pub fn into_owned(self) -> ArabicShaping<'static> {
+        ArabicShaping {
+            codepoint: self.codepoint,
+            schematic_name: Cow::Owned(self.schematic_name.into_owned()),
+            joining_type: self.joining_type,
+            joining_group: Cow::Owned(self.joining_group.into_owned()),
+        }
+    }
+
+    /// Parse a single line.
+    pub fn parse_line(line: &'a str) -> Result<ArabicShaping<'a>, Error> {
+        let fields = split_fields(line);
+        if fields.len() != 4
+            || !is_hex_codepoint(fields[0])
+            || fields[2].is_empty()
+            || fields[3].is_empty()
+        {
+            return err!("invalid ArabicShaping line");
+        }
+        Ok(ArabicShaping {
+            codepoint: fields[0].parse()?,
+            schematic_name: Cow::Borrowed(fields[1]),
+            joining_type: fields[2].parse()?,
+            joining_group: Cow::Borrowed(fields[3]),
+        })
+    }
+}
+
+impl FromStr for ArabicShaping<'static> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<ArabicShaping<'static>, Error> {
+        ArabicShaping::parse_line(s).map(|x| x.into_owned())
+    }
+}
+
+impl<'a> fmt::Display for ArabicShaping<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f, "{}; {}; {}; {}",
+            self.codepoint,
+            self.schematic_name,
+            self.joining_type,
+            self.joining_group)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ArabicShaping, JoiningType};
+
+    #[test]
+    fn parse() {
+        let line = "0628; BEH; D; BEH\n";
+        let row: ArabicShaping = line.parse().unwrap();
+        assert_eq!(row.codepoint, 0x0628);
+        assert_eq!(row.schematic_name, "BEH");
+        assert_eq!(row.joining_type, JoiningType::DualJoining);
+        assert_eq!(row.joining_group, "BEH");
+
+        let line = "200D; ZERO WIDTH JOINER; C; No_Joining_Group\n";
+        let row: ArabicShaping = line.parse().unwrap();
+        assert_eq!(row.codepoint, 0x200D);
+        assert_eq!(row.schematic_name, "ZERO WIDTH JOINER");
+        assert_eq!(row.joining_type, JoiningType::JoinCausing);
+        assert_eq!(row.joining_group, "No_Joining_Group");
+
+        let line = "0710; SYRIAC ALAPH; R; ALAPH # comment\n";
+        let row: ArabicShaping = line.parse().unwrap();
+        assert_eq!(row.joining_type, JoiningType::RightJoining);
+        assert_eq!(row.joining_group, "ALAPH");
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!("0628; BEH; D\n".parse::<ArabicShaping>().is_err());
+        assert!("0628; BEH; X; BEH\n".parse::<ArabicShaping>().is_err());
+        assert!("0628; BEH; D; \n".parse::<ArabicShaping>().is_err());
+    }
+
+    #[test]
+    fn display() {
+        let line = "0628; BEH; D; BEH\n";
+        let row: ArabicShaping = line.parse().unwrap();
+        assert_eq!(row.to_string(), "0628; BEH; D; BEH");
+        assert_eq!(row.to_string().parse::<ArabicShaping>().unwrap(), row);
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+use std::str::FromStr;
+
+use common::{
+    UcdFile, UcdFileByCodepoint, Codepoint, is_hex_codepoint, split_fields,
+};
+use error::Error;
+
+property_value_enum! {
+    /// The `Bidi_Paired_Bracket_Type` property of a codepoint.
+    ///
+    /// Codepoints that aren't listed in `BidiBrackets.txt` have the value
+    /// `None`.
+    pub enum BidiPairedBracketType ("bidi paired bracket type") {
+        Open, "o", "Open";
+        Close, "c", "Close";
+        None, "n", "None";
+    }
+}
+
+impl Default for BidiPairedBracketType {
+    fn default() -> BidiPairedBracketType {
+        BidiPairedBracketType::None
+    }
+}
+
+/// A single row in the `BidiBrackets.txt` file.
+///
+/// The `BidiBrackets.txt` file defines the `Bidi_Paired_Bracket` and
+/// `Bidi_Paired_Bracket_Type` properties, which are used by rule BD16 of
+/// the Unicode Bidirectional Algorithm to identify bracket pairs.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BidiBracket {
+    /// The codepoint corresponding to this row.
+    pub codepoint: Codepoint,
+    /// The codepoint of the bracket that pairs with `codepoint`.
+    pub pair: Codepoint,
+    /// Whether `codepoint` is an opening or a closing bracket.
+    pub bracket_type: BidiPairedBracketType,
+}
+
+impl UcdFile for BidiBracket {
+    #[cfg(feature = "std")]
+    fn relative_file_path() -> &'static Path {
+        Path::new("BidiBrackets.txt")
+    }
+}
+
+impl UcdFileByCodepoint for BidiBracket {
+    fn codepoint(&self) -> Codepoint {
+        self.codepoint
+    }
+}
+
+impl BidiBracket {
+    /// Parse a single line.
+    pub fn parse_line(line: &str) -> Result<BidiBracket, Error> {
+        let fields = split_fields(line);
+        if fields.len() != 3
+            || !is_hex_codepoint(fields[0])
+            || !is_hex_codepoint(fields[1])
+        {
+            return err!("invalid BidiBrackets line");
+        }
+        Ok(BidiBracket {
+            codepoint: fields[0].parse()?,
+            pair: fields[1].parse()?,
+            bracket_type: fields[2].parse()?,
+        })
+    }
+}
+
+impl FromStr for BidiBracket {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<BidiBracket, Error> {
+        BidiBracket::parse_line(s)
+    }
+}
+
+impl fmt::Display for BidiBracket {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f, "{}; {}; {}", self.codepoint, self.pair, self.bracket_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BidiBracket, BidiPairedBracketType};
+
+    #[test]
+    fn parse() {
+        let line = "0028; 0029; o # LEFT PARENTHESIS\n";
+        let row: BidiBracket = line.parse().unwrap();
+        assert_eq!(row.codepoint, 0x0028);
+        assert_eq!(row.pair, 0x0029);
+        assert_eq!(row.bracket_type, BidiPairedBracketType::Open);
+
+        let line = "FF63; FF62; c # HALFWIDTH RIGHT CORNER BRACKET\n";
+        let row: BidiBracket = line.parse().unwrap();
+        assert_eq!(row.codepoint, 0xFF63);
+        assert_eq!(row.pair, 0xFF62);
+        assert_eq!(row.bracket_type, BidiPairedBracketType::Close);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!("0028; 0029\n".parse::<BidiBracket>().is_err());
+        assert!("0028; 0029; x\n".parse::<BidiBracket>().is_err());
+    }
+
+    #[test]
+    fn display() {
+        let row: BidiBracket = "0028; 0029; o # LEFT\n".parse().unwrap();
+        assert_eq!(row.to_string(), "0028; 0029; o");
+        assert_eq!(row.to_string().parse::<BidiBracket>().unwrap(), row);
+    }
+}
@@ -0,0 +1,92 @@
+property_value_enum! {
+    /// The `Bidi_Class` property of a codepoint.
+    ///
+    /// Records such as `UnicodeData` store the bidi class as a raw string,
+    /// so that new classes can be read by older versions of this crate. Use
+    /// `str::parse` to convert a raw value to this type.
+    ///
+    /// Parsing accepts both the abbreviated (e.g., `AL`) and the long (e.g.,
+    /// `Arabic_Letter`) names of a class. Its `Display` impl writes the
+    /// abbreviated name, as used in `UnicodeData.txt`.
+    pub enum BidiClass ("bidi class") {
+        ArabicLetter, "AL", "Arabic_Letter";
+        ArabicNumber, "AN", "Arabic_Number";
+        ParagraphSeparator, "B", "Paragraph_Separator";
+        BoundaryNeutral, "BN", "Boundary_Neutral";
+        CommonSeparator, "CS", "Common_Separator";
+        EuropeanNumber, "EN", "European_Number";
+        EuropeanSeparator, "ES", "European_Separator";
+        EuropeanTerminator, "ET", "European_Terminator";
+        FirstStrongIsolate, "FSI", "First_Strong_Isolate";
+        LeftToRight, "L", "Left_To_Right";
+        LeftToRightEmbedding, "LRE", "Left_To_Right_Embedding";
+        LeftToRightIsolate, "LRI", "Left_To_Right_Isolate";
+        LeftToRightOverride, "LRO", "Left_To_Right_Override";
+        NonspacingMark, "NSM", "Nonspacing_Mark";
+        OtherNeutral, "ON", "Other_Neutral";
+        PopDirectionalFormat, "PDF", "Pop_Directional_Format";
+        PopDirectionalIsolate, "PDI", "Pop_Directional_Isolate";
+        RightToLeft, "R", "Right_To_Left";
+        RightToLeftEmbedding, "RLE", "Right_To_Left_Embedding";
+        RightToLeftIsolate, "RLI", "Right_To_Left_Isolate";
+        RightToLeftOverride, "RLO", "Right_To_Left_Override";
+        SegmentSeparator, "S", "Segment_Separator";
+        WhiteSpace, "WS", "White_Space";
+    }
+}
+
+impl BidiClass {
+    /// Returns true if this is a strong class, i.e., `L`, `R` or `AL`.
+    pub fn is_strong(&self) -> bool {
+        match *self {
+            BidiClass::LeftToRight
+            | BidiClass::RightToLeft
+            | BidiClass::ArabicLetter => true,
+            _ => false,
+        }
+    }
+
+    /// Returns true if this is an explicit formatting class, i.e., one of
+    /// the embedding, override and isolate classes or `PDF` and `PDI`.
+    pub fn is_explicit(&self) -> bool {
+        use self::BidiClass::*;
+        match *self {
+            LeftToRightEmbedding | LeftToRightOverride
+            | RightToLeftEmbedding | RightToLeftOverride
+            | PopDirectionalFormat | LeftToRightIsolate
+            | RightToLeftIsolate | FirstStrongIsolate
+            | PopDirectionalIsolate => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BidiClass;
+
+    #[test]
+    fn parse() {
+        let bc: BidiClass = "AL".parse().unwrap();
+        assert_eq!(bc, BidiClass::ArabicLetter);
+        let bc: BidiClass = "Arabic_Letter".parse().unwrap();
+        assert_eq!(bc, BidiClass::ArabicLetter);
+        assert!("al".parse::<BidiClass>().is_err());
+    }
+
+    #[test]
+    fn roundtrip() {
+        let bc = BidiClass::PopDirectionalIsolate;
+        assert_eq!(bc.to_string(), "PDI");
+        assert_eq!(bc.long_name(), "Pop_Directional_Isolate");
+        assert_eq!(bc.to_string().parse::<BidiClass>().unwrap(), bc);
+    }
+
+    #[test]
+    fn groups() {
+        assert!(BidiClass::ArabicLetter.is_strong());
+        assert!(!BidiClass::ArabicNumber.is_strong());
+        assert!(BidiClass::FirstStrongIsolate.is_explicit());
+        assert!(!BidiClass::WhiteSpace.is_explicit());
+    }
+}
@@ -0,0 +1,100 @@
+use std::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+use std::str::FromStr;
+
+use common::{
+    UcdFile, UcdFileByCodepoint, Codepoint, is_hex_codepoint, split_fields,
+};
+use error::Error;
+
+/// A single row in the `BidiMirroring.txt` file.
+///
+/// The `BidiMirroring.txt` file defines the `Bidi_Mirroring_Glyph`
+/// property. Each row maps a codepoint to another codepoint whose glyph is
+/// typically its mirror image. Not every codepoint with the `Bidi_Mirrored`
+/// property has a row.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BidiMirroring {
+    /// The codepoint corresponding to this row.
+    pub codepoint: Codepoint,
+    /// The codepoint whose glyph mirrors the glyph of `codepoint`.
+    pub mirror: Codepoint,
+}
+
+impl UcdFile for BidiMirroring {
+    #[cfg(feature = "std")]
+    fn relative_file_path() -> &'static Path {
+        Path::new("BidiMirroring.txt")
+    }
+}
+
+impl UcdFileByCodepoint for BidiMirroring {
+    fn codepoint(&self) -> Codepoint {
+        self.codepoint
+    }
+}
+
+impl BidiMirroring {
+    /// Parse a single line.
+    pub fn parse_line(line: &str) -> Result<BidiMirroring, Error> {
+        let fields = split_fields(line);
+        if fields.len() != 2
+            || !is_hex_codepoint(fields[0])
+            || !is_hex_codepoint(fields[1])
+        {
+            return err!("invalid BidiMirroring line");
+        }
+        Ok(BidiMirroring {
+            codepoint: fields[0].parse()?,
+            mirror: fields[1].parse()?,
+        })
+    }
+}
+
+impl FromStr for BidiMirroring {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<BidiMirroring, Error> {
+        BidiMirroring::parse_line(s)
+    }
+}
+
+impl fmt::Display for BidiMirroring {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}; {}", self.codepoint, self.mirror)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BidiMirroring;
+
+    #[test]
+    fn parse() {
+        let line = "0028; 0029 # LEFT PARENTHESIS\n";
+        let row: BidiMirroring = line.parse().unwrap();
+        assert_eq!(row.codepoint, 0x0028);
+        assert_eq!(row.mirror, 0x0029);
+
+        let line = "1D7C3; 2202 # [BEST FIT] MATHEMATICAL SANS-SERIF \
+                    BOLD ITALIC PARTIAL DIFFERENTIAL\n";
+        let row: BidiMirroring = line.parse().unwrap();
+        assert_eq!(row.codepoint, 0x1D7C3);
+        assert_eq!(row.mirror, 0x2202);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!("0028;\n".parse::<BidiMirroring>().is_err());
+        assert!("0028; 0029 0030\n".parse::<BidiMirroring>().is_err());
+    }
+
+    #[test]
+    fn display() {
+        let row: BidiMirroring = "0028; 0029 # LEFT\n".parse().unwrap();
+        assert_eq!(row.to_string(), "0028; 0029");
+        assert_eq!(row.to_string().parse::<BidiMirroring>().unwrap(), row);
+    }
+}
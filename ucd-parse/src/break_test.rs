@@ -0,0 +1,175 @@
+use std::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
+use std::str::FromStr;
+
+use common::{UcdFile, Codepoint};
+use error::Error;
+
+/// Parse a single line of a `*BreakTest.txt` file into its codepoints and
+/// break positions.
+///
+/// A line alternates between break markers (`÷` for a break and `×` for no
+/// break) and codepoints, starting and ending with a marker. Anything after
+/// a `#` is a comment and is ignored.
+fn parse_break_test(
+    line: &str,
+) -> Result<(Vec<Codepoint>, Vec<usize>), Error> {
+    let data = match line.find('#') {
+        None => line,
+        Some(i) => &line[..i],
+    };
+    let mut codepoints = vec![];
+    let mut breaks = vec![];
+    let mut expect_marker = true;
+    for field in data.split_whitespace() {
+        if expect_marker {
+            match field {
+                "÷" => breaks.push(codepoints.len()),
+                "×" => {}
+                unknown => {
+                    return err!("expected break marker, got '{}'", unknown);
+                }
+            }
+        } else {
+            codepoints.push(field.parse()?);
+        }
+        expect_marker = !expect_marker;
+    }
+    if codepoints.is_empty() {
+        return err!("break test has no codepoints");
+    }
+    if expect_marker {
+        return err!("break test does not end with a break marker");
+    }
+    Ok((codepoints, breaks))
+}
+
+/// Return the marker that precedes the codepoint at index `i` of a break
+/// test.
+fn break_marker(breaks: &[usize], i: usize) -> &'static str {
+    if breaks.contains(&i) { "÷" } else { "×" }
+}
+
+macro_rules! break_test {
+    ($name:ident, $path:expr, $doc:expr) => {
+        #[doc = $doc]
+        ///
+        /// Each row is a sequence of codepoints along with the positions at
+        /// which a conforming implementation must find a break.
+        #[derive(Clone, Debug, Default, Eq, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        pub struct $name {
+            /// The codepoints of this test.
+            pub codepoints: Vec<Codepoint>,
+            /// The positions, in ascending order, at which a break occurs. A
+            /// position `i` indicates a break immediately before
+            /// `codepoints[i]`, so that a position equal to the number of
+            /// codepoints indicates a break at the end of the text.
+            pub breaks: Vec<usize>,
+        }
+
+        impl UcdFile for $name {
+            #[cfg(feature = "std")]
+    fn relative_file_path() -> &'static Path {
+                Path::new($path)
+            }
+        }
+
+        impl $name {
+            /// Parse a single line.
+            pub fn parse_line(line: &str) -> Result<$name, Error> {
+                let (codepoints, breaks) = parse_break_test(line)?;
+                Ok($name { codepoints: codepoints, breaks: breaks })
+            }
+
+            /// Return the segments of this test, i.e., the codepoints
+            /// between each pair of consecutive breaks.
+            pub fn segments(&self) -> Vec<&[Codepoint]> {
+                self.breaks
+                    .windows(2)
+                    .map(|w| &self.codepoints[w[0]..w[1]])
+                    .collect()
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<$name, Error> {
+                $name::parse_line(s)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                for (i, cp) in self.codepoints.iter().enumerate() {
+                    write!(f, "{} {} ", break_marker(&self.breaks, i), cp)?;
+                }
+                let end = self.codepoints.len();
+                write!(f, "{}", break_marker(&self.breaks, end))
+            }
+        }
+    }
+}
+
+break_test!(
+    GraphemeBreakTest, "auxiliary/GraphemeBreakTest.txt",
+    "A single row in the `auxiliary/GraphemeBreakTest.txt` file.");
+break_test!(
+    WordBreakTest, "auxiliary/WordBreakTest.txt",
+    "A single row in the `auxiliary/WordBreakTest.txt` file.");
+break_test!(
+    SentenceBreakTest, "auxiliary/SentenceBreakTest.txt",
+    "A single row in the `auxiliary/SentenceBreakTest.txt` file.");
+break_test!(
+    LineBreakTest, "auxiliary/LineBreakTest.txt",
+    "A single row in the `auxiliary/LineBreakTest.txt` file.");
+
+#[cfg(test)]
+mod tests {
+    use super::{GraphemeBreakTest, LineBreakTest, WordBreakTest};
+
+    #[test]
+    fn parse_grapheme() {
+        let line = "÷ 0020 × 0308 ÷ 0020 ÷\t\
+                    #  ÷ [0.2] SPACE (Other) × [9.0] COMBINING DIAERESIS \
+                    (Extend_ExtCccZwj) ÷ [999.0] SPACE (Other) ÷ [0.3]\n";
+        let row: GraphemeBreakTest = line.parse().unwrap();
+        assert_eq!(row.codepoints, vec![0x0020, 0x0308, 0x0020]);
+        assert_eq!(row.breaks, vec![0, 2, 3]);
+
+        let segments = row.segments();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0], &row.codepoints[0..2]);
+        assert_eq!(segments[1], &row.codepoints[2..3]);
+    }
+
+    #[test]
+    fn parse_no_breaks() {
+        let line = "× 0023 × 0308 ×\t#  × [0.3]\n";
+        let row: LineBreakTest = line.parse().unwrap();
+        assert_eq!(row.codepoints, vec![0x0023, 0x0308]);
+        assert_eq!(row.breaks, Vec::<usize>::new());
+        assert!(row.segments().is_empty());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!("÷ 0020 0020 ÷".parse::<WordBreakTest>().is_err());
+        assert!("÷ 0020 ÷ 0020".parse::<WordBreakTest>().is_err());
+        assert!("÷".parse::<WordBreakTest>().is_err());
+        assert!("÷ ZZZZ ÷".parse::<WordBreakTest>().is_err());
+    }
+
+    #[test]
+    fn display() {
+        let line = "÷ 0020 × 0308 ÷ 0020 ÷\t#  ÷ [0.2] SPACE (Other)\n";
+        let row: GraphemeBreakTest = line.parse().unwrap();
+        assert_eq!(row.to_string(), "÷ 0020 × 0308 ÷ 0020 ÷");
+        let row: LineBreakTest = "× 0023 × 0308 ×\n".parse().unwrap();
+        assert_eq!(row.to_string(), "× 0023 × 0308 ×");
+    }
+}
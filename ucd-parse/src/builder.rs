@@ -0,0 +1,181 @@
+use std::io;
+use std::path::Path;
+
+use common::{CodepointRange, UcdFile, UcdLineParser, parser_set_filter};
+use error::Error;
+use reader::UcdReader;
+
+/// A builder for a `UcdLineParser` that only yields some of the records in a
+/// file.
+///
+/// Lines that don't match the builder's filters are skipped before they are
+/// parsed, which is much cheaper than parsing every record and discarding
+/// most of them. This is useful when only a small slice of a large file,
+/// such as `UnicodeData.txt` or one of the Unihan files, is needed.
+///
+/// Filters are applied to the raw fields of each line. The first field is
+/// treated as a codepoint or a range of codepoints, e.g., `0041`, `U+3400`
+/// or `0041..005A`. The second field is treated as a property name, which is
+/// the format used by files such as `PropList.txt` and
+/// `DerivedCoreProperties.txt`. Lines whose first field isn't a codepoint are
+/// never skipped by a codepoint filter, so that they are still parsed (and
+/// reported as errors, if they are invalid).
+///
+/// Note that the two rows of `UnicodeData.txt` that represent a range of
+/// codepoints are filtered independently, so a codepoint filter may split
+/// such a pair.
+#[derive(Clone, Debug, Default)]
+pub struct ParserBuilder {
+    filter: LineFilter,
+}
+
+/// The filters applied to each line by a parser built with `ParserBuilder`.
+#[derive(Clone, Debug, Default)]
+pub struct LineFilter {
+    codepoints: Option<CodepointRange>,
+    properties: Vec<String>,
+}
+
+impl ParserBuilder {
+    /// Create a new builder without any filters.
+    pub fn new() -> ParserBuilder {
+        ParserBuilder::default()
+    }
+
+    /// Only yield records whose codepoints intersect the given range.
+    ///
+    /// Records whose range of codepoints only partially overlaps the given
+    /// range are yielded in full.
+    pub fn codepoints(&mut self, range: CodepointRange) -> &mut ParserBuilder {
+        self.filter.codepoints = Some(range);
+        self
+    }
+
+    /// Only yield records for the given property name.
+    ///
+    /// This may be called multiple times to permit more than one property.
+    /// If the name ends with `*`, then it matches every property name that
+    /// starts with the rest of the name, e.g., `XID_*` matches both
+    /// `XID_Start` and `XID_Continue`. Names are matched exactly otherwise.
+    pub fn property(&mut self, name: &str) -> &mut ParserBuilder {
+        self.filter.properties.push(name.to_string());
+        self
+    }
+
+    /// Create a parser for the file corresponding to the given record type in
+    /// the given UCD directory.
+    pub fn from_dir<P: AsRef<Path>, D: UcdFile>(
+        &self,
+        ucd_dir: P,
+    ) -> Result<UcdLineParser<UcdReader, D>, Error> {
+        let mut parser = D::from_dir(ucd_dir)?;
+        parser_set_filter(&mut parser, self.filter.clone());
+        Ok(parser)
+    }
+
+    /// Create a parser that reads records from the given reader.
+    pub fn from_reader<R: io::Read, D>(
+        &self,
+        rdr: R,
+    ) -> UcdLineParser<R, D> {
+        let mut parser = UcdLineParser::new(rdr);
+        parser_set_filter(&mut parser, self.filter.clone());
+        parser
+    }
+}
+
+impl LineFilter {
+    /// Returns true if and only if the given line, which must not be a
+    /// comment or blank, should be parsed.
+    pub fn is_match(&self, line: &str) -> bool {
+        let mut fields = line
+            .split(|c| c == ';' || c == '#' || c == '\t')
+            .map(|field| field.trim());
+        let first = fields.next().unwrap_or("");
+        if let Some(range) = self.codepoints {
+            if let Ok(cps) = first.parse::<CodepointRange>() {
+                if cps.end < range.start || cps.start > range.end {
+                    return false;
+                }
+            }
+        }
+        if !self.properties.is_empty() {
+            let name = fields.next().unwrap_or("");
+            if !self.properties.iter().any(|p| property_matches(p, name)) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Returns true if and only if the given property name matches the given
+/// pattern, which may end with a `*` wildcard.
+fn property_matches(pattern: &str, name: &str) -> bool {
+    if pattern.ends_with('*') {
+        name.starts_with(&pattern[..pattern.len() - 1])
+    } else {
+        name == pattern
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prop_list::PropList;
+    use unicode_data::UnicodeData;
+
+    use super::ParserBuilder;
+
+    #[test]
+    fn codepoints() {
+        let data = "\
+# Comment
+0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;
+0042;LATIN CAPITAL LETTER B;Lu;0;L;;;;;N;;;;0062;
+0043;LATIN CAPITAL LETTER C;Lu;0;L;;;;;N;;;;0063;
+XYZ;INVALID
+";
+        let rows: Vec<UnicodeData> = ParserBuilder::new()
+            .codepoints("0042..0043".parse().unwrap())
+            .from_reader(data.as_bytes())
+            .take(2)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].codepoint, 0x42);
+        assert_eq!(rows[1].codepoint, 0x43);
+
+        // Lines that can't be filtered are still parsed and reported.
+        let mut rows = ParserBuilder::new()
+            .codepoints("0000..0040".parse().unwrap())
+            .from_reader::<_, UnicodeData>(data.as_bytes());
+        let err = rows.next().unwrap().unwrap_err();
+        assert_eq!(err.line(), Some(5));
+    }
+
+    #[test]
+    fn properties() {
+        let data = "\
+0009..000D    ; White_Space # Cc   [5] <control-0009>..<control-000D>
+0030..0039    ; XID_Continue
+0041..005A    ; XID_Start
+0041..005A    ; ID_Start
+";
+        let rows: Vec<PropList> = ParserBuilder::new()
+            .property("XID_*")
+            .codepoints("0040..10FFFF".parse().unwrap())
+            .from_reader(data.as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].property, "XID_Start");
+
+        let rows: Vec<PropList> = ParserBuilder::new()
+            .property("White_Space")
+            .property("ID_Start")
+            .from_reader(data.as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+    }
+}
@@ -0,0 +1,193 @@
+use std::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
+use std::str::FromStr;
+
+use common::{
+    UcdFile, UcdFileByCodepoint, Codepoint, split_fields, write_codepoints,
+};
+use error::Error;
+
+/// A single row in the `CaseFolding.txt` file.
+///
+/// Note that there may be multiple rows for the same codepoint, each with a
+/// different status. For example, a codepoint may have both a simple (`S`)
+/// and a full (`F`) case folding.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CaseFold {
+    /// The codepoint that is being mapped.
+    pub codepoint: Codepoint,
+    /// The status of this mapping.
+    pub status: CaseStatus,
+    /// The folded mapping. This contains exactly one codepoint for every
+    /// status except for `Full`, which may map to more than one codepoint.
+    pub mapping: Vec<Codepoint>,
+}
+
+impl UcdFile for CaseFold {
+    #[cfg(feature = "std")]
+    fn relative_file_path() -> &'static Path {
+        Path::new("CaseFolding.txt")
+    }
+}
+
+impl UcdFileByCodepoint for CaseFold {
+    fn codepoint(&self) -> Codepoint {
+        self.codepoint
+    }
+}
+
+impl CaseFold {
+    /// Parse a single line.
+    pub fn parse_line(line: &str) -> Result<CaseFold, Error> {
+        // The mapping is followed by a `;`, so there is always an empty
+        // field after it.
+        let fields = split_fields(line);
+        if fields.len() < 4 || fields[0].is_empty() || fields[1].is_empty() {
+            return err!("invalid CaseFolding line");
+        }
+        let mut mapping = vec![];
+        for cp in fields[2].split_whitespace() {
+            mapping.push(cp.parse()?);
+        }
+        let status: CaseStatus = fields[1].parse()?;
+        if mapping.is_empty() {
+            return err!("empty case folding mapping");
+        }
+        if mapping.len() > 1 && status != CaseStatus::Full {
+            return err!(
+                "multi-codepoint case folding mapping with status '{}'",
+                fields[1]);
+        }
+        Ok(CaseFold {
+            codepoint: fields[0].parse()?,
+            status: status,
+            mapping: mapping,
+        })
+    }
+}
+
+impl FromStr for CaseFold {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<CaseFold, Error> {
+        CaseFold::parse_line(s)
+    }
+}
+
+impl fmt::Display for CaseFold {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}; {}; ", self.codepoint, self.status)?;
+        write_codepoints(f, &self.mapping)?;
+        write!(f, ";")
+    }
+}
+
+/// The status of a case folding mapping.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CaseStatus {
+    /// A mapping common to both simple and full case folding (`C`).
+    Common,
+    /// A mapping used in full case folding, which may map to more than one
+    /// codepoint (`F`).
+    Full,
+    /// A mapping used in simple case folding, for codepoints that also have
+    /// a full case folding (`S`).
+    Simple,
+    /// A special mapping for uppercase `I` and dotted uppercase `I`, used
+    /// only for Turkic languages (`T`).
+    Special,
+}
+
+impl Default for CaseStatus {
+    fn default() -> CaseStatus {
+        // This is arbitrary, but the Default impl is convenient.
+        CaseStatus::Common
+    }
+}
+
+impl FromStr for CaseStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<CaseStatus, Error> {
+        match s {
+            "C" => Ok(CaseStatus::Common),
+            "F" => Ok(CaseStatus::Full),
+            "S" => Ok(CaseStatus::Simple),
+            "T" => Ok(CaseStatus::Special),
+            unknown => err!("unknown case folding status: '{}'", unknown),
+        }
+    }
+}
+
+impl fmt::Display for CaseStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            CaseStatus::Common => "C",
+            CaseStatus::Full => "F",
+            CaseStatus::Simple => "S",
+            CaseStatus::Special => "T",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CaseFold, CaseStatus};
+
+    #[test]
+    fn parse_common() {
+        let line = "0041; C; 0061; # LATIN CAPITAL LETTER A\n";
+        let row: CaseFold = line.parse().unwrap();
+        assert_eq!(row.codepoint, 0x0041);
+        assert_eq!(row.status, CaseStatus::Common);
+        assert_eq!(row.mapping, vec![0x0061]);
+    }
+
+    #[test]
+    fn parse_full() {
+        let line = "00DF; F; 0073 0073; # LATIN SMALL LETTER SHARP S\n";
+        let row: CaseFold = line.parse().unwrap();
+        assert_eq!(row.codepoint, 0x00DF);
+        assert_eq!(row.status, CaseStatus::Full);
+        assert_eq!(row.mapping, vec![0x0073, 0x0073]);
+    }
+
+    #[test]
+    fn parse_simple() {
+        let line = "1E9E; S; 00DF; # LATIN CAPITAL LETTER SHARP S\n";
+        let row: CaseFold = line.parse().unwrap();
+        assert_eq!(row.codepoint, 0x1E9E);
+        assert_eq!(row.status, CaseStatus::Simple);
+        assert_eq!(row.mapping, vec![0x00DF]);
+    }
+
+    #[test]
+    fn parse_special() {
+        let line = "0130; T; 0069; # LATIN CAPITAL LETTER I WITH DOT ABOVE\n";
+        let row: CaseFold = line.parse().unwrap();
+        assert_eq!(row.codepoint, 0x0130);
+        assert_eq!(row.status, CaseStatus::Special);
+        assert_eq!(row.mapping, vec![0x0069]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!("0041; X; 0061;".parse::<CaseFold>().is_err());
+        assert!("0041; C; 0061 0062;".parse::<CaseFold>().is_err());
+        assert!("0041; C;".parse::<CaseFold>().is_err());
+    }
+
+    #[test]
+    fn display() {
+        let line = "00DF; F; 0073 0073; # LATIN SMALL LETTER SHARP S\n";
+        let row: CaseFold = line.parse().unwrap();
+        assert_eq!(row.to_string(), "00DF; F; 0073 0073;");
+        assert_eq!(row.to_string().parse::<CaseFold>().unwrap(), row);
+    }
+}
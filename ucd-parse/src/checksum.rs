@@ -0,0 +1,162 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::str::FromStr;
+
+use sha2::{self, Digest};
+
+use common::UcdFile;
+use error::{Error, error_set_path};
+use reader::UcdReader;
+
+/// A SHA-256 checksum of a file in the UCD.
+///
+/// This type's string representation is 64 lowercase hexadecimal digits,
+/// which is the format printed by tools such as `sha256sum`. Parsing also
+/// accepts uppercase digits.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct Sha256([u8; 32]);
+
+impl Sha256 {
+    /// Compute the checksum of all of the data in the given reader.
+    pub fn from_reader<R: Read>(mut rdr: R) -> io::Result<Sha256> {
+        let mut hasher = sha2::Sha256::new();
+        let mut buf = [0; 8 * (1 << 10)];
+        loop {
+            let n = rdr.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+        }
+        let mut sum = [0; 32];
+        sum.copy_from_slice(&hasher.finalize());
+        Ok(Sha256(sum))
+    }
+
+    /// Compute the checksum of the file at the given path.
+    ///
+    /// This is useful for checksumming archives such as `UCD.zip`.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Sha256, Error> {
+        let path = path.as_ref();
+        File::open(path)
+            .and_then(Sha256::from_reader)
+            .map_err(|err| {
+                let mut err = Error::from(err);
+                error_set_path(&mut err, Some(path));
+                err
+            })
+    }
+
+    /// Return the raw bytes of this checksum.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl FromStr for Sha256 {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Sha256, Error> {
+        if s.len() != 64 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return err!("invalid SHA-256 checksum: '{}'", s);
+        }
+        let mut sum = [0; 32];
+        for (i, byte) in sum.iter_mut().enumerate() {
+            *byte = match u8::from_str_radix(&s[i * 2..i * 2 + 2], 16) {
+                Ok(byte) => byte,
+                Err(_) => return err!("invalid SHA-256 checksum: '{}'", s),
+            };
+        }
+        Ok(Sha256(sum))
+    }
+}
+
+impl fmt::Display for Sha256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compute the SHA-256 checksum of a particular file in the UCD.
+///
+/// The given directory should be the directory to the UCD. If the file is
+/// read from an archive (see `UcdReader`), then the checksum is of the
+/// file's contents rather than of the archive.
+pub fn checksum<P, D>(ucd_dir: P) -> Result<Sha256, Error>
+where P: AsRef<Path>, D: UcdFile
+{
+    let path = D::file_path(&ucd_dir);
+    let rdr = UcdReader::open(ucd_dir, D::relative_file_path())?;
+    Sha256::from_reader(rdr).map_err(|err| {
+        let mut err = Error::from(err);
+        error_set_path(&mut err, Some(path));
+        err
+    })
+}
+
+/// Verify that the file at the given path has the given SHA-256 checksum.
+///
+/// This is useful for checking a downloaded archive, such as `UCD.zip`,
+/// against a published checksum. If the checksums differ, then this returns
+/// an error that includes both of them.
+pub fn verify<P: AsRef<Path>>(
+    path: P,
+    expected: &Sha256,
+) -> Result<(), Error> {
+    let path = path.as_ref();
+    let actual = Sha256::from_path(path)?;
+    if actual != *expected {
+        let mut err = Error::from(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "checksum mismatch: expected {}, got {}", expected, actual),
+        ));
+        error_set_path(&mut err, Some(path));
+        return Err(err);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use jamo_short_name::JamoShortName;
+    use temp_dir::TempDir;
+
+    use super::{Sha256, checksum, verify};
+
+    const ABC: &'static str =
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    #[test]
+    fn parse_display() {
+        let sum: Sha256 = ABC.parse().unwrap();
+        assert_eq!(sum.as_bytes()[0], 0xBA);
+        assert_eq!(sum.to_string(), ABC);
+        assert_eq!(ABC.to_uppercase().parse::<Sha256>().unwrap(), sum);
+
+        assert!(ABC[1..].parse::<Sha256>().is_err());
+        assert!(ABC.replace("b", "g").parse::<Sha256>().is_err());
+        assert!(ABC.replace("ba", "+a").parse::<Sha256>().is_err());
+    }
+
+    #[test]
+    fn checksum_file() {
+        let dir = TempDir::new("ucd-parse-checksum");
+        let path = dir.write("Jamo.txt", b"abc");
+
+        let sum = checksum::<_, JamoShortName>(dir.path()).unwrap();
+        assert_eq!(sum, ABC.parse().unwrap());
+        assert_eq!(Sha256::from_reader(&b"abc"[..]).unwrap(), sum);
+
+        verify(&path, &sum).unwrap();
+        let other = Sha256::from_reader(&b"xyz"[..]).unwrap();
+        let err = verify(&path, &other).unwrap_err();
+        assert!(err.to_string().contains("checksum mismatch"));
+        assert_eq!(err.path(), Some(&*path));
+    }
+}
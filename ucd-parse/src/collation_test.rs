@@ -0,0 +1,90 @@
+use std::fmt;
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
+use std::str::FromStr;
+
+use common::{Codepoint, write_codepoints};
+use error::Error;
+
+/// A single row in one of the UCA conformance test files,
+/// `CollationTest_SHIFTED.txt` or `CollationTest_NON_IGNORABLE.txt` (or
+/// their `_SHORT` variants).
+///
+/// Each row is a sequence of codepoints. The rows of a file are sorted in
+/// ascending collation order, so a conforming implementation must order each
+/// row at or after the row preceding it.
+///
+/// These files are distributed with the Unicode Collation Algorithm rather
+/// than the UCD, so this type does not implement `UcdFile`. Instead, parse a
+/// file directly with `UcdLineParser::from_path`.
+///
+/// Note that the codepoints may include surrogates.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CollationTest {
+    /// The codepoints of this test string.
+    pub codepoints: Vec<Codepoint>,
+}
+
+impl CollationTest {
+    /// Parse a single line.
+    pub fn parse_line(line: &str) -> Result<CollationTest, Error> {
+        let data = match line.find(|c| c == ';' || c == '#') {
+            None => line,
+            Some(i) => &line[..i],
+        };
+        let mut codepoints = vec![];
+        for cp in data.split_whitespace() {
+            codepoints.push(cp.parse()?);
+        }
+        if codepoints.is_empty() {
+            return err!("invalid CollationTest line");
+        }
+        Ok(CollationTest { codepoints: codepoints })
+    }
+}
+
+impl FromStr for CollationTest {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<CollationTest, Error> {
+        CollationTest::parse_line(s)
+    }
+}
+
+impl fmt::Display for CollationTest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_codepoints(f, &self.codepoints)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CollationTest;
+
+    #[test]
+    fn parse_full() {
+        let line = "0041 0021;\t# (A!) LATIN CAPITAL LETTER A\t\
+                    [1FA1 0261 | 0020 0020 | 0008 0002 |]\n";
+        let row: CollationTest = line.parse().unwrap();
+        assert_eq!(row.codepoints, vec![0x0041, 0x0021]);
+    }
+
+    #[test]
+    fn parse_short() {
+        let row: CollationTest = "D800 0021\n".parse().unwrap();
+        assert_eq!(row.codepoints, vec![0xD800, 0x0021]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(";\t# nothing".parse::<CollationTest>().is_err());
+        assert!("0041 XYZ;".parse::<CollationTest>().is_err());
+    }
+
+    #[test]
+    fn display() {
+        let row: CollationTest = "0041 0021;\t# (A!)\n".parse().unwrap();
+        assert_eq!(row.to_string(), "0041 0021");
+    }
+}
@@ -1,16 +1,34 @@
 // This module defines various common things used throughout the UCD.
 
 use std::char;
+#[cfg(feature = "std")]
 use std::collections::BTreeMap;
 use std::fmt;
+#[cfg(feature = "std")]
 use std::fs::File;
+#[cfg(feature = "std")]
 use std::io::{self, BufRead};
 use std::marker::PhantomData;
+use std::ops::Range;
+#[cfg(feature = "std")]
 use std::path::{Path, PathBuf};
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
 use std::str::FromStr;
 
-use error::{Error, error_set_line};
+#[cfg(feature = "std")]
+use ucd_util::UnicodeVersion;
 
+#[cfg(feature = "std")]
+use builder::LineFilter;
+use error::Error;
+#[cfg(feature = "std")]
+use error::{error_set_line, error_set_path};
+use missing::MissingValue;
+#[cfg(feature = "std")]
+use reader::UcdReader;
+
+#[cfg(feature = "std")]
 /// Parse a particular file in the UCD into a sequence of rows.
 ///
 /// The given directory should be the directory to the UCD.
@@ -27,6 +45,7 @@ where P: AsRef<Path>, D: UcdFile
     Ok(xs)
 }
 
+#[cfg(feature = "std")]
 /// Parse a particular file in the UCD into a map from codepoint to the record.
 ///
 /// The given directory should be the directory to the UCD.
@@ -43,6 +62,7 @@ where P: AsRef<Path>, D: UcdFileByCodepoint
     Ok(map)
 }
 
+#[cfg(feature = "std")]
 /// Parse a particular file in the UCD into a map from codepoint to all
 /// records associated with that codepoint.
 ///
@@ -64,28 +84,127 @@ where P: AsRef<Path>, D: UcdFileByCodepoint
     Ok(map)
 }
 
+#[cfg(feature = "std")]
+/// Parse a particular file in the UCD into a map from codepoint to all
+/// records whose range of codepoints includes that codepoint.
+///
+/// This is useful for files such as `PropList.txt`, where each record
+/// applies to a range of codepoints and a single codepoint may be covered
+/// by several records. Every record is cloned once for each codepoint in
+/// its range.
+///
+/// The given directory should be the directory to the UCD.
+pub fn parse_by_codepoint_range<P, D>(
+    ucd_dir: P,
+) -> Result<BTreeMap<Codepoint, Vec<D>>, Error>
+where P: AsRef<Path>, D: UcdFileByCodepointRange + Clone
+{
+    let mut map = BTreeMap::new();
+    for result in D::from_dir(ucd_dir)? {
+        let x = result?;
+        for cp in x.codepoints() {
+            map.entry(cp).or_insert(vec![]).push(x.clone());
+        }
+    }
+    Ok(map)
+}
+
 /// A trait that describes a single UCD file.
 pub trait UcdFile: fmt::Debug + Default + Eq + FromStr<Err=Error> + PartialEq {
+    #[cfg(feature = "std")]
     /// The file path corresponding to this file, relative to the UCD
     /// directory.
     fn relative_file_path() -> &'static Path;
 
+    #[cfg(feature = "std")]
     /// The full file path corresponding to this file given the UCD directory
     /// path.
     fn file_path<P: AsRef<Path>>(ucd_dir: P) -> PathBuf {
         ucd_dir.as_ref().join(Self::relative_file_path())
     }
 
+    #[cfg(feature = "std")]
     /// Create an iterator over each record in this UCD file.
     ///
     /// The parameter should correspond to the directory containing the UCD.
+    /// When the `zip` feature is enabled, the file is read from `UCD.zip` or
+    /// `Unihan.zip` in that directory if it doesn't exist on its own.
     fn from_dir<P: AsRef<Path>>(
         ucd_dir: P,
-    ) -> Result<UcdLineParser<File, Self>, Error> {
-        UcdLineParser::from_path(Self::file_path(ucd_dir))
+    ) -> Result<UcdLineParser<UcdReader, Self>, Error> {
+        let path = Self::file_path(&ucd_dir);
+        let rdr = UcdReader::open(ucd_dir, Self::relative_file_path())?;
+        Ok(UcdLineParser::new(rdr).with_path(path))
+    }
+
+    #[cfg(feature = "std")]
+    /// Create an iterator over each record in the given reader, which should
+    /// contain data in the format of this UCD file.
+    fn from_reader<R: io::Read>(rdr: R) -> UcdLineParser<R, Self> {
+        UcdLineParser::new(rdr)
+    }
+
+    #[cfg(feature = "std")]
+    /// Create an iterator over each record in the given string, which should
+    /// contain data in the format of this UCD file.
+    ///
+    /// This is useful for parsing snippets of UCD data embedded in tests or
+    /// other programs. (It isn't called `from_str`, since every record type
+    /// implements `FromStr` to parse a single line.)
+    fn from_data(data: &str) -> UcdLineParser<&[u8], Self> {
+        UcdLineParser::new(data.as_bytes())
+    }
+
+    #[cfg(feature = "std")]
+    /// Parse a single line of this file from the given version of the UCD.
+    ///
+    /// Some files have changed format across releases of the UCD. Record
+    /// types for such files override this to accept the format used by
+    /// older releases. By default, this parses the line with `FromStr`,
+    /// which always accepts the format of the latest release.
+    fn parse_versioned(
+        line: &str,
+        version: UnicodeVersion,
+    ) -> Result<Self, Error> {
+        let _ = version;
+        line.parse()
+    }
+
+    #[cfg(feature = "std")]
+    /// Create an iterator over every line in this UCD file, including
+    /// comments and blank lines.
+    ///
+    /// The parameter should correspond to the directory containing the UCD.
+    fn lines_from_dir<P: AsRef<Path>>(
+        ucd_dir: P,
+    ) -> Result<UcdLines<UcdReader, Self>, Error> {
+        Ok(Self::from_dir(ucd_dir)?.into_lines())
     }
 }
 
+#[cfg(feature = "std")]
+/// A trait that describes a single UCD file whose records can be written
+/// back out in the format of that file.
+///
+/// This is implemented for every record type that implements `Display`,
+/// which writes a record as a single line without its line terminator. Note
+/// that comments, including trailing comments on records, are not preserved.
+/// Use `UcdFile::lines_from_dir` to preserve full-line comments.
+pub trait UcdFileWrite: UcdFile + fmt::Display {
+    /// Write each of the given records to the given writer, one per line.
+    fn write_records<'a, W, I>(mut wtr: W, records: I) -> io::Result<()>
+    where W: io::Write, I: IntoIterator<Item=&'a Self>, Self: 'a
+    {
+        for record in records {
+            writeln!(wtr, "{}", record)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<D: UcdFile + fmt::Display> UcdFileWrite for D {}
+
 /// A trait that describes a single UCD file where every record in the file
 /// has a single codepoint associated with it.
 pub trait UcdFileByCodepoint: UcdFile {
@@ -93,28 +212,49 @@ pub trait UcdFileByCodepoint: UcdFile {
     fn codepoint(&self) -> Codepoint;
 }
 
+/// A trait that describes a single UCD file where every record in the file
+/// has a range of codepoints associated with it.
+pub trait UcdFileByCodepointRange: UcdFile {
+    /// Returns the range of codepoints associated with this record.
+    fn codepoints(&self) -> CodepointRange;
+}
+
+#[cfg(feature = "std")]
 /// A line oriented parser for a particular UCD file.
 ///
 /// The `R` type parameter refers to the underlying `io::Read` implementation
 /// from which the UCD data is read.
 #[derive(Debug)]
 pub struct UcdLineParser<R, D> {
+    path: Option<PathBuf>,
+    version: Option<(
+        UnicodeVersion,
+        fn(&str, UnicodeVersion) -> Result<D, Error>,
+    )>,
+    filter: Option<LineFilter>,
     rdr: io::BufReader<R>,
     line: String,
     line_number: u64,
     _data: PhantomData<D>,
 }
 
+#[cfg(feature = "std")]
 impl<D> UcdLineParser<File, D> {
     /// Create a new parser from the given file path.
     pub fn from_path<P: AsRef<Path>>(
         path: P,
     ) -> Result<UcdLineParser<File, D>, Error> {
-        let file = File::open(path)?;
-        Ok(UcdLineParser::new(file))
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|err| {
+            let mut err = Error::from(err);
+            error_set_path(&mut err, Some(path));
+            err
+        })?;
+        Ok(UcdLineParser::new(file).with_path(path))
     }
 }
 
+#[cfg(feature = "std")]
 impl<R: io::Read, D> UcdLineParser<R, D> {
     /// Create a new parser that parses the reader given.
     ///
@@ -125,14 +265,47 @@ impl<R: io::Read, D> UcdLineParser<R, D> {
     /// need to provide their own buffering.
     pub fn new(rdr: R) -> UcdLineParser<R, D> {
         UcdLineParser {
+            path: None,
+            version: None,
+            filter: None,
             rdr: io::BufReader::new(rdr),
             line: String::new(),
             line_number: 0,
             _data: PhantomData,
         }
     }
+
+    /// Set the path of the file that this parser reads from.
+    ///
+    /// The path is only used to report the location of errors. It is set
+    /// automatically when a parser is created from a path.
+    pub fn with_path<P: AsRef<Path>>(
+        mut self,
+        path: P,
+    ) -> UcdLineParser<R, D> {
+        self.path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Parse the given line into a record.
+    fn parse_line(&self, line: &str) -> Result<D, Error>
+    where D: FromStr<Err=Error>
+    {
+        match self.version {
+            None => line.parse(),
+            Some((version, parse)) => parse(line, version),
+        }
+    }
+
+    /// Attach the location of the current line to the given error.
+    fn locate(&self, mut err: Error) -> Error {
+        error_set_line(&mut err, Some(self.line_number));
+        error_set_path(&mut err, self.path.as_ref());
+        err
+    }
 }
 
+#[cfg(feature = "std")]
 impl<R: io::Read, D: FromStr<Err=Error>> Iterator for UcdLineParser<R, D> {
     type Item = Result<D, Error>;
 
@@ -141,31 +314,142 @@ impl<R: io::Read, D: FromStr<Err=Error>> Iterator for UcdLineParser<R, D> {
             self.line_number += 1;
             self.line.clear();
             let n = match self.rdr.read_line(&mut self.line) {
-                Err(err) => return Some(Err(Error::from(err))),
+                Err(err) => return Some(Err(self.locate(Error::from(err)))),
                 Ok(n) => n,
             };
             if n == 0 {
                 return None;
             }
-            if !self.line.starts_with('#') && !self.line.trim().is_empty() {
+            if self.line.starts_with('#') || self.line.trim().is_empty() {
+                continue;
+            }
+            if self.filter.as_ref().map_or(true, |f| f.is_match(&self.line)) {
                 break;
             }
         }
-        let line_number = self.line_number;
-        Some(self.line.parse().map_err(|mut err| {
-            error_set_line(&mut err, Some(line_number));
-            err
-        }))
+        Some(self.parse_line(&self.line).map_err(|err| self.locate(err)))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: io::Read, D: UcdFile> UcdLineParser<R, D> {
+    /// Parse records in the format used by the given version of the UCD.
+    ///
+    /// By default, records are parsed in the format used by the latest
+    /// release of the UCD. Setting a version permits parsing files from
+    /// older releases whose format has since changed. See
+    /// `UcdFile::parse_versioned`. This is set automatically when records
+    /// are read from a `UcdDirectory` whose version is known.
+    pub fn with_version(
+        mut self,
+        version: UnicodeVersion,
+    ) -> UcdLineParser<R, D> {
+        self.version = Some((version, D::parse_versioned));
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+/// Set the filter that decides which lines are parsed by the given parser.
+pub fn parser_set_filter<R, D>(
+    parser: &mut UcdLineParser<R, D>,
+    filter: LineFilter,
+) {
+    parser.filter = Some(filter);
+}
+
+#[cfg(feature = "std")]
+impl<R: io::Read, D> UcdLineParser<R, D> {
+    /// Convert this parser into one that yields every line of the file,
+    /// including comments and blank lines.
+    ///
+    /// Filters set by `ParserBuilder` don't apply to the lines yielded by the
+    /// returned iterator.
+    ///
+    /// This is useful for tools that need to reproduce or rewrite whole UCD
+    /// files, including their headers.
+    pub fn into_lines(self) -> UcdLines<R, D> {
+        UcdLines { parser: self }
+    }
+}
+
+/// A single line in a UCD file.
+///
+/// This is yielded by `UcdLines`, which unlike `UcdLineParser`, doesn't skip
+/// comments and blank lines.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Line<D> {
+    /// A blank line, possibly containing whitespace.
+    Blank,
+    /// A comment line. This contains the full line, including the leading
+    /// `#` but excluding the line terminator.
+    Comment(String),
+    /// A `# @missing:` line, which gives a default property value.
+    Missing(MissingValue<'static>),
+    /// A line containing a record.
+    Record(D),
+}
+
+#[cfg(feature = "std")]
+/// A line oriented parser that yields every line of a UCD file.
+///
+/// This is created by `UcdLineParser::into_lines`.
+#[derive(Debug)]
+pub struct UcdLines<R, D> {
+    parser: UcdLineParser<R, D>,
+}
+
+#[cfg(feature = "std")]
+impl<R: io::Read, D: FromStr<Err=Error>> Iterator for UcdLines<R, D> {
+    type Item = Result<Line<D>, Error>;
+
+    fn next(&mut self) -> Option<Result<Line<D>, Error>> {
+        let p = &mut self.parser;
+        p.line_number += 1;
+        p.line.clear();
+        match p.rdr.read_line(&mut p.line) {
+            Err(err) => return Some(Err(p.locate(Error::from(err)))),
+            Ok(0) => return None,
+            Ok(_) => {}
+        }
+        // This strips the line terminator, which may be `\r\n`.
+        let line = p.line.lines().next().unwrap_or("");
+        let result = if line.trim().is_empty() {
+            Ok(Line::Blank)
+        } else if line.starts_with("# @missing:") {
+            line.parse().map(Line::Missing)
+        } else if line.starts_with('#') {
+            Ok(Line::Comment(line.to_string()))
+        } else {
+            p.parse_line(line).map(Line::Record)
+        };
+        Some(result.map_err(|err| p.locate(err)))
+    }
+}
+
+impl<D: fmt::Display> fmt::Display for Line<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Line::Blank => Ok(()),
+            Line::Comment(ref comment) => write!(f, "{}", comment),
+            Line::Missing(ref missing) => write!(f, "{}", missing),
+            Line::Record(ref record) => write!(f, "{}", record),
+        }
     }
 }
 
 /// A single Unicode codepoint.
 ///
 /// This type's string representation is a hexadecimal number. It is guaranteed
-/// to be in the range `[0, 10FFFF]`.
+/// to be in the range `[0, 10FFFF]`. When parsing, the number may be prefixed
+/// with `U+`, `u+` or `0x`. Use `Codepoint::from_decimal` to parse a decimal
+/// number.
+/// The alternate form of `Display`, i.e., `{:#}`, writes the `U+XXXX` label.
 ///
 /// Note that unlike Rust's `char` type, this may be a surrogate codepoint.
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Codepoint(u32);
 
 impl Codepoint {
@@ -181,6 +465,22 @@ impl Codepoint {
         }
     }
 
+    /// Parse a codepoint written as a decimal number, e.g., `128512`.
+    ///
+    /// `Codepoint`'s `FromStr` implementation always interprets a bare
+    /// number as hexadecimal, since that is the format used throughout the
+    /// UCD, so decimal numbers must be parsed with this function instead.
+    pub fn from_decimal(s: &str) -> Result<Codepoint, Error> {
+        match s.parse::<u32>() {
+            Ok(n) => Codepoint::from_u32(n),
+            Err(err) => {
+                return err!(
+                    "failed to parse '{}' as a decimal codepoint: {}",
+                    s, err);
+            }
+        }
+    }
+
     /// Return the underlying `u32` codepoint value.
     pub fn value(self) -> u32 { self.0 }
 
@@ -188,13 +488,79 @@ impl Codepoint {
     ///
     /// If this is a surrogate codepoint, then this returns `None`.
     pub fn scalar(self) -> Option<char> { char::from_u32(self.0) }
+
+    /// Return an iterator over every codepoint, from `0` to `10FFFF`
+    /// inclusive. This includes surrogate codepoints.
+    pub fn iter_all() -> CodepointRangeIter {
+        CodepointRange {
+            start: Codepoint(0),
+            end: Codepoint(0x10FFFF),
+        }.into_iter()
+    }
+
+    /// Add the given number to this codepoint.
+    ///
+    /// If the result isn't a valid codepoint, then this returns `None`.
+    pub fn checked_add(self, n: u32) -> Option<Codepoint> {
+        self.0.checked_add(n).and_then(|n| Codepoint::from_u32(n).ok())
+    }
+
+    /// Subtract the given number from this codepoint.
+    ///
+    /// If the result isn't a valid codepoint, then this returns `None`.
+    pub fn checked_sub(self, n: u32) -> Option<Codepoint> {
+        self.0.checked_sub(n).map(Codepoint)
+    }
+
+    /// Return the codepoint following this one, or `None` if this is
+    /// `10FFFF`.
+    pub fn next(self) -> Option<Codepoint> {
+        self.checked_add(1)
+    }
+
+    /// Return the codepoint preceding this one, or `None` if this is `0`.
+    pub fn prev(self) -> Option<Codepoint> {
+        self.checked_sub(1)
+    }
+
+    /// Return the plane that this codepoint is in, from `0` to `16`.
+    pub fn plane(self) -> u32 { self.0 >> 16 }
+
+    /// Returns true if and only if this codepoint is in the Basic
+    /// Multilingual Plane, i.e., plane `0`.
+    pub fn is_bmp(self) -> bool { self.plane() == 0 }
+
+    /// Return the first codepoint of the 16-codepoint column containing this
+    /// codepoint, as laid out in the Unicode code charts.
+    ///
+    /// Note that this is not the start of the block containing this
+    /// codepoint. Every block in `Blocks.txt` starts at the start of a
+    /// column, but most blocks span many columns.
+    pub fn column_start(self) -> Codepoint { Codepoint(self.0 & !0xF) }
+
+    /// Returns true if and only if this is a surrogate codepoint, i.e., in
+    /// the range `D800..DFFF`.
+    pub fn is_surrogate(self) -> bool {
+        0xD800 <= self.0 && self.0 <= 0xDFFF
+    }
 }
 
 impl FromStr for Codepoint {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Codepoint, Error> {
-        match u32::from_str_radix(s, 16) {
+        let hex = if s.starts_with("U+")
+            || s.starts_with("u+")
+            || s.starts_with("0x")
+        {
+            &s[2..]
+        } else {
+            s
+        };
+        if hex.starts_with('+') {
+            return err!("failed to parse '{}' as a hexadecimal codepoint", s);
+        }
+        match u32::from_str_radix(hex, 16) {
             Ok(n) => Codepoint::from_u32(n),
             Err(err) => {
                 return err!(
@@ -207,7 +573,11 @@ impl FromStr for Codepoint {
 
 impl fmt::Display for Codepoint {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:04X}", self.0)
+        if f.alternate() {
+            write!(f, "U+{:04X}", self.0)
+        } else {
+            write!(f, "{:04X}", self.0)
+        }
     }
 }
 
@@ -222,3 +592,540 @@ impl PartialEq<Codepoint> for u32 {
         *self == other.0
     }
 }
+
+/// An inclusive range of codepoints.
+///
+/// This type's string representation is either a single codepoint, e.g.,
+/// `0041`, or two codepoints separated by `..`, e.g., `0041..005A`. This is
+/// the format used for ranges throughout the UCD.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CodepointRange {
+    /// The first codepoint in this range.
+    pub start: Codepoint,
+    /// The last codepoint in this range.
+    pub end: Codepoint,
+}
+
+impl CodepointRange {
+    /// Create a new range from its first and last codepoints.
+    ///
+    /// If `start` is greater than `end`, then this returns an error.
+    pub fn new(
+        start: Codepoint,
+        end: Codepoint,
+    ) -> Result<CodepointRange, Error> {
+        if start > end {
+            return err!("invalid codepoint range: {}..{}", start, end);
+        }
+        Ok(CodepointRange { start: start, end: end })
+    }
+
+    /// Create a range containing only the given codepoint.
+    pub fn single(cp: Codepoint) -> CodepointRange {
+        CodepointRange { start: cp, end: cp }
+    }
+
+    /// Returns true if and only if the given codepoint is in this range.
+    pub fn contains(&self, cp: Codepoint) -> bool {
+        self.start <= cp && cp <= self.end
+    }
+
+    /// Return the number of codepoints in this range.
+    pub fn count(&self) -> usize {
+        (self.end.value() - self.start.value()) as usize + 1
+    }
+}
+
+impl FromStr for CodepointRange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<CodepointRange, Error> {
+        let mut parts = s.splitn(2, "..");
+        let start: Codepoint = parts.next().unwrap().parse()?;
+        let end: Codepoint = match parts.next() {
+            None => start,
+            Some(end) => end.parse()?,
+        };
+        CodepointRange::new(start, end)
+    }
+}
+
+impl fmt::Display for CodepointRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start)
+        } else {
+            write!(f, "{}..{}", self.start, self.end)
+        }
+    }
+}
+
+impl IntoIterator for CodepointRange {
+    type IntoIter = CodepointRangeIter;
+    type Item = Codepoint;
+
+    fn into_iter(self) -> CodepointRangeIter {
+        CodepointRangeIter { range: self.start.0..(self.end.0 + 1) }
+    }
+}
+
+/// An iterator over the codepoints in a `CodepointRange`, in ascending order.
+#[derive(Clone, Debug)]
+pub struct CodepointRangeIter {
+    range: Range<u32>,
+}
+
+impl Iterator for CodepointRangeIter {
+    type Item = Codepoint;
+
+    fn next(&mut self) -> Option<Codepoint> {
+        self.range.next().map(Codepoint)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+/// Write a sequence of codepoints separated by spaces.
+pub fn write_codepoints(
+    f: &mut fmt::Formatter,
+    codepoints: &[Codepoint],
+) -> fmt::Result {
+    for (i, cp) in codepoints.iter().enumerate() {
+        if i > 0 {
+            write!(f, " ")?;
+        }
+        write!(f, "{}", cp)?;
+    }
+    Ok(())
+}
+
+/// Parse a line of the form `XXXX ; value` or `XXXX..YYYY ; value`, with an
+/// optional trailing comment, into a range of codepoints and a property
+/// value.
+///
+/// This is the format used by most of the property files in the UCD.
+pub fn parse_range_value(
+    line: &str,
+) -> Result<(CodepointRange, &str), Error> {
+    let line = match line.find('#') {
+        Some(i) => &line[..i],
+        None => line,
+    };
+    let mut parts = line.splitn(2, ';');
+    let codepoints = parts.next().unwrap().trim();
+    let value = match parts.next() {
+        Some(value) => value.trim(),
+        None => return err!("invalid codepoint range and value line"),
+    };
+    if !is_hex_codepoint_range(codepoints)
+        || value.is_empty()
+        || value.contains(|c: char| c == ';' || c.is_whitespace())
+    {
+        return err!("invalid codepoint range and value line");
+    }
+    Ok((codepoints.parse()?, value))
+}
+
+/// Returns true if and only if the given string is a non-empty sequence of
+/// uppercase hexadecimal digits, which is how codepoints are written in the
+/// UCD.
+pub fn is_hex_codepoint(s: &str) -> bool {
+    !s.is_empty()
+    && s.bytes().all(|b| (b'0' <= b && b <= b'9') || (b'A' <= b && b <= b'F'))
+}
+
+/// Returns true if and only if the given string is a single codepoint or two
+/// codepoints separated by `..`, each written as by `is_hex_codepoint`.
+pub fn is_hex_codepoint_range(s: &str) -> bool {
+    s.split("..").all(is_hex_codepoint) && s.matches("..").count() <= 1
+}
+
+/// Returns true if and only if the given string is non-empty and contains no
+/// whitespace, as is the case for the names of properties and their values.
+pub fn is_word(s: &str) -> bool {
+    !s.is_empty() && !s.contains(char::is_whitespace)
+}
+
+/// Split a line into its fields, which are separated by `;`.
+///
+/// Everything from the first `#` onwards is a comment and is ignored, and
+/// whitespace is trimmed from both ends of each field. Note that a line
+/// ending with `;` has an empty last field.
+pub fn split_fields(line: &str) -> Vec<&str> {
+    let line = match line.find('#') {
+        Some(i) => &line[..i],
+        None => line,
+    };
+    line.split(';').map(|field| field.trim()).collect()
+}
+
+/// A marker type that names a UCD file containing `range ; value` lines.
+///
+/// This is used with `PropertyValueRow` to define a record type for a
+/// property file without writing a parser for it.
+pub trait PropertyFile {
+    #[cfg(feature = "std")]
+    /// The file path corresponding to this file, relative to the UCD
+    /// directory.
+    fn relative_file_path() -> &'static Path;
+}
+
+/// A single row in a property file that assigns a value to a range of
+/// codepoints, e.g., `0041..005A ; ALetter`.
+///
+/// Many UCD files use this format, so supporting a new one only requires a
+/// marker type implementing `PropertyFile` and a type alias:
+///
+/// ```
+/// use std::path::Path;
+/// use ucd_parse::{PropertyFile, PropertyValueRow};
+///
+/// #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+/// pub struct LineBreakFile;
+///
+/// impl PropertyFile for LineBreakFile {
+///     fn relative_file_path() -> &'static Path {
+///         Path::new("LineBreak.txt")
+///     }
+/// }
+///
+/// pub type LineBreak = PropertyValueRow<LineBreakFile, String>;
+///
+/// let row: LineBreak = "0041..005A;AL # L& [26] LATIN CAPITAL LETTER A..\
+///                       LATIN CAPITAL LETTER Z".parse().unwrap();
+/// assert_eq!(row.codepoints.start, 0x41);
+/// assert_eq!(row.codepoints.end, 0x5A);
+/// assert_eq!(row.value, "AL");
+/// ```
+///
+/// The value is parsed with its `FromStr` impl, so it may be a raw `String`
+/// or a typed value such as `GeneralCategory`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PropertyValueRow<F, V> {
+    /// The codepoints corresponding to this row.
+    pub codepoints: CodepointRange,
+    /// The property value assigned to the codepoints in this row.
+    pub value: V,
+    file: PhantomData<F>,
+}
+
+impl<F, V> PropertyValueRow<F, V> {
+    /// Create a new row from its codepoints and value.
+    pub fn new(
+        codepoints: CodepointRange,
+        value: V,
+    ) -> PropertyValueRow<F, V> {
+        PropertyValueRow {
+            codepoints: codepoints,
+            value: value,
+            file: PhantomData,
+        }
+    }
+}
+
+impl<F, V> PropertyValueRow<F, V>
+where V: FromStr, V::Err: fmt::Display
+{
+    /// Parse a single line.
+    pub fn parse_line(line: &str) -> Result<PropertyValueRow<F, V>, Error> {
+        let (codepoints, value) = parse_range_value(line)?;
+        match value.parse() {
+            Ok(value) => Ok(PropertyValueRow::new(codepoints, value)),
+            Err(err) => err!("invalid property value '{}': {}", value, err),
+        }
+    }
+}
+
+impl<F, V> FromStr for PropertyValueRow<F, V>
+where V: FromStr, V::Err: fmt::Display
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<PropertyValueRow<F, V>, Error> {
+        PropertyValueRow::parse_line(s)
+    }
+}
+
+impl<F, V: fmt::Display> fmt::Display for PropertyValueRow<F, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ; {}", self.codepoints, self.value)
+    }
+}
+
+impl<F, V> UcdFile for PropertyValueRow<F, V>
+where F: PropertyFile + fmt::Debug + Default + Eq,
+      V: fmt::Debug + Default + Eq + FromStr,
+      V::Err: fmt::Display
+{
+    #[cfg(feature = "std")]
+    fn relative_file_path() -> &'static Path {
+        F::relative_file_path()
+    }
+}
+
+impl<F, V> UcdFileByCodepointRange for PropertyValueRow<F, V>
+where F: PropertyFile + fmt::Debug + Default + Eq,
+      V: fmt::Debug + Default + Eq + FromStr,
+      V::Err: fmt::Display
+{
+    fn codepoints(&self) -> CodepointRange {
+        self.codepoints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use word_break::WordBreak;
+
+    use super::{
+        Codepoint, CodepointRange, Line, PropertyValueRow, UcdFile,
+        UcdLineParser,
+    };
+
+    fn codepoint(n: u32) -> Codepoint {
+        Codepoint::from_u32(n).unwrap()
+    }
+
+    #[test]
+    fn codepoint_parse() {
+        assert_eq!("1F600".parse::<Codepoint>().unwrap(), 0x1F600);
+        assert_eq!("U+1F600".parse::<Codepoint>().unwrap(), 0x1F600);
+        assert_eq!("u+1f600".parse::<Codepoint>().unwrap(), 0x1F600);
+        assert_eq!("0x1F600".parse::<Codepoint>().unwrap(), 0x1F600);
+        assert_eq!("41".parse::<Codepoint>().unwrap(), 0x41);
+        assert_eq!(Codepoint::from_decimal("128512").unwrap(), 0x1F600);
+        assert!("U+".parse::<Codepoint>().is_err());
+        assert!("U++41".parse::<Codepoint>().is_err());
+        assert!("U+110000".parse::<Codepoint>().is_err());
+        assert!(Codepoint::from_decimal("1F600").is_err());
+        assert!(Codepoint::from_decimal("1114112").is_err());
+
+        assert_eq!(codepoint(0x41).to_string(), "0041");
+        assert_eq!(format!("{:#}", codepoint(0x41)), "U+0041");
+        assert_eq!(format!("{:#}", codepoint(0x1F600)), "U+1F600");
+    }
+
+    #[test]
+    fn codepoint_arithmetic() {
+        let cp = codepoint(0xFFFF);
+        assert_eq!(cp.next(), Some(codepoint(0x10000)));
+        assert_eq!(cp.prev(), Some(codepoint(0xFFFE)));
+        assert_eq!(cp.checked_add(0x100000), Some(codepoint(0x10FFFF)));
+        assert_eq!(cp.checked_add(0x100001), None);
+        assert_eq!(cp.checked_add(u32::max_value()), None);
+        assert_eq!(cp.checked_sub(0xFFFF), Some(codepoint(0)));
+        assert_eq!(cp.checked_sub(0x10000), None);
+        assert_eq!(codepoint(0x10FFFF).next(), None);
+        assert_eq!(codepoint(0).prev(), None);
+    }
+
+    #[test]
+    fn codepoint_queries() {
+        assert_eq!(codepoint(0xFFFF).plane(), 0);
+        assert_eq!(codepoint(0x1F600).plane(), 1);
+        assert_eq!(codepoint(0x10FFFF).plane(), 16);
+        assert!(codepoint(0xFFFF).is_bmp());
+        assert!(!codepoint(0x10000).is_bmp());
+        assert_eq!(codepoint(0x1F60A).column_start(), codepoint(0x1F600));
+        // U+0041 is in the Basic Latin block, which starts at U+0000.
+        assert_eq!(codepoint(0x0041).column_start(), codepoint(0x0040));
+        assert!(codepoint(0xD800).is_surrogate());
+        assert!(codepoint(0xDFFF).is_surrogate());
+        assert!(!codepoint(0xE000).is_surrogate());
+
+        assert_eq!(Codepoint::iter_all().count(), 0x110000);
+        assert_eq!(Codepoint::iter_all().last(), Some(codepoint(0x10FFFF)));
+    }
+
+    #[test]
+    fn range_parse() {
+        let range: CodepointRange = "0041..005A".parse().unwrap();
+        assert_eq!(range.start, 0x41);
+        assert_eq!(range.end, 0x5A);
+        assert_eq!(range.count(), 26);
+        assert_eq!(range.to_string(), "0041..005A");
+
+        let range: CodepointRange = "10FFFF".parse().unwrap();
+        assert_eq!(range, CodepointRange::single(codepoint(0x10FFFF)));
+        assert_eq!(range.to_string(), "10FFFF");
+
+        assert!("005A..0041".parse::<CodepointRange>().is_err());
+        assert!("0041..".parse::<CodepointRange>().is_err());
+        assert!("0041...005A".parse::<CodepointRange>().is_err());
+    }
+
+    #[test]
+    fn range_iter() {
+        let range: CodepointRange = "10FFFD..10FFFF".parse().unwrap();
+        let cps: Vec<u32> = range.into_iter().map(|cp| cp.value()).collect();
+        assert_eq!(cps, vec![0x10FFFD, 0x10FFFE, 0x10FFFF]);
+        assert!(range.contains(codepoint(0x10FFFE)));
+        assert!(!range.contains(codepoint(0x10FFFC)));
+    }
+
+    #[test]
+    fn range_value() {
+        use super::parse_range_value;
+
+        let (cps, value) = parse_range_value("0041..005A;ALetter").unwrap();
+        assert_eq!(cps, "0041..005A".parse().unwrap());
+        assert_eq!(value, "ALetter");
+        let (_, value) = parse_range_value("  00AD ; Format # Cf\n").unwrap();
+        assert_eq!(value, "Format");
+
+        assert!(parse_range_value("0041 ; two words").is_err());
+        assert!(parse_range_value("0041 ; a ; b").is_err());
+        assert!(parse_range_value("0041 ; # comment").is_err());
+        assert!(parse_range_value("0041..0042..0043 ; X").is_err());
+        assert!(parse_range_value("U+0041 ; X").is_err());
+        assert!(parse_range_value("0041").is_err());
+    }
+
+    #[test]
+    fn fields() {
+        use super::{is_hex_codepoint_range, is_word, split_fields};
+
+        assert_eq!(
+            split_fields(" 0041; C; 0061; # LATIN CAPITAL LETTER A\n"),
+            vec!["0041", "C", "0061", ""]);
+        assert_eq!(split_fields("1100; G"), vec!["1100", "G"]);
+        assert_eq!(split_fields("# comment"), vec![""]);
+
+        assert!(is_hex_codepoint_range("0041"));
+        assert!(is_hex_codepoint_range("0041..005A"));
+        assert!(!is_hex_codepoint_range("0041.."));
+        assert!(!is_hex_codepoint_range("0041..0042..0043"));
+        assert!(is_word("Alphabetic"));
+        assert!(!is_word(""));
+        assert!(!is_word("two words"));
+    }
+
+    #[test]
+    fn property_value_row() {
+        type Row = PropertyValueRow<(), u8>;
+
+        let row: Row = "0030..0039 ; 7 # digits".parse().unwrap();
+        assert_eq!(row.codepoints, "0030..0039".parse().unwrap());
+        assert_eq!(row.value, 7);
+
+        let err = "0030 ; x".parse::<Row>().unwrap_err();
+        assert!(err.to_string().contains("invalid property value 'x'"));
+    }
+
+    #[test]
+    fn lines() {
+        let data = "\
+# WordBreakProperty-10.0.0.txt
+# @missing: 0000..10FFFF; Other
+
+0027          ; Single_Quote # Po       APOSTROPHE\r
+";
+        let lines: Vec<Line<WordBreak>> = UcdLineParser::new(data.as_bytes())
+            .into_lines()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0],
+            Line::Comment("# WordBreakProperty-10.0.0.txt".to_string()));
+        match lines[1] {
+            Line::Missing(ref missing) => assert_eq!(missing.value, "Other"),
+            ref line => panic!("expected @missing line, got {:?}", line),
+        }
+        assert_eq!(lines[2], Line::Blank);
+        match lines[3] {
+            Line::Record(ref row) => assert_eq!(row.value, "Single_Quote"),
+            ref line => panic!("expected record, got {:?}", line),
+        }
+
+        let data = "# header\n\n0027 Single_Quote\n";
+        let err = UcdLineParser::<_, WordBreak>::new(data.as_bytes())
+            .into_lines()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+        assert_eq!(err.line(), Some(3));
+    }
+
+    #[test]
+    fn error_location() {
+        use std::path::Path;
+
+        let data = "0027 ; Single_Quote
+0041..XYZ ; ALetter
+";
+        let err = UcdLineParser::<_, WordBreak>::new(data.as_bytes())
+            .with_path("auxiliary/WordBreakProperty.txt")
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+        assert_eq!(err.line(), Some(2));
+        assert_eq!(
+            err.path(), Some(Path::new("auxiliary/WordBreakProperty.txt")));
+        assert!(err
+            .to_string()
+            .starts_with("error on line 2 of auxiliary/WordBreakProperty.txt"));
+
+        let err = WordBreak::from_dir("/nonexistent/ucd").unwrap_err();
+        assert!(err.is_io_error());
+        assert!(err.to_string().starts_with("/nonexistent/ucd/auxiliary/"));
+    }
+
+    #[test]
+    fn from_data() {
+        let data = "# header\n0027 ; Single_Quote\n0041..005A ; ALetter\n";
+        let rows = WordBreak::from_data(data)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].codepoints, "0041..005A".parse().unwrap());
+
+        let rows = WordBreak::from_reader(data.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(rows[0].value, "Single_Quote");
+    }
+
+    #[test]
+    fn by_codepoint_range() {
+        use prop_list::PropList;
+        use temp_dir::TempDir;
+
+        let dir = TempDir::new("ucd-parse-by-codepoint-range");
+        dir.write("PropList.txt", b"0009..000D ; White_Space\n\
+                                    000A ; Pattern_White_Space\n");
+
+        let map = super::parse_by_codepoint_range::<_, PropList>(dir.path())
+            .unwrap();
+        assert_eq!(map.len(), 5);
+        assert_eq!(map[&codepoint(0x09)].len(), 1);
+        let props: Vec<_> = map[&codepoint(0x0A)]
+            .iter()
+            .map(|row| row.property.to_string())
+            .collect();
+        assert_eq!(props, vec!["White_Space", "Pattern_White_Space"]);
+    }
+
+    #[test]
+    fn write_records() {
+        use super::UcdFileWrite;
+
+        let data = "# header\n\n0027 ; Single_Quote\n0041..005A ; ALetter\n";
+        let rows = WordBreak::from_data(data)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let mut out = vec![];
+        WordBreak::write_records(&mut out, &rows).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "0027 ; Single_Quote\n0041..005A ; ALetter\n");
+
+        let lines: Vec<String> = WordBreak::from_data(data)
+            .into_lines()
+            .map(|line| line.unwrap().to_string() + "\n")
+            .collect();
+        assert_eq!(lines.concat(), data);
+    }
+}
@@ -0,0 +1,136 @@
+use std::borrow::Cow;
+use std::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+use std::str::FromStr;
+
+use common::{
+    UcdFile, UcdFileByCodepointRange, CodepointRange, is_hex_codepoint_range,
+    is_word, split_fields,
+};
+use error::Error;
+
+/// A single row in the `DerivedCoreProperties.txt` file, which lists
+/// codepoints for binary properties derived from other properties, such as
+/// `Alphabetic` and `XID_Start`.
+///
+/// Most rows state that a range of codepoints has a binary property. Since
+/// Unicode 15.1, this file also lists the values of the enumerated
+/// `Indic_Conjunct_Break` property, in rows such as `094D ; InCB; Linker`.
+/// The value of such rows is stored in `value`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CoreProperty<'a> {
+    /// The codepoints corresponding to this row.
+    pub codepoints: CodepointRange,
+    /// The name of the property that the codepoints in this range have.
+    pub property: Cow<'a, str>,
+    /// The value of the property, or `None` if the property is binary.
+    pub value: Option<Cow<'a, str>>,
+}
+
+impl UcdFile for CoreProperty<'static> {
+    #[cfg(feature = "std")]
+    fn relative_file_path() -> &'static Path {
+        Path::new("DerivedCoreProperties.txt")
+    }
+}
+
+impl UcdFileByCodepointRange for CoreProperty<'static> {
+    fn codepoints(&self) -> CodepointRange {
+        self.codepoints
+    }
+}
+
+impl<'a> CoreProperty<'a> {
+    /// Returns true if and only if this row states that its codepoints have
+    /// a binary property.
+    pub fn is_binary(&self) -> bool {
+        self.value.is_none()
+    }
+
+    /// Convert this record into an owned value such that it no longer
+    /// borrows from the original line that it was parsed from.
+    pub fn into_owned(self) -> CoreProperty<'static> {
+        CoreProperty {
+            codepoints: self.codepoints,
+            property: Cow::Owned(self.property.into_owned()),
+            value: self.value.map(|v| Cow::Owned(v.into_owned())),
+        }
+    }
+
+    /// Parse a single line.
+    pub fn parse_line(line: &'a str) -> Result<CoreProperty<'a>, Error> {
+        let fields = split_fields(line);
+        let value = match fields.len() {
+            2 => None,
+            3 => Some(fields[2]),
+            _ => return err!("invalid DerivedCoreProperties line"),
+        };
+        if !is_hex_codepoint_range(fields[0])
+            || !is_word(fields[1])
+            || !value.map_or(true, is_word)
+        {
+            return err!("invalid DerivedCoreProperties line");
+        }
+        Ok(CoreProperty {
+            codepoints: fields[0].parse()?,
+            property: Cow::Borrowed(fields[1]),
+            value: value.map(Cow::Borrowed),
+        })
+    }
+}
+
+impl FromStr for CoreProperty<'static> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<CoreProperty<'static>, Error> {
+        CoreProperty::parse_line(s).map(|x| x.into_owned())
+    }
+}
+
+impl<'a> fmt::Display for CoreProperty<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ; {}", self.codepoints, self.property)?;
+        if let Some(ref value) = self.value {
+            write!(f, "; {}", value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CoreProperty;
+
+    #[test]
+    fn parse_binary() {
+        let line = "0041..005A    ; Alphabetic # L&  [26] \
+                    LATIN CAPITAL LETTER A..LATIN CAPITAL LETTER Z\n";
+        let row: CoreProperty = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x0041);
+        assert_eq!(row.codepoints.end, 0x005A);
+        assert_eq!(row.property, "Alphabetic");
+        assert!(row.is_binary());
+        assert_eq!(row.to_string(), "0041..005A ; Alphabetic");
+    }
+
+    #[test]
+    fn parse_enumerated() {
+        let line = "094D          ; InCB; Linker # Mn       \
+                    DEVANAGARI SIGN VIRAMA\n";
+        let row: CoreProperty = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x094D);
+        assert_eq!(row.property, "InCB");
+        assert_eq!(row.value.as_ref().map(|v| &**v), Some("Linker"));
+        assert!(!row.is_binary());
+        assert_eq!(row.to_string(), "094D ; InCB; Linker");
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!("0041 ; ".parse::<CoreProperty>().is_err());
+        assert!("0041 ; Math ; ".parse::<CoreProperty>().is_err());
+        assert!("Math ; 0041".parse::<CoreProperty>().is_err());
+    }
+}
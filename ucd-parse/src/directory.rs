@@ -0,0 +1,236 @@
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use ucd_util::UnicodeVersion;
+
+use case_folding::CaseFold;
+use common::{UcdFile, UcdLineParser};
+use error::Error;
+use jamo_short_name::JamoShortName;
+use name_aliases::NameAlias;
+use property_aliases::PropertyAlias;
+use property_value_aliases::PropertyValueAlias;
+use reader::{self, UcdReader};
+use unicode_data::UnicodeData;
+
+/// Files whose headers are checked, in order, to determine the version of a
+/// UCD directory. `UnicodeData.txt` has no header, so it isn't listed.
+const VERSIONED_FILES: &'static [&'static str] = &[
+    "PropertyAliases.txt",
+    "PropertyValueAliases.txt",
+    "NameAliases.txt",
+    "Jamo.txt",
+];
+
+/// A directory containing the Unicode character database.
+///
+/// Opening a directory checks that it contains `UnicodeData.txt`, which is
+/// present in every release of the UCD, and determines the version of the
+/// UCD from the headers of its other files. Files are parsed with typed
+/// accessors such as `unicode_data`, or with `records` for any `UcdFile`.
+/// These return an error naming the missing file if it doesn't exist.
+///
+/// When the `zip` feature is enabled, files may also be read from `UCD.zip`
+/// or `Unihan.zip` in this directory, or the path to an archive may be opened
+/// in place of a directory. See `UcdReader` for details.
+///
+/// This type implements `AsRef<Path>`, so it can be used anywhere that a UCD
+/// directory path is accepted.
+#[derive(Clone, Debug)]
+pub struct UcdDirectory {
+    path: PathBuf,
+    version: Option<UnicodeVersion>,
+}
+
+impl UcdDirectory {
+    /// Open the UCD directory at the given path.
+    ///
+    /// If the path isn't a directory or doesn't contain `UnicodeData.txt`,
+    /// then this returns an error.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<UcdDirectory, Error> {
+        let path = path.as_ref();
+        if !reader::is_ucd_dir(path) {
+            return err!("{} is not a directory", path.display());
+        }
+        let mut dir = UcdDirectory { path: path.to_path_buf(), version: None };
+        dir.require::<UnicodeData<'static>>()?;
+        dir.version = dir.find_version()?;
+        Ok(dir)
+    }
+
+    /// Return the path to this directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Return the version of this UCD, if it could be determined.
+    ///
+    /// The version is read from the header of one of the files in this
+    /// directory when it is opened.
+    pub fn version(&self) -> Option<UnicodeVersion> {
+        self.version
+    }
+
+    /// Returns true if and only if the file for the given record type exists
+    /// in this directory.
+    pub fn has<D: UcdFile>(&self) -> bool {
+        reader::exists(&self.path, D::relative_file_path())
+    }
+
+    /// Return an error if the file for the given record type doesn't exist
+    /// in this directory.
+    pub fn require<D: UcdFile>(&self) -> Result<(), Error> {
+        if !self.has::<D>() {
+            return err!(
+                "missing UCD file: {}", D::file_path(&self.path).display());
+        }
+        Ok(())
+    }
+
+    /// Create an iterator over each record in the file for the given record
+    /// type.
+    ///
+    /// If the version of this UCD is known, then records are parsed in the
+    /// format used by that version.
+    pub fn records<D: UcdFile>(
+        &self,
+    ) -> Result<UcdLineParser<UcdReader, D>, Error> {
+        self.require::<D>()?;
+        let records = D::from_dir(&self.path)?;
+        Ok(match self.version {
+            None => records,
+            Some(version) => records.with_version(version),
+        })
+    }
+
+    /// Create an iterator over each record in `UnicodeData.txt`.
+    pub fn unicode_data(
+        &self,
+    ) -> Result<UcdLineParser<UcdReader, UnicodeData<'static>>, Error> {
+        self.records()
+    }
+
+    /// Create an iterator over each record in `NameAliases.txt`.
+    pub fn name_aliases(
+        &self,
+    ) -> Result<UcdLineParser<UcdReader, NameAlias<'static>>, Error> {
+        self.records()
+    }
+
+    /// Create an iterator over each record in `Jamo.txt`.
+    pub fn jamo_short_names(
+        &self,
+    ) -> Result<UcdLineParser<UcdReader, JamoShortName<'static>>, Error> {
+        self.records()
+    }
+
+    /// Create an iterator over each record in `PropertyAliases.txt`.
+    pub fn property_aliases(
+        &self,
+    ) -> Result<UcdLineParser<UcdReader, PropertyAlias<'static>>, Error> {
+        self.records()
+    }
+
+    /// Create an iterator over each record in `PropertyValueAliases.txt`.
+    pub fn property_value_aliases(
+        &self,
+    ) -> Result<UcdLineParser<UcdReader, PropertyValueAlias<'static>>, Error> {
+        self.records()
+    }
+
+    /// Create an iterator over each record in `CaseFolding.txt`.
+    pub fn case_folding(
+        &self,
+    ) -> Result<UcdLineParser<UcdReader, CaseFold>, Error> {
+        self.records()
+    }
+
+    fn find_version(&self) -> Result<Option<UnicodeVersion>, Error> {
+        for name in VERSIONED_FILES {
+            let name = Path::new(name);
+            if !reader::exists(&self.path, name) {
+                continue;
+            }
+            let rdr = UcdReader::open(&self.path, name)?;
+            let mut line = String::new();
+            io::BufReader::new(rdr).read_line(&mut line)?;
+            if let Some(version) = version_from_header(&line)? {
+                return Ok(Some(version));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl AsRef<Path> for UcdDirectory {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Extract the version from the first line of a UCD file, which looks like
+/// `# PropertyAliases-10.0.0.txt`.
+fn version_from_header(line: &str) -> Result<Option<UnicodeVersion>, Error> {
+    lazy_static! {
+        static ref HEADER: Regex = Regex::new(
+            r"^#\s*[A-Za-z0-9_]+-([0-9]+\.[0-9]+\.[0-9]+)\.txt"
+        ).unwrap();
+    };
+    match HEADER.captures(line.trim()) {
+        None => Ok(None),
+        Some(caps) => match caps[1].parse() {
+            Ok(version) => Ok(Some(version)),
+            Err(err) => err!("{}", err),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ucd_util::UnicodeVersion;
+
+    use jamo_short_name::JamoShortName;
+    use temp_dir::TempDir;
+
+    use super::{UcdDirectory, version_from_header};
+
+    #[test]
+    fn version_header() {
+        let v = version_from_header("# PropertyAliases-9.0.0.txt\n").unwrap();
+        assert_eq!(v, Some(UnicodeVersion(9, 0, 0)));
+        let v = version_from_header("# DerivedCoreProperties.txt\n").unwrap();
+        assert_eq!(v, None);
+    }
+
+    #[test]
+    fn open() {
+        let tmp = TempDir::new("ucd-parse-directory-open");
+        assert!(UcdDirectory::open(tmp.path()).is_err());
+
+        tmp.write("UnicodeData.txt",
+                  b"0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;\n");
+        tmp.write("PropertyAliases.txt", b"# PropertyAliases-10.0.0.txt\n");
+        let dir = UcdDirectory::open(tmp.path()).unwrap();
+        assert_eq!(dir.version(), Some(UnicodeVersion(10, 0, 0)));
+        assert_eq!(dir.unicode_data().unwrap().count(), 1);
+        assert!(!dir.has::<JamoShortName>());
+        assert!(dir.jamo_short_names().is_err());
+    }
+
+    #[test]
+    fn records_versioned() {
+        let tmp = TempDir::new("ucd-parse-directory-versioned");
+        tmp.write("UnicodeData.txt",
+                  b"01A2;LATIN CAPITAL LETTER OI;Lu;0;L;;;;;N;;;;01A3;\n");
+        tmp.write("NameAliases.txt", b"# NameAliases-6.0.0.txt\n\
+                                       01A2;LATIN CAPITAL LETTER GHA\n");
+        let dir = UcdDirectory::open(tmp.path()).unwrap();
+        assert_eq!(dir.version(), Some(UnicodeVersion(6, 0, 0)));
+        let aliases = dir.name_aliases()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(aliases[0].alias, "LATIN CAPITAL LETTER GHA");
+    }
+}
@@ -0,0 +1,38 @@
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
+
+use common::{PropertyFile, PropertyValueRow};
+
+/// The `EastAsianWidth.txt` file, which defines the `East_Asian_Width`
+/// property.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct EastAsianWidthFile;
+
+impl PropertyFile for EastAsianWidthFile {
+    #[cfg(feature = "std")]
+    fn relative_file_path() -> &'static Path {
+        Path::new("EastAsianWidth.txt")
+    }
+}
+
+/// A single row in the `EastAsianWidth.txt` file.
+///
+/// The value is the abbreviated `East_Asian_Width` property value, e.g.,
+/// `W`.
+pub type EastAsianWidth = PropertyValueRow<EastAsianWidthFile, String>;
+
+#[cfg(test)]
+mod tests {
+    use super::EastAsianWidth;
+
+    #[test]
+    fn parse() {
+        let line = "3000;F           # Zs         IDEOGRAPHIC SPACE\n";
+        let row: EastAsianWidth = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x3000);
+        assert_eq!(row.codepoints.end, 0x3000);
+        assert_eq!(row.value, "F");
+    }
+}
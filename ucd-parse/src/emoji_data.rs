@@ -0,0 +1,20 @@
+binary_property_record!(
+    EmojiProperty, "emoji/emoji-data.txt",
+    "A single row in the `emoji/emoji-data.txt` file, which lists \
+     codepoints for the emoji binary properties such as `Emoji` and \
+     `Extended_Pictographic`.");
+
+#[cfg(test)]
+mod tests {
+    use super::EmojiProperty;
+
+    #[test]
+    fn parse() {
+        let line = "1F600..1F64F  ; Emoji_Presentation   # E1.0 [80] \
+                    (😀..🙏)    grinning face..folded hands\n";
+        let row: EmojiProperty = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x1F600);
+        assert_eq!(row.codepoints.end, 0x1F64F);
+        assert_eq!(row.property, "Emoji_Presentation");
+    }
+}
@@ -1,10 +1,21 @@
+#[cfg(feature = "std")]
 use std::error;
 use std::fmt;
+#[cfg(feature = "std")]
 use std::io;
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
 
 /// Create a new error from a kind without a line number.
 pub fn error_new(kind: ErrorKind) -> Error {
-    Error { kind: kind, line: None }
+    Error {
+        kind: kind,
+        line: None,
+        #[cfg(feature = "std")]
+        path: None,
+    }
 }
 
 /// Create a new parse error from the given message.
@@ -17,17 +28,26 @@ pub fn error_set_line(err: &mut Error, line: Option<u64>) {
     err.line = line;
 }
 
+/// Set the file path on the given error.
+#[cfg(feature = "std")]
+pub fn error_set_path<P: AsRef<Path>>(err: &mut Error, path: Option<P>) {
+    err.path = path.map(|p| p.as_ref().to_path_buf());
+}
+
 /// Represents any kind of error that can occur while parsing the UCD.
 #[derive(Debug)]
 pub struct Error {
     kind: ErrorKind,
     line: Option<u64>,
+    #[cfg(feature = "std")]
+    path: Option<PathBuf>,
 }
 
 /// The kind of error that occurred while parsing the UCD.
 #[derive(Debug)]
 pub enum ErrorKind {
     /// An I/O error.
+    #[cfg(feature = "std")]
     Io(io::Error),
     /// A generic parse error.
     Parse(String),
@@ -44,6 +64,13 @@ impl Error {
         self.line
     }
 
+    /// Return the path of the file in which this error occurred, if
+    /// available.
+    #[cfg(feature = "std")]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_ref().map(|p| &**p)
+    }
+
     /// Unwrap this error into its underlying kind.
     pub fn into_kind(self) -> ErrorKind {
         self.kind
@@ -53,6 +80,7 @@ impl Error {
     ///
     /// If this returns true, the underlying `ErrorKind` is guaranteed to be
     /// `ErrorKind::Io`.
+    #[cfg(feature = "std")]
     pub fn is_io_error(&self) -> bool {
         match self.kind {
             ErrorKind::Io(_) => true,
@@ -61,6 +89,7 @@ impl Error {
     }
 }
 
+#[cfg(feature = "std")]
 impl error::Error for Error {
     fn description(&self) -> &str {
         match self.kind {
@@ -77,23 +106,55 @@ impl error::Error for Error {
     }
 }
 
+#[cfg(feature = "std")]
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.kind {
-            ErrorKind::Io(ref err) => err.fmt(f),
-            ErrorKind::Parse(ref msg) => {
-                if let Some(line) = self.line {
-                    write!(f, "error on line {}: {}", line, msg)
+            ErrorKind::Io(ref err) => {
+                if let Some(ref path) = self.path {
+                    write!(f, "{}: {}", path.display(), err)
                 } else {
-                    write!(f, "{}", msg)
+                    err.fmt(f)
+                }
+            }
+            ErrorKind::Parse(ref msg) => {
+                match (self.line, self.path.as_ref()) {
+                    (Some(line), Some(path)) => {
+                        write!(f, "error on line {} of {}: {}",
+                               line, path.display(), msg)
+                    }
+                    (Some(line), None) => {
+                        write!(f, "error on line {}: {}", line, msg)
+                    }
+                    (None, Some(path)) => {
+                        write!(f, "error in {}: {}", path.display(), msg)
+                    }
+                    (None, None) => write!(f, "{}", msg),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ErrorKind::Parse(ref msg) => {
+                match self.line {
+                    Some(line) => {
+                        write!(f, "error on line {}: {}", line, msg)
+                    }
+                    None => write!(f, "{}", msg),
                 }
             }
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl From<io::Error> for Error {
     fn from(err: io::Error) -> Error {
-        Error { kind: ErrorKind::Io(err), line: None }
+        Error { kind: ErrorKind::Io(err), line: None, path: None }
     }
 }
@@ -0,0 +1,256 @@
+// This module defines records for the files in the `extracted` directory of
+// the UCD. These files contain property values derived from other files
+// (mostly `UnicodeData.txt`). Most of them use the same `range ; value`
+// format as other property files.
+
+use std::borrow::Cow;
+use std::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+use std::str::FromStr;
+
+use common::{
+    UcdFile, UcdFileByCodepointRange, CodepointRange, is_hex_codepoint_range,
+    split_fields,
+};
+use error::Error;
+use unicode_data::UnicodeDataNumeric;
+
+range_value_record!(
+    DerivedGeneralCategory, "extracted/DerivedGeneralCategory.txt",
+    "A single row in the `extracted/DerivedGeneralCategory.txt` file, which \
+     lists the `General_Category` property of every codepoint.");
+range_value_record!(
+    DerivedBidiClass, "extracted/DerivedBidiClass.txt",
+    "A single row in the `extracted/DerivedBidiClass.txt` file, which lists \
+     the `Bidi_Class` property of every codepoint.");
+range_value_record!(
+    DerivedDecompositionType, "extracted/DerivedDecompositionType.txt",
+    "A single row in the `extracted/DerivedDecompositionType.txt` file, \
+     which lists the `Decomposition_Type` property of every codepoint.");
+range_value_record!(
+    DerivedJoiningType, "extracted/DerivedJoiningType.txt",
+    "A single row in the `extracted/DerivedJoiningType.txt` file, which \
+     lists the `Joining_Type` property of every codepoint with an explicit \
+     joining type.");
+range_value_record!(
+    DerivedJoiningGroup, "extracted/DerivedJoiningGroup.txt",
+    "A single row in the `extracted/DerivedJoiningGroup.txt` file, which \
+     lists the `Joining_Group` property of every codepoint in a joining \
+     group.");
+range_value_record!(
+    DerivedNumericType, "extracted/DerivedNumericType.txt",
+    "A single row in the `extracted/DerivedNumericType.txt` file, which \
+     lists the `Numeric_Type` property of every numeric codepoint.");
+binary_property_record!(
+    DerivedBinaryProperties, "extracted/DerivedBinaryProperties.txt",
+    "A single row in the `extracted/DerivedBinaryProperties.txt` file, which \
+     lists the binary properties derived from `UnicodeData.txt`, e.g., \
+     `Bidi_Mirrored`.");
+
+/// A single row in the `extracted/DerivedNumericValues.txt` file, which lists
+/// the `Numeric_Value` property of every numeric codepoint.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DerivedNumericValues<'a> {
+    /// The codepoints corresponding to this row.
+    pub codepoints: CodepointRange,
+    /// The decimal representation of the numeric value, e.g., `0.25`.
+    ///
+    /// This is kept as a string since it is only an approximation of
+    /// `numeric` for some values, e.g., `0.3333333333` for `1/3`.
+    pub decimal: Cow<'a, str>,
+    /// The exact numeric value.
+    pub numeric: UnicodeDataNumeric,
+}
+
+impl UcdFile for DerivedNumericValues<'static> {
+    #[cfg(feature = "std")]
+    fn relative_file_path() -> &'static Path {
+        Path::new("extracted/DerivedNumericValues.txt")
+    }
+}
+
+impl UcdFileByCodepointRange for DerivedNumericValues<'static> {
+    fn codepoints(&self) -> CodepointRange {
+        self.codepoints
+    }
+}
+
+impl<'a> DerivedNumericValues<'a> {
+    /// Convert this record into an owned value such that it no longer
+    /// borrows from the original line that it was parsed from.
+    pub fn into_owned(self) -> DerivedNumericValues<'static> {
+        DerivedNumericValues {
+            codepoints: self.codepoints,
+            decimal: Cow::Owned(self.decimal.into_owned()),
+            numeric: self.numeric,
+        }
+    }
+
+    /// Parse a single line.
+    pub fn parse_line(
+        line: &'a str,
+    ) -> Result<DerivedNumericValues<'a>, Error> {
+        let is_decimal = |s: &str| {
+            !s.is_empty() && s.bytes().all(|b| {
+                b == b'-' || b == b'.' || (b'0' <= b && b <= b'9')
+            })
+        };
+
+        let fields = split_fields(line);
+        if fields.len() != 4
+            || !is_hex_codepoint_range(fields[0])
+            || !is_decimal(fields[1])
+            || !fields[2].is_empty()
+        {
+            return err!("invalid DerivedNumericValues line");
+        }
+        Ok(DerivedNumericValues {
+            codepoints: fields[0].parse()?,
+            decimal: Cow::Borrowed(fields[1]),
+            numeric: fields[3].parse()?,
+        })
+    }
+}
+
+impl FromStr for DerivedNumericValues<'static> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<DerivedNumericValues<'static>, Error> {
+        DerivedNumericValues::parse_line(s).map(|x| x.into_owned())
+    }
+}
+
+impl<'a> fmt::Display for DerivedNumericValues<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f, "{} ; {} ; ; {}",
+            self.codepoints, self.decimal, self.numeric)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DerivedBidiClass, DerivedBinaryProperties, DerivedDecompositionType,
+        DerivedGeneralCategory, DerivedJoiningGroup, DerivedJoiningType,
+        DerivedNumericType, DerivedNumericValues,
+    };
+    use unicode_data::UnicodeDataNumeric;
+
+    #[test]
+    fn parse_general_category() {
+        let line = "0000..001F    ; Cc #  [32] \
+                    <control-0000>..<control-001F>\n";
+        let row: DerivedGeneralCategory = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x0000);
+        assert_eq!(row.codepoints.end, 0x001F);
+        assert_eq!(row.value, "Cc");
+    }
+
+    #[test]
+    fn parse_bidi_class() {
+        let line = "0041..005A    ; L # L&  [26] \
+                    LATIN CAPITAL LETTER A..LATIN CAPITAL LETTER Z\n";
+        let row: DerivedBidiClass = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x0041);
+        assert_eq!(row.codepoints.end, 0x005A);
+        assert_eq!(row.value, "L");
+    }
+
+    #[test]
+    fn parse_decomposition_type() {
+        let line = "00A0          ; Nobreak # Zs       NO-BREAK SPACE\n";
+        let row: DerivedDecompositionType = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x00A0);
+        assert_eq!(row.codepoints.end, 0x00A0);
+        assert_eq!(row.value, "Nobreak");
+    }
+
+    #[test]
+    fn parse_joining_type() {
+        let line = "0620          ; D # Lo       ARABIC LETTER KASHMIRI YEH\n";
+        let row: DerivedJoiningType = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x0620);
+        assert_eq!(row.codepoints.end, 0x0620);
+        assert_eq!(row.value, "D");
+    }
+
+    #[test]
+    fn parse_joining_group() {
+        let line = "0628..0629    ; Beh # Lo   [2] \
+                    ARABIC LETTER BEH..ARABIC LETTER TEH MARBUTA\n";
+        let row: DerivedJoiningGroup = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x0628);
+        assert_eq!(row.codepoints.end, 0x0629);
+        assert_eq!(row.value, "Beh");
+    }
+
+    #[test]
+    fn parse_numeric_type() {
+        let line = "0030..0039    ; Decimal # Nd  [10] \
+                    DIGIT ZERO..DIGIT NINE\n";
+        let row: DerivedNumericType = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x0030);
+        assert_eq!(row.codepoints.end, 0x0039);
+        assert_eq!(row.value, "Decimal");
+    }
+
+    #[test]
+    fn parse_numeric_values() {
+        let line = "0F33          ; -0.5 ; ; -1/2 # No       \
+                    TIBETAN DIGIT HALF ZERO\n";
+        let row: DerivedNumericValues = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x0F33);
+        assert_eq!(row.codepoints.end, 0x0F33);
+        assert_eq!(row.decimal, "-0.5");
+        assert_eq!(row.numeric, UnicodeDataNumeric::Rational(-1, 2));
+
+        let line = "1372..1373    ; 20.0 ; ; 20 # No   [2] \
+                    ETHIOPIC NUMBER TWENTY..ETHIOPIC NUMBER THIRTY\n";
+        let row: DerivedNumericValues = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x1372);
+        assert_eq!(row.codepoints.end, 0x1373);
+        assert_eq!(row.decimal, "20.0");
+        assert_eq!(row.numeric, UnicodeDataNumeric::Integer(20));
+    }
+
+    #[test]
+    fn parse_numeric_values_rational() {
+        let line = "2153          ; 0.3333333333 ; ; 1/3 # No       \
+                    VULGAR FRACTION ONE THIRD\n";
+        let row: DerivedNumericValues = line.parse().unwrap();
+        assert_eq!(row.decimal, "0.3333333333");
+        assert_eq!(row.numeric, UnicodeDataNumeric::Rational(1, 3));
+    }
+
+    #[test]
+    fn parse_numeric_values_errors() {
+        assert!("2153 ; 0.33 ; 1/3".parse::<DerivedNumericValues>().is_err());
+        assert!("2153 ; 0.33 ; ; x".parse::<DerivedNumericValues>().is_err());
+    }
+
+    #[test]
+    fn parse_binary_properties() {
+        let line = "0028..0029    ; Bidi_M # Ps       \
+                    LEFT PARENTHESIS..RIGHT PARENTHESIS\n";
+        let row: DerivedBinaryProperties = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x0028);
+        assert_eq!(row.codepoints.end, 0x0029);
+        assert_eq!(row.property, "Bidi_M");
+    }
+
+    #[test]
+    fn display() {
+        let row: DerivedGeneralCategory =
+            "0041..005A    ; Lu # L&  [26]\n".parse().unwrap();
+        assert_eq!(row.to_string(), "0041..005A ; Lu");
+        let row: DerivedBinaryProperties =
+            "0009          ; Bidi_Mirrored\n".parse().unwrap();
+        assert_eq!(row.to_string(), "0009 ; Bidi_Mirrored");
+        let row: DerivedNumericValues =
+            "0F33          ; -0.5 ; ; -1/2 # No\n".parse().unwrap();
+        assert_eq!(row.to_string(), "0F33 ; -0.5 ; ; -1/2");
+    }
+}
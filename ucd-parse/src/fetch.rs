@@ -0,0 +1,151 @@
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+
+use ucd_util::UnicodeVersion;
+use ureq;
+
+use directory::UcdDirectory;
+use error::Error;
+use reader::ARCHIVES;
+
+/// The URL from which releases of the UCD are downloaded by `fetch`.
+pub const UNICODE_URL: &'static str = "https://www.unicode.org/Public";
+
+/// Download a release of the UCD into the given cache directory.
+///
+/// The `UCD.zip` and `Unihan.zip` archives for the given version are saved
+/// in a subdirectory of the cache directory named after the version, e.g.,
+/// `15.1.0`, which is then opened as a UCD directory. Files are read directly
+/// from the archives, so they aren't extracted.
+///
+/// Archives that already exist in the cache directory aren't downloaded
+/// again.
+pub fn fetch<P: AsRef<Path>>(
+    version: UnicodeVersion,
+    cache_dir: P,
+) -> Result<UcdDirectory, Error> {
+    fetch_from(UNICODE_URL, version, cache_dir)
+}
+
+/// Download a release of the UCD from the given URL into the given cache
+/// directory.
+///
+/// This is like `fetch`, except the archives are downloaded from a mirror of
+/// `https://www.unicode.org/Public`. For example, `UCD.zip` for Unicode
+/// 15.1.0 is downloaded from `{base_url}/15.1.0/ucd/UCD.zip`.
+pub fn fetch_from<P: AsRef<Path>>(
+    base_url: &str,
+    version: UnicodeVersion,
+    cache_dir: P,
+) -> Result<UcdDirectory, Error> {
+    let dir = cache_dir.as_ref().join(version.to_string());
+    fs::create_dir_all(&dir)?;
+    for name in ARCHIVES {
+        let path = dir.join(name);
+        if path.is_file() {
+            continue;
+        }
+        let url = format!(
+            "{}/{}/ucd/{}", base_url.trim_right_matches('/'), version, name);
+        download(&url, &path)?;
+    }
+    UcdDirectory::open(dir)
+}
+
+/// Download the given URL to the given path.
+///
+/// The download is written to a temporary file first, so that an interrupted
+/// download never leaves a partial archive at the given path.
+fn download(url: &str, path: &Path) -> Result<(), Error> {
+    let resp = ureq::get(url).call().map_err(|err| {
+        let msg = format!("failed to download {}", err);
+        io::Error::new(io::ErrorKind::Other, msg)
+    })?;
+    let tmp = path.with_extension("part");
+    {
+        let mut file = File::create(&tmp)?;
+        io::copy(&mut resp.into_reader(), &mut file)?;
+    }
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Cursor, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use ucd_util::UnicodeVersion;
+    use zip::ZipWriter;
+    use zip::write::FileOptions;
+
+    use temp_dir::TempDir;
+
+    use super::fetch_from;
+
+    fn archive(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut zip = ZipWriter::new(Cursor::new(vec![]));
+        for &(name, data) in files {
+            zip.start_file(name, FileOptions::default()).unwrap();
+            zip.write_all(data.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn fetch_and_cache() {
+        let ucd = archive(&[(
+            "UnicodeData.txt",
+            "0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;\n",
+        )]);
+        let unihan = archive(&[]);
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let mut paths = vec![];
+            for _ in 0..2 {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut rdr = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                rdr.read_line(&mut line).unwrap();
+                let path = line.split(' ').nth(1).unwrap().to_string();
+                while line != "\r\n" {
+                    line.clear();
+                    rdr.read_line(&mut line).unwrap();
+                }
+                let body = if path.ends_with("UCD.zip") {
+                    &ucd
+                } else {
+                    &unihan
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\
+                     Connection: close\r\n\r\n",
+                    body.len()).unwrap();
+                stream.write_all(body).unwrap();
+                paths.push(path);
+            }
+            paths
+        });
+
+        let tmp = TempDir::new("ucd-parse-fetch");
+        let cache_dir = tmp.path();
+        let version = UnicodeVersion(15, 1, 0);
+        let dir = fetch_from(&base_url, version, cache_dir).unwrap();
+        assert_eq!(dir.path(), &*cache_dir.join("15.1.0"));
+        assert_eq!(dir.unicode_data().unwrap().count(), 1);
+        assert_eq!(server.join().unwrap(), vec![
+            "/15.1.0/ucd/UCD.zip".to_string(),
+            "/15.1.0/ucd/Unihan.zip".to_string(),
+        ]);
+
+        // The server has stopped, so this only succeeds if nothing is
+        // downloaded again.
+        let dir = fetch_from(&base_url, version, cache_dir).unwrap();
+        assert_eq!(dir.unicode_data().unwrap().count(), 1);
+    }
+}
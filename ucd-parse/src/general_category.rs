@@ -0,0 +1,144 @@
+property_value_enum! {
+    /// The `General_Category` property of a codepoint.
+    ///
+    /// Records such as `UnicodeData` store the general category as a raw
+    /// string, so that new categories can be read by older versions of this
+    /// crate. Use `str::parse` to convert a raw value to this type.
+    ///
+    /// Parsing accepts both the abbreviated (e.g., `Lu`) and the long (e.g.,
+    /// `Uppercase_Letter`) names of a category. Its `Display` impl writes the
+    /// abbreviated name, as used in `UnicodeData.txt`.
+    pub enum GeneralCategory ("general category") {
+        UppercaseLetter, "Lu", "Uppercase_Letter";
+        LowercaseLetter, "Ll", "Lowercase_Letter";
+        TitlecaseLetter, "Lt", "Titlecase_Letter";
+        ModifierLetter, "Lm", "Modifier_Letter";
+        OtherLetter, "Lo", "Other_Letter";
+        NonspacingMark, "Mn", "Nonspacing_Mark";
+        SpacingMark, "Mc", "Spacing_Mark";
+        EnclosingMark, "Me", "Enclosing_Mark";
+        DecimalNumber, "Nd", "Decimal_Number" | "digit";
+        LetterNumber, "Nl", "Letter_Number";
+        OtherNumber, "No", "Other_Number";
+        ConnectorPunctuation, "Pc", "Connector_Punctuation";
+        DashPunctuation, "Pd", "Dash_Punctuation";
+        OpenPunctuation, "Ps", "Open_Punctuation";
+        ClosePunctuation, "Pe", "Close_Punctuation";
+        InitialPunctuation, "Pi", "Initial_Punctuation";
+        FinalPunctuation, "Pf", "Final_Punctuation";
+        OtherPunctuation, "Po", "Other_Punctuation";
+        MathSymbol, "Sm", "Math_Symbol";
+        CurrencySymbol, "Sc", "Currency_Symbol";
+        ModifierSymbol, "Sk", "Modifier_Symbol";
+        OtherSymbol, "So", "Other_Symbol";
+        SpaceSeparator, "Zs", "Space_Separator";
+        LineSeparator, "Zl", "Line_Separator";
+        ParagraphSeparator, "Zp", "Paragraph_Separator";
+        Control, "Cc", "Control" | "cntrl";
+        Format, "Cf", "Format";
+        Surrogate, "Cs", "Surrogate";
+        PrivateUse, "Co", "Private_Use";
+        Unassigned, "Cn", "Unassigned";
+    }
+}
+
+impl GeneralCategory {
+    /// Returns true if this category is in the `Cased_Letter` (`LC`) group,
+    /// i.e., `Lu`, `Ll` or `Lt`.
+    pub fn is_cased_letter(&self) -> bool {
+        use self::GeneralCategory::*;
+        match *self {
+            UppercaseLetter | LowercaseLetter | TitlecaseLetter => true,
+            _ => false,
+        }
+    }
+
+    /// Returns true if this category is in the `Letter` (`L`) group.
+    pub fn is_letter(&self) -> bool {
+        self.abbreviation().starts_with('L')
+    }
+
+    /// Returns true if this category is in the `Mark` (`M`) group.
+    pub fn is_mark(&self) -> bool {
+        self.abbreviation().starts_with('M')
+    }
+
+    /// Returns true if this category is in the `Number` (`N`) group.
+    pub fn is_number(&self) -> bool {
+        self.abbreviation().starts_with('N')
+    }
+
+    /// Returns true if this category is in the `Punctuation` (`P`) group.
+    pub fn is_punctuation(&self) -> bool {
+        self.abbreviation().starts_with('P')
+    }
+
+    /// Returns true if this category is in the `Symbol` (`S`) group.
+    pub fn is_symbol(&self) -> bool {
+        self.abbreviation().starts_with('S')
+    }
+
+    /// Returns true if this category is in the `Separator` (`Z`) group.
+    pub fn is_separator(&self) -> bool {
+        self.abbreviation().starts_with('Z')
+    }
+
+    /// Returns true if this category is in the `Other` (`C`) group.
+    pub fn is_other(&self) -> bool {
+        self.abbreviation().starts_with('C')
+    }
+}
+
+impl Default for GeneralCategory {
+    fn default() -> GeneralCategory {
+        // Codepoints not listed in UnicodeData.txt are unassigned.
+        GeneralCategory::Unassigned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GeneralCategory;
+
+    #[test]
+    fn parse() {
+        let gc: GeneralCategory = "Lu".parse().unwrap();
+        assert_eq!(gc, GeneralCategory::UppercaseLetter);
+        let gc: GeneralCategory = "Uppercase_Letter".parse().unwrap();
+        assert_eq!(gc, GeneralCategory::UppercaseLetter);
+        let gc: GeneralCategory = "cntrl".parse().unwrap();
+        assert_eq!(gc, GeneralCategory::Control);
+
+        assert!("L".parse::<GeneralCategory>().is_err());
+        assert!("lu".parse::<GeneralCategory>().is_err());
+    }
+
+    #[test]
+    fn roundtrip() {
+        let gc = GeneralCategory::ParagraphSeparator;
+        assert_eq!(gc.to_string(), "Zp");
+        assert_eq!(gc.long_name(), "Paragraph_Separator");
+        assert_eq!(gc.to_string().parse::<GeneralCategory>().unwrap(), gc);
+        assert_eq!(gc.long_name().parse::<GeneralCategory>().unwrap(), gc);
+    }
+
+    #[test]
+    fn groups() {
+        let gc = GeneralCategory::TitlecaseLetter;
+        assert!(gc.is_letter());
+        assert!(gc.is_cased_letter());
+        assert!(!gc.is_mark());
+
+        let gc = GeneralCategory::ModifierLetter;
+        assert!(gc.is_letter());
+        assert!(!gc.is_cased_letter());
+
+        assert!(GeneralCategory::SpacingMark.is_mark());
+        assert!(GeneralCategory::LetterNumber.is_number());
+        assert!(GeneralCategory::DashPunctuation.is_punctuation());
+        assert!(GeneralCategory::CurrencySymbol.is_symbol());
+        assert!(GeneralCategory::LineSeparator.is_separator());
+        assert!(GeneralCategory::Unassigned.is_other());
+        assert!(!GeneralCategory::Unassigned.is_letter());
+    }
+}
@@ -0,0 +1,148 @@
+use std::borrow::Cow;
+use std::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
+use std::str::FromStr;
+
+use common::{UcdFile, UcdFileByCodepoint, Codepoint, is_hex_codepoint};
+use error::Error;
+
+/// Parse a single tab separated `U+XXXX <field> <value>` line, as used by
+/// `TangutSources.txt` and `NushuSources.txt`.
+fn parse_source_line(line: &str) -> Result<(Codepoint, &str, &str), Error> {
+    let is_field_name = |s: &str| {
+        s.len() > 1 && s.starts_with('k') && s.bytes().all(|b| {
+            b == b'_'
+            || (b'0' <= b && b <= b'9')
+            || (b'A' <= b && b <= b'Z')
+            || (b'a' <= b && b <= b'z')
+        })
+    };
+
+    let fields: Vec<&str> = line.trim().split('\t').collect();
+    if fields.len() != 3
+        || !fields[0].starts_with("U+")
+        || !is_hex_codepoint(&fields[0][2..])
+        || !is_field_name(fields[1])
+    {
+        return err!("invalid ideographic source line");
+    }
+    Ok((fields[0].parse()?, fields[1], fields[2]))
+}
+
+macro_rules! source_record {
+    ($name:ident, $path:expr, $doc:expr) => {
+        #[doc = $doc]
+        ///
+        /// Like the Unihan database, each row assigns a value to a single
+        /// named field of a codepoint, so there are typically several rows
+        /// for each codepoint.
+        #[derive(Clone, Debug, Default, Eq, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        pub struct $name<'a> {
+            /// The codepoint corresponding to this row.
+            pub codepoint: Codepoint,
+            /// The name of the field, e.g., `kRSTUnicode`.
+            pub field: Cow<'a, str>,
+            /// The value of the field.
+            pub value: Cow<'a, str>,
+        }
+
+        impl UcdFile for $name<'static> {
+            #[cfg(feature = "std")]
+            fn relative_file_path() -> &'static Path {
+                Path::new($path)
+            }
+        }
+
+        impl UcdFileByCodepoint for $name<'static> {
+            fn codepoint(&self) -> Codepoint {
+                self.codepoint
+            }
+        }
+
+        impl<'a> $name<'a> {
+            /// Convert this record into an owned value such that it no
+            /// longer borrows from the original line that it was parsed
+            /// from.
+            pub fn into_owned(self) -> $name<'static> {
+                $name {
+                    codepoint: self.codepoint,
+                    field: Cow::Owned(self.field.into_owned()),
+                    value: Cow::Owned(self.value.into_owned()),
+                }
+            }
+
+            /// Parse a single line.
+            pub fn parse_line(line: &'a str) -> Result<$name<'a>, Error> {
+                let (codepoint, field, value) = parse_source_line(line)?;
+                Ok($name {
+                    codepoint: codepoint,
+                    field: Cow::Borrowed(field),
+                    value: Cow::Borrowed(value),
+                })
+            }
+        }
+
+        impl FromStr for $name<'static> {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<$name<'static>, Error> {
+                $name::parse_line(s).map(|x| x.into_owned())
+            }
+        }
+
+        impl<'a> fmt::Display for $name<'a> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(
+                    f, "U+{}\t{}\t{}",
+                    self.codepoint, self.field, self.value)
+            }
+        }
+    }
+}
+
+source_record!(
+    TangutSource, "TangutSources.txt",
+    "A single row in the `TangutSources.txt` file.");
+source_record!(
+    NushuSource, "NushuSources.txt",
+    "A single row in the `NushuSources.txt` file.");
+
+#[cfg(test)]
+mod tests {
+    use super::{NushuSource, TangutSource};
+
+    #[test]
+    fn parse_tangut() {
+        let line = "U+17000\tkTGT_MergedSrc\tL2008-0008\n";
+        let row: TangutSource = line.parse().unwrap();
+        assert_eq!(row.codepoint, 0x17000);
+        assert_eq!(row.field, "kTGT_MergedSrc");
+        assert_eq!(row.value, "L2008-0008");
+    }
+
+    #[test]
+    fn parse_nushu() {
+        let line = "U+1B170\tkReading\tzuo\r\n";
+        let row: NushuSource = line.parse().unwrap();
+        assert_eq!(row.codepoint, 0x1B170);
+        assert_eq!(row.field, "kReading");
+        assert_eq!(row.value, "zuo");
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!("17000\tkRSTUnicode\t1.1".parse::<TangutSource>().is_err());
+        assert!("U+17000 kRSTUnicode 1.1".parse::<TangutSource>().is_err());
+    }
+
+    #[test]
+    fn display() {
+        let line = "U+17000\tkTGT_MergedSrc\tL2008-0008";
+        let row: TangutSource = line.parse().unwrap();
+        assert_eq!(row.to_string(), line);
+    }
+}
@@ -1,16 +1,17 @@
 use std::borrow::Cow;
+use std::fmt;
+#[cfg(feature = "std")]
 use std::path::Path;
 use std::str::FromStr;
 
-use regex::Regex;
-
-use common::{UcdFile, UcdFileByCodepoint, Codepoint};
+use common::{UcdFile, UcdFileByCodepoint, Codepoint, split_fields};
 use error::Error;
 
 /// A single row in the `Jamo.txt` file.
 ///
 /// The `Jamo.txt` file defines the `Jamo_Short_Name` property.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct JamoShortName<'a> {
     /// The codepoint corresponding to this row.
     pub codepoint: Codepoint,
@@ -20,6 +21,7 @@ pub struct JamoShortName<'a> {
 }
 
 impl UcdFile for JamoShortName<'static> {
+    #[cfg(feature = "std")]
     fn relative_file_path() -> &'static Path {
         Path::new("Jamo.txt")
     }
@@ -43,24 +45,15 @@ impl<'a> JamoShortName<'a> {
 
     /// Parse a single line.
     pub fn parse_line(line: &'a str) -> Result<JamoShortName<'a>, Error> {
-        lazy_static! {
-            static ref PARTS: Regex = Regex::new(
-                r"(?x)
-                ^
-                (?P<codepoint>[A-Z0-9]+);
-                \s*
-                (?P<name>[A-Z]*)
-                "
-            ).unwrap();
-        };
-
-        let caps = match PARTS.captures(line.trim()) {
-            Some(caps) => caps,
-            None => return err!("invalid Jamo_Short_name line"),
-        };
+        let fields = split_fields(line);
+        if fields.len() != 2
+            || !fields[1].bytes().all(|b| b'A' <= b && b <= b'Z')
+        {
+            return err!("invalid Jamo_Short_name line");
+        }
         Ok(JamoShortName {
-            codepoint: caps["codepoint"].parse()?,
-            name: Cow::Borrowed(caps.name("name").unwrap().as_str()),
+            codepoint: fields[0].parse()?,
+            name: Cow::Borrowed(fields[1]),
         })
     }
 }
@@ -73,6 +66,12 @@ impl FromStr for JamoShortName<'static> {
     }
 }
 
+impl<'a> fmt::Display for JamoShortName<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}; {}", self.codepoint, self.name)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::JamoShortName;
@@ -92,4 +91,12 @@ mod tests {
         assert_eq!(row.codepoint, 0x110B);
         assert_eq!(row.name, "");
     }
+
+    #[test]
+    fn display() {
+        let row: JamoShortName = "1164; YAE # HANGUL\n".parse().unwrap();
+        assert_eq!(row.to_string(), "1164; YAE");
+        let row: JamoShortName = "110B;     # HANGUL\n".parse().unwrap();
+        assert_eq!(row.to_string().parse::<JamoShortName>().unwrap(), row);
+    }
 }
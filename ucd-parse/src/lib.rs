@@ -1,28 +1,147 @@
 /*!
 A library for parsing the Unicode character database.
+
+# Crate features
+
+* `std` - Enabled by default. Add everything that reads files, such as
+  `parse`, `UcdFile::from_dir` and `UcdReader`, along with the `path` of
+  each `Error`. The `checksum`, `fetch` and `parallel` features enable this
+  feature, and the `zip` feature has no effect without it.
+* `zip` - Read files from `UCD.zip` and `Unihan.zip` archives when they are
+  missing from a UCD directory. See `UcdReader`.
+* `serde` - Implement `Serialize` and `Deserialize` for every record type.
+* `parallel` - Add `parse_parallel` and `parse_by_codepoint_parallel`, which
+  parse files using all available CPUs.
+* `checksum` - Add `checksum` and `verify`, which compute and check SHA-256
+  checksums of UCD files.
+* `fetch` - Add `fetch`, which downloads a release of the UCD from
+  unicode.org. This enables the `zip` feature.
+
+Without the `std` feature, this crate only requires `core` and `alloc`.
+`Codepoint`, `CodepointRange`, every record type and their `parse_line`
+methods are still available, so lines of the UCD can be parsed in
+`no_std` environments such as embedded code generators.
 */
 
 #![deny(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(not(feature = "std"))]
+#[macro_use]
+extern crate alloc;
+#[cfg(feature = "std")]
 #[macro_use]
 extern crate lazy_static;
+#[cfg(feature = "parallel")]
+extern crate rayon;
+#[cfg(feature = "std")]
 extern crate regex;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+#[cfg(feature = "checksum")]
+extern crate sha2;
+#[cfg(feature = "std")]
+extern crate ucd_util;
+#[cfg(feature = "fetch")]
+extern crate ureq;
+#[cfg(feature = "zip")]
+extern crate zip;
 
 pub use common::{
-    UcdFile, Codepoint, UcdLineParser,
-    parse, parse_by_codepoint, parse_many_by_codepoint,
+    UcdFile, UcdFileByCodepoint, UcdFileByCodepointRange,
+    Codepoint, CodepointRange, CodepointRangeIter,
+    PropertyFile, PropertyValueRow, Line,
 };
+#[cfg(feature = "std")]
+pub use common::{
+    UcdFileWrite, UcdLineParser, UcdLines,
+    parse, parse_by_codepoint, parse_by_codepoint_range,
+    parse_many_by_codepoint,
+};
+#[cfg(feature = "checksum")]
+pub use checksum::{Sha256, checksum, verify};
 pub use error::{Error, ErrorKind};
+#[cfg(feature = "fetch")]
+pub use fetch::{UNICODE_URL, fetch, fetch_from};
+#[cfg(feature = "parallel")]
+pub use parallel::{parse_by_codepoint_parallel, parse_parallel};
+#[cfg(feature = "std")]
+pub use reader::UcdReader;
+#[cfg(feature = "std")]
+pub use ucd_util::UnicodeVersion;
 
+pub use arabic_shaping::{ArabicShaping, JoiningType};
+pub use bidi_brackets::{BidiBracket, BidiPairedBracketType};
+pub use bidi_class::BidiClass;
+pub use bidi_mirroring::BidiMirroring;
+#[cfg(feature = "std")]
+pub use builder::ParserBuilder;
+pub use break_test::{
+    GraphemeBreakTest, LineBreakTest, SentenceBreakTest, WordBreakTest,
+};
+pub use case_folding::{CaseFold, CaseStatus};
+pub use collation_test::CollationTest;
+pub use core_properties::CoreProperty;
+#[cfg(feature = "std")]
+pub use directory::UcdDirectory;
+pub use east_asian_width::{EastAsianWidth, EastAsianWidthFile};
+pub use emoji_data::EmojiProperty;
+pub use extracted::{
+    DerivedBidiClass, DerivedBinaryProperties, DerivedDecompositionType,
+    DerivedGeneralCategory, DerivedJoiningGroup, DerivedJoiningType,
+    DerivedNumericType, DerivedNumericValues,
+};
+pub use general_category::GeneralCategory;
+pub use ideographic_sources::{NushuSource, TangutSource};
 pub use jamo_short_name::JamoShortName;
+pub use line_break::{LineBreak, LineBreakFile};
+pub use missing::MissingValue;
+#[cfg(feature = "std")]
+pub use missing::parse_missing_lines;
 pub use name_aliases::{NameAlias, NameAliasLabel};
+pub use normalization_corrections::NormalizationCorrection;
+pub use prop_list::PropList;
 pub use property_aliases::PropertyAlias;
 pub use property_value_aliases::PropertyValueAlias;
+#[cfg(feature = "std")]
+pub use row::{UcdRow, is_supported, parse_any, parse_any_file};
+pub use script::{Script, ScriptExtensions, Scripts, ScriptsFile};
+pub use sentence_break::SentenceBreak;
+pub use special_casing::SpecialCaseMapping;
 pub use unicode_data::{
     UnicodeData, UnicodeDataNumeric,
     UnicodeDataDecomposition, UnicodeDataDecompositionTag,
-    UnicodeDataExpander,
+    UnicodeDataCompactor, UnicodeDataExpander, UnicodeDataIndex,
+    UnicodeDataRanges,
 };
+pub use usource_data::USourceData;
+#[cfg(feature = "std")]
+pub use validate::{Diagnostic, DiagnosticKind, Report, Validator};
+pub use word_break::WordBreak;
+#[cfg(feature = "std")]
+pub use xml::{UcdXml, UcdXmlChar, UcdXmlCharKind, UcdXmlChars};
+
+/// The parts of `std` that are used by this crate, when it is built without
+/// the standard library.
+///
+/// Every module refers to these as `std::...`, so that they compile either
+/// way. Modules that use items from the standard prelude that aren't in
+/// `core`, such as `String` and `Vec`, also import `std::prelude::*` when
+/// the `std` feature is disabled.
+#[cfg(not(feature = "std"))]
+mod std {
+    pub use core::{char, fmt, iter, marker, ops, str};
+    pub use alloc::borrow;
+
+    pub mod prelude {
+        pub use alloc::boxed::Box;
+        pub use alloc::string::{String, ToString};
+        pub use alloc::vec::Vec;
+    }
+}
 
 macro_rules! err {
     ($($tt:tt)*) => {
@@ -30,11 +149,265 @@ macro_rules! err {
     }
 }
 
+/// Define a record type for a file in which each line assigns a single
+/// property value to a range of codepoints, e.g., `0041..005A ; ALetter`.
+macro_rules! range_value_record {
+    ($name:ident, $path:expr, $doc:expr) => {
+        #[doc = $doc]
+        ///
+        /// Each row assigns a property value to a range of codepoints.
+        #[derive(Clone, Debug, Default, Eq, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        pub struct $name<'a> {
+            /// The codepoints corresponding to this row.
+            pub codepoints: ::common::CodepointRange,
+            /// The property value assigned to the codepoints in this range.
+            pub value: ::std::borrow::Cow<'a, str>,
+        }
+
+        impl ::common::UcdFile for $name<'static> {
+            #[cfg(feature = "std")]
+            fn relative_file_path() -> &'static ::std::path::Path {
+                ::std::path::Path::new($path)
+            }
+        }
+
+        impl ::common::UcdFileByCodepointRange for $name<'static> {
+            fn codepoints(&self) -> ::common::CodepointRange {
+                self.codepoints
+            }
+        }
+
+        impl<'a> $name<'a> {
+            /// Convert this record into an owned value such that it no
+            /// longer borrows from the original line that it was parsed
+            /// from.
+            pub fn into_owned(self) -> $name<'static> {
+                $name {
+                    codepoints: self.codepoints,
+                    value: ::std::borrow::Cow::Owned(self.value.into_owned()),
+                }
+            }
+
+            /// Parse a single line.
+            pub fn parse_line(
+                line: &'a str,
+            ) -> Result<$name<'a>, ::error::Error> {
+                let (codepoints, value) = ::common::parse_range_value(line)?;
+                Ok($name {
+                    codepoints: codepoints,
+                    value: ::std::borrow::Cow::Borrowed(value),
+                })
+            }
+        }
+
+        impl ::std::str::FromStr for $name<'static> {
+            type Err = ::error::Error;
+
+            fn from_str(s: &str) -> Result<$name<'static>, ::error::Error> {
+                $name::parse_line(s).map(|x| x.into_owned())
+            }
+        }
+
+        impl<'a> ::std::fmt::Display for $name<'a> {
+            fn fmt(
+                &self,
+                f: &mut ::std::fmt::Formatter,
+            ) -> ::std::fmt::Result {
+                write!(f, "{} ; {}", self.codepoints, self.value)
+            }
+        }
+    }
+}
+
+/// Define a record type for a file in which each line states that a range of
+/// codepoints has a binary property, e.g., `0009..000D ; White_Space`.
+///
+/// These files use the same format as files defined by `range_value_record`,
+/// but the second field names a property rather than a value.
+macro_rules! binary_property_record {
+    ($name:ident, $path:expr, $doc:expr) => {
+        #[doc = $doc]
+        ///
+        /// Each row states that a range of codepoints has a binary property.
+        /// There are typically many rows for each property.
+        #[derive(Clone, Debug, Default, Eq, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        pub struct $name<'a> {
+            /// The codepoints corresponding to this row.
+            pub codepoints: ::common::CodepointRange,
+            /// The name of the property that the codepoints in this range
+            /// have.
+            pub property: ::std::borrow::Cow<'a, str>,
+        }
+
+        impl ::common::UcdFile for $name<'static> {
+            #[cfg(feature = "std")]
+            fn relative_file_path() -> &'static ::std::path::Path {
+                ::std::path::Path::new($path)
+            }
+        }
+
+        impl ::common::UcdFileByCodepointRange for $name<'static> {
+            fn codepoints(&self) -> ::common::CodepointRange {
+                self.codepoints
+            }
+        }
+
+        impl<'a> $name<'a> {
+            /// Convert this record into an owned value such that it no
+            /// longer borrows from the original line that it was parsed
+            /// from.
+            pub fn into_owned(self) -> $name<'static> {
+                $name {
+                    codepoints: self.codepoints,
+                    property: ::std::borrow::Cow::Owned(
+                        self.property.into_owned()),
+                }
+            }
+
+            /// Parse a single line.
+            pub fn parse_line(
+                line: &'a str,
+            ) -> Result<$name<'a>, ::error::Error> {
+                let (codepoints, property) =
+                    ::common::parse_range_value(line)?;
+                Ok($name {
+                    codepoints: codepoints,
+                    property: ::std::borrow::Cow::Borrowed(property),
+                })
+            }
+        }
+
+        impl ::std::str::FromStr for $name<'static> {
+            type Err = ::error::Error;
+
+            fn from_str(s: &str) -> Result<$name<'static>, ::error::Error> {
+                $name::parse_line(s).map(|x| x.into_owned())
+            }
+        }
+
+        impl<'a> ::std::fmt::Display for $name<'a> {
+            fn fmt(
+                &self,
+                f: &mut ::std::fmt::Formatter,
+            ) -> ::std::fmt::Result {
+                write!(f, "{} ; {}", self.codepoints, self.property)
+            }
+        }
+    }
+}
+
+/// Define an enum of the values of a property, along with conversions to and
+/// from their abbreviated and long names.
+///
+/// Each value is given by a variant name, its abbreviated name, its long
+/// name and, optionally, any other aliases accepted when parsing.
+macro_rules! property_value_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident ($what:expr) {
+            $($variant:ident, $abbrev:tt, $long:tt $(| $alias:tt)*;)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        pub enum $name {
+            $(
+                #[doc = $long]
+                $variant,
+            )*
+        }
+
+        impl $name {
+            /// Return the abbreviated name of this value.
+            pub fn abbreviation(&self) -> &'static str {
+                match *self {
+                    $($name::$variant => $abbrev,)*
+                }
+            }
+
+            /// Return the long name of this value.
+            pub fn long_name(&self) -> &'static str {
+                match *self {
+                    $($name::$variant => $long,)*
+                }
+            }
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = ::error::Error;
+
+            fn from_str(s: &str) -> Result<$name, ::error::Error> {
+                // Some values, e.g., the `Thai` script, have the same
+                // abbreviated and long names.
+                #[allow(unreachable_patterns)]
+                match s {
+                    $($abbrev | $long $(| $alias)* => Ok($name::$variant),)*
+                    unknown => err!("unknown {}: '{}'", $what, unknown),
+                }
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(
+                &self,
+                f: &mut ::std::fmt::Formatter,
+            ) -> ::std::fmt::Result {
+                write!(f, "{}", self.abbreviation())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "checksum")]
+mod checksum;
 mod common;
 mod error;
+#[cfg(feature = "fetch")]
+mod fetch;
+#[cfg(feature = "parallel")]
+mod parallel;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(all(test, feature = "std"))]
+mod temp_dir;
 
+mod arabic_shaping;
+mod bidi_brackets;
+mod bidi_class;
+mod bidi_mirroring;
+mod break_test;
+#[cfg(feature = "std")]
+mod builder;
+mod case_folding;
+mod collation_test;
+mod core_properties;
+#[cfg(feature = "std")]
+mod directory;
+mod east_asian_width;
+mod emoji_data;
+mod extracted;
+mod general_category;
+mod ideographic_sources;
 mod jamo_short_name;
+mod line_break;
+mod missing;
 mod name_aliases;
+mod normalization_corrections;
+mod prop_list;
 mod property_aliases;
 mod property_value_aliases;
+#[cfg(feature = "std")]
+mod row;
+mod script;
+mod sentence_break;
+mod special_casing;
 mod unicode_data;
+mod usource_data;
+#[cfg(feature = "std")]
+mod validate;
+mod word_break;
+#[cfg(feature = "std")]
+mod xml;
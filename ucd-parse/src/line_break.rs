@@ -0,0 +1,36 @@
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
+
+use common::{PropertyFile, PropertyValueRow};
+
+/// The `LineBreak.txt` file, which defines the `Line_Break` property.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct LineBreakFile;
+
+impl PropertyFile for LineBreakFile {
+    #[cfg(feature = "std")]
+    fn relative_file_path() -> &'static Path {
+        Path::new("LineBreak.txt")
+    }
+}
+
+/// A single row in the `LineBreak.txt` file.
+///
+/// The value is the abbreviated `Line_Break` property value, e.g., `AL`.
+pub type LineBreak = PropertyValueRow<LineBreakFile, String>;
+
+#[cfg(test)]
+mod tests {
+    use super::LineBreak;
+
+    #[test]
+    fn parse() {
+        let line = "0030..0039;NU     # Nd    [10] DIGIT ZERO..DIGIT NINE\n";
+        let row: LineBreak = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x0030);
+        assert_eq!(row.codepoints.end, 0x0039);
+        assert_eq!(row.value, "NU");
+    }
+}
@@ -0,0 +1,203 @@
+use std::borrow::Cow;
+use std::fmt;
+#[cfg(feature = "std")]
+use std::io::{self, BufRead};
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
+use std::str::FromStr;
+
+#[cfg(feature = "std")]
+use common::UcdFile;
+use common::{CodepointRange, is_hex_codepoint_range};
+use error::Error;
+#[cfg(feature = "std")]
+use error::{error_set_line, error_set_path};
+#[cfg(feature = "std")]
+use reader::UcdReader;
+
+/// A default property value given by a `# @missing:` line.
+///
+/// Many UCD files only list codepoints whose property value differs from a
+/// default. The default is given in the file's header by one or more
+/// comment lines such as `# @missing: 0000..10FFFF; Unknown`. Since these are
+/// comments, they are skipped when parsing the records of a file. Use
+/// `parse_missing_lines` to read them.
+///
+/// Files that define more than one property name the property that the
+/// default applies to, e.g., `# @missing: 0000..10FFFF; NFD_QC; Yes`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MissingValue<'a> {
+    /// The codepoints that this default applies to.
+    pub codepoints: CodepointRange,
+    /// The property that this default applies to, if the file names it.
+    pub property: Option<Cow<'a, str>>,
+    /// The default property value, e.g., `Unknown` or `<none>`.
+    pub value: Cow<'a, str>,
+}
+
+impl<'a> MissingValue<'a> {
+    /// Convert this record into an owned value such that it no longer
+    /// borrows from the original line that it was parsed from.
+    pub fn into_owned(self) -> MissingValue<'static> {
+        MissingValue {
+            codepoints: self.codepoints,
+            property: self.property.map(|p| Cow::Owned(p.into_owned())),
+            value: Cow::Owned(self.value.into_owned()),
+        }
+    }
+
+    /// Parse a single `# @missing:` line.
+    pub fn parse_line(line: &'a str) -> Result<MissingValue<'a>, Error> {
+        let line = line.trim();
+        let rest = if line.starts_with('#') { line[1..].trim() } else { "" };
+        if !rest.starts_with("@missing:") {
+            return err!("invalid @missing line");
+        }
+        let fields: Vec<&str> = rest["@missing:".len()..]
+            .split(';')
+            .map(|field| field.trim())
+            .collect();
+        let (codepoints, property, value) = match fields.len() {
+            2 => (fields[0], None, fields[1]),
+            3 => (fields[0], Some(Cow::Borrowed(fields[1])), fields[2]),
+            _ => return err!("invalid @missing line"),
+        };
+        if !is_hex_codepoint_range(codepoints) {
+            return err!("invalid @missing line");
+        }
+        if value.is_empty() {
+            return err!("missing default value in @missing line");
+        }
+        Ok(MissingValue {
+            codepoints: codepoints.parse()?,
+            property: property,
+            value: Cow::Borrowed(value),
+        })
+    }
+}
+
+impl FromStr for MissingValue<'static> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<MissingValue<'static>, Error> {
+        MissingValue::parse_line(s).map(|x| x.into_owned())
+    }
+}
+
+impl<'a> fmt::Display for MissingValue<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "# @missing: {}; ", self.codepoints)?;
+        if let Some(ref property) = self.property {
+            write!(f, "{}; ", property)?;
+        }
+        write!(f, "{}", self.value)
+    }
+}
+
+/// Parse all of the `# @missing:` lines in a particular file in the UCD.
+///
+/// The given directory should be the directory to the UCD. The defaults are
+/// returned in the order in which they appear. When more than one default
+/// applies to a codepoint, the last one takes precedence.
+#[cfg(feature = "std")]
+pub fn parse_missing_lines<P, D>(
+    ucd_dir: P,
+) -> Result<Vec<MissingValue<'static>>, Error>
+where P: AsRef<Path>, D: UcdFile
+{
+    let path = D::file_path(&ucd_dir);
+    let rdr = UcdReader::open(ucd_dir, D::relative_file_path())?;
+    missing_lines(io::BufReader::new(rdr)).map_err(|mut err| {
+        error_set_path(&mut err, Some(path));
+        err
+    })
+}
+
+/// Parse all of the `# @missing:` lines from the given reader.
+#[cfg(feature = "std")]
+fn missing_lines<R: BufRead>(
+    rdr: R,
+) -> Result<Vec<MissingValue<'static>>, Error> {
+    let mut missing = vec![];
+    for (i, line) in rdr.lines().enumerate() {
+        let line = line?;
+        if !line.starts_with("# @missing:") {
+            continue;
+        }
+        match line.parse() {
+            Ok(x) => missing.push(x),
+            Err(mut err) => {
+                error_set_line(&mut err, Some(i as u64 + 1));
+                return Err(err);
+            }
+        }
+    }
+    Ok(missing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MissingValue, missing_lines};
+
+    #[test]
+    fn parse_value() {
+        let line = "# @missing: 0000..10FFFF; Unknown\n";
+        let row: MissingValue = line.parse().unwrap();
+        assert_eq!(row.codepoints, "0000..10FFFF".parse().unwrap());
+        assert_eq!(row.property, None);
+        assert_eq!(row.value, "Unknown");
+    }
+
+    #[test]
+    fn parse_property_value() {
+        let line = "# @missing: 0000..10FFFF; Bidi_Paired_Bracket; <none>\n";
+        let row: MissingValue = line.parse().unwrap();
+        assert_eq!(row.property.unwrap(), "Bidi_Paired_Bracket");
+        assert_eq!(row.value, "<none>");
+
+        let line = "# @missing: 0000..10FFFF; NFKC_CF; <code point>";
+        let row: MissingValue = line.parse().unwrap();
+        assert_eq!(row.property.unwrap(), "NFKC_CF");
+        assert_eq!(row.value, "<code point>");
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!("# @missing: 0000..10FFFF;".parse::<MissingValue>().is_err());
+        assert!("0000..10FFFF; Unknown".parse::<MissingValue>().is_err());
+    }
+
+    #[test]
+    fn parse_file() {
+        let data = "\
+# Scripts-10.0.0.txt
+# @missing: 0000..10FFFF; Unknown
+# @missing: 0590..05FF; R
+
+0000..001F    ; Common # Cc  [32] <control-0000>..<control-001F>
+";
+        let missing = missing_lines(data.as_bytes()).unwrap();
+        assert_eq!(missing.len(), 2);
+        assert_eq!(missing[0].value, "Unknown");
+        assert_eq!(missing[1].codepoints, "0590..05FF".parse().unwrap());
+        assert_eq!(missing[1].value, "R");
+
+        let data = "# Scripts-10.0.0.txt\n# @missing: XYZ; Unknown\n";
+        let err = missing_lines(data.as_bytes()).unwrap_err();
+        assert_eq!(err.line(), Some(2));
+    }
+
+    #[test]
+    fn display() {
+        for line in &[
+            "# @missing: 0000..10FFFF; Unknown",
+            "# @missing: 0000..10FFFF; Bidi_Paired_Bracket; <none>",
+        ] {
+            let row: MissingValue = line.parse().unwrap();
+            assert_eq!(row.to_string(), *line);
+        }
+    }
+}
@@ -1,10 +1,13 @@
 use std::borrow::Cow;
+use std::fmt;
+#[cfg(feature = "std")]
 use std::path::Path;
 use std::str::FromStr;
 
-use regex::Regex;
+#[cfg(feature = "std")]
+use ucd_util::UnicodeVersion;
 
-use common::{UcdFile, UcdFileByCodepoint, Codepoint};
+use common::{UcdFile, UcdFileByCodepoint, Codepoint, split_fields};
 use error::Error;
 
 /// A single row in the `NameAliases.txt` file.
@@ -12,6 +15,7 @@ use error::Error;
 /// Note that there are multiple rows for some codepoint. Each row provides a
 /// new alias.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NameAlias<'a> {
     /// The codepoint corresponding to this row.
     pub codepoint: Codepoint,
@@ -22,9 +26,38 @@ pub struct NameAlias<'a> {
 }
 
 impl UcdFile for NameAlias<'static> {
+    #[cfg(feature = "std")]
     fn relative_file_path() -> &'static Path {
         Path::new("NameAliases.txt")
     }
+
+    /// Before Unicode 6.1.0, `NameAliases.txt` lines had no label, e.g.,
+    /// `01A2;LATIN CAPITAL LETTER GHA`. Every alias in those releases was a
+    /// correction, so lines without a label are given the `correction`
+    /// label.
+    #[cfg(feature = "std")]
+    fn parse_versioned(
+        line: &str,
+        version: UnicodeVersion,
+    ) -> Result<NameAlias<'static>, Error> {
+        if version >= UnicodeVersion(6, 1, 0) {
+            return line.parse();
+        }
+        let mut fields = line.trim().split(';');
+        let (codepoint, alias) = match (fields.next(), fields.next()) {
+            (Some(cp), Some(alias)) if !alias.trim().is_empty() => (cp, alias),
+            _ => return err!("invalid NameAliases line"),
+        };
+        let label = match fields.next() {
+            None => NameAliasLabel::Correction,
+            Some(label) => label.trim().parse()?,
+        };
+        Ok(NameAlias {
+            codepoint: codepoint.trim().parse()?,
+            alias: Cow::Owned(alias.trim().to_string()),
+            label: label,
+        })
+    }
 }
 
 impl UcdFileByCodepoint for NameAlias<'static> {
@@ -46,27 +79,14 @@ impl<'a> NameAlias<'a> {
 
     /// Parse a single line.
     pub fn parse_line(line: &'a str) -> Result<NameAlias<'a>, Error> {
-        lazy_static! {
-            static ref PARTS: Regex = Regex::new(
-                r"(?x)
-                ^
-                (?P<codepoint>[A-Z0-9]+);
-                \s*
-                (?P<alias>[^;]+);
-                \s*
-                (?P<label>\S+)
-                "
-            ).unwrap();
-        };
-
-        let caps = match PARTS.captures(line.trim()) {
-            Some(caps) => caps,
-            None => return err!("invalid NameAliases line"),
-        };
+        let fields = split_fields(line);
+        if fields.len() != 3 || fields[1].is_empty() {
+            return err!("invalid NameAliases line");
+        }
         Ok(NameAlias {
-            codepoint: caps["codepoint"].parse()?,
-            alias: Cow::Borrowed(caps.name("alias").unwrap().as_str()),
-            label: caps["label"].parse()?,
+            codepoint: fields[0].parse()?,
+            alias: Cow::Borrowed(fields[1]),
+            label: fields[2].parse()?,
         })
     }
 }
@@ -79,8 +99,15 @@ impl FromStr for NameAlias<'static> {
     }
 }
 
+impl<'a> fmt::Display for NameAlias<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{};{};{}", self.codepoint, self.alias, self.label)
+    }
+}
+
 /// The label of a name alias.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum NameAliasLabel {
     /// Corrections for serious problems in a character name.
     Correction,
@@ -119,10 +146,51 @@ impl FromStr for NameAliasLabel {
     }
 }
 
+impl fmt::Display for NameAliasLabel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            NameAliasLabel::Correction => "correction",
+            NameAliasLabel::Control => "control",
+            NameAliasLabel::Alternate => "alternate",
+            NameAliasLabel::Figment => "figment",
+            NameAliasLabel::Abbreviation => "abbreviation",
+        };
+        write!(f, "{}", s)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use ucd_util::UnicodeVersion;
+
+    use common::UcdFile;
+
     use super::{NameAlias, NameAliasLabel};
 
+    #[test]
+    fn parse_versioned() {
+        let data = "01A2;LATIN CAPITAL LETTER GHA\n";
+        assert!(NameAlias::from_data(data).next().unwrap().is_err());
+
+        let rows = NameAlias::from_data(data)
+            .with_version(UnicodeVersion(6, 0, 0))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(rows[0].codepoint, 0x01A2);
+        assert_eq!(rows[0].alias, "LATIN CAPITAL LETTER GHA");
+        assert_eq!(rows[0].label, NameAliasLabel::Correction);
+
+        let rows = NameAlias::from_data("0000;NULL;control\n")
+            .with_version(UnicodeVersion(5, 2, 0))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(rows[0].label, NameAliasLabel::Control);
+
+        let mut rows = NameAlias::from_data(data)
+            .with_version(UnicodeVersion(10, 0, 0));
+        assert!(rows.next().unwrap().is_err());
+    }
+
     #[test]
     fn parse1() {
         let line = "0000;NULL;control\n";
@@ -158,4 +226,11 @@ mod tests {
         assert_eq!(row.alias, "VS256");
         assert_eq!(row.label, NameAliasLabel::Abbreviation);
     }
+
+    #[test]
+    fn display() {
+        let line = "E01EF;VS256;abbreviation";
+        let row: NameAlias = line.parse().unwrap();
+        assert_eq!(row.to_string(), line);
+    }
 }
@@ -0,0 +1,152 @@
+use std::borrow::Cow;
+use std::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
+use std::str::FromStr;
+
+use common::{
+    UcdFile, UcdFileByCodepoint, Codepoint, is_hex_codepoint, split_fields,
+    write_codepoints,
+};
+use error::Error;
+
+/// A single row in the `NormalizationCorrections.txt` file.
+///
+/// Each row records a correction made to the decomposition mapping of a
+/// codepoint after that mapping was published.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NormalizationCorrection<'a> {
+    /// The codepoint whose decomposition was corrected.
+    pub codepoint: Codepoint,
+    /// The original, erroneous, decomposition mapping.
+    pub original: Vec<Codepoint>,
+    /// The corrected decomposition mapping.
+    pub corrected: Vec<Codepoint>,
+    /// The version of Unicode in which the correction was made, e.g.,
+    /// `3.2.0`.
+    pub version: Cow<'a, str>,
+}
+
+impl UcdFile for NormalizationCorrection<'static> {
+    #[cfg(feature = "std")]
+    fn relative_file_path() -> &'static Path {
+        Path::new("NormalizationCorrections.txt")
+    }
+}
+
+impl UcdFileByCodepoint for NormalizationCorrection<'static> {
+    fn codepoint(&self) -> Codepoint {
+        self.codepoint
+    }
+}
+
+impl<'a> NormalizationCorrection<'a> {
+    /// Convert this record into an owned value such that it no longer
+    /// borrows from the original line that it was parsed from.
+    pub fn into_owned(self) -> NormalizationCorrection<'static> {
+        NormalizationCorrection {
+            codepoint: self.codepoint,
+            original: self.original,
+            corrected: self.corrected,
+            version: Cow::Owned(self.version.into_owned()),
+        }
+    }
+
+    /// Parse a single line.
+    pub fn parse_line(
+        line: &'a str,
+    ) -> Result<NormalizationCorrection<'a>, Error> {
+        let is_version = |s: &str| {
+            !s.is_empty() && s.bytes().all(|b| {
+                b == b'.' || (b'0' <= b && b <= b'9')
+            })
+        };
+
+        let fields = split_fields(line);
+        if fields.len() != 4
+            || !is_hex_codepoint(fields[0])
+            || !is_version(fields[3])
+        {
+            return err!("invalid NormalizationCorrections line");
+        }
+        Ok(NormalizationCorrection {
+            codepoint: fields[0].parse()?,
+            original: parse_codepoints(fields[1])?,
+            corrected: parse_codepoints(fields[2])?,
+            version: Cow::Borrowed(fields[3]),
+        })
+    }
+}
+
+impl FromStr for NormalizationCorrection<'static> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<NormalizationCorrection<'static>, Error> {
+        NormalizationCorrection::parse_line(s).map(|x| x.into_owned())
+    }
+}
+
+impl<'a> fmt::Display for NormalizationCorrection<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{};", self.codepoint)?;
+        write_codepoints(f, &self.original)?;
+        write!(f, ";")?;
+        write_codepoints(f, &self.corrected)?;
+        write!(f, ";{}", self.version)
+    }
+}
+
+/// Parse a non-empty sequence of whitespace separated codepoints.
+fn parse_codepoints(s: &str) -> Result<Vec<Codepoint>, Error> {
+    let mut cps = vec![];
+    for cp in s.split_whitespace() {
+        cps.push(cp.parse()?);
+    }
+    if cps.is_empty() {
+        return err!("empty decomposition mapping");
+    }
+    Ok(cps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NormalizationCorrection;
+
+    #[test]
+    fn parse_single() {
+        let line = "F951;96FB;964B;3.2.0 # Corrigendum 3\n";
+        let row: NormalizationCorrection = line.parse().unwrap();
+        assert_eq!(row.codepoint, 0xF951);
+        assert_eq!(row.original, vec![0x96FB]);
+        assert_eq!(row.corrected, vec![0x964B]);
+        assert_eq!(row.version, "3.2.0");
+    }
+
+    #[test]
+    fn parse_multiple() {
+        let line = "0F77;0FB2 0F81;0FB2 0F71 0F80;4.0.0 # Corrigendum 4\n";
+        let row: NormalizationCorrection = line.parse().unwrap();
+        assert_eq!(row.codepoint, 0x0F77);
+        assert_eq!(row.original, vec![0x0FB2, 0x0F81]);
+        assert_eq!(row.corrected, vec![0x0FB2, 0x0F71, 0x0F80]);
+        assert_eq!(row.version, "4.0.0");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let bad = ["F951;96FB;964B", "F951; ;964B;3.2.0", "F951;96FB;964B;x"];
+        for line in &bad {
+            assert!(line.parse::<NormalizationCorrection>().is_err());
+        }
+    }
+
+    #[test]
+    fn display() {
+        let line = "0F77;0FB2 0F81;0FB2 0F71 0F80;4.0.0 # Corrigendum 4\n";
+        let row: NormalizationCorrection = line.parse().unwrap();
+        assert_eq!(row.to_string(), "0F77;0FB2 0F81;0FB2 0F71 0F80;4.0.0");
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::Path;
+
+use rayon::prelude::*;
+
+use common::{Codepoint, UcdFile, UcdFileByCodepoint};
+use error::{Error, error_set_line, error_set_path};
+use reader::UcdReader;
+
+/// Parse a particular file in the UCD into a sequence of rows, using all
+/// available CPUs.
+///
+/// The given directory should be the directory to the UCD. This returns the
+/// same rows, in the same order, as `parse`. The entire file is read into
+/// memory and its lines are parsed in parallel, which is considerably faster
+/// for large files such as `UnicodeData.txt`.
+///
+/// If more than one line fails to parse, then the error for the first such
+/// line is returned.
+pub fn parse_parallel<P, D>(
+    ucd_dir: P,
+) -> Result<Vec<D>, Error>
+where P: AsRef<Path>, D: UcdFile + Send
+{
+    let path = D::file_path(&ucd_dir);
+    let mut data = String::new();
+    UcdReader::open(ucd_dir, D::relative_file_path())?
+        .read_to_string(&mut data)?;
+
+    let lines: Vec<(usize, &str)> = data
+        .lines()
+        .enumerate()
+        .filter(|&(_, line)| !line.starts_with('#') && !line.trim().is_empty())
+        .collect();
+    let results: Vec<Result<D, Error>> = lines
+        .par_iter()
+        .map(|&(i, line)| {
+            line.parse().map_err(|mut err| {
+                error_set_line(&mut err, Some(i as u64 + 1));
+                error_set_path(&mut err, Some(&path));
+                err
+            })
+        })
+        .collect();
+    results.into_iter().collect()
+}
+
+/// Parse a particular file in the UCD into a map from codepoint to the
+/// record, using all available CPUs.
+///
+/// This is the parallel version of `parse_by_codepoint`.
+///
+/// The given directory should be the directory to the UCD.
+pub fn parse_by_codepoint_parallel<P, D>(
+    ucd_dir: P,
+) -> Result<BTreeMap<Codepoint, D>, Error>
+where P: AsRef<Path>, D: UcdFileByCodepoint + Send
+{
+    let rows: Vec<D> = parse_parallel(ucd_dir)?;
+    Ok(rows.into_iter().map(|x| (x.codepoint(), x)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use common::parse;
+    use jamo_short_name::JamoShortName;
+    use temp_dir::TempDir;
+
+    use super::{parse_by_codepoint_parallel, parse_parallel};
+
+    #[test]
+    fn same_as_sequential() {
+        let tmp = TempDir::new("ucd-parse-parallel");
+        let dir = tmp.path();
+        tmp.write("Jamo.txt", b"# Jamo-10.0.0.txt\n\n\
+                                1100; G     # HANGUL CHOSEONG KIYEOK\n\
+                                1101; GG    # HANGUL CHOSEONG SSANGKIYEOK\n\
+                                110B;       # HANGUL CHOSEONG IEUNG\n");
+
+        let seq: Vec<JamoShortName> = parse(dir).unwrap();
+        let par: Vec<JamoShortName> = parse_parallel(dir).unwrap();
+        assert_eq!(par, seq);
+
+        let map = parse_by_codepoint_parallel::<_, JamoShortName>(dir)
+            .unwrap();
+        assert_eq!(map.len(), 3);
+
+        let path = tmp.write(
+            "Jamo.txt", b"1100; G\nXYZ; GG\n1102; N\nXYZ; D\n");
+        let err = parse_parallel::<_, JamoShortName>(dir).unwrap_err();
+        assert_eq!(err.line(), Some(2));
+        assert_eq!(err.path(), Some(&*path));
+    }
+}
@@ -0,0 +1,28 @@
+binary_property_record!(
+    PropList, "PropList.txt",
+    "A single row in the `PropList.txt` file, which lists codepoints for \
+     miscellaneous binary properties such as `White_Space`.");
+
+#[cfg(test)]
+mod tests {
+    use super::PropList;
+
+    #[test]
+    fn parse_single() {
+        let line = "0020          ; White_Space # Zs       SPACE\n";
+        let row: PropList = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x0020);
+        assert_eq!(row.codepoints.end, 0x0020);
+        assert_eq!(row.property, "White_Space");
+    }
+
+    #[test]
+    fn parse_range() {
+        let line = "0009..000D    ; White_Space # Cc   [5] \
+                    <control-0009>..<control-000D>\n";
+        let row: PropList = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x0009);
+        assert_eq!(row.codepoints.end, 0x000D);
+        assert_eq!(row.property, "White_Space");
+    }
+}
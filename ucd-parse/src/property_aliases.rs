@@ -1,14 +1,17 @@
 use std::borrow::Cow;
+use std::fmt;
+#[cfg(feature = "std")]
 use std::path::Path;
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
 use std::str::FromStr;
 
-use regex::Regex;
-
-use common::UcdFile;
+use common::{UcdFile, is_word, split_fields};
 use error::Error;
 
 /// A single row in the `PropertyAliases.txt` file.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PropertyAlias<'a> {
     /// An abbreviation for this property.
     pub abbreviation: Cow<'a, str>,
@@ -19,6 +22,7 @@ pub struct PropertyAlias<'a> {
 }
 
 impl UcdFile for PropertyAlias<'static> {
+    #[cfg(feature = "std")]
     fn relative_file_path() -> &'static Path {
         Path::new("PropertyAliases.txt")
     }
@@ -40,34 +44,18 @@ impl<'a> PropertyAlias<'a> {
 
     /// Parse a single line.
     pub fn parse_line(line: &'a str) -> Result<PropertyAlias<'a>, Error> {
-        lazy_static! {
-            static ref PARTS: Regex = Regex::new(
-                r"(?x)
-                ^
-                \s*(?P<abbrev>[^\s;]+)\s*;
-                \s*(?P<long>[^\s;]+)\s*
-                (?:;(?P<aliases>.*))?
-                "
-            ).unwrap();
-            static ref ALIASES: Regex = Regex::new(
-                r"\s*(?P<alias>[^\s;]+)\s*;?\s*"
-            ).unwrap();
-        };
-
-        let caps = match PARTS.captures(line.trim()) {
-            Some(caps) => caps,
-            None => return err!("invalid PropertyAliases line"),
-        };
-        let mut aliases = vec![];
-        if let Some(m) = caps.name("aliases") {
-            for acaps in ALIASES.captures_iter(m.as_str()) {
-                let alias = acaps.name("alias").unwrap().as_str();
-                aliases.push(Cow::Borrowed(alias));
-            }
+        let fields = split_fields(line);
+        if fields.len() < 2 || !is_word(fields[0]) || !is_word(fields[1]) {
+            return err!("invalid PropertyAliases line");
         }
+        let aliases = fields[2..]
+            .iter()
+            .filter(|alias| !alias.is_empty())
+            .map(|&alias| Cow::Borrowed(alias))
+            .collect();
         Ok(PropertyAlias {
-            abbreviation: Cow::Borrowed(caps.name("abbrev").unwrap().as_str()),
-            long: Cow::Borrowed(caps.name("long").unwrap().as_str()),
+            abbreviation: Cow::Borrowed(fields[0]),
+            long: Cow::Borrowed(fields[1]),
             aliases: aliases,
         })
     }
@@ -81,6 +69,16 @@ impl FromStr for PropertyAlias<'static> {
     }
 }
 
+impl<'a> fmt::Display for PropertyAlias<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ; {}", self.abbreviation, self.long)?;
+        for alias in &self.aliases {
+            write!(f, " ; {}", alias)?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::PropertyAlias;
@@ -120,4 +118,11 @@ mod tests {
         assert_eq!(row.long, "kRSUnicode");
         assert_eq!(row.aliases, vec!["Unicode_Radical_Stroke", "URS"]);
     }
+
+    #[test]
+    fn display() {
+        let line = "cjkRSUnicode ; kRSUnicode ; Unicode_Radical_Stroke ; URS";
+        let row: PropertyAlias = line.parse().unwrap();
+        assert_eq!(row.to_string(), line);
+    }
 }
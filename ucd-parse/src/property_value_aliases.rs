@@ -1,14 +1,17 @@
 use std::borrow::Cow;
+use std::fmt;
+#[cfg(feature = "std")]
 use std::path::Path;
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
 use std::str::FromStr;
 
-use regex::Regex;
-
-use common::UcdFile;
+use common::{UcdFile, is_word, split_fields};
 use error::Error;
 
 /// A single row in the `PropertyValueAliases.txt` file.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PropertyValueAlias<'a> {
     /// The property name for which this value alias applies.
     pub property: Cow<'a, str>,
@@ -25,6 +28,7 @@ pub struct PropertyValueAlias<'a> {
 }
 
 impl UcdFile for PropertyValueAlias<'static> {
+    #[cfg(feature = "std")]
     fn relative_file_path() -> &'static Path {
         Path::new("PropertyValueAliases.txt")
     }
@@ -48,68 +52,42 @@ impl<'a> PropertyValueAlias<'a> {
 
     /// Parse a single line.
     pub fn parse_line(line: &'a str) -> Result<PropertyValueAlias<'a>, Error> {
-        lazy_static! {
-            static ref PARTS: Regex = Regex::new(
-                r"(?x)
-                ^
-                \s*(?P<prop>[^\s;]+)\s*;
-                \s*(?P<abbrev>[^\s;]+)\s*;
-                \s*(?P<long>[^\s;]+)\s*
-                (?:;(?P<aliases>.*))?
-                "
-            ).unwrap();
-            static ref PARTS_CCC: Regex = Regex::new(
-                r"(?x)
-                ^
-                ccc;
-                \s*(?P<num_class>[0-9]+)\s*;
-                \s*(?P<abbrev>[^\s;]+)\s*;
-                \s*(?P<long>[^\s;]+)
-                "
-            ).unwrap();
-            static ref ALIASES: Regex = Regex::new(
-                r"\s*(?P<alias>[^\s;]+)\s*;?\s*"
-            ).unwrap();
-        };
-
-        if line.starts_with("ccc;") {
-            let caps = match PARTS_CCC.captures(line.trim()) {
-                Some(caps) => caps,
-                None => return err!("invalid PropertyValueAliases (ccc) line"),
-            };
-            let n = match caps["num_class"].parse() {
+        let fields = split_fields(line);
+        if fields[0] == "ccc" {
+            if fields.len() < 4 || !is_word(fields[2]) || !is_word(fields[3]) {
+                return err!("invalid PropertyValueAliases (ccc) line");
+            }
+            let n = match fields[1].parse() {
                 Ok(n) => n,
                 Err(err) => return err!(
-                    "failed to parse ccc number '{}': {}",
-                    &caps["num_class"], err),
+                    "failed to parse ccc number '{}': {}", fields[1], err),
             };
-            let abbrev = caps.name("abbrev").unwrap().as_str();
-            let long = caps.name("long").unwrap().as_str();
             return Ok(PropertyValueAlias {
-                property: Cow::Borrowed(&line[0..3]),
+                property: Cow::Borrowed(fields[0]),
                 numeric: Some(n),
-                abbreviation: Cow::Borrowed(abbrev),
-                long: Cow::Borrowed(long),
+                abbreviation: Cow::Borrowed(fields[2]),
+                long: Cow::Borrowed(fields[3]),
                 aliases: vec![],
             });
         }
 
-        let caps = match PARTS.captures(line.trim()) {
-            Some(caps) => caps,
-            None => return err!("invalid PropertyValueAliases line"),
-        };
-        let mut aliases = vec![];
-        if let Some(m) = caps.name("aliases") {
-            for acaps in ALIASES.captures_iter(m.as_str()) {
-                let alias = acaps.name("alias").unwrap().as_str();
-                aliases.push(Cow::Borrowed(alias));
-            }
+        if fields.len() < 3
+            || !is_word(fields[0])
+            || !is_word(fields[1])
+            || !is_word(fields[2])
+        {
+            return err!("invalid PropertyValueAliases line");
         }
+        let aliases = fields[3..]
+            .iter()
+            .filter(|alias| !alias.is_empty())
+            .map(|&alias| Cow::Borrowed(alias))
+            .collect();
         Ok(PropertyValueAlias {
-            property: Cow::Borrowed(caps.name("prop").unwrap().as_str()),
+            property: Cow::Borrowed(fields[0]),
             numeric: None,
-            abbreviation: Cow::Borrowed(caps.name("abbrev").unwrap().as_str()),
-            long: Cow::Borrowed(caps.name("long").unwrap().as_str()),
+            abbreviation: Cow::Borrowed(fields[1]),
+            long: Cow::Borrowed(fields[2]),
             aliases: aliases,
         })
     }
@@ -123,6 +101,20 @@ impl FromStr for PropertyValueAlias<'static> {
     }
 }
 
+impl<'a> fmt::Display for PropertyValueAlias<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}; ", self.property)?;
+        if let Some(n) = self.numeric {
+            write!(f, "{}; ", n)?;
+        }
+        write!(f, "{} ; {}", self.abbreviation, self.long)?;
+        for alias in &self.aliases {
+            write!(f, " ; {}", alias)?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::PropertyValueAlias;
@@ -181,4 +173,15 @@ mod tests {
         assert_eq!(row.long, "CCC133");
         assert!(row.aliases.is_empty());
     }
+
+    #[test]
+    fn display() {
+        let line = "AHex; N ; No ; F ; False";
+        let row: PropertyValueAlias = line.parse().unwrap();
+        assert_eq!(row.to_string(), line);
+
+        let line = "ccc; 0; NR ; Not_Reordered";
+        let row: PropertyValueAlias = line.parse().unwrap();
+        assert_eq!(row.to_string(), line);
+    }
 }
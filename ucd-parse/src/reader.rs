@@ -0,0 +1,196 @@
+use std::fs::File;
+use std::io;
+use std::path::Path;
+#[cfg(feature = "zip")]
+use std::path::PathBuf;
+
+use error::{Error, error_set_path};
+
+/// The archives, relative to a UCD directory, that are searched for files
+/// that are missing from the directory itself.
+#[cfg(feature = "zip")]
+pub const ARCHIVES: &'static [&'static str] = &["UCD.zip", "Unihan.zip"];
+
+/// A reader for a single file in the UCD.
+///
+/// Files are normally read from a directory containing the UCD. When the
+/// `zip` feature is enabled and a file is missing from that directory, it is
+/// read from the `UCD.zip` or `Unihan.zip` archive in that directory instead.
+/// With the `zip` feature, the path to an archive may also be used in place
+/// of a UCD directory.
+#[derive(Debug)]
+pub struct UcdReader {
+    inner: Inner,
+}
+
+#[derive(Debug)]
+enum Inner {
+    File(File),
+    Memory(io::Cursor<Vec<u8>>),
+}
+
+impl UcdReader {
+    /// Open the file at the given path, relative to the given UCD directory.
+    pub fn open<P: AsRef<Path>>(
+        ucd_dir: P,
+        relative: &Path,
+    ) -> Result<UcdReader, Error> {
+        let ucd_dir = ucd_dir.as_ref();
+        let path = ucd_dir.join(relative);
+        if !path.is_file() {
+            if let Some(data) = read_archived(ucd_dir, relative)? {
+                let inner = Inner::Memory(io::Cursor::new(data));
+                return Ok(UcdReader { inner: inner });
+            }
+        }
+        let file = File::open(&path).map_err(|err| {
+            let mut err = Error::from(err);
+            error_set_path(&mut err, Some(&path));
+            err
+        })?;
+        Ok(UcdReader { inner: Inner::File(file) })
+    }
+}
+
+impl io::Read for UcdReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.inner {
+            Inner::File(ref mut rdr) => rdr.read(buf),
+            Inner::Memory(ref mut rdr) => rdr.read(buf),
+        }
+    }
+}
+
+/// Returns true if and only if the file at the given path, relative to the
+/// given UCD directory, can be opened with `UcdReader::open`.
+pub fn exists(ucd_dir: &Path, relative: &Path) -> bool {
+    ucd_dir.join(relative).is_file() || is_archived(ucd_dir, relative)
+}
+
+/// Returns true if and only if the given path can be used in place of a UCD
+/// directory.
+pub fn is_ucd_dir(path: &Path) -> bool {
+    path.is_dir() || (cfg!(feature = "zip") && path.is_file())
+}
+
+/// Return the archives that should be searched for files missing from the
+/// given UCD directory.
+#[cfg(feature = "zip")]
+fn archives(ucd_dir: &Path) -> Vec<PathBuf> {
+    if ucd_dir.is_file() {
+        return vec![ucd_dir.to_path_buf()];
+    }
+    ARCHIVES
+        .iter()
+        .map(|name| ucd_dir.join(name))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Convert a relative file path to the name of an entry in an archive, which
+/// always uses `/` as a separator.
+#[cfg(feature = "zip")]
+fn entry_name(relative: &Path) -> String {
+    let parts: Vec<_> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect();
+    parts.join("/")
+}
+
+/// Read the full contents of the given file from the first archive that
+/// contains it, if any.
+#[cfg(feature = "zip")]
+fn read_archived(
+    ucd_dir: &Path,
+    relative: &Path,
+) -> Result<Option<Vec<u8>>, Error> {
+    use std::io::Read;
+    use zip::ZipArchive;
+    use zip::result::ZipError;
+
+    let name = entry_name(relative);
+    for path in archives(ucd_dir) {
+        let mut archive = ZipArchive::new(File::open(path)?)
+            .map_err(io::Error::from)?;
+        let mut file = match archive.by_name(&name) {
+            Ok(file) => file,
+            Err(ZipError::FileNotFound) => continue,
+            Err(err) => return Err(Error::from(io::Error::from(err))),
+        };
+        let mut data = vec![];
+        file.read_to_end(&mut data)?;
+        return Ok(Some(data));
+    }
+    Ok(None)
+}
+
+#[cfg(not(feature = "zip"))]
+fn read_archived(_: &Path, _: &Path) -> Result<Option<Vec<u8>>, Error> {
+    Ok(None)
+}
+
+/// Returns true if and only if one of the archives for the given UCD
+/// directory contains the given file.
+#[cfg(feature = "zip")]
+fn is_archived(ucd_dir: &Path, relative: &Path) -> bool {
+    use zip::ZipArchive;
+
+    let name = entry_name(relative);
+    archives(ucd_dir).into_iter().any(|path| {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(_) => return false,
+        };
+        match ZipArchive::new(file) {
+            Ok(mut archive) => archive.by_name(&name).is_ok(),
+            Err(_) => false,
+        }
+    })
+}
+
+#[cfg(not(feature = "zip"))]
+fn is_archived(_: &Path, _: &Path) -> bool {
+    false
+}
+
+#[cfg(all(test, feature = "zip"))]
+mod tests {
+    use std::fs::File;
+    use std::io::{Read, Write};
+    use std::path::Path;
+
+    use zip::ZipWriter;
+    use zip::write::FileOptions;
+
+    use temp_dir::TempDir;
+
+    use super::{UcdReader, exists};
+
+    #[test]
+    fn archive_fallback() {
+        let tmp = TempDir::new("ucd-parse-reader-archive");
+        let dir = tmp.path();
+        {
+            let file = File::create(dir.join("UCD.zip")).unwrap();
+            let mut zip = ZipWriter::new(file);
+            zip.start_file("auxiliary/WordBreakProperty.txt",
+                           FileOptions::default()).unwrap();
+            zip.write_all(b"0041..005A ; ALetter\n").unwrap();
+            zip.finish().unwrap();
+        }
+
+        let relative = Path::new("auxiliary/WordBreakProperty.txt");
+        assert!(exists(dir, relative));
+        assert!(exists(&dir.join("UCD.zip"), relative));
+        assert!(!exists(dir, Path::new("UnicodeData.txt")));
+
+        let mut data = String::new();
+        UcdReader::open(dir, relative)
+            .unwrap()
+            .read_to_string(&mut data)
+            .unwrap();
+        assert_eq!(data, "0041..005A ; ALetter\n");
+        assert!(UcdReader::open(dir, Path::new("UnicodeData.txt")).is_err());
+    }
+}
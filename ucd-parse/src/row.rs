@@ -0,0 +1,251 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead};
+use std::path::Path;
+
+use common::UcdFile;
+use error::{Error, error_parse, error_set_line, error_set_path};
+
+use arabic_shaping::ArabicShaping;
+use bidi_brackets::BidiBracket;
+use bidi_mirroring::BidiMirroring;
+use break_test::{
+    GraphemeBreakTest, LineBreakTest, SentenceBreakTest, WordBreakTest,
+};
+use case_folding::CaseFold;
+use core_properties::CoreProperty;
+use east_asian_width::EastAsianWidth;
+use emoji_data::EmojiProperty;
+use extracted::{
+    DerivedBidiClass, DerivedBinaryProperties, DerivedDecompositionType,
+    DerivedGeneralCategory, DerivedJoiningGroup, DerivedJoiningType,
+    DerivedNumericType, DerivedNumericValues,
+};
+use ideographic_sources::{NushuSource, TangutSource};
+use jamo_short_name::JamoShortName;
+use line_break::LineBreak;
+use name_aliases::NameAlias;
+use normalization_corrections::NormalizationCorrection;
+use prop_list::PropList;
+use property_aliases::PropertyAlias;
+use property_value_aliases::PropertyValueAlias;
+use script::{ScriptExtensions, Scripts};
+use sentence_break::SentenceBreak;
+use special_casing::SpecialCaseMapping;
+use unicode_data::UnicodeData;
+use usource_data::USourceData;
+use word_break::WordBreak;
+
+macro_rules! ucd_rows {
+    ($($variant:ident($ty:ty),)*) => {
+        /// A single record from any supported file in the UCD.
+        ///
+        /// There is one variant for every record type that implements
+        /// `UcdFile`, named after that type. Use `parse_any` to parse a line
+        /// from a file without knowing its record type in advance.
+        #[derive(Clone, Debug, Eq, PartialEq)]
+        pub enum UcdRow {
+            $(
+                #[doc = concat!("A `", stringify!($variant), "` record.")]
+                $variant($ty),
+            )*
+        }
+
+        impl UcdRow {
+            /// Return the path of the file that this record belongs to,
+            /// relative to the UCD directory.
+            pub fn relative_file_path(&self) -> &'static Path {
+                match *self {
+                    $(UcdRow::$variant(_) => <$ty>::relative_file_path(),)*
+                }
+            }
+        }
+
+        impl fmt::Display for UcdRow {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match *self {
+                    $(UcdRow::$variant(ref row) => row.fmt(f),)*
+                }
+            }
+        }
+
+        /// Parse a line from the file with the given name, or return `None`
+        /// if no record type corresponds to that file.
+        fn parse_named(
+            name: &str,
+            line: &str,
+        ) -> Option<Result<UcdRow, Error>> {
+            $(
+                if is_file::<$ty>(name) {
+                    return Some(line.parse().map(UcdRow::$variant));
+                }
+            )*
+            None
+        }
+
+        /// Returns true if and only if a record type corresponds to the file
+        /// with the given name.
+        fn is_supported_name(name: &str) -> bool {
+            $(is_file::<$ty>(name))||*
+        }
+    }
+}
+
+ucd_rows! {
+    ArabicShaping(ArabicShaping<'static>),
+    BidiBracket(BidiBracket),
+    BidiMirroring(BidiMirroring),
+    CaseFold(CaseFold),
+    CoreProperty(CoreProperty<'static>),
+    DerivedBidiClass(DerivedBidiClass<'static>),
+    DerivedBinaryProperties(DerivedBinaryProperties<'static>),
+    DerivedDecompositionType(DerivedDecompositionType<'static>),
+    DerivedGeneralCategory(DerivedGeneralCategory<'static>),
+    DerivedJoiningGroup(DerivedJoiningGroup<'static>),
+    DerivedJoiningType(DerivedJoiningType<'static>),
+    DerivedNumericType(DerivedNumericType<'static>),
+    DerivedNumericValues(DerivedNumericValues<'static>),
+    EastAsianWidth(EastAsianWidth),
+    EmojiProperty(EmojiProperty<'static>),
+    GraphemeBreakTest(GraphemeBreakTest),
+    JamoShortName(JamoShortName<'static>),
+    LineBreak(LineBreak),
+    LineBreakTest(LineBreakTest),
+    NameAlias(NameAlias<'static>),
+    NormalizationCorrection(NormalizationCorrection<'static>),
+    NushuSource(NushuSource<'static>),
+    PropList(PropList<'static>),
+    PropertyAlias(PropertyAlias<'static>),
+    PropertyValueAlias(PropertyValueAlias<'static>),
+    ScriptExtensions(ScriptExtensions),
+    Scripts(Scripts),
+    SentenceBreak(SentenceBreak<'static>),
+    SentenceBreakTest(SentenceBreakTest),
+    SpecialCaseMapping(SpecialCaseMapping),
+    TangutSource(TangutSource<'static>),
+    USourceData(USourceData<'static>),
+    UnicodeData(UnicodeData<'static>),
+    WordBreak(WordBreak<'static>),
+    WordBreakTest(WordBreakTest),
+}
+
+/// Returns true if and only if the given file name is the name of the file
+/// for the given record type.
+fn is_file<D: UcdFile>(name: &str) -> bool {
+    D::relative_file_path().file_name().map_or(false, |n| n == name)
+}
+
+/// Parse a single line from the UCD file at the given path.
+///
+/// The record type is determined by the file name of the given path, e.g.,
+/// `UnicodeData.txt` or `DerivedBidiClass.txt`, so the path doesn't need to
+/// exist or be inside a UCD directory. If no record type corresponds to the
+/// file name, then this returns an error.
+///
+/// Like `FromStr`, this doesn't accept comments or blank lines.
+pub fn parse_any<P: AsRef<Path>>(
+    path: P,
+    line: &str,
+) -> Result<UcdRow, Error> {
+    let path = path.as_ref();
+    match parse_named(file_name(path), line) {
+        Some(result) => result,
+        None => err!("unsupported UCD file: {}", path.display()),
+    }
+}
+
+/// Parse every record in the UCD file at the given path.
+///
+/// The record type is determined by the file name of the given path, in the
+/// same way as `parse_any`. Comments and blank lines are skipped.
+pub fn parse_any_file<P: AsRef<Path>>(
+    path: P,
+) -> Result<Vec<UcdRow>, Error> {
+    let path = path.as_ref();
+    let locate = |mut err: Error, line: Option<u64>| {
+        error_set_line(&mut err, line);
+        error_set_path(&mut err, Some(path));
+        err
+    };
+    if !is_supported(path) {
+        return Err(locate(error_parse("unsupported UCD file".into()), None));
+    }
+    let file = File::open(path).map_err(|err| locate(err.into(), None))?;
+    let mut rows = vec![];
+    for (i, line) in io::BufReader::new(file).lines().enumerate() {
+        let line_number = Some(i as u64 + 1);
+        let line = line.map_err(|err| locate(err.into(), line_number))?;
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        let row = parse_any(path, &line)
+            .map_err(|err| locate(err, line_number))?;
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Returns true if and only if `parse_any` supports the file at the given
+/// path.
+pub fn is_supported<P: AsRef<Path>>(path: P) -> bool {
+    is_supported_name(file_name(path.as_ref()))
+}
+
+/// Return the file name of the given path, or an empty string if it has
+/// none.
+fn file_name(path: &Path) -> &str {
+    path.file_name().and_then(|name| name.to_str()).unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use temp_dir::TempDir;
+
+    use super::{UcdRow, is_supported, parse_any, parse_any_file};
+
+    #[test]
+    fn parse_line() {
+        let row = parse_any(
+            "UnicodeData.txt",
+            "0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;",
+        ).unwrap();
+        match row {
+            UcdRow::UnicodeData(ref row) => assert_eq!(row.codepoint, 0x41),
+            ref row => panic!("unexpected row: {:?}", row),
+        }
+        assert_eq!(row.relative_file_path(), Path::new("UnicodeData.txt"));
+
+        let line = "0600..0605    ; AN # Cf   [6] ARABIC NUMBER SIGN..";
+        let row = parse_any("ucd/extracted/DerivedBidiClass.txt", line)
+            .unwrap();
+        assert_eq!(row.to_string(), "0600..0605 ; AN");
+        assert_eq!(
+            row.relative_file_path(),
+            Path::new("extracted/DerivedBidiClass.txt"));
+
+        assert!(parse_any("UnicodeData.txt", "0041 ; AN").is_err());
+        assert!(parse_any("Unknown.txt", "0041 ; AN").is_err());
+        assert!(is_supported("auxiliary/WordBreakTest.txt"));
+        assert!(!is_supported("Unknown.txt"));
+    }
+
+    #[test]
+    fn parse_file() {
+        let dir = TempDir::new("ucd-parse-row");
+        let path = dir.write(
+            "Jamo.txt", b"# Jamo-10.0.0.txt\n\n1100; G\n1101; GG\nXYZ; D\n");
+        let err = parse_any_file(&path).unwrap_err();
+        assert_eq!(err.line(), Some(5));
+        assert_eq!(err.path(), Some(&*path));
+
+        dir.write("Jamo.txt", b"# Jamo-10.0.0.txt\n\n1100; G\n1101; GG\n");
+        let rows = parse_any_file(&path).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].to_string(), "1101; GG");
+
+        let err = parse_any_file(dir.path().join("Unknown.txt")).unwrap_err();
+        assert!(err.to_string().contains("unsupported UCD file"));
+    }
+}
@@ -0,0 +1,299 @@
+use std::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
+use std::str::FromStr;
+
+use common::{
+    CodepointRange, PropertyFile, PropertyValueRow, UcdFile,
+    UcdFileByCodepointRange, split_fields,
+};
+use error::Error;
+
+property_value_enum! {
+    /// The `Script` property of a codepoint.
+    ///
+    /// This covers the scripts of Unicode 10.0. Records store scripts as raw
+    /// strings, so that scripts added in later versions can still be read.
+    /// Use `str::parse` to convert a raw value to this type.
+    ///
+    /// Parsing accepts both the abbreviated (e.g., `Latn`) and the long
+    /// (e.g., `Latin`) names of a script. Its `Display` impl writes the
+    /// abbreviated name, which is the script's ISO 15924 code.
+    pub enum Script ("script") {
+        Adlam, "Adlm", "Adlam";
+        CaucasianAlbanian, "Aghb", "Caucasian_Albanian";
+        Ahom, "Ahom", "Ahom";
+        Arabic, "Arab", "Arabic";
+        ImperialAramaic, "Armi", "Imperial_Aramaic";
+        Armenian, "Armn", "Armenian";
+        Avestan, "Avst", "Avestan";
+        Balinese, "Bali", "Balinese";
+        Bamum, "Bamu", "Bamum";
+        BassaVah, "Bass", "Bassa_Vah";
+        Batak, "Batk", "Batak";
+        Bengali, "Beng", "Bengali";
+        Bhaiksuki, "Bhks", "Bhaiksuki";
+        Bopomofo, "Bopo", "Bopomofo";
+        Brahmi, "Brah", "Brahmi";
+        Braille, "Brai", "Braille";
+        Buginese, "Bugi", "Buginese";
+        Buhid, "Buhd", "Buhid";
+        Chakma, "Cakm", "Chakma";
+        CanadianAboriginal, "Cans", "Canadian_Aboriginal";
+        Carian, "Cari", "Carian";
+        Cham, "Cham", "Cham";
+        Cherokee, "Cher", "Cherokee";
+        Coptic, "Copt", "Coptic" | "Qaac";
+        Cypriot, "Cprt", "Cypriot";
+        Cyrillic, "Cyrl", "Cyrillic";
+        Devanagari, "Deva", "Devanagari";
+        Deseret, "Dsrt", "Deseret";
+        Duployan, "Dupl", "Duployan";
+        EgyptianHieroglyphs, "Egyp", "Egyptian_Hieroglyphs";
+        Elbasan, "Elba", "Elbasan";
+        Ethiopic, "Ethi", "Ethiopic";
+        Georgian, "Geor", "Georgian";
+        Glagolitic, "Glag", "Glagolitic";
+        MasaramGondi, "Gonm", "Masaram_Gondi";
+        Gothic, "Goth", "Gothic";
+        Grantha, "Gran", "Grantha";
+        Greek, "Grek", "Greek";
+        Gujarati, "Gujr", "Gujarati";
+        Gurmukhi, "Guru", "Gurmukhi";
+        Hangul, "Hang", "Hangul";
+        Han, "Hani", "Han";
+        Hanunoo, "Hano", "Hanunoo";
+        Hatran, "Hatr", "Hatran";
+        Hebrew, "Hebr", "Hebrew";
+        Hiragana, "Hira", "Hiragana";
+        AnatolianHieroglyphs, "Hluw", "Anatolian_Hieroglyphs";
+        PahawhHmong, "Hmng", "Pahawh_Hmong";
+        KatakanaOrHiragana, "Hrkt", "Katakana_Or_Hiragana";
+        OldHungarian, "Hung", "Old_Hungarian";
+        OldItalic, "Ital", "Old_Italic";
+        Javanese, "Java", "Javanese";
+        KayahLi, "Kali", "Kayah_Li";
+        Katakana, "Kana", "Katakana";
+        Kharoshthi, "Khar", "Kharoshthi";
+        Khmer, "Khmr", "Khmer";
+        Khojki, "Khoj", "Khojki";
+        Kannada, "Knda", "Kannada";
+        Kaithi, "Kthi", "Kaithi";
+        TaiTham, "Lana", "Tai_Tham";
+        Lao, "Laoo", "Lao";
+        Latin, "Latn", "Latin";
+        Lepcha, "Lepc", "Lepcha";
+        Limbu, "Limb", "Limbu";
+        LinearA, "Lina", "Linear_A";
+        LinearB, "Linb", "Linear_B";
+        Lisu, "Lisu", "Lisu";
+        Lycian, "Lyci", "Lycian";
+        Lydian, "Lydi", "Lydian";
+        Mahajani, "Mahj", "Mahajani";
+        Mandaic, "Mand", "Mandaic";
+        Manichaean, "Mani", "Manichaean";
+        Marchen, "Marc", "Marchen";
+        MendeKikakui, "Mend", "Mende_Kikakui";
+        MeroiticCursive, "Merc", "Meroitic_Cursive";
+        MeroiticHieroglyphs, "Mero", "Meroitic_Hieroglyphs";
+        Malayalam, "Mlym", "Malayalam";
+        Modi, "Modi", "Modi";
+        Mongolian, "Mong", "Mongolian";
+        Mro, "Mroo", "Mro";
+        MeeteiMayek, "Mtei", "Meetei_Mayek";
+        Multani, "Mult", "Multani";
+        Myanmar, "Mymr", "Myanmar";
+        OldNorthArabian, "Narb", "Old_North_Arabian";
+        Nabataean, "Nbat", "Nabataean";
+        Newa, "Newa", "Newa";
+        Nko, "Nkoo", "Nko";
+        Nushu, "Nshu", "Nushu";
+        Ogham, "Ogam", "Ogham";
+        OlChiki, "Olck", "Ol_Chiki";
+        OldTurkic, "Orkh", "Old_Turkic";
+        Oriya, "Orya", "Oriya";
+        Osage, "Osge", "Osage";
+        Osmanya, "Osma", "Osmanya";
+        Palmyrene, "Palm", "Palmyrene";
+        PauCinHau, "Pauc", "Pau_Cin_Hau";
+        OldPermic, "Perm", "Old_Permic";
+        PhagsPa, "Phag", "Phags_Pa";
+        InscriptionalPahlavi, "Phli", "Inscriptional_Pahlavi";
+        PsalterPahlavi, "Phlp", "Psalter_Pahlavi";
+        Phoenician, "Phnx", "Phoenician";
+        Miao, "Plrd", "Miao";
+        InscriptionalParthian, "Prti", "Inscriptional_Parthian";
+        Rejang, "Rjng", "Rejang";
+        Runic, "Runr", "Runic";
+        Samaritan, "Samr", "Samaritan";
+        OldSouthArabian, "Sarb", "Old_South_Arabian";
+        Saurashtra, "Saur", "Saurashtra";
+        SignWriting, "Sgnw", "SignWriting";
+        Shavian, "Shaw", "Shavian";
+        Sharada, "Shrd", "Sharada";
+        Siddham, "Sidd", "Siddham";
+        Khudawadi, "Sind", "Khudawadi";
+        Sinhala, "Sinh", "Sinhala";
+        SoraSompeng, "Sora", "Sora_Sompeng";
+        Soyombo, "Soyo", "Soyombo";
+        Sundanese, "Sund", "Sundanese";
+        SylotiNagri, "Sylo", "Syloti_Nagri";
+        Syriac, "Syrc", "Syriac";
+        Tagbanwa, "Tagb", "Tagbanwa";
+        Takri, "Takr", "Takri";
+        TaiLe, "Tale", "Tai_Le";
+        NewTaiLue, "Talu", "New_Tai_Lue";
+        Tamil, "Taml", "Tamil";
+        Tangut, "Tang", "Tangut";
+        TaiViet, "Tavt", "Tai_Viet";
+        Telugu, "Telu", "Telugu";
+        Tifinagh, "Tfng", "Tifinagh";
+        Tagalog, "Tglg", "Tagalog";
+        Thaana, "Thaa", "Thaana";
+        Thai, "Thai", "Thai";
+        Tibetan, "Tibt", "Tibetan";
+        Tirhuta, "Tirh", "Tirhuta";
+        Ugaritic, "Ugar", "Ugaritic";
+        Vai, "Vaii", "Vai";
+        WarangCiti, "Wara", "Warang_Citi";
+        OldPersian, "Xpeo", "Old_Persian";
+        Cuneiform, "Xsux", "Cuneiform";
+        Yi, "Yiii", "Yi";
+        ZanabazarSquare, "Zanb", "Zanabazar_Square";
+        Inherited, "Zinh", "Inherited" | "Qaai";
+        Common, "Zyyy", "Common";
+        Unknown, "Zzzz", "Unknown";
+    }
+}
+
+impl Default for Script {
+    fn default() -> Script {
+        // Codepoints not listed in Scripts.txt have the Unknown script.
+        Script::Unknown
+    }
+}
+
+/// The `Scripts.txt` file, which defines the `Script` property.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ScriptsFile;
+
+impl PropertyFile for ScriptsFile {
+    #[cfg(feature = "std")]
+    fn relative_file_path() -> &'static Path {
+        Path::new("Scripts.txt")
+    }
+}
+
+/// A single row in the `Scripts.txt` file.
+///
+/// The value is the long name of the script, e.g., `Latin`.
+pub type Scripts = PropertyValueRow<ScriptsFile, String>;
+
+/// A single row in the `ScriptExtensions.txt` file, which defines the
+/// `Script_Extensions` property.
+///
+/// Codepoints that aren't listed in `ScriptExtensions.txt` have a
+/// `Script_Extensions` value consisting of just their `Script` value.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScriptExtensions {
+    /// The codepoints corresponding to this row.
+    pub codepoints: CodepointRange,
+    /// The abbreviated names of the scripts of these codepoints, e.g.,
+    /// `Arab` and `Syrc`. This is never empty.
+    pub scripts: Vec<String>,
+}
+
+impl UcdFile for ScriptExtensions {
+    #[cfg(feature = "std")]
+    fn relative_file_path() -> &'static Path {
+        Path::new("ScriptExtensions.txt")
+    }
+}
+
+impl UcdFileByCodepointRange for ScriptExtensions {
+    fn codepoints(&self) -> CodepointRange {
+        self.codepoints
+    }
+}
+
+impl FromStr for ScriptExtensions {
+    type Err = Error;
+
+    fn from_str(line: &str) -> Result<ScriptExtensions, Error> {
+        let fields = split_fields(line);
+        if fields.len() != 2 || fields[0].is_empty() || fields[1].is_empty() {
+            return err!("invalid Script_Extensions line");
+        }
+        Ok(ScriptExtensions {
+            codepoints: fields[0].parse()?,
+            scripts: fields[1]
+                .split_whitespace()
+                .map(|s| s.to_string())
+                .collect(),
+        })
+    }
+}
+
+impl fmt::Display for ScriptExtensions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ; {}", self.codepoints, self.scripts.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Script, ScriptExtensions, Scripts};
+
+    #[test]
+    fn parse() {
+        let sc: Script = "Latn".parse().unwrap();
+        assert_eq!(sc, Script::Latin);
+        let sc: Script = "Latin".parse().unwrap();
+        assert_eq!(sc, Script::Latin);
+        let sc: Script = "Qaai".parse().unwrap();
+        assert_eq!(sc, Script::Inherited);
+        assert!("latin".parse::<Script>().is_err());
+    }
+
+    #[test]
+    fn roundtrip() {
+        let sc = Script::KatakanaOrHiragana;
+        assert_eq!(sc.to_string(), "Hrkt");
+        assert_eq!(sc.long_name(), "Katakana_Or_Hiragana");
+        assert_eq!(sc.to_string().parse::<Script>().unwrap(), sc);
+        assert_eq!(sc.long_name().parse::<Script>().unwrap(), sc);
+    }
+
+    #[test]
+    fn parse_scripts() {
+        let line = "0041..005A    ; Latin # L&  [26] \
+                    LATIN CAPITAL LETTER A..LATIN CAPITAL LETTER Z\n";
+        let row: Scripts = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x0041);
+        assert_eq!(row.codepoints.end, 0x005A);
+        assert_eq!(row.value, "Latin");
+    }
+
+    #[test]
+    fn parse_script_extensions() {
+        let line = "060C          ; Arab Rohg Syrc Thaa # Po       \
+                    ARABIC COMMA\n";
+        let row: ScriptExtensions = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x060C);
+        assert_eq!(row.codepoints.end, 0x060C);
+        assert_eq!(row.scripts, vec!["Arab", "Rohg", "Syrc", "Thaa"]);
+        assert_eq!(row.to_string(), "060C ; Arab Rohg Syrc Thaa");
+
+        let line = "1CD0..1CD2    ; Beng Deva Gran Knda";
+        let row: ScriptExtensions = line.parse().unwrap();
+        assert_eq!(row.codepoints.end, 0x1CD2);
+        assert_eq!(row.scripts.len(), 4);
+
+        assert!("060C ;".parse::<ScriptExtensions>().is_err());
+        assert!("060C ; # Po".parse::<ScriptExtensions>().is_err());
+        assert!("Arab Syrc".parse::<ScriptExtensions>().is_err());
+    }
+}
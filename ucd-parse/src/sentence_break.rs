@@ -0,0 +1,28 @@
+range_value_record!(
+    SentenceBreak, "auxiliary/SentenceBreakProperty.txt",
+    "A single row in the `auxiliary/SentenceBreakProperty.txt` file, which \
+     defines the `Sentence_Break` property.");
+
+#[cfg(test)]
+mod tests {
+    use super::SentenceBreak;
+
+    #[test]
+    fn parse_single() {
+        let line = "002E          ; ATerm # Po       FULL STOP\n";
+        let row: SentenceBreak = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x002E);
+        assert_eq!(row.codepoints.end, 0x002E);
+        assert_eq!(row.value, "ATerm");
+    }
+
+    #[test]
+    fn parse_range() {
+        let line = "0041..005A    ; Upper # L&  [26] \
+                    LATIN CAPITAL LETTER A..LATIN CAPITAL LETTER Z\n";
+        let row: SentenceBreak = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x0041);
+        assert_eq!(row.codepoints.end, 0x005A);
+        assert_eq!(row.value, "Upper");
+    }
+}
@@ -0,0 +1,160 @@
+use std::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
+use std::str::FromStr;
+
+use common::{
+    UcdFile, UcdFileByCodepoint, Codepoint, split_fields, write_codepoints,
+};
+use error::Error;
+
+/// A single row in the `SpecialCasing.txt` file.
+///
+/// This file lists the full case mappings that can't be represented in
+/// `UnicodeData.txt`, either because they map to more than one codepoint or
+/// because they only apply under certain conditions. Note that there may be
+/// multiple rows for the same codepoint, each with different conditions.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SpecialCaseMapping {
+    /// The codepoint that is being mapped.
+    pub codepoint: Codepoint,
+    /// The lowercase mapping, which may be empty.
+    pub lowercase: Vec<Codepoint>,
+    /// The titlecase mapping, which may be empty.
+    pub titlecase: Vec<Codepoint>,
+    /// The uppercase mapping, which may be empty.
+    pub uppercase: Vec<Codepoint>,
+    /// The conditions under which this mapping applies, e.g., `Final_Sigma`
+    /// or a language tag such as `tr`. When this is empty, the mapping
+    /// always applies.
+    pub conditions: Vec<String>,
+}
+
+impl UcdFile for SpecialCaseMapping {
+    #[cfg(feature = "std")]
+    fn relative_file_path() -> &'static Path {
+        Path::new("SpecialCasing.txt")
+    }
+}
+
+impl UcdFileByCodepoint for SpecialCaseMapping {
+    fn codepoint(&self) -> Codepoint {
+        self.codepoint
+    }
+}
+
+impl SpecialCaseMapping {
+    /// Returns true if and only if this mapping only applies under certain
+    /// conditions.
+    pub fn is_conditional(&self) -> bool {
+        !self.conditions.is_empty()
+    }
+
+    /// Parse a single line.
+    pub fn parse_line(line: &str) -> Result<SpecialCaseMapping, Error> {
+        // The uppercase mapping, and the conditions if there are any, are
+        // followed by a `;`, so the last field is always empty.
+        let fields = split_fields(line);
+        let conditions = match fields.len() {
+            5 if fields[4].is_empty() => vec![],
+            6 if !fields[4].is_empty() && fields[5].is_empty() => {
+                fields[4].split_whitespace().map(|s| s.to_string()).collect()
+            }
+            _ => return err!("invalid SpecialCasing line"),
+        };
+        Ok(SpecialCaseMapping {
+            codepoint: fields[0].parse()?,
+            lowercase: parse_codepoints(fields[1])?,
+            titlecase: parse_codepoints(fields[2])?,
+            uppercase: parse_codepoints(fields[3])?,
+            conditions: conditions,
+        })
+    }
+}
+
+fn parse_codepoints(s: &str) -> Result<Vec<Codepoint>, Error> {
+    let mut codepoints = vec![];
+    for cp in s.split_whitespace() {
+        codepoints.push(cp.parse()?);
+    }
+    Ok(codepoints)
+}
+
+impl FromStr for SpecialCaseMapping {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<SpecialCaseMapping, Error> {
+        SpecialCaseMapping::parse_line(s)
+    }
+}
+
+impl fmt::Display for SpecialCaseMapping {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}; ", self.codepoint)?;
+        write_codepoints(f, &self.lowercase)?;
+        write!(f, "; ")?;
+        write_codepoints(f, &self.titlecase)?;
+        write!(f, "; ")?;
+        write_codepoints(f, &self.uppercase)?;
+        write!(f, ";")?;
+        if self.is_conditional() {
+            write!(f, " {};", self.conditions.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpecialCaseMapping;
+
+    #[test]
+    fn parse_unconditional() {
+        let line = "00DF; 00DF; 0053 0073; 0053 0053; \
+                    # LATIN SMALL LETTER SHARP S\n";
+        let row: SpecialCaseMapping = line.parse().unwrap();
+        assert_eq!(row.codepoint, 0x00DF);
+        assert_eq!(row.lowercase, vec![0x00DF]);
+        assert_eq!(row.titlecase, vec![0x0053, 0x0073]);
+        assert_eq!(row.uppercase, vec![0x0053, 0x0053]);
+        assert!(!row.is_conditional());
+    }
+
+    #[test]
+    fn parse_conditional() {
+        let line = "03A3; 03C2; 03A3; 03A3; Final_Sigma; \
+                    # GREEK CAPITAL LETTER SIGMA\n";
+        let row: SpecialCaseMapping = line.parse().unwrap();
+        assert_eq!(row.lowercase, vec![0x03C2]);
+        assert_eq!(row.conditions, vec!["Final_Sigma"]);
+
+        let line = "0307; ; 0307; 0307; tr After_I; # COMBINING DOT ABOVE\n";
+        let row: SpecialCaseMapping = line.parse().unwrap();
+        assert!(row.lowercase.is_empty());
+        assert_eq!(row.conditions, vec!["tr", "After_I"]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!("00DF; 00DF; 0053 0073;".parse::<SpecialCaseMapping>()
+                .is_err());
+        assert!("00DF; XYZ; 0053; 0053;".parse::<SpecialCaseMapping>()
+                .is_err());
+    }
+
+    #[test]
+    fn display() {
+        let line = "0307; ; 0307; 0307; tr After_I; # COMBINING DOT ABOVE\n";
+        let row: SpecialCaseMapping = line.parse().unwrap();
+        assert_eq!(row.to_string(), "0307; ; 0307; 0307; tr After_I;");
+        let parsed: SpecialCaseMapping = row.to_string().parse().unwrap();
+        assert_eq!(parsed, row);
+
+        let line = "FB00; FB00; 0046 0066; 0046 0046; # LATIN SMALL LIGATURE";
+        let row: SpecialCaseMapping = line.parse().unwrap();
+        assert_eq!(row.to_string(), "FB00; FB00; 0046 0066; 0046 0046;");
+    }
+}
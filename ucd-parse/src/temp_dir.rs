@@ -0,0 +1,50 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A directory for the files written by a test, which is removed when it is
+/// dropped.
+///
+/// Every directory has a unique path, so that tests running in parallel,
+/// even in different processes, never write to the same files.
+#[derive(Debug)]
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Create a new empty directory whose name starts with `prefix`.
+    pub fn new(prefix: &str) -> TempDir {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        let n = COUNT.fetch_add(1, Ordering::SeqCst);
+        let path = env::temp_dir()
+            .join(format!("{}-{}-{}", prefix, process::id(), n));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+
+    /// Return the path of this directory.
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+
+    /// Write a file at the given path relative to this directory, replacing
+    /// it if it already exists, and return its full path.
+    pub fn write<P: AsRef<Path>>(&self, relative: P, data: &[u8]) -> PathBuf {
+        let path = self.0.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        File::create(&path).unwrap().write_all(data).unwrap();
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
@@ -2,13 +2,16 @@ use std::borrow::Cow;
 use std::fmt;
 use std::iter;
 use std::ops::Range;
+#[cfg(feature = "std")]
 use std::path::Path;
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
 use std::str::FromStr;
 
-use regex::Regex;
-
-use common::{UcdFile, UcdFileByCodepoint, Codepoint};
-use error::Error;
+use common::{
+    UcdFile, UcdFileByCodepoint, Codepoint, CodepointRange, is_hex_codepoint,
+};
+use error::{Error, error_parse, error_set_line};
 
 /// Represents a single row in the `UnicodeData.txt` file.
 ///
@@ -16,12 +19,16 @@ use error::Error;
 /// for the `UnicodeData.txt` file:
 /// http://www.unicode.org/reports/tr44/#UnicodeData.txt
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UnicodeData<'a> {
     /// The codepoint corresponding to this row.
     pub codepoint: Codepoint,
     /// The name of this codepoint.
     pub name: Cow<'a, str>,
     /// The "general category" of this codepoint.
+    ///
+    /// This is the raw abbreviated value, e.g., `Lu`. It can be converted
+    /// to a `GeneralCategory` with `str::parse`.
     pub general_category: Cow<'a, str>,
     /// The class of this codepoint used in the Canonical Ordering Algorithm.
     ///
@@ -32,6 +39,9 @@ pub struct UnicodeData<'a> {
     ///
     /// Possible values are listed in UAX44, Table 13:
     /// http://www.unicode.org/reports/tr44/#Bidi_Class_Values
+    ///
+    /// This is the raw abbreviated value, e.g., `L`. It can be converted to
+    /// a `BidiClass` with `str::parse`.
     pub bidi_class: Cow<'a, str>,
     /// The decomposition mapping for this codepoint. This includes its
     /// formatting tag (if present).
@@ -66,6 +76,7 @@ pub struct UnicodeData<'a> {
 }
 
 impl UcdFile for UnicodeData<'static> {
+    #[cfg(feature = "std")]
     fn relative_file_path() -> &'static Path {
         Path::new("UnicodeData.txt")
     }
@@ -80,20 +91,25 @@ impl UcdFileByCodepoint for UnicodeData<'static> {
 impl<'a> UnicodeData<'a> {
     /// Convert this record into an owned value such that it no longer
     /// borrows from the original line that it was parsed from.
+    ///
+    /// Field values that repeat across many records, such as the general
+    /// category, the bidi class and empty fields, are replaced with
+    /// borrowed static strings instead of being allocated.
     pub fn into_owned(self) -> UnicodeData<'static> {
         UnicodeData {
             codepoint: self.codepoint,
-            name: Cow::Owned(self.name.into_owned()),
-            general_category: Cow::Owned(self.general_category.into_owned()),
+            name: intern(self.name, &[]),
+            general_category: intern(
+                self.general_category, GENERAL_CATEGORIES),
             canonical_combining_class: self.canonical_combining_class,
-            bidi_class: Cow::Owned(self.bidi_class.into_owned()),
+            bidi_class: intern(self.bidi_class, BIDI_CLASSES),
             decomposition: self.decomposition,
             numeric_type_decimal: self.numeric_type_decimal,
             numeric_type_digit: self.numeric_type_digit,
             numeric_type_numeric: self.numeric_type_numeric,
             bidi_mirrored: self.bidi_mirrored,
-            unicode1_name: Cow::Owned(self.unicode1_name.into_owned()),
-            iso_comment: Cow::Owned(self.iso_comment.into_owned()),
+            unicode1_name: intern(self.unicode1_name, &[]),
+            iso_comment: intern(self.iso_comment, &[]),
             simple_uppercase_mapping: self.simple_uppercase_mapping,
             simple_lowercase_mapping: self.simple_lowercase_mapping,
             simple_titlecase_mapping: self.simple_titlecase_mapping,
@@ -102,81 +118,83 @@ impl<'a> UnicodeData<'a> {
 
     /// Parse a single line.
     pub fn parse_line(line: &'a str) -> Result<UnicodeData<'a>, Error> {
-        lazy_static! {
-            static ref PARTS: Regex = Regex::new(
-                r"(?x)
-                ^
-                ([A-Z0-9]+);  #  1; codepoint
-                ([^;]+);      #  2; name
-                ([^;]+);      #  3; general category
-                ([0-9]+);     #  4; canonical combining class
-                ([^;]+);      #  5; bidi class
-                ([^;]*);      #  6; decomposition
-                ([0-9]*);     #  7; numeric type decimal
-                ([0-9]*);     #  8; numeric type digit
-                ([-0-9/]*);   #  9; numeric type numeric
-                ([YN]);       # 10; bidi mirrored
-                ([^;]*);      # 11; unicode1 name
-                ([^;]*);      # 12; ISO comment
-                ([^;]*);      # 13; simple uppercase mapping
-                ([^;]*);      # 14; simple lowercase mapping
-                ([^;]*)       # 15; simple titlecase mapping
-                $
-                "
-            ).unwrap();
-        };
-        let caps = match PARTS.captures(line.trim()) {
-            Some(caps) => caps,
-            None => return err!("invalid UnicodeData line"),
-        };
-        let capget = |n| caps.get(n).unwrap().as_str();
+        // Every line has exactly 15 fields separated by `;`:
+        //
+        //   0 codepoint               5 decomposition        10 unicode1 name
+        //   1 name                    6 numeric decimal      11 ISO comment
+        //   2 general category        7 numeric digit        12 uppercase
+        //   3 canonical combining     8 numeric numeric      13 lowercase
+        //   4 bidi class              9 bidi mirrored        14 titlecase
+        let mut fields = [""; 15];
+        let mut it = line.trim().split(';');
+        for field in fields.iter_mut() {
+            *field = match it.next() {
+                Some(field) => field,
+                None => return err!("invalid UnicodeData line"),
+            };
+        }
+        if it.next().is_some()
+            || !is_hex_codepoint(fields[0])
+            || fields[1].is_empty()
+            || fields[2].is_empty()
+            || fields[3].is_empty()
+            || !is_digits(fields[3])
+            || fields[4].is_empty()
+            || !is_digits(fields[6])
+            || !is_digits(fields[7])
+            || !fields[8].bytes().all(|b| b == b'-' || b == b'/'
+                                           || (b'0' <= b && b <= b'9'))
+            || (fields[9] != "Y" && fields[9] != "N")
+        {
+            return err!("invalid UnicodeData line");
+        }
         let mut data = UnicodeData::default();
 
-        data.codepoint = capget(1).parse()?;
-        data.name = Cow::Borrowed(capget(2));
-        data.general_category = Cow::Borrowed(capget(3));
-        data.canonical_combining_class = match capget(4).parse() {
+        data.codepoint = fields[0].parse()?;
+        data.name = Cow::Borrowed(fields[1]);
+        data.general_category = Cow::Borrowed(fields[2]);
+        data.canonical_combining_class = match fields[3].parse() {
             Ok(n) => n,
             Err(err) => return err!(
                 "failed to parse canonical combining class '{}': {}",
-                capget(4), err),
+                fields[3], err),
         };
-        data.bidi_class = Cow::Borrowed(capget(5));
-        if !caps[6].is_empty() {
-            data.decomposition = caps[6].parse()?;
+        data.bidi_class = Cow::Borrowed(fields[4]);
+        if !fields[5].is_empty() {
+            data.decomposition = fields[5].parse()?;
         } else {
             data.decomposition.push(data.codepoint)?;
         }
-        if !capget(7).is_empty() {
-            data.numeric_type_decimal = Some(match capget(7).parse() {
+        if !fields[6].is_empty() {
+            data.numeric_type_decimal = Some(match fields[6].parse() {
                 Ok(n) => n,
                 Err(err) => return err!(
                     "failed to parse numeric type decimal '{}': {}",
-                    capget(7), err),
+                    fields[6], err),
             });
         }
-        if !capget(8).is_empty() {
-            data.numeric_type_digit = Some(match capget(8).parse() {
+        if !fields[7].is_empty() {
+            data.numeric_type_digit = Some(match fields[7].parse() {
                 Ok(n) => n,
                 Err(err) => return err!(
                     "failed to parse numeric type digit '{}': {}",
-                    capget(8), err),
+                    fields[7], err),
             });
         }
-        if !capget(9).is_empty() {
-            data.numeric_type_numeric = Some(capget(9).parse()?);
+        if !fields[8].is_empty() {
+            data.numeric_type_numeric = Some(fields[8].parse()?);
         }
-        data.bidi_mirrored = capget(10) == "Y";
-        data.unicode1_name = Cow::Borrowed(capget(11));
-        data.iso_comment = Cow::Borrowed(capget(12));
-        if !capget(13).is_empty() {
-            data.simple_uppercase_mapping = Some(capget(13).parse()?);
+        data.bidi_mirrored = fields[9] == "Y";
+        data.unicode1_name = Cow::Borrowed(fields[10]);
+        data.iso_comment = Cow::Borrowed(fields[11]);
+        if !fields[12].is_empty() {
+            data.simple_uppercase_mapping = Some(fields[12].parse()?);
         }
-        if !capget(14).is_empty() {
-            data.simple_lowercase_mapping = Some(capget(14).parse()?);
+        if !fields[13].is_empty() {
+            data.simple_lowercase_mapping = Some(fields[13].parse()?);
         }
-        if !capget(15).is_empty() {
-            data.simple_titlecase_mapping = Some(capget(15).parse()?);
+        if !fields[14].is_empty() {
+            data.simple_titlecase_mapping = Some(fields[14].parse()?);
         }
         Ok(data)
     }
@@ -198,6 +216,36 @@ impl<'a> UnicodeData<'a> {
     }
 }
 
+/// Returns true if and only if the given string contains only ASCII digits.
+fn is_digits(s: &str) -> bool {
+    s.bytes().all(|b| b'0' <= b && b <= b'9')
+}
+
+/// Every General_Category value that appears in `UnicodeData.txt`.
+const GENERAL_CATEGORIES: &'static [&'static str] = &[
+    "Cc", "Cf", "Cn", "Co", "Cs", "Ll", "Lm", "Lo", "Lt", "Lu", "Mc", "Me",
+    "Mn", "Nd", "Nl", "No", "Pc", "Pd", "Pe", "Pf", "Pi", "Po", "Ps", "Sc",
+    "Sk", "Sm", "So", "Zl", "Zp", "Zs",
+];
+
+/// Every Bidi_Class value that appears in `UnicodeData.txt`.
+const BIDI_CLASSES: &'static [&'static str] = &[
+    "AL", "AN", "B", "BN", "CS", "EN", "ES", "ET", "FSI", "L", "LRE", "LRI",
+    "LRO", "NSM", "ON", "PDF", "PDI", "R", "RLE", "RLI", "RLO", "S", "WS",
+];
+
+/// Convert the given string to a `'static` string without allocating if it
+/// is empty or if it is in the given sorted table of known values.
+fn intern<'a>(s: Cow<'a, str>, known: &[&'static str]) -> Cow<'static, str> {
+    if s.is_empty() {
+        return Cow::Borrowed("");
+    }
+    match known.binary_search(&&*s) {
+        Ok(i) => Cow::Borrowed(known[i]),
+        Err(_) => Cow::Owned(s.into_owned()),
+    }
+}
+
 impl FromStr for UnicodeData<'static> {
     type Err = Error;
 
@@ -258,6 +306,7 @@ impl<'a> fmt::Display for UnicodeData<'a> {
 /// Represents a decomposition mapping of a single row in the
 /// `UnicodeData.txt` file.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UnicodeDataDecomposition {
     /// The formatting tag associated with this mapping, if present.
     pub tag: Option<UnicodeDataDecompositionTag>,
@@ -313,29 +362,28 @@ impl FromStr for UnicodeDataDecomposition {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<UnicodeDataDecomposition, Error> {
-        lazy_static! {
-            static ref WITH_TAG: Regex = Regex::new(
-                r"^(?:<(?P<tag>[^>]+)>)?\s*(?P<chars>[\s0-9A-F]+)$"
-            ).unwrap();
-            static ref CHARS: Regex = Regex::new(r"[0-9A-F]+").unwrap();
-        };
         if s.is_empty() {
             return err!("expected non-empty string for \
                          UnicodeDataDecomposition value");
         }
-        let caps = match WITH_TAG.captures(s) {
-            Some(caps) => caps,
-            None => return err!("invalid decomposition value"),
-        };
         let mut decomp = UnicodeDataDecomposition::default();
         let mut codepoints = s;
-        if let Some(m) = caps.name("tag") {
-            decomp.tag = Some(m.as_str().parse()?);
-            codepoints = &caps["chars"];
+        if s.starts_with('<') {
+            let end = match s.find('>') {
+                Some(end) if end > 1 => end,
+                _ => return err!("invalid decomposition value"),
+            };
+            decomp.tag = Some(s[1..end].parse()?);
+            codepoints = &s[end + 1..];
+        }
+        for cp in codepoints.split_whitespace() {
+            if !is_hex_codepoint(cp) {
+                return err!("invalid decomposition value");
+            }
+            decomp.push(cp.parse()?)?;
         }
-        for m in CHARS.find_iter(codepoints) {
-            let cp = m.as_str().parse()?;
-            decomp.push(cp)?;
+        if decomp.len == 0 {
+            return err!("invalid decomposition value");
         }
         Ok(decomp)
     }
@@ -363,6 +411,7 @@ impl fmt::Display for UnicodeDataDecomposition {
 /// This is taken from UAX44, Table 14:
 /// http://www.unicode.org/reports/tr44/#Character_Decomposition_Mappings
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum UnicodeDataDecompositionTag {
     /// <font>
     Font,
@@ -454,6 +503,7 @@ impl fmt::Display for UnicodeDataDecompositionTag {
 ///
 /// A numeric value can either be a signed integer or a rational number.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum UnicodeDataNumeric {
     /// An integer.
     Integer(i64),
@@ -462,6 +512,13 @@ pub enum UnicodeDataNumeric {
     Rational(i64, i64),
 }
 
+impl Default for UnicodeDataNumeric {
+    fn default() -> UnicodeDataNumeric {
+        // This is arbitrary, but the Default impl is convenient.
+        UnicodeDataNumeric::Integer(0)
+    }
+}
+
 impl FromStr for UnicodeDataNumeric {
     type Err = Error;
 
@@ -529,15 +586,17 @@ pub struct UnicodeDataExpander<I: Iterator> {
     it: iter::Peekable<I>,
     /// A range of codepoints to emit when we've found a pair. Otherwise,
     /// `None`.
-    range: CodepointRange,
+    range: ExpandedRange,
 }
 
-struct CodepointRange {
+struct ExpandedRange {
     /// The codepoint range.
     range: Range<u32>,
-    /// The start record. All subsequent records in this range are generated
-    /// by cloning this and updating the codepoint/name.
-    start_record: UnicodeData<'static>,
+    /// The start record with an empty name. All records in this range are
+    /// generated by cloning this and updating the codepoint. Since the name
+    /// is cleared once up front, cloning this doesn't allocate unless some
+    /// other field of the start record is owned.
+    template: UnicodeData<'static>,
 }
 
 impl<I: Iterator<Item=UnicodeData<'static>>> UnicodeDataExpander<I> {
@@ -548,9 +607,9 @@ impl<I: Iterator<Item=UnicodeData<'static>>> UnicodeDataExpander<I> {
     {
         UnicodeDataExpander {
             it: it.into_iter().peekable(),
-            range: CodepointRange {
+            range: ExpandedRange {
                 range: 0..0,
-                start_record: UnicodeData::default(),
+                template: UnicodeData::default(),
             },
         }
     }
@@ -575,15 +634,15 @@ impl<I: Iterator<Item=UnicodeData<'static>>>
             return Some(row1)
         }
         let row2 = self.it.next().unwrap();
-        self.range = CodepointRange {
+        self.range = ExpandedRange {
             range: row1.codepoint.value()..(row2.codepoint.value() + 1),
-            start_record: row1,
+            template: UnicodeData { name: Cow::Borrowed(""), ..row1 },
         };
         self.next()
     }
 }
 
-impl Iterator for CodepointRange {
+impl Iterator for ExpandedRange {
     type Item = UnicodeData<'static>;
 
     fn next(&mut self) -> Option<UnicodeData<'static>> {
@@ -591,11 +650,388 @@ impl Iterator for CodepointRange {
             None => return None,
             Some(cp) => cp,
         };
-        Some(UnicodeData {
-            codepoint: Codepoint::from_u32(cp).unwrap(),
-            name: Cow::Borrowed(""),
-            ..self.start_record.clone()
-        })
+        let mut data = self.template.clone();
+        let cp = Codepoint::from_u32(cp).unwrap();
+        if decomposes_to_self(&self.template) {
+            data.decomposition.mapping[0] = cp;
+        }
+        data.codepoint = cp;
+        Some(data)
+    }
+}
+
+/// An iterator adapter that groups rows in `UnicodeData.txt` into ranges.
+///
+/// This is like `UnicodeDataExpander`, except a pair of rows that represents
+/// a range of codepoints is yielded as a single item instead of one record
+/// for each codepoint in the range. This is much cheaper when the caller
+/// only needs to know which codepoints share a set of properties.
+///
+/// Each item is an inclusive range of codepoints along with the record for
+/// those codepoints. Records that aren't part of a range are yielded with a
+/// range containing only their codepoint. As with `UnicodeDataExpander`,
+/// the record for a range has an empty name and the codepoint at the start
+/// of the range.
+pub struct UnicodeDataRanges<I: Iterator> {
+    /// The underlying iterator.
+    it: iter::Peekable<I>,
+}
+
+impl<I: Iterator<Item=UnicodeData<'static>>> UnicodeDataRanges<I> {
+    /// Create a new iterator that groups pairs of `UnicodeData` range
+    /// records. All other records are passed through as single codepoint
+    /// ranges.
+    pub fn new<T>(it: T) -> UnicodeDataRanges<I>
+            where T: IntoIterator<IntoIter=I, Item=I::Item>
+    {
+        UnicodeDataRanges { it: it.into_iter().peekable() }
+    }
+}
+
+impl<I: Iterator<Item=UnicodeData<'static>>>
+    Iterator for UnicodeDataRanges<I>
+{
+    type Item = (CodepointRange, UnicodeData<'static>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row1 = match self.it.next() {
+            None => return None,
+            Some(row1) => row1,
+        };
+        if !row1.is_range_start()
+            || !self.it.peek().map_or(false, |row2| row2.is_range_end())
+        {
+            return Some((CodepointRange::single(row1.codepoint), row1));
+        }
+        let row2 = self.it.next().unwrap();
+        let data = UnicodeData { name: Cow::Borrowed(""), ..row1 };
+        let range = CodepointRange {
+            start: data.codepoint,
+            end: row2.codepoint,
+        };
+        Some((range, data))
+    }
+}
+
+/// An iterator adapter that collapses runs of records back into pairs of
+/// rows that represent a range of codepoints.
+///
+/// This is the inverse of `UnicodeDataExpander`. A run is a sequence of two
+/// or more records with consecutive codepoints, empty names and otherwise
+/// identical properties. Since the name of a range (e.g., `CJK Ideograph`)
+/// isn't retained when a range is expanded, the caller provides a function
+/// that returns the name for each run. If it returns `None`, then the records
+/// in the run are passed through as-is. All other records are always passed
+/// through as-is.
+///
+/// Each run that is named is yielded as two records: one at the start of the
+/// range with a name like `<CJK Ideograph, First>`, and one at the end of the
+/// range with a name like `<CJK Ideograph, Last>`. This is the form used in
+/// `UnicodeData.txt`.
+pub struct UnicodeDataCompactor<I: Iterator, F> {
+    /// The underlying iterator.
+    it: iter::Peekable<I>,
+    /// A function that returns the name of a range of codepoints.
+    range_name: F,
+    /// The record at the end of a range whose start was just emitted.
+    last: Option<UnicodeData<'static>>,
+    /// The records in a run that wasn't named, excluding the first.
+    range: ExpandedRange,
+}
+
+impl<I, F> UnicodeDataCompactor<I, F>
+where I: Iterator<Item=UnicodeData<'static>>,
+      F: FnMut(CodepointRange) -> Option<String>
+{
+    /// Create a new iterator that collapses runs of records into pairs of
+    /// `UnicodeData` range records, using `range_name` to name each range.
+    pub fn new<T>(it: T, range_name: F) -> UnicodeDataCompactor<I, F>
+            where T: IntoIterator<IntoIter=I, Item=I::Item>
+    {
+        UnicodeDataCompactor {
+            it: it.into_iter().peekable(),
+            range_name: range_name,
+            last: None,
+            range: ExpandedRange {
+                range: 0..0,
+                template: UnicodeData::default(),
+            },
+        }
+    }
+}
+
+impl<I, F> Iterator for UnicodeDataCompactor<I, F>
+where I: Iterator<Item=UnicodeData<'static>>,
+      F: FnMut(CodepointRange) -> Option<String>
+{
+    type Item = UnicodeData<'static>;
+
+    fn next(&mut self) -> Option<UnicodeData<'static>> {
+        if let Some(last) = self.last.take() {
+            return Some(last);
+        }
+        if let Some(udata) = self.range.next() {
+            return Some(udata);
+        }
+        let first = match self.it.next() {
+            None => return None,
+            Some(first) => first,
+        };
+        if !first.name.is_empty() {
+            return Some(first);
+        }
+        let mut end = first.codepoint;
+        while let Some(next) = self.it.peek() {
+            if !next.name.is_empty()
+                || Some(next.codepoint) != end.next()
+                || !same_range_properties(&first, next)
+            {
+                break;
+            }
+            end = next.codepoint;
+            self.it.next();
+        }
+        if end == first.codepoint {
+            return Some(first);
+        }
+        let range = CodepointRange { start: first.codepoint, end: end };
+        let name = match (self.range_name)(range) {
+            Some(name) => name,
+            None => {
+                self.range = ExpandedRange {
+                    range: (range.start.value() + 1)..(range.end.value() + 1),
+                    template: first.clone(),
+                };
+                return Some(first);
+            }
+        };
+        let mut last = first.clone();
+        last.codepoint = end;
+        if decomposes_to_self(&first) {
+            last.decomposition.mapping[0] = end;
+        }
+        last.name = Cow::Owned(format!("<{}, Last>", name));
+        self.last = Some(last);
+        let name = Cow::Owned(format!("<{}, First>", name));
+        Some(UnicodeData { name: name, ..first })
+    }
+}
+
+/// Returns true if and only if the given record has no decomposition
+/// mapping, which is represented by a mapping to its own codepoint.
+fn decomposes_to_self(udata: &UnicodeData) -> bool {
+    udata.decomposition.is_canonical()
+    && udata.decomposition.mapping() == &[udata.codepoint]
+}
+
+/// Returns true if and only if the given records have the same properties,
+/// ignoring their codepoints and names.
+fn same_range_properties(a: &UnicodeData, b: &UnicodeData) -> bool {
+    let same_decomposition =
+        if decomposes_to_self(a) || decomposes_to_self(b) {
+            decomposes_to_self(a) && decomposes_to_self(b)
+        } else {
+            a.decomposition == b.decomposition
+        };
+    same_decomposition
+    && a.general_category == b.general_category
+    && a.canonical_combining_class == b.canonical_combining_class
+    && a.bidi_class == b.bidi_class
+    && a.numeric_type_decimal == b.numeric_type_decimal
+    && a.numeric_type_digit == b.numeric_type_digit
+    && a.numeric_type_numeric == b.numeric_type_numeric
+    && a.bidi_mirrored == b.bidi_mirrored
+    && a.unicode1_name == b.unicode1_name
+    && a.iso_comment == b.iso_comment
+    && a.simple_uppercase_mapping == b.simple_uppercase_mapping
+    && a.simple_lowercase_mapping == b.simple_lowercase_mapping
+    && a.simple_titlecase_mapping == b.simple_titlecase_mapping
+}
+
+/// An index over the rows in `UnicodeData.txt` that permits looking up a
+/// single codepoint without parsing every row.
+///
+/// Building the index only requires finding the codepoint at the start of
+/// each row. Rows are parsed lazily, and callers that only need one or two
+/// fields can fetch those fields without parsing anything else.
+///
+/// Codepoints that are part of a range (such as the Hangul syllables) are
+/// resolved to the row at the start of their range, in the same way as
+/// `UnicodeDataExpander`.
+#[derive(Clone, Debug)]
+pub struct UnicodeDataIndex {
+    /// The full contents of `UnicodeData.txt`.
+    data: String,
+    /// One entry for every row, sorted by codepoint.
+    rows: Vec<IndexRow>,
+    /// Inclusive codepoint ranges, along with the index of the row at the
+    /// start of each range.
+    ranges: Vec<(u32, u32, usize)>,
+}
+
+#[derive(Clone, Debug)]
+struct IndexRow {
+    /// The codepoint at the start of this row.
+    codepoint: u32,
+    /// The line number of this row, starting at 1.
+    line_number: u64,
+    /// The byte offsets of this row in the file, excluding its line
+    /// terminator.
+    span: Range<usize>,
+}
+
+impl UnicodeDataIndex {
+    /// Build an index of the `UnicodeData.txt` file in the given UCD
+    /// directory.
+    #[cfg(feature = "std")]
+    pub fn from_dir<P: AsRef<Path>>(
+        ucd_dir: P,
+    ) -> Result<UnicodeDataIndex, Error> {
+        use std::io::Read;
+        use reader::UcdReader;
+
+        let relative = UnicodeData::relative_file_path();
+        let mut data = String::new();
+        UcdReader::open(ucd_dir, relative)?.read_to_string(&mut data)?;
+        UnicodeDataIndex::new(data)
+    }
+
+    /// Build an index of the given contents of a `UnicodeData.txt` file.
+    pub fn new(data: String) -> Result<UnicodeDataIndex, Error> {
+        let mut rows: Vec<IndexRow> = vec![];
+        let mut ranges = vec![];
+        let mut range_start = None;
+        let mut offset = 0;
+        for (i, line) in data.split('\n').enumerate() {
+            let span = offset..(offset + line.trim_right().len());
+            offset += line.len() + 1;
+            if line.starts_with('#') || line.trim().is_empty() {
+                continue;
+            }
+            let line_number = i as u64 + 1;
+            let mut fields = line.splitn(3, ';');
+            let cp: Codepoint = match fields.next().unwrap().parse() {
+                Ok(cp) => cp,
+                Err(mut err) => {
+                    error_set_line(&mut err, Some(line_number));
+                    return Err(err);
+                }
+            };
+            let cp = cp.value();
+            if rows.last().map_or(false, |row| row.codepoint >= cp) {
+                let mut err = error_parse(format!(
+                    "codepoint {:04X} is out of order", cp));
+                error_set_line(&mut err, Some(line_number));
+                return Err(err);
+            }
+            let name = fields.next().unwrap_or("");
+            if name.starts_with('<') && name.ends_with(", First>") {
+                range_start = Some((cp, rows.len()));
+            } else if name.starts_with('<') && name.ends_with(", Last>") {
+                if let Some((start, row)) = range_start.take() {
+                    ranges.push((start, cp, row));
+                }
+            }
+            rows.push(IndexRow {
+                codepoint: cp,
+                line_number: line_number,
+                span: span,
+            });
+        }
+        Ok(UnicodeDataIndex { data: data, rows: rows, ranges: ranges })
+    }
+
+    /// Returns the number of rows in this index. Each pair of rows that
+    /// corresponds to a range is counted as two rows.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns true if and only if this index has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Returns true if and only if the given codepoint is assigned, i.e., it
+    /// has a row or is part of a range.
+    pub fn contains(&self, cp: Codepoint) -> bool {
+        self.find(cp).is_some()
+    }
+
+    /// Returns the unparsed row for the given codepoint. If the codepoint is
+    /// part of a range, then the row at the start of the range is returned.
+    pub fn line(&self, cp: Codepoint) -> Option<&str> {
+        self.find(cp).map(|(row, _)| &self.data[row.span.clone()])
+    }
+
+    /// Returns a single unparsed field of the row for the given codepoint.
+    ///
+    /// Fields are numbered as in UAX44, Table 9. That is, field `0` is the
+    /// codepoint, field `1` is the name and so on. Note that the name of a
+    /// codepoint in a range is the name of the range's start row.
+    pub fn field(&self, cp: Codepoint, field: usize) -> Option<&str> {
+        self.line(cp).and_then(|line| line.split(';').nth(field))
+    }
+
+    /// Parse the row for the given codepoint.
+    ///
+    /// If the codepoint is part of a range, then the row returned is the
+    /// row at the start of the range, with its codepoint set to the given
+    /// codepoint and with an empty name. This is the same record that
+    /// `UnicodeDataExpander` produces.
+    ///
+    /// If the codepoint is unassigned, then `None` is returned.
+    pub fn get(&self, cp: Codepoint) -> Result<Option<UnicodeData>, Error> {
+        let (row, in_range) = match self.find(cp) {
+            None => return Ok(None),
+            Some(found) => found,
+        };
+        let mut data = self.parse_row(row)?;
+        if in_range {
+            if decomposes_to_self(&data) {
+                data.decomposition.mapping[0] = cp;
+            }
+            data.codepoint = cp;
+            data.name = Cow::Borrowed("");
+        }
+        Ok(Some(data))
+    }
+
+    /// Returns an iterator over every parsed row, in the order in which
+    /// they appear in `UnicodeData.txt`. Ranges are not expanded.
+    pub fn rows<'a>(
+        &'a self,
+    ) -> Box<Iterator<Item=Result<UnicodeData<'a>, Error>> + 'a> {
+        Box::new(self.rows.iter().map(move |row| self.parse_row(row)))
+    }
+
+    fn parse_row(&self, row: &IndexRow) -> Result<UnicodeData, Error> {
+        UnicodeData::parse_line(&self.data[row.span.clone()]).map_err(
+            |mut err| {
+                error_set_line(&mut err, Some(row.line_number));
+                err
+            })
+    }
+
+    /// Find the row for the given codepoint, along with whether the
+    /// codepoint is part of a range.
+    fn find(&self, cp: Codepoint) -> Option<(&IndexRow, bool)> {
+        let cp = cp.value();
+        let i = match self.ranges.binary_search_by_key(&cp, |r| r.0) {
+            Ok(i) => Some(i),
+            Err(0) => None,
+            Err(i) => Some(i - 1),
+        };
+        if let Some(i) = i {
+            let (_, end, row) = self.ranges[i];
+            if cp <= end {
+                return Some((&self.rows[row], true));
+            }
+        }
+        self.rows
+            .binary_search_by_key(&cp, |row| row.codepoint)
+            .ok()
+            .map(|i| (&self.rows[i], false))
     }
 }
 
@@ -738,6 +1174,25 @@ mod tests {
         });
     }
 
+    #[test]
+    fn parse_errors() {
+        let lines = &[
+            "0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061",
+            "0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;;",
+            "0041;;Lu;0;L;;;;;N;;;;0061;",
+            "0041;LATIN CAPITAL LETTER A;Lu;x;L;;;;;N;;;;0061;",
+            "0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;X;;;;0061;",
+            "0041;LATIN CAPITAL LETTER A;Lu;0;L;;a;;;N;;;;0061;",
+            "0041;LATIN CAPITAL LETTER A;Lu;0;L;<font>;;;;N;;;;0061;",
+            "0041;LATIN CAPITAL LETTER A;Lu;0;L;<> 0061;;;;N;;;;0061;",
+            "0041;LATIN CAPITAL LETTER A;Lu;0;L;0x61;;;;N;;;;0061;",
+            "U+0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;",
+        ];
+        for line in lines {
+            assert!(line.parse::<UnicodeData>().is_err(), "{}", line);
+        }
+    }
+
     #[test]
     fn expander() {
         use common::UcdLineParser;
@@ -754,4 +1209,160 @@ D7B0;HANGUL JUNGSEONG O-YEO;Lo;0;L;;;;;N;;;;;
             .unwrap();
         assert_eq!(UnicodeDataExpander::new(records).count(), 11174);
     }
+
+    #[test]
+    fn compactor() {
+        use common::UcdLineParser;
+        use super::{UnicodeDataCompactor, UnicodeDataExpander};
+
+        let data = "\
+ABF9;MEETEI MAYEK DIGIT NINE;Nd;0;L;;9;9;9;N;;;;;
+AC00;<Hangul Syllable, First>;Lo;0;L;;;;;N;;;;;
+D7A3;<Hangul Syllable, Last>;Lo;0;L;;;;;N;;;;;
+D7B0;HANGUL JUNGSEONG O-YEO;Lo;0;L;;;;;N;;;;;
+";
+        let records: Vec<UnicodeData> = UcdLineParser::new(data.as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let expanded: Vec<_> = UnicodeDataExpander::new(records.clone())
+            .collect();
+        assert_eq!(expanded[2].to_string(), "AC01;;Lo;0;L;;;;;N;;;;;");
+
+        let compacted: Vec<_> = UnicodeDataCompactor::new(
+            expanded.clone(),
+            |_| Some("Hangul Syllable".to_string()),
+        ).collect();
+        assert_eq!(compacted, records);
+
+        let unnamed: Vec<_> = UnicodeDataCompactor::new(
+            expanded.clone(),
+            |_| None,
+        ).collect();
+        assert_eq!(unnamed, expanded);
+
+        // A record with different properties splits a run.
+        let mut split = expanded.clone();
+        split[2].general_category = Cow::Borrowed("Cn");
+        let compacted: Vec<_> = UnicodeDataCompactor::new(
+            split,
+            |r| if r.count() > 1 { Some("X".to_string()) } else { None },
+        ).collect();
+        assert_eq!(compacted.len(), 6);
+        assert_eq!(compacted[1].codepoint, codepoint(0xAC00));
+        assert_eq!(compacted[1].name, "");
+        assert_eq!(compacted[2].codepoint, codepoint(0xAC01));
+        assert_eq!(compacted[2].general_category, "Cn");
+        assert_eq!(compacted[3].name, "<X, First>");
+        assert_eq!(compacted[4].name, "<X, Last>");
+        assert_eq!(compacted[4].codepoint, codepoint(0xD7A3));
+    }
+
+    #[test]
+    fn index() {
+        use super::UnicodeDataIndex;
+
+        let data = "\
+# A comment.
+0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;
+ABF9;MEETEI MAYEK DIGIT NINE;Nd;0;L;;9;9;9;N;;;;;
+AC00;<Hangul Syllable, First>;Lo;0;L;;;;;N;;;;;
+D7A3;<Hangul Syllable, Last>;Lo;0;L;;;;;N;;;;;
+D7B0;HANGUL JUNGSEONG O-YEO;Lo;0;L;;;;;N;;;;;
+";
+        let index = UnicodeDataIndex::new(data.to_string()).unwrap();
+        assert_eq!(index.len(), 5);
+        assert_eq!(index.rows().count(), 5);
+
+        let a = index.get(codepoint(0x41)).unwrap().unwrap();
+        assert_eq!(a.name, "LATIN CAPITAL LETTER A");
+        assert_eq!(a.simple_lowercase_mapping, Some(codepoint(0x61)));
+        assert_eq!(index.field(codepoint(0x41), 12), Some(""));
+        assert_eq!(index.field(codepoint(0x41), 13), Some("0061"));
+        assert_eq!(index.field(codepoint(0xABF9), 2), Some("Nd"));
+
+        for &cp in &[0xAC00, 0xAC01, 0xD7A3] {
+            let syllable = index.get(codepoint(cp)).unwrap().unwrap();
+            assert_eq!(syllable.codepoint, codepoint(cp));
+            assert_eq!(syllable.name, "");
+            assert_eq!(syllable.general_category, "Lo");
+            assert_eq!(syllable.decomposition.mapping(), &[codepoint(cp)]);
+        }
+
+        assert!(index.contains(codepoint(0xD7B0)));
+        assert!(!index.contains(codepoint(0x42)));
+        assert!(!index.contains(codepoint(0xD7A4)));
+        assert!(index.get(codepoint(0x10FFFF)).unwrap().is_none());
+    }
+
+    #[test]
+    fn index_error_line() {
+        use super::UnicodeDataIndex;
+
+        let data = "\
+0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;
+0042;LATIN CAPITAL LETTER B;Lu;0;L;;;;;Z;;;;0062;
+";
+        let index = UnicodeDataIndex::new(data.to_string()).unwrap();
+        let err = index.get(codepoint(0x42)).unwrap_err();
+        assert_eq!(err.line(), Some(2));
+
+        let err = UnicodeDataIndex::new("0042;B\n0041;A\n".to_string())
+            .unwrap_err();
+        assert_eq!(err.line(), Some(2));
+    }
+
+    #[test]
+    fn into_owned_interned() {
+        use std::borrow::Cow;
+
+        let line = "0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;";
+        let data = UnicodeData::parse_line(line).unwrap().into_owned();
+        let is_borrowed = |s: &Cow<str>| match *s {
+            Cow::Borrowed(_) => true,
+            Cow::Owned(_) => false,
+        };
+        assert!(is_borrowed(&data.general_category));
+        assert!(is_borrowed(&data.bidi_class));
+        assert!(is_borrowed(&data.unicode1_name));
+        assert!(is_borrowed(&data.iso_comment));
+        assert!(!is_borrowed(&data.name));
+        assert_eq!(data, line.parse().unwrap());
+    }
+
+    #[test]
+    fn ranges() {
+        use common::UcdLineParser;
+        use super::UnicodeDataRanges;
+
+        let data = "\
+ABF9;MEETEI MAYEK DIGIT NINE;Nd;0;L;;9;9;9;N;;;;;
+AC00;<Hangul Syllable, First>;Lo;0;L;;;;;N;;;;;
+D7A3;<Hangul Syllable, Last>;Lo;0;L;;;;;N;;;;;
+D7B0;HANGUL JUNGSEONG O-YEO;Lo;0;L;;;;;N;;;;;
+";
+        let records = UcdLineParser::new(data.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let ranges: Vec<_> = UnicodeDataRanges::new(records).collect();
+        assert_eq!(ranges.len(), 3);
+        assert_eq!(ranges[0].0.start, codepoint(0xABF9));
+        assert_eq!(ranges[0].0.end, codepoint(0xABF9));
+        assert_eq!(ranges[0].1.name, "MEETEI MAYEK DIGIT NINE");
+        assert_eq!(ranges[1].0.start, codepoint(0xAC00));
+        assert_eq!(ranges[1].0.end, codepoint(0xD7A3));
+        assert_eq!(ranges[1].1.name, "");
+        assert_eq!(ranges[1].1.general_category, "Lo");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        use serde_json;
+
+        let line = "00BD;VULGAR FRACTION ONE HALF;No;0;ON;<fraction> 0031 2044 0032;;;1/2;N;FRACTION ONE HALF;;;;\n";
+        let data: UnicodeData = line.parse().unwrap();
+        let json = serde_json::to_string(&data).unwrap();
+        let got: UnicodeData = serde_json::from_str(&json).unwrap();
+        assert_eq!(got, data);
+    }
 }
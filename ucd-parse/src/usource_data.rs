@@ -0,0 +1,153 @@
+use std::borrow::Cow;
+use std::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(not(feature = "std"))]
+use std::prelude::*;
+use std::str::FromStr;
+
+use common::{UcdFile, Codepoint};
+use error::Error;
+
+/// A single row in the `USourceData.txt` file.
+///
+/// Each row describes a single UTC (U-source) ideograph. Unlike most UCD
+/// files, many rows describe ideographs that have not been encoded, so the
+/// codepoint is optional.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct USourceData<'a> {
+    /// The U-source ID, e.g., `UTC-00001`.
+    pub id: Cow<'a, str>,
+    /// The status of this ideograph, e.g., `D` for disunified.
+    pub status: Cow<'a, str>,
+    /// The codepoint of this ideograph, if it is encoded.
+    pub codepoint: Option<Codepoint>,
+    /// The radical-stroke index of this ideograph.
+    pub radical_stroke: Cow<'a, str>,
+    /// The virtual KangXi dictionary position of this ideograph.
+    pub virtual_kangxi: Cow<'a, str>,
+    /// The ideographic description sequence of this ideograph.
+    pub ids: Cow<'a, str>,
+    /// The source(s) of this ideograph.
+    pub sources: Cow<'a, str>,
+    /// General comments. This is empty when there are no comments.
+    pub comments: Cow<'a, str>,
+}
+
+impl UcdFile for USourceData<'static> {
+    #[cfg(feature = "std")]
+    fn relative_file_path() -> &'static Path {
+        Path::new("USourceData.txt")
+    }
+}
+
+impl<'a> USourceData<'a> {
+    /// Convert this record into an owned value such that it no longer
+    /// borrows from the original line that it was parsed from.
+    pub fn into_owned(self) -> USourceData<'static> {
+        USourceData {
+            id: Cow::Owned(self.id.into_owned()),
+            status: Cow::Owned(self.status.into_owned()),
+            codepoint: self.codepoint,
+            radical_stroke: Cow::Owned(self.radical_stroke.into_owned()),
+            virtual_kangxi: Cow::Owned(self.virtual_kangxi.into_owned()),
+            ids: Cow::Owned(self.ids.into_owned()),
+            sources: Cow::Owned(self.sources.into_owned()),
+            comments: Cow::Owned(self.comments.into_owned()),
+        }
+    }
+
+    /// Parse a single line.
+    pub fn parse_line(line: &'a str) -> Result<USourceData<'a>, Error> {
+        let fields: Vec<&str> = line.trim().split(';').collect();
+        if fields.len() < 7 || fields.len() > 8 {
+            return err!(
+                "expected 7 or 8 fields in USourceData line, got {}",
+                fields.len());
+        }
+        if fields[0].is_empty() {
+            return err!("missing U-source ID");
+        }
+        let codepoint = if fields[2].starts_with("U+") {
+            Some(fields[2][2..].parse()?)
+        } else {
+            None
+        };
+        Ok(USourceData {
+            id: Cow::Borrowed(fields[0]),
+            status: Cow::Borrowed(fields[1]),
+            codepoint: codepoint,
+            radical_stroke: Cow::Borrowed(fields[3]),
+            virtual_kangxi: Cow::Borrowed(fields[4]),
+            ids: Cow::Borrowed(fields[5]),
+            sources: Cow::Borrowed(fields[6]),
+            comments: Cow::Borrowed(fields.get(7).map_or("", |s| *s)),
+        })
+    }
+}
+
+impl FromStr for USourceData<'static> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<USourceData<'static>, Error> {
+        USourceData::parse_line(s).map(|x| x.into_owned())
+    }
+}
+
+impl<'a> fmt::Display for USourceData<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{};{};", self.id, self.status)?;
+        if let Some(cp) = self.codepoint {
+            write!(f, "U+{}", cp)?;
+        }
+        write!(
+            f, ";{};{};{};{};{}",
+            self.radical_stroke, self.virtual_kangxi, self.ids,
+            self.sources, self.comments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::USourceData;
+
+    #[test]
+    fn parse_encoded() {
+        let line = "UTC-00001;D;U+20164;1.11;0076.471;⿰丿⿱⿰丿丿丿;UTC;\n";
+        let row: USourceData = line.parse().unwrap();
+        assert_eq!(row.id, "UTC-00001");
+        assert_eq!(row.status, "D");
+        assert_eq!(row.codepoint.unwrap(), 0x20164);
+        assert_eq!(row.radical_stroke, "1.11");
+        assert_eq!(row.virtual_kangxi, "0076.471");
+        assert_eq!(row.ids, "⿰丿⿱⿰丿丿丿");
+        assert_eq!(row.sources, "UTC");
+        assert_eq!(row.comments, "");
+    }
+
+    #[test]
+    fn parse_unencoded() {
+        let line = "UTC-01296;N;;61.5;0384.101;⿰忄冬;UTC;variant\n";
+        let row: USourceData = line.parse().unwrap();
+        assert_eq!(row.id, "UTC-01296");
+        assert_eq!(row.codepoint, None);
+        assert_eq!(row.comments, "variant");
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!("UTC-00001;D;U+20164".parse::<USourceData>().is_err());
+        assert!("UTC-00001;D;U+XYZ;;;;;".parse::<USourceData>().is_err());
+    }
+
+    #[test]
+    fn display() {
+        let line = "UTC-01296;N;;61.5;0384.101;⿰忄冬;UTC;variant";
+        let row: USourceData = line.parse().unwrap();
+        assert_eq!(row.to_string(), line);
+        let line = "UTC-00001;D;U+20164;1.11;0076.471;⿰丿⿱⿰丿丿丿;UTC;";
+        let row: USourceData = line.parse().unwrap();
+        assert_eq!(row.to_string(), line);
+    }
+}
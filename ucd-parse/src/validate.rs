@@ -0,0 +1,396 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+
+use common::{Codepoint, CodepointRange, parse};
+use error::Error;
+use property_aliases::PropertyAlias;
+use property_value_aliases::PropertyValueAlias;
+use unicode_data::UnicodeData;
+
+/// The kind of problem found by a `Validator`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DiagnosticKind {
+    /// A property name that isn't in `PropertyAliases.txt`.
+    UnknownProperty,
+    /// A property value that isn't in `PropertyValueAliases.txt`.
+    UnknownValue,
+    /// A canonical combining class that isn't in `PropertyValueAliases.txt`.
+    InvalidCombiningClass,
+    /// A case or decomposition mapping that refers to a codepoint that
+    /// isn't assigned in `UnicodeData.txt`.
+    UnassignedMapping,
+}
+
+/// A single problem found by a `Validator`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    /// The kind of problem.
+    pub kind: DiagnosticKind,
+    /// The codepoints that the problem applies to, if any.
+    pub codepoints: Option<CodepointRange>,
+    /// A human readable description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.codepoints {
+            None => write!(f, "{}", self.message),
+            Some(ref cps) => write!(f, "{}: {}", cps, self.message),
+        }
+    }
+}
+
+/// A report of every problem found by a `Validator`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Report {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Report {
+    /// Returns true if and only if no problems were found.
+    pub fn is_ok(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// Return every problem found, in the order in which they were found.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Return the number of problems of the given kind.
+    pub fn count(&self, kind: DiagnosticKind) -> usize {
+        self.diagnostics.iter().filter(|d| d.kind == kind).count()
+    }
+
+    /// Add all of the problems in the given report to this one.
+    pub fn extend(&mut self, other: Report) {
+        self.diagnostics.extend(other.diagnostics);
+    }
+
+    fn push(
+        &mut self,
+        kind: DiagnosticKind,
+        codepoints: Option<CodepointRange>,
+        message: String,
+    ) {
+        self.diagnostics.push(Diagnostic {
+            kind: kind,
+            codepoints: codepoints,
+            message: message,
+        });
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for diag in &self.diagnostics {
+            writeln!(f, "{}", diag)?;
+        }
+        Ok(())
+    }
+}
+
+/// Cross-checks parsed UCD data against `PropertyAliases.txt` and
+/// `PropertyValueAliases.txt`.
+///
+/// Property names and values are compared using the loose matching rule in
+/// UAX44-LM3, so `General_Category`, `gc` and `general category` all name the
+/// same property.
+#[derive(Clone, Debug, Default)]
+pub struct Validator {
+    /// A map from every loose property name to its abbreviation.
+    properties: HashMap<String, String>,
+    /// A map from a property's abbreviation to its loose value names. Only
+    /// properties with values in `PropertyValueAliases.txt` are present.
+    values: HashMap<String, HashSet<String>>,
+    /// Every canonical combining class.
+    combining_classes: BTreeSet<u8>,
+}
+
+impl Validator {
+    /// Create a validator from the property aliases in the given UCD
+    /// directory.
+    pub fn from_dir<P: AsRef<Path>>(ucd_dir: P) -> Result<Validator, Error> {
+        let props: Vec<PropertyAlias> = parse(&ucd_dir)?;
+        let values: Vec<PropertyValueAlias> = parse(&ucd_dir)?;
+        Ok(Validator::new(&props, &values))
+    }
+
+    /// Create a validator from the given property and property value
+    /// aliases.
+    pub fn new(
+        props: &[PropertyAlias],
+        values: &[PropertyValueAlias],
+    ) -> Validator {
+        let mut v = Validator::default();
+        for prop in props {
+            let abbrev = prop.abbreviation.to_string();
+            let names = Some(&prop.abbreviation).into_iter()
+                .chain(Some(&prop.long))
+                .chain(&prop.aliases);
+            for name in names {
+                v.properties.insert(loose(name), abbrev.clone());
+            }
+        }
+        for value in values {
+            let prop = match v.properties.get(&loose(&value.property)) {
+                None => loose(&value.property),
+                Some(abbrev) => abbrev.clone(),
+            };
+            if prop == "ccc" {
+                if let Some(n) = value.numeric {
+                    v.combining_classes.insert(n);
+                }
+            }
+            let names = Some(&value.abbreviation).into_iter()
+                .chain(Some(&value.long))
+                .chain(&value.aliases);
+            let set = v.values.entry(prop).or_insert_with(HashSet::new);
+            for name in names {
+                set.insert(loose(name));
+            }
+        }
+        v
+    }
+
+    /// Return the abbreviation of the given property, if it exists.
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties.get(&loose(name)).map(|s| &**s)
+    }
+
+    /// Returns true if and only if the given value is valid for the given
+    /// property.
+    ///
+    /// Properties without values in `PropertyValueAliases.txt`, such as
+    /// string properties, accept any value.
+    pub fn is_value(&self, property: &str, value: &str) -> bool {
+        let abbrev = match self.property(property) {
+            None => return false,
+            Some(abbrev) => abbrev,
+        };
+        match self.values.get(abbrev) {
+            None => true,
+            Some(set) => set.contains(&loose(value)),
+        }
+    }
+
+    /// Check that every property value in the given rows is valid for the
+    /// given property.
+    pub fn check_values<I, S>(&self, property: &str, rows: I) -> Report
+    where I: IntoIterator<Item=(CodepointRange, S)>, S: AsRef<str>
+    {
+        let mut report = Report::default();
+        if self.property(property).is_none() {
+            report.push(
+                DiagnosticKind::UnknownProperty,
+                None,
+                format!("unknown property '{}'", property));
+            return report;
+        }
+        for (cps, value) in rows {
+            self.check_value(&mut report, cps, property, value.as_ref());
+        }
+        report
+    }
+
+    /// Check that every property named in the given rows exists.
+    ///
+    /// This is useful for files that list binary properties, such as
+    /// `PropList.txt`.
+    pub fn check_properties<I, S>(&self, rows: I) -> Report
+    where I: IntoIterator<Item=(CodepointRange, S)>, S: AsRef<str>
+    {
+        let mut report = Report::default();
+        for (cps, property) in rows {
+            let property = property.as_ref();
+            if self.property(property).is_none() {
+                report.push(
+                    DiagnosticKind::UnknownProperty,
+                    Some(cps),
+                    format!("unknown property '{}'", property));
+            }
+        }
+        report
+    }
+
+    /// Check the records in `UnicodeData.txt`.
+    ///
+    /// This checks the general category, bidi class and canonical combining
+    /// class of every record, and that every case and decomposition mapping
+    /// refers to an assigned codepoint.
+    pub fn check_unicode_data(&self, rows: &[UnicodeData]) -> Report {
+        let mut report = Report::default();
+        let assigned = assigned_ranges(rows);
+        let is_assigned = |cp: Codepoint| {
+            assigned.binary_search_by(|r| {
+                if r.end < cp {
+                    Ordering::Less
+                } else if r.start > cp {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            }).is_ok()
+        };
+        for row in rows {
+            let cps = CodepointRange::single(row.codepoint);
+            self.check_value(&mut report, cps, "gc", &row.general_category);
+            self.check_value(&mut report, cps, "bc", &row.bidi_class);
+            let ccc = row.canonical_combining_class;
+            if !self.combining_classes.contains(&ccc) {
+                report.push(
+                    DiagnosticKind::InvalidCombiningClass,
+                    Some(cps),
+                    format!("invalid canonical combining class {}", ccc));
+            }
+            let mappings = row.decomposition.mapping().iter()
+                .chain(&row.simple_uppercase_mapping)
+                .chain(&row.simple_lowercase_mapping)
+                .chain(&row.simple_titlecase_mapping);
+            for &cp in mappings {
+                if !is_assigned(cp) {
+                    report.push(
+                        DiagnosticKind::UnassignedMapping,
+                        Some(cps),
+                        format!("mapping to unassigned codepoint {}", cp));
+                }
+            }
+        }
+        report
+    }
+
+    fn check_value(
+        &self,
+        report: &mut Report,
+        cps: CodepointRange,
+        property: &str,
+        value: &str,
+    ) {
+        if !self.is_value(property, value) {
+            report.push(
+                DiagnosticKind::UnknownValue,
+                Some(cps),
+                format!(
+                    "unknown value '{}' for property '{}'", value, property));
+        }
+    }
+}
+
+/// Return the sorted ranges of codepoints assigned by the given records in
+/// `UnicodeData.txt`.
+fn assigned_ranges(rows: &[UnicodeData]) -> Vec<CodepointRange> {
+    let mut ranges = vec![];
+    let mut i = 0;
+    while i < rows.len() {
+        let start = rows[i].codepoint;
+        let mut end = start;
+        if rows[i].is_range_start()
+            && rows.get(i + 1).map_or(false, |r| r.is_range_end())
+        {
+            end = rows[i + 1].codepoint;
+            i += 1;
+        }
+        ranges.push(CodepointRange { start: start, end: end });
+        i += 1;
+    }
+    ranges.sort();
+    ranges
+}
+
+/// Normalize a property name or value according to UAX44-LM3, which
+/// ignores case, whitespace, underscores and hyphens.
+fn loose(name: &str) -> String {
+    name.chars()
+        .filter(|&c| !c.is_whitespace() && c != '_' && c != '-')
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use common::UcdFile;
+    use property_aliases::PropertyAlias;
+    use property_value_aliases::PropertyValueAlias;
+    use unicode_data::UnicodeData;
+
+    use super::{DiagnosticKind, Validator};
+
+    fn validator() -> Validator {
+        let props: Vec<PropertyAlias> = PropertyAlias::from_data("\
+bc        ; Bidi_Class
+ccc       ; Canonical_Combining_Class
+gc        ; General_Category
+WSpace    ; White_Space                 ; space
+").collect::<Result<_, _>>().unwrap();
+        let values: Vec<PropertyValueAlias> = PropertyValueAlias::from_data("\
+bc ; L                                ; Left_To_Right
+bc ; NSM                              ; Nonspacing_Mark
+ccc;   0; NR                         ; Not_Reordered
+ccc; 230; A                          ; Above
+gc ; Lu                               ; Uppercase_Letter
+gc ; Ll                               ; Lowercase_Letter
+gc ; Mn                               ; Nonspacing_Mark
+").collect::<Result<_, _>>().unwrap();
+        Validator::new(&props, &values)
+    }
+
+    #[test]
+    fn names() {
+        let v = validator();
+        assert_eq!(v.property("General_Category"), Some("gc"));
+        assert_eq!(v.property("general category"), Some("gc"));
+        assert_eq!(v.property("space"), Some("WSpace"));
+        assert_eq!(v.property("Script"), None);
+        assert!(v.is_value("gc", "Lu"));
+        assert!(v.is_value("General_Category", "lowercase-letter"));
+        assert!(!v.is_value("gc", "Xx"));
+        assert!(v.is_value("White_Space", "anything"));
+    }
+
+    #[test]
+    fn unicode_data() {
+        let v = validator();
+        let rows: Vec<UnicodeData> = UnicodeData::from_data("\
+0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;
+0061;LATIN SMALL LETTER A;Ll;0;L;;;;;N;;;0041;;0041
+0300;COMBINING GRAVE ACCENT;Mn;230;NSM;;;;;N;;;;;
+00C0;LATIN CAPITAL LETTER A WITH GRAVE;Lu;0;L;0041 0300;;;;N;;;;00E0;
+0062;LATIN SMALL LETTER B;Xx;7;L;;;;;N;;;0042;;0042
+").collect::<Result<_, _>>().unwrap();
+        let report = v.check_unicode_data(&rows);
+        assert_eq!(report.count(DiagnosticKind::UnknownValue), 1);
+        assert_eq!(report.count(DiagnosticKind::InvalidCombiningClass), 1);
+        assert_eq!(report.count(DiagnosticKind::UnassignedMapping), 3);
+        assert_eq!(
+            report.diagnostics()[0].to_string(),
+            "00C0: mapping to unassigned codepoint 00E0");
+        assert!(v.check_unicode_data(&rows[..3]).is_ok());
+    }
+
+    #[test]
+    fn values() {
+        let v = validator();
+        let rows = vec![
+            ("0041..005A".parse().unwrap(), "Lu"),
+            ("0030..0039".parse().unwrap(), "Nd"),
+        ];
+        let report = v.check_values("gc", rows.clone());
+        assert_eq!(report.diagnostics().len(), 1);
+        assert_eq!(
+            report.to_string(),
+            "0030..0039: unknown value 'Nd' for property 'gc'\n");
+
+        let report = v.check_values("Script", rows);
+        assert_eq!(report.count(DiagnosticKind::UnknownProperty), 1);
+
+        let rows = vec![
+            ("0009..000D".parse().unwrap(), "White_Space"),
+            ("0020".parse().unwrap(), "Dash"),
+        ];
+        let report = v.check_properties(rows);
+        assert_eq!(report.count(DiagnosticKind::UnknownProperty), 1);
+    }
+}
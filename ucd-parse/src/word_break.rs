@@ -0,0 +1,34 @@
+range_value_record!(
+    WordBreak, "auxiliary/WordBreakProperty.txt",
+    "A single row in the `auxiliary/WordBreakProperty.txt` file, which \
+     defines the `Word_Break` property.");
+
+#[cfg(test)]
+mod tests {
+    use super::WordBreak;
+
+    #[test]
+    fn parse_single() {
+        let line = "0027          ; Single_Quote # Po       APOSTROPHE\n";
+        let row: WordBreak = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x0027);
+        assert_eq!(row.codepoints.end, 0x0027);
+        assert_eq!(row.value, "Single_Quote");
+    }
+
+    #[test]
+    fn parse_range() {
+        let line = "0041..005A    ; ALetter # L&  [26] \
+                    LATIN CAPITAL LETTER A..LATIN CAPITAL LETTER Z\n";
+        let row: WordBreak = line.parse().unwrap();
+        assert_eq!(row.codepoints.start, 0x0041);
+        assert_eq!(row.codepoints.end, 0x005A);
+        assert_eq!(row.value, "ALetter");
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!("005A..0041 ; ALetter".parse::<WordBreak>().is_err());
+        assert!("0041 ALetter".parse::<WordBreak>().is_err());
+    }
+}
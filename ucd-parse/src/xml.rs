@@ -0,0 +1,593 @@
+use std::borrow::Cow;
+use std::char;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use regex::{self, Regex};
+
+use common::{Codepoint, CodepointRange};
+use error::{Error, error_set_line};
+use name_aliases::NameAlias;
+use unicode_data::{UnicodeData, UnicodeDataDecomposition};
+
+/// The XML representation of the UCD, as described by UAX #42.
+///
+/// Both the flat (e.g., `ucd.all.flat.xml`) and grouped (e.g.,
+/// `ucd.all.grouped.xml`) forms are supported. Elements in the repertoire
+/// are parsed lazily, so that the properties of each codepoint can be
+/// inspected without holding all of them in memory at once.
+///
+/// The XML distribution contains every property, so in addition to iterating
+/// over the raw elements with `chars`, records in the same form as those
+/// parsed from the text files can be built with methods such as
+/// `unicode_data`.
+#[derive(Clone, Debug)]
+pub struct UcdXml {
+    data: String,
+}
+
+/// The kind of an element in the repertoire of the UCD XML.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UcdXmlCharKind {
+    /// An assigned codepoint, given by a `char` element.
+    Char,
+    /// An unassigned codepoint, given by a `reserved` element.
+    Reserved,
+    /// A noncharacter, given by a `noncharacter` element.
+    Noncharacter,
+    /// A surrogate codepoint, given by a `surrogate` element.
+    Surrogate,
+}
+
+/// A single element in the repertoire of the UCD XML.
+///
+/// Each element describes either a single codepoint or a range of codepoints
+/// that share all of their properties. In the grouped form of the UCD XML,
+/// properties that aren't given on an element are inherited from its group.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UcdXmlChar<'a> {
+    /// The kind of this element.
+    pub kind: UcdXmlCharKind,
+    /// The codepoints described by this element.
+    pub codepoints: CodepointRange,
+    /// The name aliases of this codepoint, in the order in which they
+    /// appear.
+    pub name_aliases: Vec<NameAlias<'a>>,
+    /// The properties of this element, followed by the properties of its
+    /// group. Properties are looked up from front to back.
+    properties: Vec<(&'a str, Cow<'a, str>)>,
+}
+
+impl UcdXml {
+    /// Read the UCD XML at the given file path.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<UcdXml, Error> {
+        let mut data = String::new();
+        File::open(path)?.read_to_string(&mut data)?;
+        Ok(UcdXml::new(data))
+    }
+
+    /// Create a UCD XML from its full contents.
+    ///
+    /// The contents aren't parsed until they are needed, so this never
+    /// fails.
+    pub fn new(data: String) -> UcdXml {
+        UcdXml { data: data }
+    }
+
+    /// Return the contents of the `description` element, if one exists,
+    /// e.g., `Unicode 10.0.0`.
+    pub fn description(&self) -> Option<&str> {
+        lazy_static! {
+            static ref DESCRIPTION: Regex = Regex::new(
+                r"<description>([^<]*)</description>"
+            ).unwrap();
+        };
+        DESCRIPTION
+            .captures(&self.data)
+            .map(|caps| caps.get(1).unwrap().as_str().trim())
+    }
+
+    /// Create an iterator over each element in the repertoire, in the order
+    /// in which they appear.
+    pub fn chars(&self) -> UcdXmlChars {
+        UcdXmlChars {
+            data: &self.data,
+            tags: tag_regex().captures_iter(&self.data),
+            in_repertoire: false,
+            group: vec![],
+            pending: None,
+        }
+    }
+
+    /// Build a `UnicodeData` record for every codepoint listed in
+    /// `UnicodeData.txt`.
+    ///
+    /// Unlike `UnicodeData.txt`, ranges of codepoints are always expanded, in
+    /// the same way as `UnicodeDataExpander`. That is, every codepoint in a
+    /// range gets its own record with an empty name.
+    pub fn unicode_data(&self) -> Result<Vec<UnicodeData<'static>>, Error> {
+        let mut rows = vec![];
+        for result in self.chars() {
+            rows.extend(result?.unicode_data()?);
+        }
+        Ok(rows)
+    }
+
+    /// Build a `NameAlias` record for every name alias.
+    pub fn name_aliases(&self) -> Result<Vec<NameAlias<'static>>, Error> {
+        let mut aliases = vec![];
+        for result in self.chars() {
+            for alias in result?.name_aliases {
+                aliases.push(alias.into_owned());
+            }
+        }
+        Ok(aliases)
+    }
+}
+
+impl<'a> UcdXmlChar<'a> {
+    /// Return the raw value of the property with the given short name, e.g.,
+    /// `gc` or `Bidi_M`.
+    ///
+    /// Note that `#` in a value refers to the codepoint itself.
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|&&(k, _)| k == name)
+            .map(|&(_, ref v)| &**v)
+    }
+
+    /// Build a `UnicodeData` record for each codepoint described by this
+    /// element.
+    ///
+    /// Reserved codepoints and noncharacters aren't listed in
+    /// `UnicodeData.txt`, so this returns no records for them. Every record
+    /// in a range of more than one codepoint has an empty name.
+    pub fn unicode_data(&self) -> Result<Vec<UnicodeData<'static>>, Error> {
+        match self.kind {
+            UcdXmlCharKind::Reserved | UcdXmlCharKind::Noncharacter => {
+                return Ok(vec![]);
+            }
+            UcdXmlCharKind::Char | UcdXmlCharKind::Surrogate => {}
+        }
+        let mut rows = vec![];
+        for cp in self.codepoints {
+            rows.push(self.unicode_data_for(cp)?);
+        }
+        Ok(rows)
+    }
+
+    fn unicode_data_for(
+        &self,
+        cp: Codepoint,
+    ) -> Result<UnicodeData<'static>, Error> {
+        let gc = self.required("gc")?;
+        let name = if self.codepoints.count() > 1 {
+            String::new()
+        } else if self.required("na")?.is_empty() && gc == "Cc" {
+            "<control>".to_string()
+        } else {
+            self.required("na")?.replace('#', &cp.to_string())
+        };
+        let ccc = self.required("ccc")?;
+        let ccc = match ccc.parse() {
+            Ok(ccc) => ccc,
+            Err(err) => {
+                return err!("invalid canonical combining class '{}': {}",
+                            ccc, err);
+            }
+        };
+
+        let mut row = UnicodeData {
+            codepoint: cp,
+            name: Cow::Owned(name),
+            general_category: Cow::Owned(gc.to_string()),
+            canonical_combining_class: ccc,
+            bidi_class: Cow::Owned(self.required("bc")?.to_string()),
+            decomposition: self.decomposition(cp)?,
+            bidi_mirrored: self.required("Bidi_M")? == "Y",
+            unicode1_name: Cow::Owned(self.required("na1")?.to_string()),
+            iso_comment: Cow::Owned(self.required("isc")?.to_string()),
+            simple_uppercase_mapping: self.simple_mapping("suc")?,
+            simple_lowercase_mapping: self.simple_mapping("slc")?,
+            simple_titlecase_mapping: self.simple_mapping("stc")?,
+            ..UnicodeData::default()
+        };
+        let nv = self.required("nv")?;
+        match self.required("nt")? {
+            "None" => {}
+            "De" => {
+                let n = parse_digit(nv)?;
+                row.numeric_type_decimal = Some(n);
+                row.numeric_type_digit = Some(n);
+                row.numeric_type_numeric = Some(nv.parse()?);
+            }
+            "Di" => {
+                row.numeric_type_digit = Some(parse_digit(nv)?);
+                row.numeric_type_numeric = Some(nv.parse()?);
+            }
+            "Nu" => {
+                row.numeric_type_numeric = Some(nv.parse()?);
+            }
+            unknown => return err!("unknown numeric type: '{}'", unknown),
+        }
+        Ok(row)
+    }
+
+    fn decomposition(
+        &self,
+        cp: Codepoint,
+    ) -> Result<UnicodeDataDecomposition, Error> {
+        use unicode_data::UnicodeDataDecompositionTag::*;
+
+        let dm = self.required("dm")?;
+        let tag = match self.required("dt")? {
+            "none" | "can" => None,
+            "com" => Some(Compat),
+            "enc" => Some(Circle),
+            "fin" => Some(Final),
+            "font" => Some(Font),
+            "fra" => Some(Fraction),
+            "init" => Some(Initial),
+            "iso" => Some(Isolated),
+            "med" => Some(Medial),
+            "nar" => Some(Narrow),
+            "nb" => Some(NoBreak),
+            "sml" => Some(Small),
+            "sqr" => Some(Square),
+            "sub" => Some(Sub),
+            "sup" => Some(Super),
+            "vert" => Some(Vertical),
+            "wide" => Some(Wide),
+            unknown => {
+                return err!("unknown decomposition type: '{}'", unknown);
+            }
+        };
+        let mut mapping = vec![];
+        for s in dm.split_whitespace() {
+            mapping.push(if s == "#" { cp } else { s.parse()? });
+        }
+        if mapping.is_empty() {
+            return err!("empty decomposition mapping");
+        }
+        UnicodeDataDecomposition::new(tag, &mapping)
+    }
+
+    fn simple_mapping(&self, name: &str) -> Result<Option<Codepoint>, Error> {
+        match self.required(name)? {
+            "#" => Ok(None),
+            s => Ok(Some(s.parse()?)),
+        }
+    }
+
+    fn required(&self, name: &str) -> Result<&str, Error> {
+        match self.property(name) {
+            Some(v) => Ok(v),
+            None => err!(
+                "missing property '{}' for codepoints {}",
+                name, self.codepoints),
+        }
+    }
+}
+
+/// An iterator over the elements in the repertoire of the UCD XML.
+///
+/// The lifetime `'a` refers to the `UcdXml` that this iterates over.
+pub struct UcdXmlChars<'a> {
+    data: &'a str,
+    tags: regex::CaptureMatches<'static, 'a>,
+    in_repertoire: bool,
+    group: Vec<(&'a str, Cow<'a, str>)>,
+    pending: Option<UcdXmlChar<'a>>,
+}
+
+impl<'a> Iterator for UcdXmlChars<'a> {
+    type Item = Result<UcdXmlChar<'a>, Error>;
+
+    fn next(&mut self) -> Option<Result<UcdXmlChar<'a>, Error>> {
+        while let Some(caps) = self.tags.next() {
+            let offset = caps.get(0).unwrap().start();
+            match self.next_tag(&caps) {
+                Ok(None) => {}
+                Ok(Some(c)) => return Some(Ok(c)),
+                Err(mut err) => {
+                    let line = self.data[..offset].matches('\n').count();
+                    error_set_line(&mut err, Some(line as u64 + 1));
+                    return Some(Err(err));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<'a> UcdXmlChars<'a> {
+    /// Handle a single tag, returning an element if this tag completes one.
+    fn next_tag(
+        &mut self,
+        caps: &regex::Captures<'a>,
+    ) -> Result<Option<UcdXmlChar<'a>>, Error> {
+        let closing = !caps["close"].is_empty();
+        let empty = !caps["empty"].is_empty();
+        let name = caps.name("name").unwrap().as_str();
+        if name == "repertoire" {
+            self.in_repertoire = !closing && !empty;
+            return Ok(None);
+        }
+        if !self.in_repertoire {
+            return Ok(None);
+        }
+        if closing {
+            if name == "group" {
+                self.group.clear();
+            } else if char_kind(name).is_some() {
+                return Ok(self.pending.take());
+            }
+            return Ok(None);
+        }
+
+        let attrs = parse_attributes(caps.name("attrs").unwrap().as_str())?;
+        if name == "group" {
+            if !empty {
+                self.group = attrs;
+            }
+            return Ok(None);
+        }
+        if name == "name-alias" {
+            let c = match self.pending {
+                Some(ref mut c) => c,
+                None => return err!("name-alias outside of a char element"),
+            };
+            let alias = find(&attrs, "alias");
+            let label = find(&attrs, "type");
+            let (alias, label) = match (alias, label) {
+                (Some(alias), Some(label)) => (alias, label),
+                _ => return err!("name-alias requires alias and type"),
+            };
+            c.name_aliases.push(NameAlias {
+                codepoint: c.codepoints.start,
+                alias: alias.clone(),
+                label: label.parse()?,
+            });
+            return Ok(None);
+        }
+        let kind = match char_kind(name) {
+            Some(kind) => kind,
+            None => return Ok(None),
+        };
+        let codepoints = match find(&attrs, "cp") {
+            Some(cp) => CodepointRange::single(cp.parse()?),
+            None => {
+                let first = find(&attrs, "first-cp");
+                let last = find(&attrs, "last-cp");
+                match (first, last) {
+                    (Some(first), Some(last)) => {
+                        CodepointRange::new(first.parse()?, last.parse()?)?
+                    }
+                    _ => return err!("{} element without codepoints", name),
+                }
+            }
+        };
+        let mut properties = attrs;
+        properties.extend(self.group.iter().cloned());
+        let c = UcdXmlChar {
+            kind: kind,
+            codepoints: codepoints,
+            name_aliases: vec![],
+            properties: properties,
+        };
+        if empty {
+            return Ok(Some(c));
+        }
+        self.pending = Some(c);
+        Ok(None)
+    }
+}
+
+fn tag_regex() -> &'static Regex {
+    lazy_static! {
+        static ref TAG: Regex = Regex::new(
+            r#"(?x)
+            <
+            (?P<close>/?)
+            (?P<name>[A-Za-z][-A-Za-z0-9_:.]*)
+            (?P<attrs>(?:\s+[-A-Za-z0-9_:.]+\s*=\s*"[^"]*")*)
+            \s*
+            (?P<empty>/?)
+            >
+            "#
+        ).unwrap();
+    };
+    &TAG
+}
+
+fn char_kind(name: &str) -> Option<UcdXmlCharKind> {
+    match name {
+        "char" => Some(UcdXmlCharKind::Char),
+        "reserved" => Some(UcdXmlCharKind::Reserved),
+        "noncharacter" => Some(UcdXmlCharKind::Noncharacter),
+        "surrogate" => Some(UcdXmlCharKind::Surrogate),
+        _ => None,
+    }
+}
+
+/// Parse the attributes of a single tag.
+fn parse_attributes(s: &str) -> Result<Vec<(&str, Cow<str>)>, Error> {
+    lazy_static! {
+        static ref ATTR: Regex = Regex::new(
+            r#"([-A-Za-z0-9_:.]+)\s*=\s*"([^"]*)""#
+        ).unwrap();
+    };
+    let mut attrs = vec![];
+    for caps in ATTR.captures_iter(s) {
+        let name = caps.get(1).unwrap().as_str();
+        let value = unescape(caps.get(2).unwrap().as_str())?;
+        attrs.push((name, value));
+    }
+    Ok(attrs)
+}
+
+fn find<'a, 'b>(
+    attrs: &'b [(&'a str, Cow<'a, str>)],
+    name: &str,
+) -> Option<&'b Cow<'a, str>> {
+    attrs.iter().find(|&&(k, _)| k == name).map(|&(_, ref v)| v)
+}
+
+/// Replace the predefined XML entities and character references in an
+/// attribute value.
+fn unescape(s: &str) -> Result<Cow<str>, Error> {
+    if !s.contains('&') {
+        return Ok(Cow::Borrowed(s));
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(i) = rest.find('&') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i + 1..];
+        let end = match rest.find(';') {
+            Some(end) => end,
+            None => return err!("unterminated XML entity in '{}'", s),
+        };
+        let entity = &rest[..end];
+        rest = &rest[end + 1..];
+        let ch = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ if entity.starts_with("#x") => {
+                u32::from_str_radix(&entity[2..], 16)
+                    .ok()
+                    .and_then(char::from_u32)
+            }
+            _ if entity.starts_with('#') => {
+                entity[1..].parse().ok().and_then(char::from_u32)
+            }
+            _ => None,
+        };
+        match ch {
+            Some(ch) => out.push(ch),
+            None => return err!("invalid XML entity '&{};'", entity),
+        }
+    }
+    out.push_str(rest);
+    Ok(Cow::Owned(out))
+}
+
+fn parse_digit(s: &str) -> Result<u8, Error> {
+    match s.parse() {
+        Ok(n) if n <= 9 => Ok(n),
+        _ => err!("invalid digit value: '{}'", s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::CodepointRange;
+    use name_aliases::NameAliasLabel;
+    use unicode_data::{UnicodeData, UnicodeDataNumeric};
+
+    use super::{UcdXml, UcdXmlCharKind};
+
+    const FLAT: &'static str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<ucd xmlns="http://www.unicode.org/ns/2003/ucd/1.0">
+   <description>Unicode 10.0.0</description>
+   <repertoire>
+      <char cp="0000" age="1.1" na="" gc="Cc" ccc="0" dt="none" dm="#" nt="None" nv="NaN" bc="BN" Bidi_M="N" suc="#" slc="#" stc="#" na1="NULL" isc=""><name-alias alias="NULL" type="control"/><name-alias alias="NUL" type="abbreviation"/></char>
+      <char cp="0033" age="1.1" na="DIGIT THREE" gc="Nd" ccc="0" dt="none" dm="#" nt="De" nv="3" bc="EN" Bidi_M="N" suc="#" slc="#" stc="#" na1="" isc=""/>
+      <char cp="00BD" age="1.1" na="VULGAR FRACTION ONE HALF" gc="No" ccc="0" dt="fra" dm="0031 2044 0032" nt="Nu" nv="1/2" bc="ON" Bidi_M="N" suc="#" slc="#" stc="#" na1="FRACTION ONE HALF" isc=""/>
+      <char cp="0041" age="1.1" na="LATIN CAPITAL LETTER A" gc="Lu" ccc="0" dt="none" dm="#" nt="None" nv="NaN" bc="L" Bidi_M="N" suc="#" slc="0061" stc="#" na1="" isc="&lt;A&gt;"/>
+      <reserved first-cp="0378" last-cp="0379" age="unassigned" na="" gc="Cn"/>
+      <surrogate first-cp="D800" last-cp="D801" age="2.0" na="" gc="Cs" ccc="0" dt="none" dm="#" nt="None" nv="NaN" bc="L" Bidi_M="N" suc="#" slc="#" stc="#" na1="" isc=""/>
+   </repertoire>
+</ucd>
+"##;
+
+    const GROUPED: &'static str = r##"<ucd>
+   <repertoire>
+      <group gc="Lo" ccc="0" dt="none" dm="#" nt="None" nv="NaN" bc="L" Bidi_M="N" suc="#" slc="#" stc="#" na1="" isc="">
+         <char cp="F900" na="CJK COMPATIBILITY IDEOGRAPH-#" dt="can" dm="8C48"/>
+         <char first-cp="4E00" last-cp="4E01" na="CJK UNIFIED IDEOGRAPH-#"/>
+      </group>
+   </repertoire>
+</ucd>
+"##;
+
+    #[test]
+    fn flat() {
+        let xml = UcdXml::new(FLAT.to_string());
+        assert_eq!(xml.description(), Some("Unicode 10.0.0"));
+
+        let chars: Vec<_> = xml.chars().map(|x| x.unwrap()).collect();
+        assert_eq!(chars.len(), 6);
+        assert_eq!(chars[0].property("na1"), Some("NULL"));
+        assert_eq!(chars[0].name_aliases.len(), 2);
+        assert_eq!(chars[4].kind, UcdXmlCharKind::Reserved);
+        assert_eq!(chars[4].codepoints, "0378..0379".parse().unwrap());
+        assert_eq!(chars[3].property("isc"), Some("<A>"));
+
+        let aliases = xml.name_aliases().unwrap();
+        assert_eq!(aliases[1].codepoint, 0x0000);
+        assert_eq!(aliases[1].alias, "NUL");
+        assert_eq!(aliases[1].label, NameAliasLabel::Abbreviation);
+    }
+
+    #[test]
+    fn flat_unicode_data() {
+        let rows = UcdXml::new(FLAT.to_string()).unicode_data().unwrap();
+        assert_eq!(rows.len(), 6);
+
+        let expected: UnicodeData = "0000;<control>;Cc;0;BN;;;;;N;NULL;;;;"
+            .parse().unwrap();
+        assert_eq!(rows[0], expected);
+        let expected: UnicodeData = "0033;DIGIT THREE;Nd;0;EN;;3;3;3;N;;;;;"
+            .parse().unwrap();
+        assert_eq!(rows[1], expected);
+        let expected: UnicodeData = "00BD;VULGAR FRACTION ONE HALF;No;0;ON;\
+                                     <fraction> 0031 2044 0032;;;1/2;N;\
+                                     FRACTION ONE HALF;;;;"
+            .parse().unwrap();
+        assert_eq!(rows[2], expected);
+        assert_eq!(
+            rows[2].numeric_type_numeric,
+            Some(UnicodeDataNumeric::Rational(1, 2)));
+        assert_eq!(rows[3].simple_lowercase_mapping.unwrap(), 0x0061);
+        assert_eq!(rows[5].codepoint, 0xD801);
+        assert_eq!(rows[5].name, "");
+    }
+
+    #[test]
+    fn grouped() {
+        let xml = UcdXml::new(GROUPED.to_string());
+        let chars: Vec<_> = xml.chars().map(|x| x.unwrap()).collect();
+        assert_eq!(chars.len(), 2);
+        assert_eq!(chars[0].property("gc"), Some("Lo"));
+        assert_eq!(chars[0].property("dt"), Some("can"));
+        assert_eq!(
+            chars[1].codepoints,
+            CodepointRange::new(
+                "4E00".parse().unwrap(), "4E01".parse().unwrap()).unwrap());
+
+        let rows = xml.unicode_data().unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].name, "CJK COMPATIBILITY IDEOGRAPH-F900");
+        assert_eq!(rows[0].decomposition.mapping(), &[0x8C48]);
+        assert!(rows[0].decomposition.is_canonical());
+        assert_eq!(rows[2].codepoint, 0x4E01);
+    }
+
+    #[test]
+    fn errors() {
+        let data = "<repertoire>\n<char cp=\"XYZ\"/>\n</repertoire>";
+        let xml = UcdXml::new(data.to_string());
+        let err = xml.chars().next().unwrap().unwrap_err();
+        assert_eq!(err.line(), Some(2));
+
+        let data = "<repertoire><char cp=\"0041\"/></repertoire>";
+        assert!(UcdXml::new(data.to_string()).unicode_data().is_err());
+    }
+}
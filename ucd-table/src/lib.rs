@@ -0,0 +1,56 @@
+/*!
+The `ucd-table` crate provides a common interface for looking up codepoints
+in tables generated by `ucd-generate`.
+
+`ucd-generate` can emit the same table in several representations, such as
+a sorted slice or an FST. This crate defines the `CodepointSet` and
+`CodepointMap` traits, and implements them for each representation. Code
+that is written against these traits doesn't need to change when the
+representation of a table changes.
+
+For example, a table of codepoint ranges emitted as a slice implements
+`CodepointSet`:
+
+```
+use ucd_table::CodepointSet;
+
+const WHITE_SPACE: &'static [(u32, u32)] = &[
+  (9, 13), (32, 32), (133, 133), (160, 160),
+];
+
+fn is_white_space<S: CodepointSet + ?Sized>(table: &S, c: char) -> bool {
+    table.contains_char(c)
+}
+
+assert!(is_white_space(WHITE_SPACE, ' '));
+assert!(!is_white_space(WHITE_SPACE, 'a'));
+```
+
+Maps from one codepoint to another, such as case folding tables, are
+emitted as slices of codepoint pairs. Since those look exactly like sets of
+codepoint ranges, they must be wrapped in `CodepointPairs` to be looked up
+as maps.
+
+Support for FSTs requires enabling the `fst` feature, and support for tries
+requires enabling the `trie` feature.
+*/
+
+#![deny(missing_docs)]
+
+#[cfg(feature = "fst")]
+extern crate fst;
+#[cfg(feature = "trie")]
+extern crate ucd_trie;
+
+pub use map::{CodepointMap, CodepointPairs};
+pub use set::CodepointSet;
+
+mod map;
+mod set;
+
+/// Return the given codepoint encoded in big-endian, which is how codepoints
+/// are encoded as keys in FSTs generated by `ucd-generate`.
+#[cfg(feature = "fst")]
+fn u32_key(cp: u32) -> [u8; 4] {
+    [(cp >> 24) as u8, (cp >> 16) as u8, (cp >> 8) as u8, cp as u8]
+}
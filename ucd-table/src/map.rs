@@ -0,0 +1,248 @@
+use std::cmp::Ordering;
+
+/// A map from codepoints to values.
+pub trait CodepointMap {
+    /// The type of value associated with each codepoint.
+    type Value;
+
+    /// Return the value associated with the given codepoint, if one exists.
+    fn get_u32(&self, cp: u32) -> Option<Self::Value>;
+
+    /// Return the value associated with the given scalar value, if one
+    /// exists.
+    fn get_char(&self, c: char) -> Option<Self::Value> {
+        self.get_u32(c as u32)
+    }
+}
+
+impl<'a, M: CodepointMap + ?Sized> CodepointMap for &'a M {
+    type Value = M::Value;
+
+    fn get_u32(&self, cp: u32) -> Option<M::Value> {
+        (**self).get_u32(cp)
+    }
+}
+
+macro_rules! range_value_map {
+    ($cp:ty, $value:ty) => {
+        /// A sorted slice of non-overlapping inclusive ranges, each
+        /// associated with a value.
+        impl CodepointMap for [($cp, $cp, $value)] {
+            type Value = $value;
+
+            fn get_u32(&self, cp: u32) -> Option<$value> {
+                self.binary_search_by(|&(start, end, _)| {
+                    if cp < start as u32 {
+                        Ordering::Greater
+                    } else if cp > end as u32 {
+                        Ordering::Less
+                    } else {
+                        Ordering::Equal
+                    }
+                }).ok().map(|i| self[i].2)
+            }
+        }
+    }
+}
+
+range_value_map!(u32, u8);
+range_value_map!(u32, u16);
+range_value_map!(u32, u32);
+range_value_map!(u32, u64);
+range_value_map!(u32, i8);
+range_value_map!(u32, i16);
+range_value_map!(u32, i32);
+range_value_map!(u32, i64);
+range_value_map!(char, u8);
+range_value_map!(char, u16);
+range_value_map!(char, u32);
+range_value_map!(char, u64);
+range_value_map!(char, i8);
+range_value_map!(char, i16);
+range_value_map!(char, i32);
+range_value_map!(char, i64);
+
+macro_rules! string_map {
+    ($cp:ty) => {
+        /// A slice of codepoint and string pairs, sorted by codepoint.
+        impl<'a> CodepointMap for [($cp, &'a str)] {
+            type Value = &'a str;
+
+            fn get_u32(&self, cp: u32) -> Option<&'a str> {
+                self.binary_search_by_key(&cp, |&(k, _)| k as u32)
+                    .ok()
+                    .map(|i| self[i].1)
+            }
+        }
+    }
+}
+
+string_map!(u32);
+string_map!(char);
+
+macro_rules! codepoints_map {
+    ($cp:ty) => {
+        /// A slice of codepoint and codepoint sequence pairs, sorted by
+        /// codepoint.
+        impl<'a> CodepointMap for [($cp, &'a [$cp])] {
+            type Value = &'a [$cp];
+
+            fn get_u32(&self, cp: u32) -> Option<&'a [$cp]> {
+                self.binary_search_by_key(&cp, |&(k, _)| k as u32)
+                    .ok()
+                    .map(|i| self[i].1)
+            }
+        }
+    }
+}
+
+codepoints_map!(u32);
+codepoints_map!(char);
+
+/// A slice of codepoint pairs, sorted by the first codepoint of each pair,
+/// that maps each first codepoint to the second.
+///
+/// Slices of codepoint pairs are also how sets of codepoint ranges are
+/// written, and those implement `CodepointSet`. Wrapping a slice in this
+/// type looks it up as a map instead:
+///
+/// ```
+/// use ucd_table::{CodepointMap, CodepointPairs};
+///
+/// const FOLD: &'static [(char, char)] = &[('A', 'a'), ('B', 'b')];
+///
+/// assert_eq!(CodepointPairs(FOLD).get_char('B'), Some('b'));
+/// assert_eq!(CodepointPairs(FOLD).get_char('C'), None);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct CodepointPairs<'a, T: 'a>(pub &'a [(T, T)]);
+
+macro_rules! pair_map {
+    ($cp:ty) => {
+        impl<'a> CodepointMap for CodepointPairs<'a, $cp> {
+            type Value = $cp;
+
+            fn get_u32(&self, cp: u32) -> Option<$cp> {
+                self.0.binary_search_by_key(&cp, |&(k, _)| k as u32)
+                    .ok()
+                    .map(|i| self.0[i].1)
+            }
+        }
+    }
+}
+
+pair_map!(u32);
+pair_map!(char);
+
+/// An FST whose keys are big-endian encoded codepoints.
+///
+/// When the FST was generated from a map of codepoints to strings, then the
+/// value returned is the packed representation of the string.
+#[cfg(feature = "fst")]
+impl CodepointMap for ::fst::Map {
+    type Value = u64;
+
+    fn get_u32(&self, cp: u32) -> Option<u64> {
+        self.get(::u32_key(cp))
+    }
+}
+
+#[cfg(feature = "trie")]
+impl<V: Copy> CodepointMap for ::ucd_trie::TrieMap<V> {
+    type Value = V;
+
+    fn get_u32(&self, cp: u32) -> Option<V> {
+        ::ucd_trie::TrieMap::get_u32(self, cp)
+    }
+}
+
+#[cfg(feature = "trie")]
+impl<'a, V: Copy> CodepointMap for ::ucd_trie::TrieMapSlice<'a, V> {
+    type Value = V;
+
+    fn get_u32(&self, cp: u32) -> Option<V> {
+        ::ucd_trie::TrieMapSlice::get_u32(self, cp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use set::CodepointSet;
+
+    use super::{CodepointMap, CodepointPairs};
+
+    const RANGES: &'static [(u32, u32, u8)] = &[(5, 10, 1), (20, 20, 2)];
+    const DELTAS: &'static [(char, char, i16)] = &[
+        ('A', 'C', 32), ('a', 'a', -32), ('\u{1E9E}', '\u{1E9E}', -7615),
+    ];
+    const STRINGS: &'static [(char, &'static str)] = &[
+        ('A', "LATIN CAPITAL LETTER A"), ('B', "LATIN CAPITAL LETTER B"),
+    ];
+    const FOLD: &'static [(u32, u32)] = &[(0x41, 0x61), (0x1E9E, 0xDF)];
+    const UPPER: &'static [(char, &'static [char])] = &[
+        ('ß', &['S', 'S']), ('\u{307}', &[]),
+    ];
+
+    #[test]
+    fn slice_ranges() {
+        assert_eq!(RANGES.get_u32(4), None);
+        assert_eq!(RANGES.get_u32(5), Some(1));
+        assert_eq!(RANGES.get_u32(10), Some(1));
+        assert_eq!(RANGES.get_u32(20), Some(2));
+        assert_eq!(RANGES.get_char('\u{15}'), None);
+    }
+
+    #[test]
+    fn slice_signed_ranges() {
+        assert_eq!(DELTAS.get_char('B'), Some(32));
+        assert_eq!(DELTAS.get_char('a'), Some(-32));
+        assert_eq!(DELTAS.get_u32(0x1E9E), Some(-7615));
+        assert_eq!(DELTAS.get_char('D'), None);
+    }
+
+    #[test]
+    fn slice_pairs() {
+        assert_eq!(CodepointPairs(FOLD).get_u32(0x41), Some(0x61));
+        assert_eq!(CodepointPairs(FOLD).get_char('\u{1E9E}'), Some(0xDF));
+        assert_eq!(CodepointPairs(FOLD).get_u32(0x42), None);
+        // The same slice is still a set of ranges when it isn't wrapped.
+        assert!(FOLD.contains_u32(0x42));
+    }
+
+    #[test]
+    fn slice_codepoints() {
+        assert_eq!(UPPER.get_char('ß'), Some(&['S', 'S'][..]));
+        assert_eq!(UPPER.get_u32(0x307), Some(&[][..]));
+        assert_eq!(UPPER.get_char('a'), None);
+    }
+
+    #[test]
+    fn slice_strings() {
+        assert_eq!(STRINGS.get_char('A'), Some("LATIN CAPITAL LETTER A"));
+        assert_eq!(STRINGS.get_char('B'), Some("LATIN CAPITAL LETTER B"));
+        assert_eq!(STRINGS.get_char('C'), None);
+    }
+
+    #[cfg(feature = "fst")]
+    #[test]
+    fn fst() {
+        use fst::Map;
+
+        let keys = vec![(::u32_key(5), 1), (::u32_key(0x10000), 2)];
+        let map = Map::from_iter(keys).unwrap();
+        assert_eq!(map.get_u32(5), Some(1));
+        assert_eq!(map.get_char('\u{10000}'), Some(2));
+        assert_eq!(map.get_u32(6), None);
+    }
+
+    #[cfg(feature = "trie")]
+    #[test]
+    fn trie() {
+        use ucd_trie::TrieMap;
+
+        let trie = TrieMap::from_codepoints(&[(5, 1u8), (0x10000, 2)]);
+        assert_eq!(trie.get_u32(5), Some(1));
+        assert_eq!(trie.as_slice().get_char('\u{10000}'), Some(2));
+        assert_eq!(trie.get_u32(6), None);
+    }
+}
@@ -0,0 +1,118 @@
+use std::cmp::Ordering;
+
+/// A set of codepoints.
+pub trait CodepointSet {
+    /// Return true if and only if the given codepoint is in this set.
+    fn contains_u32(&self, cp: u32) -> bool;
+
+    /// Return true if and only if the given scalar value is in this set.
+    fn contains_char(&self, c: char) -> bool {
+        self.contains_u32(c as u32)
+    }
+}
+
+impl<'a, S: CodepointSet + ?Sized> CodepointSet for &'a S {
+    fn contains_u32(&self, cp: u32) -> bool {
+        (**self).contains_u32(cp)
+    }
+}
+
+macro_rules! range_set {
+    ($ty:ty) => {
+        /// A sorted slice of non-overlapping inclusive ranges.
+        impl CodepointSet for [($ty, $ty)] {
+            fn contains_u32(&self, cp: u32) -> bool {
+                self.binary_search_by(|&(start, end)| {
+                    if cp < start as u32 {
+                        Ordering::Greater
+                    } else if cp > end as u32 {
+                        Ordering::Less
+                    } else {
+                        Ordering::Equal
+                    }
+                }).is_ok()
+            }
+        }
+    }
+}
+
+range_set!(u32);
+range_set!(char);
+
+/// An FST whose keys are big-endian encoded codepoints.
+#[cfg(feature = "fst")]
+impl CodepointSet for ::fst::Set {
+    fn contains_u32(&self, cp: u32) -> bool {
+        self.contains(::u32_key(cp))
+    }
+}
+
+#[cfg(feature = "trie")]
+impl CodepointSet for ::ucd_trie::TrieSet {
+    fn contains_u32(&self, cp: u32) -> bool {
+        ::ucd_trie::TrieSet::contains_u32(self, cp)
+    }
+}
+
+#[cfg(feature = "trie")]
+impl<'a> CodepointSet for ::ucd_trie::TrieSetSlice<'a> {
+    fn contains_u32(&self, cp: u32) -> bool {
+        ::ucd_trie::TrieSetSlice::contains_u32(self, cp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CodepointSet;
+
+    const U32: &'static [(u32, u32)] = &[
+        (5, 10), (20, 20), (0x10000, 0x10FFFF),
+    ];
+    const CHAR: &'static [(char, char)] = &[
+        ('a', 'c'), ('\u{E000}', '\u{E000}'),
+    ];
+
+    #[test]
+    fn slice_u32() {
+        assert!(!U32.contains_u32(4));
+        assert!(U32.contains_u32(5));
+        assert!(U32.contains_u32(10));
+        assert!(!U32.contains_u32(11));
+        assert!(U32.contains_u32(20));
+        assert!(U32.contains_char('\u{10FFFF}'));
+        assert!(!U32.contains_char('\u{FFFF}'));
+    }
+
+    #[test]
+    fn slice_char() {
+        assert!(CHAR.contains_char('b'));
+        assert!(!CHAR.contains_char('d'));
+        assert!(CHAR.contains_u32(0xE000));
+        assert!(!CHAR.contains_u32(0xD800));
+    }
+
+    #[cfg(feature = "fst")]
+    #[test]
+    fn fst() {
+        use fst::Set;
+
+        let keys = vec![::u32_key(5), ::u32_key(0x10000)];
+        let set = Set::from_iter(keys).unwrap();
+        assert!(set.contains_u32(5));
+        assert!(set.contains_char('\u{10000}'));
+        assert!(!set.contains_u32(6));
+    }
+
+    #[cfg(feature = "trie")]
+    #[test]
+    fn trie() {
+        use ucd_trie::TrieSet;
+
+        let trie = TrieSet::from_codepoints(&[5, 0x10000]);
+        for set in &[&trie as &CodepointSet, &trie.as_slice()] {
+            assert!(set.contains_u32(5));
+            assert!(set.contains_char('\u{10000}'));
+            assert!(!set.contains_u32(6));
+        }
+    }
+}
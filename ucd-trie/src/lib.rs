@@ -1,5 +1,7 @@
 #![allow(dead_code)]
 
-pub use set::TrieSet;
+pub use map::{TrieMap, TrieMapSlice};
+pub use set::{TrieSet, TrieSetSlice};
 
+mod map;
 mod set;
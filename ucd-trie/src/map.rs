@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::mem::size_of;
+
+use set::{TrieSet, TrieSetSlice};
+
+const CHUNK_SIZE: usize = 64;
+
+/// A map from codepoints to values, stored as a trie.
+///
+/// This has the same structure as `TrieSet`, except each leaf is a block of
+/// 64 values instead of a bitset, and blocks are referenced by `u16` indices
+/// instead of `u8` indices. The set of codepoints in the map is stored
+/// separately, so that codepoints without a value can be distinguished from
+/// codepoints with a default value.
+pub struct TrieMap<V> {
+    keys: TrieSet,
+    tree1_level1: Vec<V>,
+    tree2_level1: Vec<u16>,
+    tree2_level2: Vec<V>,
+    tree3_level1: Vec<u16>,
+    tree3_level2: Vec<u16>,
+    tree3_level3: Vec<V>,
+}
+
+/// A borrowed trie map of codepoints.
+///
+/// Like `TrieSetSlice`, its fields are public so that tries can be emitted
+/// as constants in generated code, and should not be constructed by hand.
+#[derive(Clone, Copy, Debug)]
+pub struct TrieMapSlice<'a, V: 'a> {
+    #[doc(hidden)]
+    pub keys: TrieSetSlice<'a>,
+    #[doc(hidden)]
+    pub tree1_level1: &'a [V],
+    #[doc(hidden)]
+    pub tree2_level1: &'a [u16],
+    #[doc(hidden)]
+    pub tree2_level2: &'a [V],
+    #[doc(hidden)]
+    pub tree3_level1: &'a [u16],
+    #[doc(hidden)]
+    pub tree3_level2: &'a [u16],
+    #[doc(hidden)]
+    pub tree3_level3: &'a [V],
+}
+
+impl<V: Copy + Default + Eq + Hash> TrieMap<V> {
+    /// Create a new trie map from the given codepoint and value pairs.
+    ///
+    /// If a codepoint is given more than once, then its last value is used.
+    /// This panics if any codepoint is greater than `0x10FFFF`.
+    pub fn from_codepoints(pairs: &[(u32, V)]) -> TrieMap<V> {
+        let mut all = vec![V::default(); 0x110000];
+        let mut keys = Vec::with_capacity(pairs.len());
+        for &(cp, v) in pairs {
+            all[cp as usize] = v;
+            keys.push(cp);
+        }
+
+        let tree1_level1 = all[..0x800].to_vec();
+
+        let (tree2_level1, tree2_level2) =
+            compress_leaves(&all[0x800..0x10000]);
+
+        let (mid, tree3_level3) = compress_leaves(&all[0x10000..]);
+        let (tree3_level1, tree3_level2) = compress_leaves(&mid);
+
+        TrieMap {
+            keys: TrieSet::from_codepoints(&keys),
+            tree1_level1: tree1_level1,
+            tree2_level1: tree2_level1,
+            tree2_level2: tree2_level2,
+            tree3_level1: tree3_level1,
+            tree3_level2: tree3_level2,
+            tree3_level3: tree3_level3,
+        }
+    }
+}
+
+impl<V: Copy> TrieMap<V> {
+    /// Borrow the levels of this trie.
+    pub fn as_slice(&self) -> TrieMapSlice<V> {
+        TrieMapSlice {
+            keys: self.keys.as_slice(),
+            tree1_level1: &self.tree1_level1,
+            tree2_level1: &self.tree2_level1,
+            tree2_level2: &self.tree2_level2,
+            tree3_level1: &self.tree3_level1,
+            tree3_level2: &self.tree3_level2,
+            tree3_level3: &self.tree3_level3,
+        }
+    }
+
+    /// Return the number of bytes used by all levels of this trie, including
+    /// the set of codepoints in the map.
+    pub fn memory_usage(&self) -> usize {
+        self.keys.memory_usage()
+        + ((self.tree1_level1.len()
+            + self.tree2_level2.len()
+            + self.tree3_level3.len()) * size_of::<V>())
+        + ((self.tree2_level1.len()
+            + self.tree3_level1.len()
+            + self.tree3_level2.len()) * size_of::<u16>())
+    }
+
+    /// Return the value associated with the given scalar value, if one
+    /// exists.
+    pub fn get_char(&self, c: char) -> Option<V> {
+        self.as_slice().get_char(c)
+    }
+
+    /// Return the value associated with the given codepoint, if one exists.
+    pub fn get_u32(&self, cp: u32) -> Option<V> {
+        self.as_slice().get_u32(cp)
+    }
+}
+
+impl<'a, V: Copy> TrieMapSlice<'a, V> {
+    /// Return the value associated with the given scalar value, if one
+    /// exists.
+    pub fn get_char(&self, c: char) -> Option<V> {
+        self.get_u32(c as u32)
+    }
+
+    /// Return the value associated with the given codepoint, if one exists.
+    pub fn get_u32(&self, cp: u32) -> Option<V> {
+        if !self.keys.contains_u32(cp) {
+            return None;
+        }
+        Some(self.get(cp as usize))
+    }
+
+    #[inline(always)]
+    fn get(&self, cp: usize) -> V {
+        if cp < 0x800 {
+            self.tree1_level1[cp]
+        } else if cp < 0x10000 {
+            let leaf = self.tree2_level1[(cp >> 6) - 0x20] as usize;
+            self.tree2_level2[(leaf << 6) + (cp & 0x3F)]
+        } else {
+            let child = self.tree3_level1[(cp >> 12) - 0x10] as usize;
+            let leaf = self.tree3_level2[(child << 6) + ((cp >> 6) & 0x3F)];
+            self.tree3_level3[((leaf as usize) << 6) + (cp & 0x3F)]
+        }
+    }
+}
+
+/// Split the given values into blocks of 64 and store each distinct block
+/// once. This returns the index of each block, in order, along with the
+/// distinct blocks.
+fn compress_leaves<V: Copy + Eq + Hash>(values: &[V]) -> (Vec<u16>, Vec<V>) {
+    let mut root = vec![];
+    let mut children = vec![];
+    let mut bychild = HashMap::new();
+    for chunk in values.chunks(CHUNK_SIZE) {
+        if !bychild.contains_key(chunk) {
+            let start = bychild.len();
+            assert!(start < ::std::u16::MAX as usize);
+            bychild.insert(chunk, start as u16);
+            children.extend_from_slice(chunk);
+        }
+        root.push(bychild[chunk]);
+    }
+    (root, children)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TrieMap;
+
+    /// Generate a map that spans all three trees, and that has both long
+    /// runs of the same value and codepoints with no value.
+    fn pairs() -> Vec<(u32, u8)> {
+        (0..0x110000)
+            .filter(|&cp| {
+                (cp / 37) % 5 != 4 || (cp >= 0x20000 && cp < 0x2A000)
+            })
+            .map(|cp| (cp, ((cp / 37) % 3) as u8))
+            .collect()
+    }
+
+    #[test]
+    fn map_matches_naive() {
+        use std::collections::BTreeMap;
+
+        let pairs = pairs();
+        let naive: BTreeMap<u32, u8> = pairs.iter().cloned().collect();
+        let map = TrieMap::from_codepoints(&pairs);
+        for cp in 0..0x110000 {
+            assert_eq!(naive.get(&cp).cloned(), map.get_u32(cp), "{:X}", cp);
+        }
+        assert_eq!(None, map.get_u32(0x110000));
+    }
+
+    #[test]
+    fn map_default_value() {
+        let pairs = &[('a' as u32, 0u8), ('☃' as u32, 3)];
+        let map = TrieMap::from_codepoints(pairs);
+        assert_eq!(Some(0), map.get_char('a'));
+        assert_eq!(Some(3), map.get_char('☃'));
+        assert_eq!(None, map.get_char('b'));
+        assert_eq!(None, map.get_char('😼'));
+    }
+}
@@ -3,45 +3,46 @@ use std::collections::HashMap;
 const CHUNK_SIZE: usize = 64;
 const CHUNKS: usize = 0x110000 / CHUNK_SIZE;
 
+/// A set of codepoints, stored as a trie of bitsets.
+///
+/// Codepoints are split into three ranges, each with its own tree:
+/// `0..0x800` (one or two bytes in UTF-8), `0x800..0x10000` (three bytes) and
+/// `0x10000..0x110000` (four bytes). Each leaf is a bitset of 64
+/// codepoints, and identical leaves (and, in the last tree, identical runs of
+/// leaves) are stored once.
 pub struct TrieSet {
-    oneortwo: OneOrTwo,
-    three: Three,
-    four: Four,
-}
-
-struct OneOrTwo(Vec<u64>);
-
-struct Three {
-    level1: Vec<u8>,
-    level2: Vec<u64>,
-}
-
-struct Four {
-    level1: Vec<u8>,
-    level2: Vec<u8>,
-    level3: Vec<u64>,
+    tree1_level1: Vec<u64>,
+    tree2_level1: Vec<u8>,
+    tree2_level2: Vec<u64>,
+    tree3_level1: Vec<u8>,
+    tree3_level2: Vec<u8>,
+    tree3_level3: Vec<u64>,
 }
 
+/// A borrowed trie of codepoints.
+///
+/// This has the same structure as `TrieSet`, but borrows its levels. Its
+/// fields are public so that tries can be emitted as constants in generated
+/// code. They should be considered an implementation detail of
+/// `ucd-generate`'s output, and should not be constructed by hand.
+#[derive(Clone, Copy, Debug)]
 pub struct TrieSetSlice<'a> {
-    oneortwo: OneOrTwoSlice<'a>,
-    three: ThreeSlice<'a>,
-    four: FourSlice<'a>,
-}
-
-struct OneOrTwoSlice<'a>(&'a [u64]);
-
-struct ThreeSlice<'a> {
-    level1: &'a [u8],
-    level2: &'a [u64],
-}
-
-struct FourSlice<'a> {
-    level1: &'a [u8],
-    level2: &'a [u8],
-    level3: &'a [u64],
+    #[doc(hidden)]
+    pub tree1_level1: &'a [u64],
+    #[doc(hidden)]
+    pub tree2_level1: &'a [u8],
+    #[doc(hidden)]
+    pub tree2_level2: &'a [u64],
+    #[doc(hidden)]
+    pub tree3_level1: &'a [u8],
+    #[doc(hidden)]
+    pub tree3_level2: &'a [u8],
+    #[doc(hidden)]
+    pub tree3_level3: &'a [u64],
 }
 
 impl TrieSet {
+    /// Create a new trie set from the given scalar values.
     pub fn from_scalars(scalars: &[char]) -> TrieSet {
         let mut all = vec![false; 0x110000];
         for &s in scalars {
@@ -50,6 +51,9 @@ impl TrieSet {
         TrieSet::new(&all)
     }
 
+    /// Create a new trie set from the given codepoints.
+    ///
+    /// This panics if any codepoint is greater than `0x10FFFF`.
     pub fn from_codepoints(codepoints: &[u32]) -> TrieSet {
         let mut all = vec![false; 0x110000];
         for &cp in codepoints {
@@ -58,21 +62,30 @@ impl TrieSet {
         TrieSet::new(&all)
     }
 
+    /// Borrow the levels of this trie.
     pub fn as_slice(&self) -> TrieSetSlice {
         TrieSetSlice {
-            oneortwo: OneOrTwoSlice(&self.oneortwo.0),
-            three: ThreeSlice {
-                level1: &self.three.level1,
-                level2: &self.three.level2,
-            },
-            four: FourSlice {
-                level1: &self.four.level1,
-                level2: &self.four.level2,
-                level3: &self.four.level3,
-            },
+            tree1_level1: &self.tree1_level1,
+            tree2_level1: &self.tree2_level1,
+            tree2_level2: &self.tree2_level2,
+            tree3_level1: &self.tree3_level1,
+            tree3_level2: &self.tree3_level2,
+            tree3_level3: &self.tree3_level3,
         }
     }
 
+    /// Return the number of bytes used by all levels of this trie.
+    pub fn memory_usage(&self) -> usize {
+        use std::mem::size_of;
+
+        (self.tree1_level1.len() * size_of::<u64>())
+        + self.tree2_level1.len()
+        + (self.tree2_level2.len() * size_of::<u64>())
+        + self.tree3_level1.len()
+        + self.tree3_level2.len()
+        + (self.tree3_level3.len() * size_of::<u64>())
+    }
+
     fn new(all: &[bool]) -> TrieSet {
         let mut bitvectors = Vec::with_capacity(CHUNKS);
         for i in 0..CHUNKS {
@@ -85,80 +98,68 @@ impl TrieSet {
             bitvectors.push(bitvector);
         }
 
-        let oneortwo = OneOrTwo(
+        let tree1_level1 =
             bitvectors.iter()
                 .cloned()
                 .take(0x800 / CHUNK_SIZE)
-                .collect());
+                .collect();
 
-        let (level1, level2) = compress_postfix_leaves(
+        let (tree2_level1, tree2_level2) = compress_postfix_leaves(
             &bitvectors[0x800 / CHUNK_SIZE..0x10000 / CHUNK_SIZE]);
-        let three = Three { level1: level1, level2: level2 };
 
-        let (mid, level3) = compress_postfix_leaves(
+        let (mid, tree3_level3) = compress_postfix_leaves(
             &bitvectors[0x10000 / CHUNK_SIZE..0x110000 / CHUNK_SIZE]);
-        let (level1, level2) = compress_postfix_mid(&mid, 64);
-        let four = Four {
-            level1: level1, level2: level2, level3: level3,
-        };
+        let (tree3_level1, tree3_level2) = compress_postfix_mid(&mid, 64);
 
         TrieSet {
-            oneortwo: oneortwo,
-            three: three,
-            four: four,
+            tree1_level1: tree1_level1,
+            tree2_level1: tree2_level1,
+            tree2_level2: tree2_level2,
+            tree3_level1: tree3_level1,
+            tree3_level2: tree3_level2,
+            tree3_level3: tree3_level3,
         }
     }
 
+    /// Return true if and only if the given scalar value is in this set.
     pub fn contains_char(&self, c: char) -> bool {
-        self.contains(c as usize)
+        self.as_slice().contains_char(c)
     }
 
+    /// Return true if and only if the given codepoint is in this set.
     pub fn contains_u32(&self, cp: u32) -> bool {
-        self.contains(cp as usize)
-    }
-
-    #[inline(always)]
-    fn contains(&self, cp: usize) -> bool {
-        if cp < 0x800 {
-            self.chunk_contains(cp, self.oneortwo.0[cp >> 6])
-        } else if cp < 0x10000 {
-            let leaf = self.three.level1[(cp >> 6) - 0x20];
-            self.chunk_contains(cp, self.three.level2[leaf as usize])
-        } else {
-            let child = self.four.level1[(cp >> 12) - 0x10];
-            let i = ((child as usize) << 6) + ((cp >> 6) & 0x3F);
-            let leaf = self.four.level2[i];
-            self.chunk_contains(cp, self.four.level3[leaf as usize])
-        }
-    }
-
-    #[inline(always)]
-    fn chunk_contains(&self, cp: usize, chunk: u64) -> bool {
-        ((chunk >> (cp & 63)) & 1) != 0
+        self.as_slice().contains_u32(cp)
     }
 }
 
 impl<'a> TrieSetSlice<'a> {
+    /// Return true if and only if the given scalar value is in this set.
     pub fn contains_char(&self, c: char) -> bool {
         self.contains(c as usize)
     }
 
+    /// Return true if and only if the given codepoint is in this set.
+    ///
+    /// Codepoints greater than `0x10FFFF` are never in the set.
     pub fn contains_u32(&self, cp: u32) -> bool {
+        if cp > 0x10FFFF {
+            return false;
+        }
         self.contains(cp as usize)
     }
 
     #[inline(always)]
     fn contains(&self, cp: usize) -> bool {
         if cp < 0x800 {
-            self.chunk_contains(cp, self.oneortwo.0[cp >> 6])
+            self.chunk_contains(cp, self.tree1_level1[cp >> 6])
         } else if cp < 0x10000 {
-            let leaf = self.three.level1[(cp >> 6) - 0x20];
-            self.chunk_contains(cp, self.three.level2[leaf as usize])
+            let leaf = self.tree2_level1[(cp >> 6) - 0x20];
+            self.chunk_contains(cp, self.tree2_level2[leaf as usize])
         } else {
-            let child = self.four.level1[(cp >> 12) - 0x10];
+            let child = self.tree3_level1[(cp >> 12) - 0x10];
             let i = ((child as usize) << 6) + ((cp >> 6) & 0x3F);
-            let leaf = self.four.level2[i];
-            self.chunk_contains(cp, self.four.level3[leaf as usize])
+            let leaf = self.tree3_level2[i];
+            self.chunk_contains(cp, self.tree3_level3[leaf as usize])
         }
     }
 
@@ -232,4 +233,20 @@ mod tests {
         assert!(!set.contains_char('⛇'));
         assert!(!set.contains_char('🐲'));
     }
+
+    #[test]
+    fn set_matches_naive() {
+        let contains = |cp: u32| {
+            (cp / 37) % 5 == 0
+            || (cp >= 0x4E00 && cp < 0xA000)
+            || (cp >= 0x20000 && cp < 0x2A6E0)
+        };
+        let codepoints: Vec<u32> =
+            (0..0x110000).filter(|&cp| contains(cp)).collect();
+        let set = TrieSet::from_codepoints(&codepoints);
+        for cp in 0..0x110000 {
+            assert_eq!(contains(cp), set.contains_u32(cp), "{:X}", cp);
+        }
+        assert!(!set.contains_u32(0x110000));
+    }
 }
@@ -0,0 +1,136 @@
+// This implementation should correspond to the bracket pair identification
+// described in UAX #9, BD16.
+
+/// The values of the `Bidi_Class` property.
+///
+/// These values are defined in UAX #9, Table 4, and are named after their
+/// short property value aliases.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BidiClass {
+    L, R, AL, EN, ES, ET, AN, CS, NSM, BN, B, S, WS, ON, LRE, LRO, RLE, RLO,
+    PDF, LRI, RLI, FSI, PDI,
+}
+
+/// The value of the `Bidi_Paired_Bracket_Type` property for a bracket.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BracketType {
+    /// An opening paired bracket, e.g., `(`.
+    Open,
+    /// A closing paired bracket, e.g., `)`.
+    Close,
+}
+
+/// The maximum depth of the stack of opening brackets, as defined by BD16.
+const MAX_DEPTH: usize = 63;
+
+/// Return the bracket pairs in the given isolating run sequence.
+///
+/// `bracket` should return the `Bidi_Paired_Bracket` and
+/// `Bidi_Paired_Bracket_Type` property values of a character as listed in
+/// `BidiBrackets.txt`, or `None` if the character is not a paired bracket.
+/// Characters whose current bidirectional character type is not `ON` should
+/// not be reported as brackets. `canonical` should return the canonical
+/// equivalent of a bracket, so that, e.g., U+2329 and U+3008 match.
+///
+/// Each pair is returned as the indices of its opening and closing brackets
+/// in `chars`, and the pairs are sorted by the index of the opening bracket.
+/// If more than 63 opening brackets are nested, then processing stops and
+/// only the pairs found before that point are returned.
+pub fn bracket_pairs<F, G>(
+    chars: &[char],
+    bracket: F,
+    canonical: G,
+) -> Vec<(usize, usize)>
+where F: Fn(char) -> Option<(char, BracketType)>,
+      G: Fn(char) -> char
+{
+    // The canonical closing bracket and index of every unmatched opening
+    // bracket.
+    let mut stack: Vec<(char, usize)> = vec![];
+    let mut pairs = vec![];
+    for (i, &c) in chars.iter().enumerate() {
+        match bracket(c) {
+            None => {}
+            Some((paired, BracketType::Open)) => {
+                if stack.len() == MAX_DEPTH {
+                    break;
+                }
+                stack.push((canonical(paired), i));
+            }
+            Some((_, BracketType::Close)) => {
+                let closing = canonical(c);
+                let found = stack.iter().rposition(|&(p, _)| p == closing);
+                if let Some(depth) = found {
+                    pairs.push((stack[depth].1, i));
+                    stack.truncate(depth);
+                }
+            }
+        }
+    }
+    pairs.sort();
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BracketType::{self, *};
+    use super::bracket_pairs;
+
+    // A small excerpt of BidiBrackets.txt.
+    fn bracket(c: char) -> Option<(char, BracketType)> {
+        match c {
+            '(' => Some((')', Open)),
+            ')' => Some(('(', Close)),
+            '[' => Some((']', Open)),
+            ']' => Some(('[', Close)),
+            '\u{2329}' => Some(('\u{232A}', Open)),
+            '\u{232A}' => Some(('\u{2329}', Close)),
+            '\u{3008}' => Some(('\u{3009}', Open)),
+            '\u{3009}' => Some(('\u{3008}', Close)),
+            _ => None,
+        }
+    }
+
+    fn canonical(c: char) -> char {
+        match c {
+            '\u{2329}' => '\u{3008}',
+            '\u{232A}' => '\u{3009}',
+            c => c,
+        }
+    }
+
+    fn pairs(s: &str) -> Vec<(usize, usize)> {
+        let chars: Vec<char> = s.chars().collect();
+        bracket_pairs(&chars, bracket, canonical)
+    }
+
+    #[test]
+    fn pairs_basic() {
+        assert_eq!(pairs("a(b)c"), vec![(1, 3)]);
+        assert_eq!(pairs("a(b[c)d]"), vec![(1, 5)]);
+        assert_eq!(pairs("a(b]c)d"), vec![(1, 5)]);
+        assert_eq!(pairs("[a(b)c]"), vec![(0, 6), (2, 4)]);
+        assert_eq!(pairs("(a)(b)"), vec![(0, 2), (3, 5)]);
+        assert_eq!(pairs("a)b(c"), vec![]);
+        assert_eq!(pairs(""), vec![]);
+    }
+
+    #[test]
+    fn pairs_canonical() {
+        assert_eq!(pairs("\u{2329}a\u{3009}"), vec![(0, 2)]);
+        assert_eq!(pairs("\u{3008}a\u{232A}"), vec![(0, 2)]);
+    }
+
+    #[test]
+    fn pairs_overflow() {
+        let mut s = "(".repeat(63);
+        s.push_str(&")".repeat(63));
+        assert_eq!(pairs(&s).len(), 63);
+
+        let mut s = "()".to_string();
+        s.push_str(&"(".repeat(64));
+        s.push_str(&")".repeat(64));
+        assert_eq!(pairs(&s), vec![(0, 1)]);
+    }
+}
@@ -0,0 +1,231 @@
+use std::vec;
+
+use normalization::{canonical_reorder, full_canonical_decomposition};
+
+// This implementation should correspond to the case folding described in
+// Unicode 3.13 and `CaseFolding.txt`. The folding tables are supplied by the
+// caller, and must be sorted by their first element so that they can be
+// binary searched.
+
+/// The mappings with status `T` in `CaseFolding.txt`.
+///
+/// These replace the default foldings of `I` and `İ` in Turkic languages.
+const TURKIC: &'static [(char, char)] = &[
+    ('\u{0049}', '\u{0131}'),
+    ('\u{0130}', '\u{0069}'),
+];
+
+/// Return the simple case folding of the given character.
+///
+/// `table` should contain the mappings with status `C` and `S` in
+/// `CaseFolding.txt`, sorted by character. Characters without a mapping fold
+/// to themselves.
+pub fn simple_fold(c: char, table: &[(char, char)]) -> char {
+    match table.binary_search_by_key(&c, |&(k, _)| k) {
+        Ok(i) => table[i].1,
+        Err(_) => c,
+    }
+}
+
+/// Append the full case folding of the given character to `buf`.
+///
+/// `table` should contain the mappings with status `C` and `F` in
+/// `CaseFolding.txt`, sorted by character. Characters without a mapping fold
+/// to themselves.
+pub fn full_fold(c: char, table: &[(char, &[char])], buf: &mut Vec<char>) {
+    match table.binary_search_by_key(&c, |&(k, _)| k) {
+        Ok(i) => buf.extend_from_slice(table[i].1),
+        Err(_) => buf.push(c),
+    }
+}
+
+/// Returns true if and only if the given strings are canonical caseless
+/// matches.
+///
+/// That is, this compares `NFD(toCasefold(NFD(a)))` with
+/// `NFD(toCasefold(NFD(b)))`. `mapping` and `ccc` are the canonical
+/// decomposition mapping and combining class lookups used by
+/// `full_canonical_decomposition` and `canonical_reorder`, and `fold` is the
+/// full case folding table used by `full_fold`.
+///
+/// This implements canonical caseless matching as defined in Unicode 3.13
+/// (D145). Unlike default caseless matching (D144), it treats canonically
+/// equivalent strings as equal, e.g., `Å` matches both `å` and `a` followed
+/// by U+030A COMBINING RING ABOVE.
+pub fn caseless_eq<'a, F, G>(
+    a: &str,
+    b: &str,
+    mapping: F,
+    ccc: G,
+    fold: &[(char, &[char])],
+) -> bool
+where F: Fn(char) -> Option<&'a [char]>,
+      G: Fn(char) -> u8
+{
+    let key = |s: &str| {
+        let nfd = |chars: &[char]| {
+            let mut out = vec![];
+            for &c in chars {
+                full_canonical_decomposition(c, &mapping, &mut out);
+            }
+            canonical_reorder(&mut out, &ccc);
+            out
+        };
+        let mut folded = vec![];
+        for c in nfd(&s.chars().collect::<Vec<char>>()) {
+            full_fold(c, fold, &mut folded);
+        }
+        nfd(&folded)
+    };
+    key(a) == key(b)
+}
+
+/// An iterator adapter that applies full case folding to a sequence of
+/// characters.
+///
+/// The folding table has the same format as the one used by `full_fold`.
+/// By default, the mappings with status `T` are not used. Enable them with
+/// `turkic` to fold text in Turkic languages.
+#[derive(Clone, Debug)]
+pub struct CaseFold<'a, I> {
+    it: I,
+    table: &'a [(char, &'a [char])],
+    turkic: bool,
+    pending: vec::IntoIter<char>,
+}
+
+impl<'a, I: Iterator<Item=char>> CaseFold<'a, I> {
+    /// Create a new adapter that folds the characters yielded by the given
+    /// iterator with the given table.
+    pub fn new<T>(it: T, table: &'a [(char, &'a [char])]) -> CaseFold<'a, I>
+    where T: IntoIterator<IntoIter=I, Item=char>
+    {
+        CaseFold {
+            it: it.into_iter(),
+            table: table,
+            turkic: false,
+            pending: vec![].into_iter(),
+        }
+    }
+
+    /// When enabled, fold `I` to `ı` and `İ` to `i`, as specified by the
+    /// mappings with status `T` in `CaseFolding.txt`.
+    ///
+    /// This is disabled by default.
+    pub fn turkic(mut self, yes: bool) -> CaseFold<'a, I> {
+        self.turkic = yes;
+        self
+    }
+}
+
+impl<'a, I: Iterator<Item=char>> Iterator for CaseFold<'a, I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if let Some(c) = self.pending.next() {
+            return Some(c);
+        }
+        let c = match self.it.next() {
+            None => return None,
+            Some(c) => c,
+        };
+        if self.turkic {
+            let t = TURKIC.iter().find(|&&(k, _)| k == c);
+            if let Some(&(_, folded)) = t {
+                return Some(folded);
+            }
+        }
+        let mut buf = vec![];
+        full_fold(c, self.table, &mut buf);
+        self.pending = buf.into_iter();
+        self.pending.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CaseFold, caseless_eq, full_fold, simple_fold};
+
+    // A small excerpt of CaseFolding.txt.
+    const SIMPLE: &'static [(char, char)] = &[
+        ('A', 'a'), ('B', 'b'), ('I', 'i'), ('S', 's'),
+        ('\u{1E9E}', '\u{00DF}'), ('\u{212A}', 'k'),
+    ];
+
+    const FULL: &'static [(char, &'static [char])] = &[
+        ('A', &['a']), ('B', &['b']), ('I', &['i']), ('S', &['s']),
+        ('\u{00C5}', &['\u{00E5}']), ('\u{00DF}', &['s', 's']),
+        ('\u{0130}', &['i', '\u{0307}']), ('\u{01FA}', &['\u{01FB}']),
+        ('\u{1E9E}', &['s', 's']), ('\u{212A}', &['k']),
+        ('\u{212B}', &['\u{00E5}']),
+    ];
+
+    // A small excerpt of the canonical decomposition mappings and combining
+    // classes in UnicodeData.txt.
+    fn mapping(c: char) -> Option<&'static [char]> {
+        match c {
+            '\u{00C5}' => Some(&['A', '\u{030A}']),
+            '\u{00E5}' => Some(&['a', '\u{030A}']),
+            '\u{01FA}' => Some(&['\u{00C5}', '\u{0301}']),
+            '\u{212B}' => Some(&['\u{00C5}']),
+            '\u{0130}' => Some(&['I', '\u{0307}']),
+            _ => None,
+        }
+    }
+
+    fn ccc(c: char) -> u8 {
+        match c {
+            '\u{0301}' | '\u{0307}' | '\u{030A}' => 230,
+            '\u{0323}' => 220,
+            _ => 0,
+        }
+    }
+
+    fn caseless(a: &str, b: &str) -> bool {
+        caseless_eq(a, b, mapping, ccc, FULL)
+    }
+
+    fn fold(s: &str, turkic: bool) -> String {
+        CaseFold::new(s.chars(), FULL).turkic(turkic).collect()
+    }
+
+    #[test]
+    fn simple() {
+        assert_eq!(simple_fold('A', SIMPLE), 'a');
+        assert_eq!(simple_fold('a', SIMPLE), 'a');
+        assert_eq!(simple_fold('\u{212A}', SIMPLE), 'k');
+        assert_eq!(simple_fold('\u{1E9E}', SIMPLE), '\u{00DF}');
+        assert_eq!(simple_fold('\u{00DF}', SIMPLE), '\u{00DF}');
+    }
+
+    #[test]
+    fn full() {
+        let mut buf = vec![];
+        full_fold('\u{00DF}', FULL, &mut buf);
+        full_fold('x', FULL, &mut buf);
+        full_fold('B', FULL, &mut buf);
+        assert_eq!(buf, vec!['s', 's', 'x', 'b']);
+    }
+
+    #[test]
+    fn iter() {
+        assert_eq!(fold("BASS IS Baß", false), "bass is bass");
+        assert_eq!(fold("", false), "");
+        assert_eq!(fold("\u{212A}I\u{0130}", false), "ki\u{0069}\u{0307}");
+        assert_eq!(fold("\u{212A}I\u{0130}", true), "k\u{0131}i");
+    }
+
+    #[test]
+    fn canonical_caseless() {
+        assert!(caseless("BASS", "bass"));
+        assert!(caseless("Baß", "BASS"));
+        assert!(caseless("\u{00C5}", "\u{00E5}"));
+        assert!(caseless("\u{212B}", "a\u{030A}"));
+        assert!(caseless("A\u{030A}", "\u{00E5}"));
+        assert!(caseless("\u{01FA}", "a\u{030A}\u{0301}"));
+        assert!(caseless("a\u{0307}\u{0323}", "A\u{0323}\u{0307}"));
+        assert!(caseless("", ""));
+        assert!(!caseless("a\u{030A}", "a"));
+        assert!(!caseless("I", "\u{0130}"));
+    }
+}
@@ -0,0 +1,295 @@
+use ranges::find_in_ranges;
+
+// This implementation should correspond to the default case conversion
+// described in Unicode 3.13. The mappings in `UnicodeData.txt` and
+// `SpecialCasing.txt` are supplied by the caller, and must be sorted by
+// character so that they can be binary searched.
+
+/// The tables required by the default case conversion functions.
+#[derive(Clone, Copy, Debug)]
+pub struct CaseTables<'a> {
+    /// The simple lowercase mappings in `UnicodeData.txt`.
+    pub lowercase: &'a [(char, char)],
+    /// The simple uppercase mappings in `UnicodeData.txt`.
+    pub uppercase: &'a [(char, char)],
+    /// The simple titlecase mappings in `UnicodeData.txt`.
+    pub titlecase: &'a [(char, char)],
+    /// The records in `SpecialCasing.txt`.
+    ///
+    /// Records with language-specific conditions, such as `tr` or `lt`,
+    /// should be excluded, since they are never applied.
+    pub special: &'a [SpecialCasing<'a>],
+    /// The ranges of codepoints with the `Cased` property.
+    pub cased: &'a [(u32, u32)],
+    /// The ranges of codepoints with the `Case_Ignorable` property.
+    pub case_ignorable: &'a [(u32, u32)],
+}
+
+/// A single record in `SpecialCasing.txt`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SpecialCasing<'a> {
+    /// The character that this record applies to.
+    pub codepoint: char,
+    /// The full lowercase mapping of the character.
+    pub lowercase: &'a [char],
+    /// The full titlecase mapping of the character.
+    pub titlecase: &'a [char],
+    /// The full uppercase mapping of the character.
+    pub uppercase: &'a [char],
+    /// The condition under which this record applies, if any.
+    pub condition: Option<CasingCondition>,
+}
+
+/// A language-independent casing context, as defined in Unicode 3.13,
+/// Table 3-17.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CasingCondition {
+    /// The character is preceded by a cased letter and is not followed by
+    /// one, ignoring case-ignorable characters in between.
+    FinalSigma,
+}
+
+/// Convert the given string to lowercase.
+///
+/// Every character is mapped by its full lowercase mapping, i.e., by the
+/// first record in `SpecialCasing.txt` for it whose condition holds, or by
+/// its simple lowercase mapping if there is no such record.
+///
+/// This implements `toLowercase(X)` as defined in Unicode 3.13 (R2).
+pub fn to_lowercase_full(s: &str, tables: &CaseTables) -> String {
+    let mut out = String::with_capacity(s.len());
+    for (i, c) in s.char_indices() {
+        push_mapping(&mut out, s, i, c, tables, Case::Lower);
+    }
+    out
+}
+
+/// Convert the given string to uppercase.
+///
+/// This is like `to_lowercase_full`, but uses uppercase mappings.
+///
+/// This implements `toUppercase(X)` as defined in Unicode 3.13 (R1).
+pub fn to_uppercase_full(s: &str, tables: &CaseTables) -> String {
+    let mut out = String::with_capacity(s.len());
+    for (i, c) in s.char_indices() {
+        push_mapping(&mut out, s, i, c, tables, Case::Upper);
+    }
+    out
+}
+
+/// Convert the given string to titlecase.
+///
+/// `word_boundaries` should be the byte offsets of the word boundaries in
+/// the string, e.g., as returned by `word_boundaries`. In each word, the
+/// first cased character is mapped by its full titlecase mapping, and every
+/// character after it is mapped by its full lowercase mapping. Characters
+/// before the first cased character are left as is.
+///
+/// This implements `toTitlecase(X)` as defined in Unicode 3.13 (R3).
+pub fn to_titlecase_full(
+    s: &str,
+    tables: &CaseTables,
+    word_boundaries: &[usize],
+) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut seen_cased = false;
+    for (i, c) in s.char_indices() {
+        if word_boundaries.binary_search(&i).is_ok() {
+            seen_cased = false;
+        }
+        if seen_cased {
+            push_mapping(&mut out, s, i, c, tables, Case::Lower);
+        } else if in_ranges(tables.cased, c) {
+            seen_cased = true;
+            push_mapping(&mut out, s, i, c, tables, Case::Title);
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Case {
+    Lower,
+    Upper,
+    Title,
+}
+
+/// Push the full mapping of the character `c` at byte offset `i` in `s`.
+fn push_mapping(
+    out: &mut String,
+    s: &str,
+    i: usize,
+    c: char,
+    tables: &CaseTables,
+    case: Case,
+) {
+    let start = lower_bound(tables.special, c);
+    let special = tables.special[start..]
+        .iter()
+        .take_while(|sc| sc.codepoint == c)
+        .find(|sc| sc.condition.map_or(true, |cond| {
+            is_condition_met(cond, s, i, c, tables)
+        }));
+    if let Some(sc) = special {
+        let mapping = match case {
+            Case::Lower => sc.lowercase,
+            Case::Upper => sc.uppercase,
+            Case::Title => sc.titlecase,
+        };
+        out.extend(mapping.iter());
+        return;
+    }
+    let simple = match case {
+        Case::Lower => tables.lowercase,
+        Case::Upper => tables.uppercase,
+        Case::Title => tables.titlecase,
+    };
+    match simple.binary_search_by_key(&c, |&(k, _)| k) {
+        Ok(j) => out.push(simple[j].1),
+        Err(_) => out.push(c),
+    }
+}
+
+/// Return the index of the first record for the given character.
+fn lower_bound(special: &[SpecialCasing], c: char) -> usize {
+    let (mut lo, mut hi) = (0, special.len());
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if special[mid].codepoint < c {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// Returns true if and only if the given condition holds for the character
+/// `c` at byte offset `i` in `s`.
+fn is_condition_met(
+    cond: CasingCondition,
+    s: &str,
+    i: usize,
+    c: char,
+    tables: &CaseTables,
+) -> bool {
+    match cond {
+        CasingCondition::FinalSigma => {
+            let before = s[..i].chars().rev();
+            let after = s[i + c.len_utf8()..].chars();
+            starts_with_cased(before, tables)
+                && !starts_with_cased(after, tables)
+        }
+    }
+}
+
+/// Returns true if and only if the first character yielded by the given
+/// iterator that isn't case-ignorable is cased.
+fn starts_with_cased<I>(chars: I, tables: &CaseTables) -> bool
+where I: Iterator<Item=char>
+{
+    chars
+        .skip_while(|&c| in_ranges(tables.case_ignorable, c))
+        .next()
+        .map_or(false, |c| in_ranges(tables.cased, c))
+}
+
+fn in_ranges(ranges: &[(u32, u32)], c: char) -> bool {
+    find_in_ranges(ranges, c as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CaseTables, CasingCondition, SpecialCasing, to_lowercase_full,
+        to_titlecase_full, to_uppercase_full,
+    };
+
+    // Small excerpts of UnicodeData.txt, SpecialCasing.txt and
+    // DerivedCoreProperties.txt.
+    const TABLES: CaseTables<'static> = CaseTables {
+        lowercase: &[
+            ('A', 'a'), ('B', 'b'), ('I', 'i'), ('O', 'o'), ('S', 's'),
+            ('\u{01C4}', '\u{01C6}'), ('\u{01C5}', '\u{01C6}'),
+            ('\u{03A3}', '\u{03C3}'),
+        ],
+        uppercase: &[
+            ('a', 'A'), ('b', 'B'), ('i', 'I'), ('o', 'O'), ('s', 'S'),
+            ('\u{01C5}', '\u{01C4}'), ('\u{01C6}', '\u{01C4}'),
+            ('\u{03C2}', '\u{03A3}'), ('\u{03C3}', '\u{03A3}'),
+        ],
+        titlecase: &[
+            ('a', 'A'), ('b', 'B'), ('i', 'I'), ('o', 'O'), ('s', 'S'),
+            ('\u{01C4}', '\u{01C5}'), ('\u{01C6}', '\u{01C5}'),
+            ('\u{03C2}', '\u{03A3}'), ('\u{03C3}', '\u{03A3}'),
+        ],
+        special: &[
+            SpecialCasing {
+                codepoint: '\u{00DF}',
+                lowercase: &['\u{00DF}'],
+                titlecase: &['S', 's'],
+                uppercase: &['S', 'S'],
+                condition: None,
+            },
+            SpecialCasing {
+                codepoint: '\u{03A3}',
+                lowercase: &['\u{03C2}'],
+                titlecase: &['\u{03A3}'],
+                uppercase: &['\u{03A3}'],
+                condition: Some(CasingCondition::FinalSigma),
+            },
+            SpecialCasing {
+                codepoint: '\u{FB00}',
+                lowercase: &['\u{FB00}'],
+                titlecase: &['F', 'f'],
+                uppercase: &['F', 'F'],
+                condition: None,
+            },
+        ],
+        cased: &[
+            (0x41, 0x5A), (0x61, 0x7A), (0xDF, 0xDF), (0x1C4, 0x1C6),
+            (0x391, 0x3A9), (0x3B1, 0x3C9), (0xFB00, 0xFB00),
+        ],
+        case_ignorable: &[(0x27, 0x27), (0x2E, 0x2E), (0x300, 0x36F)],
+    };
+
+    #[test]
+    fn lower() {
+        assert_eq!(to_lowercase_full("BASS", &TABLES), "bass");
+        assert_eq!(to_lowercase_full("\u{00DF}", &TABLES), "\u{00DF}");
+        assert_eq!(to_lowercase_full("\u{01C5}", &TABLES), "\u{01C6}");
+    }
+
+    #[test]
+    fn final_sigma() {
+        let lower = |s| to_lowercase_full(s, &TABLES);
+        assert_eq!(lower("\u{03A3}\u{03A3}"), "\u{03C3}\u{03C2}");
+        assert_eq!(lower("\u{03A3}"), "\u{03C3}");
+        assert_eq!(lower("A\u{03A3} B"), "a\u{03C2} b");
+        assert_eq!(lower("A\u{03A3}B"), "a\u{03C3}b");
+        // Case-ignorable characters are skipped in both directions.
+        assert_eq!(lower("A.\u{03A3}'"), "a.\u{03C2}'");
+        assert_eq!(lower("A\u{03A3}\u{0301}B"), "a\u{03C3}\u{0301}b");
+        assert_eq!(lower(" \u{03A3} "), " \u{03C3} ");
+    }
+
+    #[test]
+    fn upper() {
+        assert_eq!(to_uppercase_full("bo\u{00DF}", &TABLES), "BOSS");
+        assert_eq!(to_uppercase_full("\u{FB00}", &TABLES), "FF");
+        assert_eq!(to_uppercase_full("\u{03C2}", &TABLES), "\u{03A3}");
+    }
+
+    #[test]
+    fn title() {
+        let title = |s: &str, b: &[usize]| to_titlecase_full(s, &TABLES, b);
+        assert_eq!(title("boss bob", &[0, 4, 5, 8]), "Boss Bob");
+        assert_eq!(title("BOSS", &[0, 4]), "Boss");
+        assert_eq!(title("\u{00DF}a", &[0, 3]), "Ssa");
+        assert_eq!(title("'bob'", &[0, 5]), "'Bob'");
+        assert_eq!(title("\u{01C4}\u{01C4}", &[0, 4]), "\u{01C5}\u{01C6}");
+        assert_eq!(title("", &[]), "");
+    }
+}
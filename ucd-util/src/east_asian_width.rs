@@ -0,0 +1,86 @@
+use ranges::find_in_range_values;
+
+// This implementation should correspond to the recommendations in UAX #11,
+// section 5.
+
+/// The values of the `East_Asian_Width` property.
+///
+/// These values are defined in UAX #11, section 4, and are named after their
+/// short property value aliases.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EastAsianWidth {
+    A, F, H, N, Na, W,
+}
+
+/// How to resolve characters whose `East_Asian_Width` is ambiguous (`A`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AmbiguousWidth {
+    /// Treat ambiguous characters as narrow, which is appropriate outside
+    /// of East Asian contexts.
+    Narrow,
+    /// Treat ambiguous characters as wide, which is appropriate in East
+    /// Asian contexts, e.g., when using a legacy East Asian font.
+    Wide,
+}
+
+/// Return the effective width of the given codepoint, in columns.
+///
+/// `widths` should be the ranges of codepoints and their `East_Asian_Width`
+/// values, as listed in `EastAsianWidth.txt`. The ranges must be sorted and
+/// non-overlapping. Codepoints missing from this table are treated as
+/// neutral (`N`), so the table should include the default values of
+/// unassigned codepoints if they matter to the caller.
+///
+/// Fullwidth (`F`) and wide (`W`) characters are two columns wide, and
+/// ambiguous (`A`) characters are resolved according to `ambiguous`. Every
+/// other character is one column wide. Note that this doesn't account for
+/// characters that don't occupy a column at all, such as combining marks
+/// and control characters.
+pub fn effective_width(
+    cp: u32,
+    widths: &[(u32, u32, EastAsianWidth)],
+    ambiguous: AmbiguousWidth,
+) -> usize {
+    match find_in_range_values(widths, cp).unwrap_or(EastAsianWidth::N) {
+        EastAsianWidth::F | EastAsianWidth::W => 2,
+        EastAsianWidth::A if ambiguous == AmbiguousWidth::Wide => 2,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AmbiguousWidth::*;
+    use super::EastAsianWidth::{self, *};
+    use super::effective_width;
+
+    // A small excerpt of EastAsianWidth.txt.
+    const WIDTHS: &'static [(u32, u32, EastAsianWidth)] = &[
+        (0x0020, 0x007E, Na),
+        (0x00A1, 0x00A1, A),
+        (0x00A9, 0x00A9, N),
+        (0x03B1, 0x03C1, A),
+        (0x3000, 0x3000, F),
+        (0x4E00, 0x9FFF, W),
+        (0xFF01, 0xFF60, F),
+        (0xFF61, 0xFFBE, H),
+    ];
+
+    #[test]
+    fn widths() {
+        for &ambiguous in &[Narrow, Wide] {
+            let width = |cp| effective_width(cp, WIDTHS, ambiguous);
+            assert_eq!(width(0x41), 1);
+            assert_eq!(width(0xA9), 1);
+            assert_eq!(width(0x3000), 2);
+            assert_eq!(width(0x4E00), 2);
+            assert_eq!(width(0xFF21), 2);
+            assert_eq!(width(0xFF71), 1);
+            assert_eq!(width(0x10000), 1);
+        }
+        assert_eq!(effective_width(0x3B1, WIDTHS, Narrow), 1);
+        assert_eq!(effective_width(0x3B1, WIDTHS, Wide), 2);
+        assert_eq!(effective_width(0xA1, WIDTHS, Wide), 2);
+    }
+}
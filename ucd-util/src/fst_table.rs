@@ -0,0 +1,120 @@
+use fst;
+
+/// A set of codepoints stored in an FST.
+///
+/// This is the format of the sets emitted by `ucd-generate` when an FST
+/// directory is given, in which every codepoint is encoded as a big-endian
+/// `u32` key. This wrapper encodes codepoints in the same way, so that
+/// callers don't need to know the key format.
+#[derive(Debug)]
+pub struct FstSet {
+    set: fst::Set,
+}
+
+impl FstSet {
+    /// Create a set from the bytes of an FST, e.g., as included with
+    /// `include_bytes!`.
+    pub fn from_static_slice(
+        bytes: &'static [u8],
+    ) -> Result<FstSet, fst::Error> {
+        let fst = fst::raw::Fst::from_static_slice(bytes)?;
+        Ok(FstSet { set: fst::Set::from(fst) })
+    }
+
+    /// Returns true if and only if the given codepoint is in this set.
+    pub fn contains_codepoint(&self, cp: u32) -> bool {
+        self.set.contains(codepoint_key(cp))
+    }
+
+    /// Return the underlying FST set.
+    pub fn as_fst(&self) -> &fst::Set {
+        &self.set
+    }
+}
+
+impl From<fst::Set> for FstSet {
+    fn from(set: fst::Set) -> FstSet {
+        FstSet { set: set }
+    }
+}
+
+/// A map from codepoints to integers stored in an FST.
+///
+/// Like `FstSet`, every codepoint is encoded as a big-endian `u32` key,
+/// which is the format of the maps emitted by `ucd-generate`.
+#[derive(Debug)]
+pub struct FstMap {
+    map: fst::Map,
+}
+
+impl FstMap {
+    /// Create a map from the bytes of an FST, e.g., as included with
+    /// `include_bytes!`.
+    pub fn from_static_slice(
+        bytes: &'static [u8],
+    ) -> Result<FstMap, fst::Error> {
+        let fst = fst::raw::Fst::from_static_slice(bytes)?;
+        Ok(FstMap { map: fst::Map::from(fst) })
+    }
+
+    /// Return the value associated with the given codepoint, if one exists.
+    pub fn get_codepoint(&self, cp: u32) -> Option<u64> {
+        self.map.get(codepoint_key(cp))
+    }
+
+    /// Return the underlying FST map.
+    pub fn as_fst(&self) -> &fst::Map {
+        &self.map
+    }
+}
+
+impl From<fst::Map> for FstMap {
+    fn from(map: fst::Map) -> FstMap {
+        FstMap { map: map }
+    }
+}
+
+/// Return the given codepoint encoded in big-endian.
+fn codepoint_key(cp: u32) -> [u8; 4] {
+    [(cp >> 24) as u8, (cp >> 16) as u8, (cp >> 8) as u8, cp as u8]
+}
+
+#[cfg(test)]
+mod tests {
+    use fst;
+
+    use super::{FstMap, FstSet, codepoint_key};
+
+    #[test]
+    fn set() {
+        let cps = &[0x41, 0x100, 0x10FFFF];
+        let set = fst::Set::from_iter(cps.iter().map(|&cp| codepoint_key(cp)))
+            .unwrap();
+        let set = FstSet::from(set);
+        assert!(set.contains_codepoint(0x41));
+        assert!(set.contains_codepoint(0x100));
+        assert!(set.contains_codepoint(0x10FFFF));
+        assert!(!set.contains_codepoint(0x42));
+        assert!(!set.contains_codepoint(0x1));
+        assert_eq!(set.as_fst().len(), 3);
+    }
+
+    #[test]
+    fn map() {
+        let pairs = &[(0x30, 0), (0x41, 1), (0x1F600, 2)];
+        let map = fst::Map::from_iter(
+            pairs.iter().map(|&(cp, v)| (codepoint_key(cp), v)))
+            .unwrap();
+        let map = FstMap::from(map);
+        assert_eq!(map.get_codepoint(0x30), Some(0));
+        assert_eq!(map.get_codepoint(0x41), Some(1));
+        assert_eq!(map.get_codepoint(0x1F600), Some(2));
+        assert_eq!(map.get_codepoint(0x31), None);
+    }
+
+    #[test]
+    fn static_slice() {
+        assert!(FstSet::from_static_slice(b"not an fst").is_err());
+        assert!(FstMap::from_static_slice(b"").is_err());
+    }
+}
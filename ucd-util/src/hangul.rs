@@ -15,8 +15,27 @@ const S_BASE: u32 = 0xAC00;
 const L_BASE: u32 = 0x1100;
 const V_BASE: u32 = 0x1161;
 const T_BASE: u32 = 0x11A7;
+const L_COUNT: u32 = 19;
+const V_COUNT: u32 = 21;
 const T_COUNT: u32 = 28;
 const N_COUNT: u32 = 588;
+const S_COUNT: u32 = 11172;
+
+/// The values of the `Hangul_Syllable_Type` property, except for
+/// `Not_Applicable`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HangulSyllableType {
+    /// A leading consonant, e.g., U+1100 HANGUL CHOSEONG KIYEOK.
+    L,
+    /// A vowel, e.g., U+1161 HANGUL JUNGSEONG A.
+    V,
+    /// A trailing consonant, e.g., U+11A8 HANGUL JONGSEONG KIYEOK.
+    T,
+    /// A precomposed syllable without a trailing consonant.
+    LV,
+    /// A precomposed syllable with a trailing consonant.
+    LVT,
+}
 
 /// Return the character name of the given precomposed Hangul codepoint.
 ///
@@ -31,12 +50,92 @@ pub fn hangul_name(cp: u32) -> Option<String> {
         Some(triple) => triple,
     };
 
-    name.push_str(jamo_short_name(lpart));
-    name.push_str(jamo_short_name(vpart));
-    name.push_str(tpart.map_or("", jamo_short_name));
+    name.push_str(jamo_short_name(lpart).unwrap());
+    name.push_str(jamo_short_name(vpart).unwrap());
+    name.push_str(tpart.and_then(jamo_short_name).unwrap_or(""));
     Some(name)
 }
 
+/// Return the precomposed Hangul codepoint with the given character name.
+///
+/// The name must be of the form `HANGUL SYLLABLE <L><V><T>`, where each part
+/// is the Jamo short name of a leading consonant, a vowel and an optional
+/// trailing consonant, e.g., `HANGUL SYLLABLE GAG`. Names are matched
+/// exactly, so callers that want loose matching should map the name to its
+/// canonical form first.
+///
+/// If the given name does not correspond to a precomposed Hangul codepoint,
+/// then this returns `None`.
+///
+/// This is the inverse of `hangul_name`.
+pub fn hangul_name_to_codepoint(name: &str) -> Option<u32> {
+    let prefix = "HANGUL SYLLABLE ";
+    if !name.starts_with(prefix) {
+        return None;
+    }
+    let name = &name[prefix.len()..];
+    // Short names may be prefixes of one another, and the short name of
+    // U+110B is empty, so try every leading consonant and vowel that is a
+    // prefix of the name. Character names are unique, so the first complete
+    // match is the only one.
+    for l_index in 0..L_COUNT {
+        let lpart = jamo_short_name(L_BASE + l_index).unwrap();
+        if !name.starts_with(lpart) {
+            continue;
+        }
+        let rest = &name[lpart.len()..];
+        for v_index in 0..V_COUNT {
+            let vpart = jamo_short_name(V_BASE + v_index).unwrap();
+            if !rest.starts_with(vpart) {
+                continue;
+            }
+            let tpart = &rest[vpart.len()..];
+            let lv = S_BASE + l_index * N_COUNT + v_index * T_COUNT;
+            if tpart.is_empty() {
+                return Some(lv);
+            }
+            for t_index in 1..T_COUNT {
+                if jamo_short_name(T_BASE + t_index) == Some(tpart) {
+                    return Some(lv + t_index);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Return the `Jamo_Short_Name` of the given codepoint.
+///
+/// The short names are the ones listed in `Jamo.txt`, and are used to
+/// construct the names of precomposed Hangul syllables. Note that the short
+/// name of U+110B HANGUL CHOSEONG IEUNG is the empty string.
+///
+/// If the given codepoint is not a conjoining Jamo with a short name, then
+/// this returns `None`.
+pub fn jamo_short_name(cp: u32) -> Option<&'static str> {
+    JAMO_SHORT_NAME
+        .binary_search_by_key(&cp, |p| p.0)
+        .ok()
+        .map(|i| JAMO_SHORT_NAME[i].1)
+}
+
+/// Return the `Hangul_Syllable_Type` of the given codepoint.
+///
+/// The types of the conjoining Jamo are determined by the ranges listed in
+/// `HangulSyllableType.txt`, and the types of precomposed Hangul syllables
+/// are computed arithmetically. If the given codepoint has the type
+/// `Not_Applicable`, then this returns `None`.
+pub fn hangul_syllable_type(cp: u32) -> Option<HangulSyllableType> {
+    match cp {
+        0x1100..=0x115F | 0xA960..=0xA97C => Some(HangulSyllableType::L),
+        0x1160..=0x11A7 | 0xD7B0..=0xD7C6 => Some(HangulSyllableType::V),
+        0x11A8..=0x11FF | 0xD7CB..=0xD7FB => Some(HangulSyllableType::T),
+        _ if is_lv(cp) => Some(HangulSyllableType::LV),
+        0xAC00..=0xD7A3 => Some(HangulSyllableType::LVT),
+        _ => None,
+    }
+}
+
 /// Return the full canonical decomposition of the given precomposed Hangul
 /// codepoint.
 ///
@@ -70,14 +169,61 @@ pub fn hangul_full_canonical_decomposition(
     Some((l_part, v_part, t_part))
 }
 
-fn jamo_short_name(cp: u32) -> &'static str {
-    let i = JAMO_SHORT_NAME.binary_search_by_key(&cp, |p| p.0).unwrap();
-    JAMO_SHORT_NAME[i].1
+/// Return the precomposed Hangul syllable that is canonically equivalent to
+/// the given sequence of Jamo.
+///
+/// The sequence must be a leading consonant followed by a vowel, optionally
+/// followed by a trailing consonant, e.g., `[U+1111, U+1171, U+11B6]`. An LV
+/// syllable followed by a trailing consonant, e.g., `[U+D4CC, U+11B6]`, is
+/// also composed. For any other sequence, this returns `None`.
+///
+/// This is the inverse of `hangul_full_canonical_decomposition`, and
+/// implements the algorithms described in Unicode 3.12.
+pub fn hangul_full_canonical_composition(chars: &[char]) -> Option<char> {
+    let (lv, t) = match *chars {
+        [lv, t] if is_lv(lv as u32) => (lv as u32, Some(t as u32)),
+        [l, v] => (compose_lv(l as u32, v as u32)?, None),
+        [l, v, t] => (compose_lv(l as u32, v as u32)?, Some(t as u32)),
+        _ => return None,
+    };
+    let cp = match t {
+        None => lv,
+        Some(t) if T_BASE < t && t < T_BASE + T_COUNT => lv + (t - T_BASE),
+        Some(_) => return None,
+    };
+    ::std::char::from_u32(cp)
+}
+
+/// Compose a leading consonant and a vowel into an LV syllable.
+fn compose_lv(l: u32, v: u32) -> Option<u32> {
+    if !(L_BASE <= l && l < L_BASE + L_COUNT) {
+        return None;
+    }
+    if !(V_BASE <= v && v < V_BASE + V_COUNT) {
+        return None;
+    }
+    let lv_index = (l - L_BASE) * N_COUNT + (v - V_BASE) * T_COUNT;
+    Some(S_BASE + lv_index)
+}
+
+/// Returns true if and only if the given codepoint is an LV syllable, i.e.,
+/// a precomposed Hangul syllable without a trailing consonant.
+fn is_lv(cp: u32) -> bool {
+    S_BASE <= cp && cp < S_BASE + S_COUNT && (cp - S_BASE) % T_COUNT == 0
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{hangul_name, hangul_full_canonical_decomposition};
+    use super::HangulSyllableType::*;
+    use super::{
+        hangul_name, hangul_name_to_codepoint, hangul_syllable_type,
+        hangul_full_canonical_composition,
+        hangul_full_canonical_decomposition, jamo_short_name,
+    };
+
+    fn chars(cps: &[u32]) -> Vec<char> {
+        cps.iter().map(|&cp| ::std::char::from_u32(cp).unwrap()).collect()
+    }
 
     #[test]
     fn canon_decomp() {
@@ -86,20 +232,97 @@ mod tests {
             Some((0x1111, 0x1171, Some(0x11B6))));
     }
 
+    #[test]
+    fn canon_comp() {
+        let comp = |cps: &[u32]| {
+            hangul_full_canonical_composition(&chars(cps)).map(|c| c as u32)
+        };
+        assert_eq!(comp(&[0x1111, 0x1171, 0x11B6]), Some(0xD4DB));
+        assert_eq!(comp(&[0x1111, 0x1171]), Some(0xD4CC));
+        assert_eq!(comp(&[0xD4CC, 0x11B6]), Some(0xD4DB));
+
+        // An LVT syllable can't be composed with another trailing consonant.
+        assert_eq!(comp(&[0xD4DB, 0x11B6]), None);
+        // U+11A7 is not a trailing consonant.
+        assert_eq!(comp(&[0x1111, 0x1171, 0x11A7]), None);
+        assert_eq!(comp(&[0x1171, 0x1111]), None);
+        assert_eq!(comp(&[0x1111]), None);
+        assert_eq!(comp(&[]), None);
+    }
+
+    #[test]
+    fn canon_comp_roundtrip() {
+        for cp in 0xAC00..(0xD7A3 + 1) {
+            let (l, v, t) = hangul_full_canonical_decomposition(cp).unwrap();
+            let mut cps = vec![l, v];
+            cps.extend(t);
+            let c = hangul_full_canonical_composition(&chars(&cps));
+            assert_eq!(c, ::std::char::from_u32(cp));
+        }
+    }
+
+    #[test]
+    fn syllable_type() {
+        let hst = hangul_syllable_type;
+        assert_eq!(hst(0x1100), Some(L));
+        assert_eq!(hst(0x115F), Some(L));
+        assert_eq!(hst(0xA97C), Some(L));
+        assert_eq!(hst(0x1160), Some(V));
+        assert_eq!(hst(0x11A7), Some(V));
+        assert_eq!(hst(0xD7B0), Some(V));
+        assert_eq!(hst(0x11A8), Some(T));
+        assert_eq!(hst(0xD7FB), Some(T));
+        assert_eq!(hst(0xAC00), Some(LV));
+        assert_eq!(hst(0xAC01), Some(LVT));
+        assert_eq!(hst(0xD4CC), Some(LV));
+        assert_eq!(hst(0xD7A3), Some(LVT));
+        assert_eq!(hst(0xA97D), None);
+        assert_eq!(hst(0xD7A4), None);
+        assert_eq!(hst(0xD7C7), None);
+        assert_eq!(hst(0x41), None);
+    }
+
+    #[test]
+    fn short_name() {
+        assert_eq!(jamo_short_name(0x1100), Some("G"));
+        assert_eq!(jamo_short_name(0x110B), Some(""));
+        assert_eq!(jamo_short_name(0x1171), Some("WI"));
+        assert_eq!(jamo_short_name(0x11B6), Some("LH"));
+        assert_eq!(jamo_short_name(0x11A7), None);
+        assert_eq!(jamo_short_name(0xAC00), None);
+    }
+
     #[test]
     fn name() {
         assert_eq!(hangul_name(0xD4DB).unwrap(), "HANGUL SYLLABLE PWILH");
     }
 
+    #[test]
+    fn name_to_codepoint() {
+        let cp = hangul_name_to_codepoint;
+        assert_eq!(cp("HANGUL SYLLABLE GAG"), Some(0xAC01));
+        assert_eq!(cp("HANGUL SYLLABLE PWILH"), Some(0xD4DB));
+        assert_eq!(cp("HANGUL SYLLABLE A"), Some(0xC544));
+        assert_eq!(cp("HANGUL SYLLABLE AG"), Some(0xC545));
+        assert_eq!(cp("HANGUL SYLLABLE GGAG"), Some(0xAE4D));
+    }
+
     #[test]
     fn all() {
         for cp in 0xAC00..(0xD7A3 + 1) {
-            hangul_name(cp).unwrap();
+            let name = hangul_name(cp).unwrap();
+            assert_eq!(hangul_name_to_codepoint(&name), Some(cp));
         }
     }
 
     #[test]
     fn invalid() {
         assert!(hangul_name(0).is_none());
+        assert!(hangul_name_to_codepoint("HANGUL SYLLABLE").is_none());
+        assert!(hangul_name_to_codepoint("HANGUL SYLLABLE G").is_none());
+        assert!(hangul_name_to_codepoint("HANGUL SYLLABLE GAX").is_none());
+        assert!(hangul_name_to_codepoint("hangul syllable gag").is_none());
+        assert!(hangul_name_to_codepoint("CJK UNIFIED IDEOGRAPH-4E00")
+                .is_none());
     }
 }
@@ -56,9 +56,41 @@ pub fn ideograph_name(cp: u32) -> Option<String> {
     }
 }
 
+/// Return the ideograph codepoint with the given character name.
+///
+/// The name must be of the form `CJK UNIFIED IDEOGRAPH-XXXX`,
+/// `TANGUT IDEOGRAPH-XXXX` or `CJK COMPATIBILITY IDEOGRAPH-XXXX`, where
+/// `XXXX` is the codepoint in uppercase hexadecimal, e.g.,
+/// `CJK UNIFIED IDEOGRAPH-4E00`. The codepoint must also be in one of the
+/// ranges in `RANGE_IDEOGRAPH` that corresponds to the name's prefix.
+///
+/// If the given name does not correspond to an ideograph codepoint, then
+/// this returns `None`.
+///
+/// This is the inverse of `ideograph_name`.
+pub fn ideograph_name_to_codepoint(name: &str) -> Option<u32> {
+    let hex = match name.rfind('-') {
+        None => return None,
+        Some(i) => &name[i + 1..],
+    };
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let cp = match u32::from_str_radix(hex, 16) {
+        Err(_) => return None,
+        Ok(cp) => cp,
+    };
+    // Generating the name again checks the prefix, the range and that the
+    // codepoint is formatted exactly as it would be in the name.
+    match ideograph_name(cp) {
+        Some(ref canonical) if canonical == name => Some(cp),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::ideograph_name;
+    use super::{ideograph_name, ideograph_name_to_codepoint};
 
     #[test]
     fn name() {
@@ -76,8 +108,31 @@ mod tests {
             "CJK COMPATIBILITY IDEOGRAPH-F900");
     }
 
+    #[test]
+    fn name_to_codepoint() {
+        let cp = ideograph_name_to_codepoint;
+        assert_eq!(cp("CJK UNIFIED IDEOGRAPH-4E00"), Some(0x4E00));
+        assert_eq!(cp("CJK UNIFIED IDEOGRAPH-20000"), Some(0x20000));
+        assert_eq!(cp("TANGUT IDEOGRAPH-17000"), Some(0x17000));
+        assert_eq!(cp("CJK COMPATIBILITY IDEOGRAPH-F900"), Some(0xF900));
+    }
+
     #[test]
     fn invalid() {
         assert!(ideograph_name(0).is_none());
+
+        let cp = ideograph_name_to_codepoint;
+        // Outside of RANGE_IDEOGRAPH.
+        assert!(cp("CJK UNIFIED IDEOGRAPH-0041").is_none());
+        assert!(cp("CJK UNIFIED IDEOGRAPH-9FFF").is_none());
+        // The prefix doesn't match the range.
+        assert!(cp("TANGUT IDEOGRAPH-4E00").is_none());
+        assert!(cp("CJK UNIFIED IDEOGRAPH-F900").is_none());
+        // Not formatted the way the name is generated.
+        assert!(cp("CJK UNIFIED IDEOGRAPH-4e00").is_none());
+        assert!(cp("CJK UNIFIED IDEOGRAPH-04E00").is_none());
+        assert!(cp("CJK UNIFIED IDEOGRAPH-+4E00").is_none());
+        assert!(cp("CJK UNIFIED IDEOGRAPH-").is_none());
+        assert!(cp("CJK UNIFIED IDEOGRAPH 4E00").is_none());
     }
 }
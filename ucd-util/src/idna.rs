@@ -0,0 +1,795 @@
+use std::error;
+use std::fmt;
+
+use bidi::BidiClass;
+use joining::JoiningType;
+use ranges::{find_in_range_values, find_in_ranges};
+
+// This implementation should correspond to the processing steps described in
+// UTS #46, section 4. The IDNA mapping table and the other properties used
+// to validate labels are supplied by the caller, and must be sorted so that
+// they can be binary searched.
+
+/// The status of a codepoint in `IdnaMappingTable.txt`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IdnaMapping<'a> {
+    /// The codepoint is valid, and is not modified.
+    Valid,
+    /// The codepoint is removed.
+    Ignored,
+    /// The codepoint is replaced by the given mapping.
+    Mapped(&'a [char]),
+    /// The codepoint is replaced by the given mapping during transitional
+    /// processing, and is valid otherwise.
+    Deviation(&'a [char]),
+    /// The codepoint is not allowed.
+    Disallowed,
+    /// The codepoint is not allowed if `use_std3_ascii_rules` is enabled,
+    /// and is valid otherwise.
+    DisallowedStd3Valid,
+    /// The codepoint is not allowed if `use_std3_ascii_rules` is enabled,
+    /// and is replaced by the given mapping otherwise.
+    DisallowedStd3Mapped(&'a [char]),
+}
+
+/// The tables required by the IDNA processing functions.
+#[derive(Clone, Copy, Debug)]
+pub struct IdnaTables<'a> {
+    /// The ranges of codepoints in `IdnaMappingTable.txt` and their status.
+    /// Codepoints missing from this table are disallowed.
+    pub mapping: &'a [(u32, u32, IdnaMapping<'a>)],
+    /// The ranges of codepoints and their `Bidi_Class` values. Codepoints
+    /// missing from this table are treated as `L`.
+    pub bidi_class: &'a [(u32, u32, BidiClass)],
+    /// The ranges of codepoints and their `Joining_Type` values. Codepoints
+    /// missing from this table are treated as `NonJoining`.
+    pub joining_type: &'a [(u32, u32, JoiningType)],
+    /// The ranges of codepoints with a `General_Category` of `Mark`.
+    pub mark: &'a [(u32, u32)],
+    /// The ranges of codepoints with a `Canonical_Combining_Class` of
+    /// `Virama`.
+    pub virama: &'a [(u32, u32)],
+}
+
+/// The flags that control IDNA processing, as defined in UTS #46, section
+/// 4.
+///
+/// The default enables every check and uses nontransitional processing.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct IdnaOptions {
+    /// Disallow the codepoints that are not allowed by STD3, such as `_`.
+    pub use_std3_ascii_rules: bool,
+    /// Disallow hyphens at the start and end of labels, and in their third
+    /// and fourth positions.
+    pub check_hyphens: bool,
+    /// Apply the Bidi Rule of RFC 5893 to domain names with right-to-left
+    /// characters.
+    pub check_bidi: bool,
+    /// Apply the CONTEXTJ rules of RFC 5892 to U+200C and U+200D.
+    pub check_joiners: bool,
+    /// Map deviation characters, such as `ß`, instead of keeping them.
+    pub transitional_processing: bool,
+    /// Check the length of the domain name and its labels. This only
+    /// applies to `to_ascii`.
+    pub verify_dns_length: bool,
+}
+
+impl Default for IdnaOptions {
+    fn default() -> IdnaOptions {
+        IdnaOptions {
+            use_std3_ascii_rules: true,
+            check_hyphens: true,
+            check_bidi: true,
+            check_joiners: true,
+            transitional_processing: false,
+            verify_dns_length: true,
+        }
+    }
+}
+
+/// An error that occurs while processing a domain name.
+///
+/// When a domain name has several errors, only the first one found is
+/// reported.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IdnaError {
+    /// A codepoint is disallowed by the mapping table.
+    Disallowed,
+    /// A label starting with `xn--` is not valid Punycode.
+    Punycode,
+    /// A label is not in Normalization Form C.
+    NotNfc,
+    /// A label has a hyphen in a disallowed position, or starts with `xn--`
+    /// without being an A-label.
+    Hyphen,
+    /// A label contains a full stop.
+    FullStop,
+    /// A label starts with a combining mark.
+    LeadingMark,
+    /// A label contains a codepoint whose status isn't valid.
+    InvalidStatus,
+    /// A label violates the CONTEXTJ rules.
+    ContextJ,
+    /// A label violates the Bidi Rule.
+    Bidi,
+    /// The domain name or one of its labels is empty or too long.
+    DnsLength,
+}
+
+impl IdnaError {
+    fn message(&self) -> &'static str {
+        match *self {
+            IdnaError::Disallowed => "disallowed codepoint",
+            IdnaError::Punycode => "invalid punycode",
+            IdnaError::NotNfc => "label is not in NFC",
+            IdnaError::Hyphen => "invalid hyphen in label",
+            IdnaError::FullStop => "full stop in label",
+            IdnaError::LeadingMark => "label starts with a combining mark",
+            IdnaError::InvalidStatus => "invalid codepoint in label",
+            IdnaError::ContextJ => "label violates the CONTEXTJ rules",
+            IdnaError::Bidi => "label violates the Bidi Rule",
+            IdnaError::DnsLength => "invalid DNS length",
+        }
+    }
+}
+
+impl error::Error for IdnaError {
+    fn description(&self) -> &str {
+        self.message()
+    }
+}
+
+impl fmt::Display for IdnaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+/// Convert the given domain name to its ASCII form.
+///
+/// `nfc` should return the Normalization Form C of a string, e.g., as
+/// computed with `full_canonical_decomposition`, `canonical_reorder` and
+/// `canonical_composition`.
+///
+/// Every label that isn't ASCII is encoded with Punycode and prefixed with
+/// `xn--`. If the domain name is invalid, then the first error found is
+/// returned.
+///
+/// This implements `ToASCII` as defined in UTS #46, section 4.2.
+pub fn to_ascii<F>(
+    domain: &str,
+    tables: &IdnaTables,
+    options: &IdnaOptions,
+    nfc: F,
+) -> Result<String, IdnaError>
+where F: Fn(&str) -> String
+{
+    let (labels, result) = process(domain, tables, options, &nfc);
+    result?;
+    let mut ascii = vec![];
+    for label in labels {
+        if label.is_ascii() {
+            ascii.push(label);
+        } else {
+            let chars: Vec<char> = label.chars().collect();
+            match punycode_encode(&chars) {
+                None => return Err(IdnaError::Punycode),
+                Some(encoded) => ascii.push(format!("xn--{}", encoded)),
+            }
+        }
+    }
+    if options.verify_dns_length {
+        // The root label, if any, is excluded from the length checks.
+        let mut labels = &ascii[..];
+        if labels.len() > 1 && labels[labels.len() - 1].is_empty() {
+            labels = &labels[..labels.len() - 1];
+        }
+        let len = labels.iter().map(|l| l.len()).sum::<usize>()
+            + labels.len() - 1;
+        if len > 253 || labels.iter().any(|l| l.is_empty() || l.len() > 63) {
+            return Err(IdnaError::DnsLength);
+        }
+    }
+    Ok(ascii.join("."))
+}
+
+/// Convert the given domain name to its Unicode form.
+///
+/// `nfc` is used in the same way as for `to_ascii`.
+///
+/// Every label starting with `xn--` is decoded from Punycode. The converted
+/// domain name is always returned, along with the first error found, if
+/// any.
+///
+/// This implements `ToUnicode` as defined in UTS #46, section 4.3.
+pub fn to_unicode<F>(
+    domain: &str,
+    tables: &IdnaTables,
+    options: &IdnaOptions,
+    nfc: F,
+) -> (String, Result<(), IdnaError>)
+where F: Fn(&str) -> String
+{
+    let (labels, result) = process(domain, tables, options, &nfc);
+    (labels.join("."), result)
+}
+
+/// Apply the processing steps to the given domain name, and return its
+/// labels along with the first error found, if any.
+fn process<F>(
+    domain: &str,
+    tables: &IdnaTables,
+    options: &IdnaOptions,
+    nfc: &F,
+) -> (Vec<String>, Result<(), IdnaError>)
+where F: Fn(&str) -> String
+{
+    let mut result = Ok(());
+    // Step 1: Map.
+    let mut mapped = String::with_capacity(domain.len());
+    for c in domain.chars() {
+        match status(tables, c) {
+            IdnaMapping::Valid => mapped.push(c),
+            IdnaMapping::Ignored => {}
+            IdnaMapping::Mapped(m) => mapped.extend(m),
+            IdnaMapping::Deviation(m) => {
+                if options.transitional_processing {
+                    mapped.extend(m);
+                } else {
+                    mapped.push(c);
+                }
+            }
+            IdnaMapping::Disallowed => {
+                record(&mut result, IdnaError::Disallowed);
+                mapped.push(c);
+            }
+            IdnaMapping::DisallowedStd3Valid => {
+                if options.use_std3_ascii_rules {
+                    record(&mut result, IdnaError::Disallowed);
+                }
+                mapped.push(c);
+            }
+            IdnaMapping::DisallowedStd3Mapped(m) => {
+                if options.use_std3_ascii_rules {
+                    record(&mut result, IdnaError::Disallowed);
+                    mapped.push(c);
+                } else {
+                    mapped.extend(m);
+                }
+            }
+        }
+    }
+    // Step 2: Normalize.
+    let normalized = nfc(&mapped);
+    // Steps 3 and 4: Break and convert/validate.
+    let mut labels = vec![];
+    for label in normalized.split('.') {
+        if label.starts_with("xn--") {
+            match punycode_decode(&label[4..]) {
+                None => {
+                    record(&mut result, IdnaError::Punycode);
+                    labels.push(label.to_string());
+                }
+                Some(decoded) => {
+                    let decoded: String = decoded.into_iter().collect();
+                    let nontransitional = IdnaOptions {
+                        transitional_processing: false,
+                        ..*options
+                    };
+                    if let Err(err) = validate(
+                        &decoded, tables, &nontransitional, nfc,
+                    ) {
+                        record(&mut result, err);
+                    }
+                    labels.push(decoded);
+                }
+            }
+        } else {
+            if let Err(err) = validate(label, tables, options, nfc) {
+                record(&mut result, err);
+            }
+            labels.push(label.to_string());
+        }
+    }
+    if options.check_bidi && is_bidi_domain_name(&labels, tables) {
+        for label in &labels {
+            if !label.is_empty() && !satisfies_bidi_rule(label, tables) {
+                record(&mut result, IdnaError::Bidi);
+            }
+        }
+    }
+    (labels, result)
+}
+
+/// Check the validity criteria of UTS #46, section 4.1, except for the Bidi
+/// Rule, which applies to the domain name as a whole.
+fn validate<F>(
+    label: &str,
+    tables: &IdnaTables,
+    options: &IdnaOptions,
+    nfc: &F,
+) -> Result<(), IdnaError>
+where F: Fn(&str) -> String
+{
+    if nfc(label) != label {
+        return Err(IdnaError::NotNfc);
+    }
+    if options.check_hyphens {
+        if label.get(2..4) == Some("--") {
+            return Err(IdnaError::Hyphen);
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return Err(IdnaError::Hyphen);
+        }
+    } else if label.starts_with("xn--") {
+        return Err(IdnaError::Hyphen);
+    }
+    if label.contains('.') {
+        return Err(IdnaError::FullStop);
+    }
+    if let Some(c) = label.chars().next() {
+        if find_in_ranges(tables.mark, c as u32) {
+            return Err(IdnaError::LeadingMark);
+        }
+    }
+    for c in label.chars() {
+        let valid = match status(tables, c) {
+            IdnaMapping::Valid => true,
+            IdnaMapping::Deviation(_) => !options.transitional_processing,
+            IdnaMapping::DisallowedStd3Valid => {
+                !options.use_std3_ascii_rules
+            }
+            _ => false,
+        };
+        if !valid {
+            return Err(IdnaError::InvalidStatus);
+        }
+    }
+    if options.check_joiners && !satisfies_context_j(label, tables) {
+        return Err(IdnaError::ContextJ);
+    }
+    Ok(())
+}
+
+/// Returns true if and only if every U+200C ZERO WIDTH NON-JOINER and
+/// U+200D ZERO WIDTH JOINER in the given label satisfies the rules in RFC
+/// 5892, Appendix A.
+fn satisfies_context_j(label: &str, tables: &IdnaTables) -> bool {
+    let chars: Vec<char> = label.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        if c != '\u{200C}' && c != '\u{200D}' {
+            continue;
+        }
+        if i > 0 && find_in_ranges(tables.virama, chars[i - 1] as u32) {
+            continue;
+        }
+        if c == '\u{200D}' {
+            return false;
+        }
+        let is = |c: char, a: JoiningType, b: JoiningType| {
+            let jt = joining_type(tables, c);
+            jt == a || jt == b
+        };
+        let transparent = |&c: &char| {
+            joining_type(tables, c) == JoiningType::Transparent
+        };
+        let before = chars[..i].iter().rev().skip_while(|c| transparent(c))
+            .next();
+        let after = chars[i + 1..].iter().skip_while(|c| transparent(c))
+            .next();
+        let left = before.map_or(false, |&c| {
+            is(c, JoiningType::LeftJoining, JoiningType::DualJoining)
+        });
+        let right = after.map_or(false, |&c| {
+            is(c, JoiningType::RightJoining, JoiningType::DualJoining)
+        });
+        if !left || !right {
+            return false;
+        }
+    }
+    true
+}
+
+/// Returns true if and only if any of the given labels contains a
+/// right-to-left character, as defined in RFC 5893, section 1.4.
+fn is_bidi_domain_name(labels: &[String], tables: &IdnaTables) -> bool {
+    labels.iter().flat_map(|l| l.chars()).any(|c| {
+        match bidi_class(tables, c) {
+            BidiClass::R | BidiClass::AL | BidiClass::AN => true,
+            _ => false,
+        }
+    })
+}
+
+/// Returns true if and only if the given non-empty label satisfies the
+/// Bidi Rule in RFC 5893, section 2.
+fn satisfies_bidi_rule(label: &str, tables: &IdnaTables) -> bool {
+    use bidi::BidiClass::*;
+
+    let classes: Vec<BidiClass> =
+        label.chars().map(|c| bidi_class(tables, c)).collect();
+    // The last class that isn't NSM.
+    let last = classes.iter().rev().find(|&&bc| bc != NSM);
+    match classes[0] {
+        R | AL => {
+            let allowed = classes.iter().all(|&bc| match bc {
+                R | AL | AN | EN | ES | CS | ET | ON | BN | NSM => true,
+                _ => false,
+            });
+            let end = match last {
+                Some(&R) | Some(&AL) | Some(&EN) | Some(&AN) => true,
+                _ => false,
+            };
+            let mixed_numbers =
+                classes.contains(&EN) && classes.contains(&AN);
+            allowed && end && !mixed_numbers
+        }
+        L => {
+            let allowed = classes.iter().all(|&bc| match bc {
+                L | EN | ES | CS | ET | ON | BN | NSM => true,
+                _ => false,
+            });
+            let end = match last {
+                Some(&L) | Some(&EN) => true,
+                _ => false,
+            };
+            allowed && end
+        }
+        _ => false,
+    }
+}
+
+fn status<'a>(tables: &IdnaTables<'a>, c: char) -> IdnaMapping<'a> {
+    find_in_range_values(tables.mapping, c as u32)
+        .unwrap_or(IdnaMapping::Disallowed)
+}
+
+fn bidi_class(tables: &IdnaTables, c: char) -> BidiClass {
+    find_in_range_values(tables.bidi_class, c as u32)
+        .unwrap_or(BidiClass::L)
+}
+
+fn joining_type(tables: &IdnaTables, c: char) -> JoiningType {
+    find_in_range_values(tables.joining_type, c as u32)
+        .unwrap_or(JoiningType::NonJoining)
+}
+
+/// Record the given error, unless an error has already been recorded.
+fn record(result: &mut Result<(), IdnaError>, err: IdnaError) {
+    if result.is_ok() {
+        *result = Err(err);
+    }
+}
+
+// The Punycode parameters defined in RFC 3492, section 5.
+const BASE: u32 = 36;
+const T_MIN: u32 = 1;
+const T_MAX: u32 = 26;
+const SKEW: u32 = 38;
+const DAMP: u32 = 700;
+const INITIAL_BIAS: u32 = 72;
+const INITIAL_N: u32 = 128;
+
+/// Encode the given characters with Punycode, as defined in RFC 3492,
+/// section 6.3. If the encoding overflows, then this returns `None`.
+fn punycode_encode(input: &[char]) -> Option<String> {
+    let mut output: String =
+        input.iter().cloned().filter(|c| c.is_ascii()).collect();
+    let basic = output.len() as u32;
+    if basic > 0 {
+        output.push('-');
+    }
+    let (mut n, mut delta, mut bias) = (INITIAL_N, 0u32, INITIAL_BIAS);
+    let mut handled = basic;
+    while (handled as usize) < input.len() {
+        let m = input
+            .iter()
+            .map(|&c| c as u32)
+            .filter(|&c| c >= n)
+            .min()
+            .unwrap();
+        delta = delta.checked_add((m - n).checked_mul(handled + 1)?)?;
+        n = m;
+        for &c in input {
+            let c = c as u32;
+            if c < n {
+                delta = delta.checked_add(1)?;
+            }
+            if c == n {
+                let mut q = delta;
+                let mut k = BASE;
+                loop {
+                    let t = threshold(k, bias);
+                    if q < t {
+                        break;
+                    }
+                    output.push(encode_digit(t + (q - t) % (BASE - t)));
+                    q = (q - t) / (BASE - t);
+                    k += BASE;
+                }
+                output.push(encode_digit(q));
+                bias = adapt(delta, handled + 1, handled == basic);
+                delta = 0;
+                handled += 1;
+            }
+        }
+        delta = delta.checked_add(1)?;
+        n += 1;
+    }
+    Some(output)
+}
+
+/// Decode the given Punycode string, as defined in RFC 3492, section 6.2.
+/// If the string is not valid Punycode, then this returns `None`.
+fn punycode_decode(input: &str) -> Option<Vec<char>> {
+    let (basic, extended) = match input.rfind('-') {
+        None => ("", input),
+        Some(i) => (&input[..i], &input[i + 1..]),
+    };
+    if !basic.is_ascii() {
+        return None;
+    }
+    let mut output: Vec<char> = basic.chars().collect();
+    let (mut n, mut i, mut bias) = (INITIAL_N, 0u32, INITIAL_BIAS);
+    let mut bytes = extended.bytes();
+    while bytes.len() > 0 {
+        let old_i = i;
+        let mut w = 1u32;
+        let mut k = BASE;
+        loop {
+            let digit = decode_digit(bytes.next()?)?;
+            i = i.checked_add(digit.checked_mul(w)?)?;
+            let t = threshold(k, bias);
+            if digit < t {
+                break;
+            }
+            w = w.checked_mul(BASE - t)?;
+            k += BASE;
+        }
+        let len = output.len() as u32 + 1;
+        bias = adapt(i - old_i, len, old_i == 0);
+        n = n.checked_add(i / len)?;
+        i %= len;
+        output.insert(i as usize, ::std::char::from_u32(n)?);
+        i += 1;
+    }
+    Some(output)
+}
+
+fn threshold(k: u32, bias: u32) -> u32 {
+    if k <= bias {
+        T_MIN
+    } else if k >= bias + T_MAX {
+        T_MAX
+    } else {
+        k - bias
+    }
+}
+
+/// The bias adaptation function defined in RFC 3492, section 6.1.
+fn adapt(delta: u32, num_points: u32, first_time: bool) -> u32 {
+    let mut delta = if first_time { delta / DAMP } else { delta / 2 };
+    delta += delta / num_points;
+    let mut k = 0;
+    while delta > ((BASE - T_MIN) * T_MAX) / 2 {
+        delta /= BASE - T_MIN;
+        k += BASE;
+    }
+    k + (((BASE - T_MIN + 1) * delta) / (delta + SKEW))
+}
+
+fn encode_digit(d: u32) -> char {
+    if d < 26 {
+        (b'a' + d as u8) as char
+    } else {
+        (b'0' + (d - 26) as u8) as char
+    }
+}
+
+fn decode_digit(b: u8) -> Option<u32> {
+    match b {
+        b'a'..=b'z' => Some((b - b'a') as u32),
+        b'A'..=b'Z' => Some((b - b'A') as u32),
+        b'0'..=b'9' => Some((b - b'0') as u32 + 26),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bidi::BidiClass;
+    use joining::JoiningType;
+
+    use super::IdnaMapping::*;
+    use super::{
+        IdnaError, IdnaOptions, IdnaTables, punycode_decode, punycode_encode,
+        to_ascii, to_unicode,
+    };
+
+    // Small excerpts of IdnaMappingTable.txt, DerivedBidiClass.txt,
+    // DerivedJoiningType.txt, DerivedGeneralCategory.txt and
+    // DerivedCombiningClass.txt.
+    const TABLES: IdnaTables<'static> = IdnaTables {
+        mapping: &[
+            (0x2B, 0x2B, DisallowedStd3Valid),
+            (0x2D, 0x2E, Valid),
+            (0x30, 0x39, Valid),
+            (0x41, 0x41, Mapped(&['a'])),
+            (0x42, 0x42, Mapped(&['b'])),
+            (0x43, 0x5A, Mapped(&['x'])),
+            (0x5F, 0x5F, DisallowedStd3Valid),
+            (0x61, 0x7A, Valid),
+            (0xAD, 0xAD, Ignored),
+            (0xDF, 0xDF, Deviation(&['s', 's'])),
+            (0xE0, 0xFF, Valid),
+            (0x301, 0x301, Valid),
+            (0x5D0, 0x5EA, Valid),
+            (0x627, 0x628, Valid),
+            (0x660, 0x669, Valid),
+            (0x915, 0x915, Valid),
+            (0x94D, 0x94D, Valid),
+            (0x200C, 0x200D, Deviation(&[])),
+            (0x2488, 0x2488, DisallowedStd3Mapped(&['1', '.'])),
+            (0x3002, 0x3002, Mapped(&['.'])),
+            (0x4E00, 0x9FFF, Valid),
+            (0xFF21, 0xFF21, Mapped(&['a'])),
+        ],
+        bidi_class: &[
+            (0x30, 0x39, BidiClass::EN),
+            (0x301, 0x301, BidiClass::NSM),
+            (0x5D0, 0x5EA, BidiClass::R),
+            (0x627, 0x628, BidiClass::AL),
+            (0x660, 0x669, BidiClass::AN),
+            (0x94D, 0x94D, BidiClass::NSM),
+            (0x200C, 0x200D, BidiClass::BN),
+        ],
+        joining_type: &[
+            (0x301, 0x301, JoiningType::Transparent),
+            (0x627, 0x627, JoiningType::RightJoining),
+            (0x628, 0x628, JoiningType::DualJoining),
+            (0x94D, 0x94D, JoiningType::Transparent),
+        ],
+        mark: &[(0x301, 0x301), (0x94D, 0x94D)],
+        virama: &[(0x94D, 0x94D)],
+    };
+
+    // A tiny NFC that only composes `e` and U+0301.
+    fn nfc(s: &str) -> String {
+        s.replace("e\u{301}", "\u{E9}")
+    }
+
+    fn ascii(s: &str) -> Result<String, IdnaError> {
+        to_ascii(s, &TABLES, &IdnaOptions::default(), nfc)
+    }
+
+    fn unicode(s: &str) -> (String, Result<(), IdnaError>) {
+        to_unicode(s, &TABLES, &IdnaOptions::default(), nfc)
+    }
+
+    #[test]
+    fn punycode() {
+        let cases: &[(&str, &str)] = &[
+            ("b\u{FC}cher", "bcher-kva"),
+            ("m\u{FC}nchen", "mnchen-3ya"),
+            ("\u{FC}", "tda"),
+            ("abc", "abc-"),
+            (
+                "\u{4ED6}\u{4EEC}\u{4E3A}\u{4EC0}\u{4E48}\u{4E0D}\u{8BF4}\
+                 \u{4E2D}\u{6587}",
+                "ihqwcrb4cv8a8dqg056pqjye",
+            ),
+        ];
+        for &(decoded, encoded) in cases {
+            let chars: Vec<char> = decoded.chars().collect();
+            assert_eq!(punycode_encode(&chars).unwrap(), encoded);
+            assert_eq!(punycode_decode(encoded).unwrap(), chars);
+        }
+        assert_eq!(punycode_decode("BCHER-KVA").unwrap().len(), 6);
+        assert_eq!(punycode_decode("b\u{FC}-kva"), None);
+        assert_eq!(punycode_decode("bcher-kv!"), None);
+        assert_eq!(punycode_decode("bcher-k"), None);
+        assert_eq!(punycode_decode("99999999999"), None);
+    }
+
+    #[test]
+    fn ascii_basic() {
+        assert_eq!(ascii("example.com").unwrap(), "example.com");
+        assert_eq!(ascii("ABC.com").unwrap(), "abx.com");
+        assert_eq!(ascii("b\u{FC}cher.de").unwrap(), "xn--bcher-kva.de");
+        assert_eq!(ascii("\u{FF21}\u{3002}b").unwrap(), "a.b");
+        assert_eq!(ascii("a\u{AD}b").unwrap(), "ab");
+        assert_eq!(
+            ascii("caf\u{E9}").unwrap(),
+            ascii("cafe\u{301}").unwrap());
+        assert_eq!(ascii("example.com.").unwrap(), "example.com.");
+    }
+
+    #[test]
+    fn ascii_deviation() {
+        assert_eq!(ascii("fa\u{DF}").unwrap(), "xn--fa-hia");
+        let transitional = IdnaOptions {
+            transitional_processing: true,
+            ..IdnaOptions::default()
+        };
+        assert_eq!(
+            to_ascii("fa\u{DF}", &TABLES, &transitional, nfc).unwrap(),
+            "fass");
+    }
+
+    #[test]
+    fn ascii_errors() {
+        assert_eq!(ascii("a\u{2603}"), Err(IdnaError::Disallowed));
+        assert_eq!(ascii("a_b"), Err(IdnaError::Disallowed));
+        assert_eq!(ascii("\u{2488}com"), Err(IdnaError::Disallowed));
+        assert_eq!(ascii("-ab"), Err(IdnaError::Hyphen));
+        assert_eq!(ascii("ab--c"), Err(IdnaError::Hyphen));
+        assert_eq!(ascii("\u{301}a"), Err(IdnaError::LeadingMark));
+        assert_eq!(ascii("xn--bcher-k"), Err(IdnaError::Punycode));
+        assert_eq!(ascii("a..b"), Err(IdnaError::DnsLength));
+        assert_eq!(ascii(&"a".repeat(64)), Err(IdnaError::DnsLength));
+
+        let lenient = IdnaOptions {
+            use_std3_ascii_rules: false,
+            check_hyphens: false,
+            verify_dns_length: false,
+            ..IdnaOptions::default()
+        };
+        let ascii = |s| to_ascii(s, &TABLES, &lenient, nfc);
+        assert_eq!(ascii("a_b").unwrap(), "a_b");
+        assert_eq!(ascii("\u{2488}com").unwrap(), "1.com");
+        assert_eq!(ascii("-ab").unwrap(), "-ab");
+        assert_eq!(ascii("a..b").unwrap(), "a..b");
+    }
+
+    #[test]
+    fn context_j() {
+        // ZWJ and ZWNJ are allowed after a virama.
+        assert!(ascii("\u{915}\u{94D}\u{200D}").is_ok());
+        assert!(ascii("\u{915}\u{94D}\u{200C}").is_ok());
+        assert_eq!(ascii("a\u{200D}b"), Err(IdnaError::ContextJ));
+        // ZWNJ is allowed between joining characters.
+        let ok = "\u{628}\u{200C}\u{627}";
+        assert!(ascii(ok).is_ok());
+        assert!(ascii("\u{628}\u{301}\u{200C}\u{301}\u{627}").is_ok());
+        assert_eq!(
+            ascii("\u{627}\u{200C}\u{628}"),
+            Err(IdnaError::ContextJ));
+    }
+
+    #[test]
+    fn bidi() {
+        assert!(ascii("\u{5D0}\u{5D1}.com").is_ok());
+        assert!(ascii("\u{627}\u{660}").is_ok());
+        assert!(ascii("\u{5D0}1").is_ok());
+        assert_eq!(ascii("\u{5D0}a"), Err(IdnaError::Bidi));
+        assert_eq!(ascii("1\u{5D0}"), Err(IdnaError::Bidi));
+        assert_eq!(ascii("\u{627}1\u{660}"), Err(IdnaError::Bidi));
+        // LTR labels are only checked when the domain name has RTL
+        // characters.
+        assert!(ascii("1a.com").is_ok());
+        assert_eq!(ascii("1a.\u{5D0}"), Err(IdnaError::Bidi));
+        assert!(ascii("a1.\u{5D0}").is_ok());
+    }
+
+    #[test]
+    fn unicode_basic() {
+        assert_eq!(
+            unicode("xn--bcher-kva.de"),
+            ("b\u{FC}cher.de".to_string(), Ok(())));
+        assert_eq!(
+            unicode("xn--fa-hia.com"),
+            ("fa\u{DF}.com".to_string(), Ok(())));
+        assert_eq!(
+            unicode("a\u{2603}.xn--bcher-kva"),
+            ("a\u{2603}.b\u{FC}cher".to_string(),
+             Err(IdnaError::Disallowed)));
+        assert_eq!(
+            unicode("xn--bcher-k.b"),
+            ("xn--bcher-k.b".to_string(), Err(IdnaError::Punycode)));
+        // Decoded labels must be in NFC.
+        let label = punycode_encode(&['e', '\u{301}']).unwrap();
+        assert_eq!(
+            unicode(&format!("xn--{}", label)).1,
+            Err(IdnaError::NotNfc));
+    }
+}
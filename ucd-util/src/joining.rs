@@ -0,0 +1,161 @@
+// This implementation should correspond to the cursive joining behavior
+// described in Unicode 9.2.
+
+/// The values of the `Joining_Type` property.
+///
+/// Characters missing from `ArabicShaping.txt` are `Transparent` if their
+/// `General_Category` is `Mn`, `Me` or `Cf`, and `NonJoining` otherwise.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JoiningType {
+    /// Joins to characters on both sides, e.g., U+0628 ARABIC LETTER BEH.
+    DualJoining,
+    /// Forces joining on both sides without changing shape, e.g., U+0640
+    /// ARABIC TATWEEL.
+    JoinCausing,
+    /// Joins only to the character on its left, in visual order.
+    LeftJoining,
+    /// Does not join to any character.
+    NonJoining,
+    /// Joins only to the character on its right, in visual order, e.g.,
+    /// U+0627 ARABIC LETTER ALEF.
+    RightJoining,
+    /// Is skipped when determining joining, e.g., combining marks.
+    Transparent,
+}
+
+/// The contextual form of a joining character.
+///
+/// These correspond to the `<isolated>`, `<initial>`, `<medial>` and
+/// `<final>` decomposition tags in `UnicodeData.txt`, which identify the
+/// presentation forms of Arabic letters.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JoiningForm {
+    /// The character doesn't join to either of its neighbors.
+    Isolated,
+    /// The character only joins to the character that follows it.
+    Initial,
+    /// The character joins to the characters on both sides of it.
+    Medial,
+    /// The character only joins to the character that precedes it.
+    Final,
+}
+
+/// Return the contextual form of every character in the given text.
+///
+/// `jt` should return the `Joining_Type` of a character. The result has one
+/// entry for every character in the text, which is `None` for characters
+/// that don't have contextual forms, i.e., for characters that are
+/// non-joining, join causing or transparent.
+///
+/// Transparent characters, such as combining marks, are skipped when
+/// determining whether two characters join, as described in Unicode 9.2.
+/// The text is assumed to be in logical order, so right joining characters
+/// join to the characters that precede them and left joining characters
+/// join to the characters that follow them.
+pub fn joining_forms<F>(text: &str, jt: F) -> Vec<Option<JoiningForm>>
+where F: Fn(char) -> JoiningType
+{
+    use self::JoiningType::*;
+
+    let types: Vec<JoiningType> = text.chars().map(jt).collect();
+    let mut forms = vec![None; types.len()];
+    // The joining type of the last character that isn't transparent.
+    let mut prev = NonJoining;
+    for i in 0..types.len() {
+        let cur = types[i];
+        if cur == Transparent {
+            continue;
+        }
+        if cur == DualJoining || cur == RightJoining || cur == LeftJoining {
+            let next = types[i + 1..]
+                .iter()
+                .cloned()
+                .find(|&t| t != Transparent)
+                .unwrap_or(NonJoining);
+            let joins_prev = (cur == DualJoining || cur == RightJoining)
+                && (prev == DualJoining
+                    || prev == LeftJoining
+                    || prev == JoinCausing);
+            let joins_next = (cur == DualJoining || cur == LeftJoining)
+                && (next == DualJoining
+                    || next == RightJoining
+                    || next == JoinCausing);
+            forms[i] = Some(match (joins_prev, joins_next) {
+                (false, false) => JoiningForm::Isolated,
+                (false, true) => JoiningForm::Initial,
+                (true, true) => JoiningForm::Medial,
+                (true, false) => JoiningForm::Final,
+            });
+        }
+        prev = cur;
+    }
+    forms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JoiningForm::*;
+    use super::JoiningType::{self, *};
+    use super::joining_forms;
+
+    // A small excerpt of ArabicShaping.txt.
+    fn jt(c: char) -> JoiningType {
+        match c {
+            '\u{0627}' | '\u{062F}' | '\u{0631}' | '\u{0648}' => {
+                RightJoining
+            }
+            '\u{0628}' | '\u{062A}' | '\u{0644}' | '\u{0645}' => {
+                DualJoining
+            }
+            '\u{0640}' | '\u{200D}' => JoinCausing,
+            '\u{064E}' | '\u{0651}' => Transparent,
+            '\u{A872}' => LeftJoining,
+            _ => NonJoining,
+        }
+    }
+
+    #[test]
+    fn forms() {
+        // BEH ALEF BEH
+        assert_eq!(
+            joining_forms("\u{0628}\u{0627}\u{0628}", jt),
+            vec![Some(Initial), Some(Final), Some(Isolated)]);
+        // BEH TEH BEH
+        assert_eq!(
+            joining_forms("\u{0628}\u{062A}\u{0628}", jt),
+            vec![Some(Initial), Some(Medial), Some(Final)]);
+        // ALEF LAM MEEM DAL
+        assert_eq!(
+            joining_forms("\u{0627}\u{0644}\u{0645}\u{062F}", jt),
+            vec![Some(Isolated), Some(Initial), Some(Medial), Some(Final)]);
+        assert_eq!(joining_forms("\u{0628}", jt), vec![Some(Isolated)]);
+        assert_eq!(joining_forms("", jt), vec![]);
+    }
+
+    #[test]
+    fn forms_transparent() {
+        // BEH FATHA SHADDA TEH
+        assert_eq!(
+            joining_forms("\u{0628}\u{064E}\u{0651}\u{062A}", jt),
+            vec![Some(Initial), None, None, Some(Final)]);
+    }
+
+    #[test]
+    fn forms_non_joining() {
+        // BEH SPACE BEH
+        assert_eq!(
+            joining_forms("\u{0628} \u{0628}", jt),
+            vec![Some(Isolated), None, Some(Isolated)]);
+        // Join causing characters join, but have no forms themselves.
+        assert_eq!(
+            joining_forms("\u{0628}\u{0640}", jt),
+            vec![Some(Initial), None]);
+        assert_eq!(
+            joining_forms("\u{200D}\u{0628}", jt),
+            vec![None, Some(Final)]);
+        // PHAGS-PA SUPERFIXED LETTER RA is left joining.
+        assert_eq!(
+            joining_forms("\u{A872}\u{0628}", jt),
+            vec![Some(Initial), Some(Final)]);
+    }
+}
@@ -2,21 +2,112 @@
 The `ucd-util` crate contains a smattering of utility functions that implement
 various algorithms specified by Unicode. There is no specific goal for
 exhaustiveness. Instead, implementations should be added on an as-needed basis.
+
+Most of the algorithms in this crate are parameterized over lookups of the
+Unicode properties they need, which are typically backed by tables generated
+by `ucd-generate`. Tables emitted as slices of ranges can be searched with
+`find_in_ranges` and `find_in_range_values`. Tables emitted as tries, with
+`ucd-generate`'s `--trie` flag, are represented by `TrieSetSlice` and
+`TrieMapSlice`, which provide constant time lookups via their `contains_u32`
+and `get_u32` methods. `TrieSet` and `TrieMap` are their owned counterparts,
+which build a trie at runtime:
+
+```
+use ucd_util::TrieSet;
+
+let set = TrieSet::from_codepoints(&[0x41, 0x1F600]);
+assert!(set.contains_u32(0x1F600));
+assert!(!set.as_slice().contains_char('B'));
+```
+
+These types are defined by the `ucd-trie` crate, which code generated by
+`ucd-generate` may also depend on directly.
+
+# Crate features
+
+* **fst** - Adds `FstSet` and `FstMap`, which look up codepoints in the FSTs
+  emitted by `ucd-generate` when given an FST directory.
 */
 
 #![deny(missing_docs)]
 
+#[cfg(feature = "fst")]
+extern crate fst;
+extern crate ucd_trie;
+
 mod tables;
 
+mod bidi;
+mod case_folding;
+mod case_mapping;
+mod east_asian_width;
+#[cfg(feature = "fst")]
+mod fst_table;
 mod hangul;
 mod ideograph;
+mod idna;
+mod joining;
+mod line_break;
 mod name;
+mod name_resolver;
+mod normalization;
+mod property;
+mod ranges;
+mod script;
+mod security;
+mod segmentation;
+mod version;
 
+pub use bidi::{BidiClass, BracketType, bracket_pairs};
+pub use case_folding::{CaseFold, caseless_eq, full_fold, simple_fold};
+pub use case_mapping::{
+    CaseTables, CasingCondition, SpecialCasing, to_lowercase_full,
+    to_titlecase_full, to_uppercase_full,
+};
+pub use east_asian_width::{
+    AmbiguousWidth, EastAsianWidth, effective_width,
+};
+#[cfg(feature = "fst")]
+pub use fst_table::{FstMap, FstSet};
 pub use hangul::{
-    RANGE_HANGUL_SYLLABLE, hangul_name, hangul_full_canonical_decomposition,
+    HangulSyllableType, RANGE_HANGUL_SYLLABLE, hangul_name,
+    hangul_name_to_codepoint, hangul_full_canonical_composition,
+    hangul_full_canonical_decomposition, hangul_syllable_type,
+    jamo_short_name,
+};
+pub use ideograph::{
+    RANGE_IDEOGRAPH, ideograph_name, ideograph_name_to_codepoint,
+};
+pub use idna::{
+    IdnaError, IdnaMapping, IdnaOptions, IdnaTables, to_ascii, to_unicode,
+};
+pub use joining::{JoiningForm, JoiningType, joining_forms};
+pub use line_break::{
+    BreakOpportunity, LineBreak, line_breaks, line_breaks_chars,
 };
-pub use ideograph::{RANGE_IDEOGRAPH, ideograph_name};
 pub use name::{
     character_name_normalize, character_name_normalize_bytes,
+    codepoint_label, name_abbreviation_to_codepoint, name_abbreviations,
     symbolic_name_normalize, symbolic_name_normalize_bytes,
 };
+pub use name_resolver::NameResolver;
+pub use normalization::{
+    canonical_composition, canonical_reorder, full_canonical_decomposition,
+    full_compatibility_decomposition, is_nfc_quick, is_nfd_quick,
+    QuickCheckResult,
+};
+pub use property::{PropertyTable, PropertyValueTable};
+pub use ranges::{find_in_range_values, find_in_ranges};
+pub use script::{
+    ResolvedScriptSet, resolved_script_set, resolved_script_set_of_str,
+};
+pub use security::{
+    RestrictionLevel, is_mixed_script, restriction_level, skeleton,
+};
+pub use segmentation::{
+    GraphemeClusterBreak, GraphemeClusterMode, SentenceBreak, WordBreak,
+    grapheme_boundaries, grapheme_boundaries_chars, sentence_boundaries,
+    sentence_boundaries_chars, word_boundaries, word_boundaries_chars,
+};
+pub use ucd_trie::{TrieMap, TrieMapSlice, TrieSet, TrieSetSlice};
+pub use version::{ParseUnicodeVersionError, UnicodeVersion, assigned_in};
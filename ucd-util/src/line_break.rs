@@ -0,0 +1,369 @@
+// This implementation should correspond to the algorithm described in
+// UAX #14. Like segmentation, the `Line_Break` property is not included in
+// this crate, so callers supply a lookup function for it.
+
+/// The values of the `Line_Break` property.
+///
+/// These values are defined in UAX #14, Table 1, and are named after their
+/// short property value aliases. Characters missing from `LineBreak.txt`
+/// have the value `XX`.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LineBreak {
+    BK, CR, LF, NL, SP, ZW, ZWJ, CM, WJ, GL, BA, BB, B2, HY, CB, CL, CP, EX,
+    IN, NS, OP, QU, IS, NU, PO, PR, SY, AI, AL, CJ, EB, EM, H2, H3, HL, ID,
+    JL, JV, JT, RI, SA, SG, XX,
+}
+
+/// The kind of a line break opportunity.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BreakOpportunity {
+    /// The line must be broken here, e.g., after a line feed.
+    Mandatory,
+    /// The line may be broken here, e.g., after a space.
+    Allowed,
+}
+
+/// Return the line break opportunities in the given text.
+///
+/// `lb` should return the `Line_Break` property value of a character.
+///
+/// Each opportunity is returned as the byte offset of the character that
+/// would start the next line, paired with the kind of the opportunity.
+/// Opportunities are returned in increasing order of offset. There is never
+/// an opportunity at the start of the text, and, unless the text is empty,
+/// there is always a mandatory break at `text.len()`.
+///
+/// This implements the rules LB1 through LB31 described in UAX #14, with
+/// the following simplifications:
+///
+/// * LB1 resolves `AI`, `SA`, `SG` and `XX` to `AL`, and `CJ` to `NS`,
+///   since resolving `SA` by general category requires another table.
+/// * LB30 applies to every `OP` and `CP`, rather than only to those whose
+///   `East_Asian_Width` is not `F`, `W` or `H`.
+/// * LB30b only applies to `EB` followed by `EM`, since unassigned
+///   `Extended_Pictographic` characters require another table.
+pub fn line_breaks<F>(text: &str, lb: F) -> Vec<(usize, BreakOpportunity)>
+where F: Fn(char) -> LineBreak
+{
+    let offsets: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+    line_breaks_chars(text.chars(), lb)
+        .into_iter()
+        .map(|(i, kind)| {
+            (offsets.get(i).map_or(text.len(), |&offset| offset), kind)
+        })
+        .collect()
+}
+
+/// Return the line break opportunities in the given sequence of characters.
+///
+/// This is like `line_breaks`, except the opportunities are returned as
+/// indices into the sequence of characters.
+pub fn line_breaks_chars<I, F>(
+    chars: I,
+    lb: F,
+) -> Vec<(usize, BreakOpportunity)>
+where I: IntoIterator<Item=char>,
+      F: Fn(char) -> LineBreak
+{
+    let mut chars = chars.into_iter().map(|c| resolve(lb(c)));
+    let mut state = match chars.next() {
+        None => return vec![],
+        Some(first) => LineBreakState::new(first),
+    };
+    let mut breaks = vec![];
+    let mut len = 1;
+    for (i, cur) in chars.enumerate() {
+        if let Some(kind) = state.next(cur) {
+            breaks.push((i + 1, kind));
+        }
+        len = i + 2;
+    }
+    // LB3: ! eot
+    breaks.push((len, BreakOpportunity::Mandatory));
+    breaks
+}
+
+/// Resolve the classes that LB1 leaves to tailoring.
+fn resolve(lb: LineBreak) -> LineBreak {
+    use self::LineBreak::*;
+
+    match lb {
+        AI | SA | SG | XX => AL,
+        CJ => NS,
+        lb => lb,
+    }
+}
+
+/// The state required to determine whether there is a line break
+/// opportunity before the next character.
+#[derive(Debug)]
+struct LineBreakState {
+    /// The class of the previous character.
+    raw_prev: LineBreak,
+    /// The class of the previous character after applying LB9 and LB10.
+    prev: LineBreak,
+    /// The class of the character before `prev` after applying LB9 and LB10.
+    prev2: Option<LineBreak>,
+    /// The class of the last character that isn't `SP`, after applying LB9
+    /// and LB10.
+    before_sp: LineBreak,
+    /// The number of consecutive regional indicators ending at `prev`.
+    ri_count: usize,
+}
+
+impl LineBreakState {
+    fn new(first: LineBreak) -> LineBreakState {
+        // LB10 applies at the start of text.
+        let resolved = match first {
+            LineBreak::CM | LineBreak::ZWJ => LineBreak::AL,
+            first => first,
+        };
+        LineBreakState {
+            raw_prev: first,
+            prev: resolved,
+            prev2: None,
+            before_sp: resolved,
+            ri_count: if resolved == LineBreak::RI { 1 } else { 0 },
+        }
+    }
+
+    /// Advance the state by one character, and return the kind of break
+    /// opportunity before that character, if there is one.
+    fn next(&mut self, cur: LineBreak) -> Option<BreakOpportunity> {
+        use self::BreakOpportunity::*;
+        use self::LineBreak::*;
+
+        let (prev, raw_prev) = (self.prev, self.raw_prev);
+        self.raw_prev = cur;
+        // LB9: X (CM | ZWJ)* → X
+        let absorbed = match (prev, cur) {
+            (BK, _) | (CR, _) | (LF, _) | (NL, _) | (SP, _) | (ZW, _) => {
+                false
+            }
+            (_, CM) | (_, ZWJ) => true,
+            _ => false,
+        };
+        // LB10
+        let cur = match cur {
+            CM | ZWJ if !absorbed => AL,
+            cur => cur,
+        };
+        let lhs = if prev == SP { self.before_sp } else { prev };
+
+        let kind = match (prev, cur) {
+            // LB4, LB5
+            (CR, LF) => None,
+            (BK, _) | (CR, _) | (LF, _) | (NL, _) => Some(Mandatory),
+            // LB6
+            (_, BK) | (_, CR) | (_, LF) | (_, NL) => None,
+            // LB7
+            (_, SP) | (_, ZW) => None,
+            // LB8
+            _ if lhs == ZW => Some(Allowed),
+            // LB8a
+            _ if raw_prev == ZWJ => None,
+            // LB9
+            _ if absorbed => None,
+            // LB11
+            (_, WJ) | (WJ, _) => None,
+            // LB12, LB12a
+            (GL, _) => None,
+            (_, GL) if prev != SP && prev != BA && prev != HY => None,
+            // LB13
+            (_, CL) | (_, CP) | (_, EX) | (_, IS) | (_, SY) => None,
+            // LB14, LB15, LB16, LB17
+            _ if lhs == OP => None,
+            (_, OP) if lhs == QU => None,
+            (_, NS) if lhs == CL || lhs == CP => None,
+            (_, B2) if lhs == B2 => None,
+            // LB18
+            (SP, _) => Some(Allowed),
+            // LB19
+            (_, QU) | (QU, _) => None,
+            // LB20
+            (_, CB) | (CB, _) => Some(Allowed),
+            // LB21, LB21a, LB21b
+            (_, BA) | (_, HY) | (_, NS) | (BB, _) => None,
+            (HY, _) | (BA, _) if self.prev2 == Some(HL) => None,
+            (SY, HL) => None,
+            // LB22
+            (_, IN) => None,
+            // LB23, LB23a, LB24
+            (AL, NU) | (HL, NU) | (NU, AL) | (NU, HL) => None,
+            (PR, ID) | (PR, EB) | (PR, EM) => None,
+            (ID, PO) | (EB, PO) | (EM, PO) => None,
+            (PR, AL) | (PR, HL) | (PO, AL) | (PO, HL) => None,
+            (AL, PR) | (AL, PO) | (HL, PR) | (HL, PO) => None,
+            // LB25
+            (CL, PO) | (CP, PO) | (CL, PR) | (CP, PR) => None,
+            (NU, PO) | (NU, PR) | (PO, OP) | (PO, NU) => None,
+            (PR, OP) | (PR, NU) | (HY, NU) | (IS, NU) => None,
+            (NU, NU) | (SY, NU) => None,
+            // LB26, LB27
+            (JL, JL) | (JL, JV) | (JL, H2) | (JL, H3) => None,
+            (JV, JV) | (JV, JT) | (H2, JV) | (H2, JT) => None,
+            (JT, JT) | (H3, JT) => None,
+            (JL, PO) | (JV, PO) | (JT, PO) | (H2, PO) | (H3, PO) => None,
+            (PR, JL) | (PR, JV) | (PR, JT) | (PR, H2) | (PR, H3) => None,
+            // LB28, LB29
+            (AL, AL) | (AL, HL) | (HL, AL) | (HL, HL) => None,
+            (IS, AL) | (IS, HL) => None,
+            // LB30
+            (AL, OP) | (HL, OP) | (NU, OP) => None,
+            (CP, AL) | (CP, HL) | (CP, NU) => None,
+            // LB30a
+            (RI, RI) if self.ri_count % 2 == 1 => None,
+            // LB30b
+            (EB, EM) => None,
+            // LB31
+            _ => Some(Allowed),
+        };
+
+        if !absorbed {
+            if cur != SP {
+                self.before_sp = cur;
+            }
+            self.ri_count = if cur == RI { self.ri_count + 1 } else { 0 };
+            self.prev2 = Some(prev);
+            self.prev = cur;
+        }
+        kind
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BreakOpportunity::{self, *};
+    use super::LineBreak::{self, *};
+    use super::{line_breaks, line_breaks_chars};
+
+    // A small excerpt of LineBreak.txt.
+    fn lb(c: char) -> LineBreak {
+        match c {
+            '\n' => LF,
+            '\r' => CR,
+            '\u{000B}' | '\u{000C}' => BK,
+            ' ' => SP,
+            '\u{200B}' => ZW,
+            '\u{200D}' => ZWJ,
+            '\u{0300}'..='\u{036F}' => CM,
+            '\u{2060}' => WJ,
+            '\u{00A0}' => GL,
+            '-' => HY,
+            '\u{2014}' => B2,
+            '(' | '[' => OP,
+            ')' | ']' => CP,
+            '!' | '?' => EX,
+            '"' => QU,
+            '%' => PO,
+            '$' => PR,
+            ',' | '.' => IS,
+            '/' => SY,
+            '0'..='9' => NU,
+            '\u{05D0}'..='\u{05EA}' => HL,
+            '\u{3041}' => CJ,
+            '\u{3042}' | '\u{4E00}' => ID,
+            '\u{1F1E6}'..='\u{1F1FF}' => RI,
+            '\u{1F466}' => EB,
+            '\u{1F3FB}' => EM,
+            '\u{1100}' => JL,
+            '\u{1161}' => JV,
+            '\u{11A8}' => JT,
+            _ => AL,
+        }
+    }
+
+    /// Split the text into lines at every opportunity, and mark mandatory
+    /// breaks with a `!` at the end of the line.
+    fn lines(text: &str) -> Vec<String> {
+        let mut last = 0;
+        let mut lines = vec![];
+        for (i, kind) in line_breaks(text, lb) {
+            let mut line = text[last..i].to_string();
+            if kind == Mandatory {
+                line.push('!');
+            }
+            lines.push(line);
+            last = i;
+        }
+        lines
+    }
+
+    #[test]
+    fn basic() {
+        assert_eq!(lines("Hello world"), vec!["Hello ", "world!"]);
+        assert_eq!(
+            lines("a  b\nc\r\nd"),
+            vec!["a  ", "b\n!", "c\r\n!", "d!"]);
+        assert_eq!(lines("a\n"), vec!["a\n!"]);
+        assert_eq!(
+            lines("a\u{000B}\u{000B}"),
+            vec!["a\u{000B}!", "\u{000B}!"]);
+        assert!(line_breaks("", lb).is_empty());
+    }
+
+    #[test]
+    fn punctuation() {
+        assert_eq!(lines("well-known"), vec!["well-", "known!"]);
+        assert_eq!(lines("(see) it!"), vec!["(see) ", "it!!"]);
+        assert_eq!(lines("( a )b"), vec!["( a )b!"]);
+        assert_eq!(
+            lines("$1,000.00 50% -1"),
+            vec!["$1,000.00 ", "50% ", "-1!"]);
+        assert_eq!(
+            lines("a/b a\u{00A0}b -\u{00A0}c"),
+            vec!["a/", "b ", "a\u{00A0}b ", "-", "\u{00A0}c!"]);
+        assert_eq!(lines("( \u{00A0}a"), vec!["( \u{00A0}a!"]);
+        assert_eq!(
+            lines("a \u{2014}\u{2014} b"),
+            vec!["a ", "\u{2014}\u{2014} ", "b!"]);
+        assert_eq!(lines("\"a\" b"), vec!["\"a\" ", "b!"]);
+        assert_eq!(lines("\u{05D0}-\u{05D0}"), vec!["\u{05D0}-\u{05D0}!"]);
+    }
+
+    #[test]
+    fn ideographs() {
+        assert_eq!(
+            lines("\u{4E00}\u{3042}\u{3041}\u{4E00}"),
+            vec!["\u{4E00}", "\u{3042}\u{3041}", "\u{4E00}!"]);
+        assert_eq!(
+            lines("\u{1100}\u{1161}\u{11A8}\u{1100}"),
+            vec!["\u{1100}\u{1161}\u{11A8}", "\u{1100}!"]);
+    }
+
+    #[test]
+    fn combining() {
+        // Marks are treated like the character they follow...
+        assert_eq!(
+            lines("a\u{0301} b\u{0301}-c"),
+            vec!["a\u{0301} ", "b\u{0301}-", "c!"]);
+        // ... except after spaces, where they are treated like letters.
+        assert_eq!(lines("a \u{0301}b"), vec!["a ", "\u{0301}b!"]);
+        // Nothing breaks after a ZWJ, but a zero width space always does.
+        assert_eq!(
+            lines("\u{4E00}\u{200D}\u{4E00}"),
+            vec!["\u{4E00}\u{200D}\u{4E00}!"]);
+        assert_eq!(lines("a\u{200B}  b"), vec!["a\u{200B}  ", "b!"]);
+        assert_eq!(lines("\u{200D}\u{4E00}"), vec!["\u{200D}\u{4E00}!"]);
+        assert_eq!(lines("a\u{2060}\u{4E00}"), vec!["a\u{2060}\u{4E00}!"]);
+    }
+
+    #[test]
+    fn emoji() {
+        let flags = "\u{1F1FA}\u{1F1F8}\u{1F1EB}\u{1F1F7}\u{1F1E8}";
+        assert_eq!(
+            lines(flags),
+            vec!["\u{1F1FA}\u{1F1F8}", "\u{1F1EB}\u{1F1F7}", "\u{1F1E8}!"]);
+        assert_eq!(
+            lines("\u{1F466}\u{1F3FB}\u{1F466}"),
+            vec!["\u{1F466}\u{1F3FB}", "\u{1F466}!"]);
+    }
+
+    #[test]
+    fn chars() {
+        let breaks: Vec<(usize, BreakOpportunity)> =
+            line_breaks_chars("ab c\n".chars(), lb);
+        assert_eq!(breaks, vec![(3, Allowed), (5, Mandatory)]);
+    }
+}
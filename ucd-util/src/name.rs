@@ -124,9 +124,193 @@ pub fn symbolic_name_normalize_bytes(slice: &mut [u8]) -> &mut [u8] {
     &mut slice[..next_write]
 }
 
+/// The abbreviations in `NameAliases.txt`, except for the variation
+/// selectors, which are handled algorithmically.
+const ABBREVIATIONS: &'static [(u32, &'static [&'static str])] = &[
+    (0x0000, &["NUL"]),
+    (0x0001, &["SOH"]),
+    (0x0002, &["STX"]),
+    (0x0003, &["ETX"]),
+    (0x0004, &["EOT"]),
+    (0x0005, &["ENQ"]),
+    (0x0006, &["ACK"]),
+    (0x0007, &["BEL"]),
+    (0x0008, &["BS"]),
+    (0x0009, &["HT", "TAB"]),
+    (0x000A, &["LF", "NL", "EOL"]),
+    (0x000B, &["VT"]),
+    (0x000C, &["FF"]),
+    (0x000D, &["CR"]),
+    (0x000E, &["SO"]),
+    (0x000F, &["SI"]),
+    (0x0010, &["DLE"]),
+    (0x0011, &["DC1"]),
+    (0x0012, &["DC2"]),
+    (0x0013, &["DC3"]),
+    (0x0014, &["DC4"]),
+    (0x0015, &["NAK"]),
+    (0x0016, &["SYN"]),
+    (0x0017, &["ETB"]),
+    (0x0018, &["CAN"]),
+    (0x0019, &["EOM"]),
+    (0x001A, &["SUB"]),
+    (0x001B, &["ESC"]),
+    (0x001C, &["FS"]),
+    (0x001D, &["GS"]),
+    (0x001E, &["RS"]),
+    (0x001F, &["US"]),
+    (0x0020, &["SP"]),
+    (0x007F, &["DEL"]),
+    (0x0080, &["PAD"]),
+    (0x0081, &["HOP"]),
+    (0x0082, &["BPH"]),
+    (0x0083, &["NBH"]),
+    (0x0084, &["IND"]),
+    (0x0085, &["NEL"]),
+    (0x0086, &["SSA"]),
+    (0x0087, &["ESA"]),
+    (0x0088, &["HTS"]),
+    (0x0089, &["HTJ"]),
+    (0x008A, &["VTS"]),
+    (0x008B, &["PLD"]),
+    (0x008C, &["PLU"]),
+    (0x008D, &["RI"]),
+    (0x008E, &["SS2"]),
+    (0x008F, &["SS3"]),
+    (0x0090, &["DCS"]),
+    (0x0091, &["PU1"]),
+    (0x0092, &["PU2"]),
+    (0x0093, &["STS"]),
+    (0x0094, &["CCH"]),
+    (0x0095, &["MW"]),
+    (0x0096, &["SPA"]),
+    (0x0097, &["EPA"]),
+    (0x0098, &["SOS"]),
+    (0x0099, &["SGC"]),
+    (0x009A, &["SCI"]),
+    (0x009B, &["CSI"]),
+    (0x009C, &["ST"]),
+    (0x009D, &["OSC"]),
+    (0x009E, &["PM"]),
+    (0x009F, &["APC"]),
+    (0x00A0, &["NBSP"]),
+    (0x00AD, &["SHY"]),
+    (0x034F, &["CGJ"]),
+    (0x061C, &["ALM"]),
+    (0x180B, &["FVS1"]),
+    (0x180C, &["FVS2"]),
+    (0x180D, &["FVS3"]),
+    (0x180E, &["MVS"]),
+    (0x200B, &["ZWSP"]),
+    (0x200C, &["ZWNJ"]),
+    (0x200D, &["ZWJ"]),
+    (0x200E, &["LRM"]),
+    (0x200F, &["RLM"]),
+    (0x202A, &["LRE"]),
+    (0x202B, &["RLE"]),
+    (0x202C, &["PDF"]),
+    (0x202D, &["LRO"]),
+    (0x202E, &["RLO"]),
+    (0x202F, &["NNBSP"]),
+    (0x205F, &["MMSP"]),
+    (0x2060, &["WJ"]),
+    (0x2066, &["LRI"]),
+    (0x2067, &["RLI"]),
+    (0x2068, &["FSI"]),
+    (0x2069, &["PDI"]),
+    (0xFEFF, &["ZWNBSP"]),
+];
+
+/// Return the abbreviations of the given codepoint.
+///
+/// These are the aliases of type `abbreviation` in `NameAliases.txt`, e.g.,
+/// `CR` for U+000D and `NBSP` for U+00A0, in the order in which they are
+/// listed. Variation selectors have the abbreviations `VS1` through `VS256`.
+/// If the given codepoint has no abbreviation, then this returns an empty
+/// vector.
+pub fn name_abbreviations(cp: u32) -> Vec<String> {
+    if let Some(n) = variation_selector_number(cp) {
+        return vec![format!("VS{}", n)];
+    }
+    match ABBREVIATIONS.binary_search_by_key(&cp, |&(cp, _)| cp) {
+        Err(_) => vec![],
+        Ok(i) => ABBREVIATIONS[i].1.iter().map(|s| s.to_string()).collect(),
+    }
+}
+
+/// Return the codepoint with the given abbreviation.
+///
+/// The abbreviation is matched loosely according to UAX44-LM2, e.g., `nbsp`
+/// and `NBSP` both resolve to U+00A0. This makes it possible for `\N{...}`
+/// style lookups to accept abbreviations wherever they accept character
+/// names.
+///
+/// If no codepoint has the given abbreviation, then this returns `None`.
+pub fn name_abbreviation_to_codepoint(name: &str) -> Option<u32> {
+    let mut name = name.to_string();
+    character_name_normalize(&mut name);
+    if name.starts_with("vs") && !name[2..].starts_with('0') {
+        return match name[2..].parse::<u32>() {
+            Ok(n @ 1..=16) => Some(0xFE00 + n - 1),
+            Ok(n @ 17..=256) => Some(0xE0100 + n - 17),
+            _ => None,
+        };
+    }
+    ABBREVIATIONS
+        .iter()
+        .find(|&&(_, abbrs)| {
+            abbrs.iter().any(|abbr| abbr.eq_ignore_ascii_case(&name))
+        })
+        .map(|&(cp, _)| cp)
+}
+
+/// Return the code point label of the given codepoint.
+///
+/// `gc` should be the short name of the codepoint's `General_Category`,
+/// e.g., `Cc`. Code point labels are only defined for codepoints that don't
+/// have a name, i.e., for control (`Cc`), private use (`Co`), surrogate
+/// (`Cs`) and unassigned (`Cn`) codepoints, e.g., `<control-0009>` or
+/// `<surrogate-D800>`. Unassigned codepoints are labeled as noncharacters if
+/// they are one of the 66 noncharacters, e.g., `<noncharacter-FDD0>`, and as
+/// reserved otherwise, e.g., `<reserved-0378>`. For any other general
+/// category, this returns `None`.
+///
+/// This implements the rules for code point labels described in Unicode
+/// 4.8, Table 4-11.
+pub fn codepoint_label(cp: u32, gc: &str) -> Option<String> {
+    let kind = match gc {
+        "Cc" => "control",
+        "Co" => "private-use",
+        "Cs" => "surrogate",
+        "Cn" if is_noncharacter(cp) => "noncharacter",
+        "Cn" => "reserved",
+        _ => return None,
+    };
+    Some(format!("<{}-{:04X}>", kind, cp))
+}
+
+/// Returns true if and only if the given codepoint is a noncharacter, i.e.,
+/// one of `FDD0..FDEF` or the last two codepoints of any plane.
+fn is_noncharacter(cp: u32) -> bool {
+    (0xFDD0 <= cp && cp <= 0xFDEF) || cp & 0xFFFE == 0xFFFE
+}
+
+/// Return the number of the given variation selector, e.g., `1` for U+FE00.
+fn variation_selector_number(cp: u32) -> Option<u32> {
+    match cp {
+        0xFE00..=0xFE0F => Some(cp - 0xFE00 + 1),
+        0xE0100..=0xE01EF => Some(cp - 0xE0100 + 17),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{character_name_normalize, symbolic_name_normalize};
+    use super::{
+        character_name_normalize, codepoint_label,
+        name_abbreviation_to_codepoint, name_abbreviations,
+        symbolic_name_normalize,
+    };
 
     fn char_norm(s: &str) -> String {
         let mut s = s.to_string();
@@ -160,4 +344,73 @@ mod tests {
         assert_eq!(sym_norm("isGreek"), "greek");
         assert_eq!(sym_norm("IS_Greek"), "greek");
     }
+
+    #[test]
+    fn labels() {
+        assert_eq!(codepoint_label(0x9, "Cc").unwrap(), "<control-0009>");
+        assert_eq!(codepoint_label(0x9F, "Cc").unwrap(), "<control-009F>");
+        assert_eq!(
+            codepoint_label(0xD800, "Cs").unwrap(),
+            "<surrogate-D800>");
+        assert_eq!(
+            codepoint_label(0xE000, "Co").unwrap(),
+            "<private-use-E000>");
+        assert_eq!(
+            codepoint_label(0x10FFFD, "Co").unwrap(),
+            "<private-use-10FFFD>");
+        assert_eq!(
+            codepoint_label(0xFDD0, "Cn").unwrap(),
+            "<noncharacter-FDD0>");
+        assert_eq!(
+            codepoint_label(0x1FFFE, "Cn").unwrap(),
+            "<noncharacter-1FFFE>");
+        assert_eq!(
+            codepoint_label(0x10FFFF, "Cn").unwrap(),
+            "<noncharacter-10FFFF>");
+        assert_eq!(codepoint_label(0x378, "Cn").unwrap(), "<reserved-0378>");
+        assert_eq!(codepoint_label(0xFDF0, "Cn").unwrap(), "<reserved-FDF0>");
+        assert_eq!(codepoint_label(0x41, "Lu"), None);
+        assert_eq!(codepoint_label(0x20, "Zs"), None);
+    }
+
+    #[test]
+    fn abbreviations() {
+        assert_eq!(name_abbreviations(0x0D), vec!["CR"]);
+        assert_eq!(name_abbreviations(0x0A), vec!["LF", "NL", "EOL"]);
+        assert_eq!(name_abbreviations(0xA0), vec!["NBSP"]);
+        assert_eq!(name_abbreviations(0x200D), vec!["ZWJ"]);
+        assert_eq!(name_abbreviations(0xFE00), vec!["VS1"]);
+        assert_eq!(name_abbreviations(0xFE0F), vec!["VS16"]);
+        assert_eq!(name_abbreviations(0xE0100), vec!["VS17"]);
+        assert_eq!(name_abbreviations(0xE01EF), vec!["VS256"]);
+        assert!(name_abbreviations(0x41).is_empty());
+    }
+
+    #[test]
+    fn abbreviation_to_codepoint() {
+        let cp = name_abbreviation_to_codepoint;
+        assert_eq!(cp("CR"), Some(0x0D));
+        assert_eq!(cp("cr"), Some(0x0D));
+        assert_eq!(cp("EOL"), Some(0x0A));
+        assert_eq!(cp("nbsp"), Some(0xA0));
+        assert_eq!(cp("ZW J"), Some(0x200D));
+        assert_eq!(cp("PU2"), Some(0x92));
+        assert_eq!(cp("VS1"), Some(0xFE00));
+        assert_eq!(cp("vs 17"), Some(0xE0100));
+        assert_eq!(cp("VS256"), Some(0xE01EF));
+        assert_eq!(cp("VS0"), None);
+        assert_eq!(cp("VS01"), None);
+        assert_eq!(cp("VS257"), None);
+        assert_eq!(cp("CARRIAGE RETURN"), None);
+        assert_eq!(cp(""), None);
+    }
+
+    #[test]
+    fn abbreviations_sorted() {
+        let cps: Vec<u32> = super::ABBREVIATIONS.iter().map(|p| p.0).collect();
+        let mut sorted = cps.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(cps, sorted);
+    }
 }
@@ -0,0 +1,156 @@
+#[cfg(feature = "fst")]
+use fst;
+
+use hangul::hangul_name_to_codepoint;
+use ideograph::ideograph_name_to_codepoint;
+use name::{character_name_normalize, name_abbreviation_to_codepoint};
+
+/// The prefixes of algorithmically generated ideograph names, normalized
+/// according to UAX44-LM2, along with their canonical forms.
+const IDEOGRAPH_PREFIXES: &'static [(&'static str, &'static str)] = &[
+    ("cjkcompatibilityideograph", "CJK COMPATIBILITY IDEOGRAPH-"),
+    ("cjkunifiedideograph", "CJK UNIFIED IDEOGRAPH-"),
+    ("tangutideograph", "TANGUT IDEOGRAPH-"),
+];
+
+/// Resolves character names to codepoints.
+///
+/// A resolver is built from a table of names emitted by `ucd-generate names`
+/// with the `--normalize` flag. Names are matched loosely according to
+/// UAX44-LM2, e.g., `zero width joiner`, `ZERO-WIDTH JOINER` and
+/// `ZeroWidthJoiner` all resolve to U+200D.
+///
+/// Names that are not in the table fall back to the algorithms for Hangul
+/// syllables and ideographs, and to the abbreviations in `NameAliases.txt`.
+/// This means the table may be generated with `--no-hangul` and
+/// `--no-ideograph` to make it smaller without losing any names.
+#[derive(Clone, Copy, Debug)]
+pub struct NameResolver<'a> {
+    names: Names<'a>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Names<'a> {
+    Slice(&'a [(&'a str, u32)]),
+    #[cfg(feature = "fst")]
+    Fst(&'a fst::Map),
+}
+
+impl<'a> NameResolver<'a> {
+    /// Create a resolver from the given table of normalized names, sorted
+    /// by name.
+    pub fn new(names: &'a [(&'a str, u32)]) -> NameResolver<'a> {
+        NameResolver { names: Names::Slice(names) }
+    }
+
+    /// Create a resolver from the given FST of normalized names.
+    ///
+    /// The values of the FST may be tagged, i.e., generated with `--tagged`,
+    /// since only their lower 32 bits are used.
+    #[cfg(feature = "fst")]
+    pub fn from_fst(names: &'a fst::Map) -> NameResolver<'a> {
+        NameResolver { names: Names::Fst(names) }
+    }
+
+    /// Return the codepoint with the given name, if one exists.
+    pub fn resolve(&self, name: &str) -> Option<u32> {
+        let mut name = name.to_string();
+        character_name_normalize(&mut name);
+        if let Some(cp) = self.lookup(&name) {
+            return Some(cp);
+        }
+        if name.starts_with("hangulsyllable") {
+            let canonical = format!(
+                "HANGUL SYLLABLE {}",
+                name["hangulsyllable".len()..].to_uppercase());
+            return hangul_name_to_codepoint(&canonical);
+        }
+        for &(prefix, canonical) in IDEOGRAPH_PREFIXES {
+            if name.starts_with(prefix) {
+                let canonical = format!(
+                    "{}{}", canonical, name[prefix.len()..].to_uppercase());
+                return ideograph_name_to_codepoint(&canonical);
+            }
+        }
+        name_abbreviation_to_codepoint(&name)
+    }
+
+    fn lookup(&self, name: &str) -> Option<u32> {
+        match self.names {
+            Names::Slice(names) => {
+                names
+                    .binary_search_by_key(&name, |&(n, _)| n)
+                    .ok()
+                    .map(|i| names[i].1)
+            }
+            #[cfg(feature = "fst")]
+            Names::Fst(names) => names.get(name).map(|v| v as u32),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NameResolver;
+
+    // A small excerpt of a normalized names table, without Hangul syllables
+    // or ideographs.
+    const NAMES: &'static [(&'static str, u32)] = &[
+        ("carriagereturn", 0x000D),
+        ("hanguljungseongo-e", 0x1180),
+        ("latincapitallettera", 0x0041),
+        ("linefeed", 0x000A),
+        ("zerowidthjoiner", 0x200D),
+    ];
+
+    #[test]
+    fn table() {
+        let names = NameResolver::new(NAMES);
+        assert_eq!(names.resolve("zero width joiner"), Some(0x200D));
+        assert_eq!(names.resolve("ZERO-WIDTH JOINER"), Some(0x200D));
+        assert_eq!(names.resolve("ZeroWidthJoiner"), Some(0x200D));
+        assert_eq!(names.resolve("LATIN CAPITAL LETTER A"), Some(0x41));
+        assert_eq!(names.resolve("hangul jungseong o-e"), Some(0x1180));
+        assert_eq!(names.resolve("LINE FEED"), Some(0x0A));
+        assert_eq!(names.resolve("LATIN CAPITAL LETTER"), None);
+        assert_eq!(names.resolve(""), None);
+    }
+
+    #[test]
+    fn algorithmic() {
+        let names = NameResolver::new(NAMES);
+        assert_eq!(names.resolve("HANGUL SYLLABLE GAG"), Some(0xAC01));
+        assert_eq!(names.resolve("hangul syllable pwilh"), Some(0xD4DB));
+        assert_eq!(names.resolve("hangul syllable xyz"), None);
+        assert_eq!(names.resolve("CJK UNIFIED IDEOGRAPH-4E00"), Some(0x4E00));
+        assert_eq!(names.resolve("cjk unified ideograph-4e00"), Some(0x4E00));
+        assert_eq!(
+            names.resolve("CJK COMPATIBILITY IDEOGRAPH-F900"),
+            Some(0xF900));
+        assert_eq!(names.resolve("tangut ideograph-17000"), Some(0x17000));
+        assert_eq!(names.resolve("CJK UNIFIED IDEOGRAPH-0041"), None);
+    }
+
+    #[test]
+    fn abbreviations() {
+        let names = NameResolver::new(NAMES);
+        assert_eq!(names.resolve("CR"), Some(0x0D));
+        assert_eq!(names.resolve("zwj"), Some(0x200D));
+        assert_eq!(names.resolve("NBSP"), Some(0xA0));
+        assert_eq!(names.resolve("VS16"), Some(0xFE0F));
+    }
+
+    #[cfg(feature = "fst")]
+    #[test]
+    fn fst() {
+        use fst;
+
+        let map = fst::Map::from_iter(
+            NAMES.iter().map(|&(name, cp)| (name, (1 << 33) | cp as u64)))
+            .unwrap();
+        let names = NameResolver::from_fst(&map);
+        assert_eq!(names.resolve("zero width joiner"), Some(0x200D));
+        assert_eq!(names.resolve("HANGUL SYLLABLE GAG"), Some(0xAC01));
+        assert_eq!(names.resolve("nothing"), None);
+    }
+}
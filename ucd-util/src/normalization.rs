@@ -0,0 +1,503 @@
+use hangul::{
+    hangul_full_canonical_composition, hangul_full_canonical_decomposition,
+};
+
+// This implementation should correspond to the algorithms described in
+// Unicode 3.11 and UAX #15. The Unicode character database itself is not
+// included in this crate, so every function here is parameterized over
+// lookup functions that are typically backed by tables generated by
+// `ucd-generate`.
+
+/// Append the full canonical decomposition of the given character to `out`.
+///
+/// `mapping` should return the canonical decomposition mapping of a
+/// character, as specified by the `Decomposition_Mapping` property in
+/// `UnicodeData.txt`, or `None` if the character has no canonical
+/// decomposition mapping. Compatibility decomposition mappings must not be
+/// returned. A mapping of a character to itself is treated the same as
+/// `None`.
+///
+/// Mappings are applied recursively until no mapping applies to any of the
+/// resulting characters. Precomposed Hangul syllables are decomposed
+/// algorithmically, so `mapping` does not need to include them.
+///
+/// The characters appended to `out` are not necessarily in canonical order.
+/// Use `canonical_reorder` to put the full decomposition of a sequence of
+/// characters in canonical order.
+pub fn full_canonical_decomposition<'a, F>(
+    c: char,
+    mapping: F,
+    out: &mut Vec<char>,
+) where F: Fn(char) -> Option<&'a [char]> {
+    decompose(c, &mapping, out)
+}
+
+/// Append the full compatibility decomposition of the given character to
+/// `out`.
+///
+/// This is like `full_canonical_decomposition`, except `mapping` should
+/// return the decomposition mapping of a character regardless of whether it
+/// is canonical or has a compatibility formatting tag such as `<compat>` or
+/// `<font>`. The tag itself should not be included in the mapping.
+///
+/// Decomposing every character of a sequence with this function and putting
+/// the result in canonical order produces Normalization Form KD (NFKD).
+/// Applying `canonical_composition` to NFKD produces Normalization Form KC
+/// (NFKC).
+pub fn full_compatibility_decomposition<'a, F>(
+    c: char,
+    mapping: F,
+    out: &mut Vec<char>,
+) where F: Fn(char) -> Option<&'a [char]> {
+    decompose(c, &mapping, out)
+}
+
+fn decompose<'a, F>(c: char, mapping: &F, out: &mut Vec<char>)
+where F: Fn(char) -> Option<&'a [char]>
+{
+    if let Some((l, v, t)) = hangul_full_canonical_decomposition(c as u32) {
+        // The Jamo produced by the Hangul algorithm are always valid
+        // codepoints that don't decompose any further.
+        out.push(jamo(l));
+        out.push(jamo(v));
+        out.extend(t.map(jamo));
+        return;
+    }
+    match mapping(c) {
+        Some(decomposed) if decomposed != &[c] => {
+            for &d in decomposed {
+                decompose(d, mapping, out);
+            }
+        }
+        _ => out.push(c),
+    }
+}
+
+/// Put the given characters in canonical order.
+///
+/// `ccc` should return the `Canonical_Combining_Class` of a character, where
+/// `0` indicates a starter. Every maximal run of non-starters is sorted by
+/// combining class. The sort is stable, so non-starters with the same
+/// combining class keep their relative order, and starters never move.
+///
+/// This implements the Canonical Ordering Algorithm described in Unicode
+/// 3.11 (D109) and UAX #15.
+pub fn canonical_reorder<F>(chars: &mut [char], ccc: F)
+where F: Fn(char) -> u8
+{
+    let mut i = 0;
+    while i < chars.len() {
+        if ccc(chars[i]) == 0 {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < chars.len() && ccc(chars[i]) != 0 {
+            i += 1;
+        }
+        chars[start..i].sort_by_key(|&c| ccc(c));
+    }
+}
+
+/// Apply the Canonical Composition Algorithm to the given characters.
+///
+/// The characters given should be fully decomposed and in canonical order,
+/// e.g., by `full_canonical_decomposition` and `canonical_reorder`, in which
+/// case the result is in Normalization Form C (NFC). If the characters were
+/// decomposed by `full_compatibility_decomposition` instead, then the result
+/// is in Normalization Form KC (NFKC).
+///
+/// `ccc` should return the `Canonical_Combining_Class` of a character.
+/// `compose` should return the character whose canonical decomposition
+/// mapping is the given pair of characters, if one exists, and
+/// `excluded` should return true for characters with the
+/// `Full_Composition_Exclusion` property. Together, these determine the
+/// primary composite of a pair of characters. Precomposed Hangul syllables
+/// are composed algorithmically, so `compose` does not need to include them.
+///
+/// A character is only combined with the last starter before it when it is
+/// not blocked from that starter, i.e., when every character between them
+/// has a combining class that is non-zero and lower than its own.
+///
+/// This implements the algorithm described in Unicode 3.11 (D117).
+pub fn canonical_composition<F, G, H>(
+    chars: &mut Vec<char>,
+    ccc: F,
+    compose: G,
+    excluded: H,
+) where F: Fn(char) -> u8,
+        G: Fn(char, char) -> Option<char>,
+        H: Fn(char) -> bool
+{
+    let primary_composite = |a: char, b: char| -> Option<char> {
+        if let Some(c) = hangul_full_canonical_composition(&[a, b]) {
+            return Some(c);
+        }
+        compose(a, b).and_then(|c| if excluded(c) { None } else { Some(c) })
+    };
+
+    // Characters are composed in place: `len` is the length of the composed
+    // prefix, which never overtakes the character being read.
+    let mut len = 0;
+    // The position of the last starter in the composed prefix.
+    let mut starter: Option<usize> = None;
+    // The combining class of the last character after that starter.
+    let mut last_ccc: Option<u8> = None;
+    for i in 0..chars.len() {
+        let c = chars[i];
+        let cc = ccc(c);
+        if let Some(s) = starter {
+            let blocked = match last_ccc {
+                None => false,
+                Some(last) => last == 0 || last >= cc,
+            };
+            if !blocked {
+                if let Some(composite) = primary_composite(chars[s], c) {
+                    chars[s] = composite;
+                    continue;
+                }
+            }
+        }
+        if cc == 0 {
+            starter = Some(len);
+            last_ccc = None;
+        } else {
+            last_ccc = Some(cc);
+        }
+        chars[len] = c;
+        len += 1;
+    }
+    chars.truncate(len);
+}
+
+/// The result of a normalization quick check.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum QuickCheckResult {
+    /// The text is definitely normalized.
+    Yes,
+    /// The text is definitely not normalized.
+    No,
+    /// The text may or may not be normalized. Callers need to normalize the
+    /// text and compare it with the original to find out.
+    Maybe,
+}
+
+/// Quickly check whether the given characters are in Normalization Form C.
+///
+/// `ccc` should return the `Canonical_Combining_Class` of a character and
+/// `qc` should return its `NFC_Quick_Check` property value, as specified in
+/// `DerivedNormalizationProps.txt`. Characters missing from that file have
+/// the value `Yes`.
+///
+/// Passing a lookup of the `NFKC_Quick_Check` property as `qc` checks for
+/// Normalization Form KC instead.
+///
+/// This implements the algorithm described in UAX #15, section 9.
+pub fn is_nfc_quick<I, F, G>(chars: I, ccc: F, qc: G) -> QuickCheckResult
+where I: IntoIterator<Item=char>,
+      F: Fn(char) -> u8,
+      G: Fn(char) -> QuickCheckResult
+{
+    quick_check(chars, ccc, qc)
+}
+
+/// Quickly check whether the given characters are in Normalization Form D.
+///
+/// This is like `is_nfc_quick`, except `qc` should return the
+/// `NFD_Quick_Check` property value of a character. Since that property
+/// never has the value `Maybe`, neither does the result.
+///
+/// Passing a lookup of the `NFKD_Quick_Check` property as `qc` checks for
+/// Normalization Form KD instead.
+pub fn is_nfd_quick<I, F, G>(chars: I, ccc: F, qc: G) -> QuickCheckResult
+where I: IntoIterator<Item=char>,
+      F: Fn(char) -> u8,
+      G: Fn(char) -> QuickCheckResult
+{
+    quick_check(chars, ccc, qc)
+}
+
+fn quick_check<I, F, G>(chars: I, ccc: F, qc: G) -> QuickCheckResult
+where I: IntoIterator<Item=char>,
+      F: Fn(char) -> u8,
+      G: Fn(char) -> QuickCheckResult
+{
+    let mut last_ccc = 0;
+    let mut result = QuickCheckResult::Yes;
+    for c in chars {
+        let cc = ccc(c);
+        if last_ccc > cc && cc != 0 {
+            return QuickCheckResult::No;
+        }
+        match qc(c) {
+            QuickCheckResult::Yes => {}
+            QuickCheckResult::No => return QuickCheckResult::No,
+            QuickCheckResult::Maybe => result = QuickCheckResult::Maybe,
+        }
+        last_ccc = cc;
+    }
+    result
+}
+
+fn jamo(cp: u32) -> char {
+    ::std::char::from_u32(cp).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        canonical_composition, canonical_reorder, full_canonical_decomposition,
+        full_compatibility_decomposition, is_nfc_quick, is_nfd_quick,
+    };
+    use super::QuickCheckResult::{self, Maybe, No, Yes};
+
+    // A small excerpt of the canonical decomposition mappings in
+    // UnicodeData.txt.
+    const MAPPINGS: &'static [(char, &'static [char])] = &[
+        ('\u{00C5}', &['\u{0041}', '\u{030A}']),
+        ('\u{00F4}', &['\u{006F}', '\u{0302}']),
+        ('\u{1E69}', &['\u{1E63}', '\u{0307}']),
+        ('\u{1E63}', &['\u{0073}', '\u{0323}']),
+        ('\u{1EDB}', &['\u{01A1}', '\u{0301}']),
+        ('\u{01A1}', &['\u{006F}', '\u{031B}']),
+        ('\u{212B}', &['\u{00C5}']),
+        ('\u{1E9B}', &['\u{017F}', '\u{0307}']),
+        ('\u{0344}', &['\u{0308}', '\u{0301}']),
+        ('\u{00E4}', &['\u{0061}', '\u{0308}']),
+        ('\u{0160}', &['\u{0053}', '\u{030C}']),
+    ];
+
+    fn mapping(c: char) -> Option<&'static [char]> {
+        MAPPINGS.iter().find(|&&(k, _)| k == c).map(|&(_, v)| v)
+    }
+
+    // A small excerpt of the canonical combining classes in
+    // UnicodeData.txt.
+    fn ccc(c: char) -> u8 {
+        match c {
+            '\u{0301}' | '\u{0302}' | '\u{0307}' | '\u{0308}'
+            | '\u{030A}' | '\u{030C}' => 230,
+            '\u{031B}' => 216,
+            '\u{0323}' => 220,
+            '\u{0315}' => 232,
+            _ => 0,
+        }
+    }
+
+    // A small excerpt of the compatibility decomposition mappings in
+    // UnicodeData.txt.
+    const COMPAT_MAPPINGS: &'static [(char, &'static [char])] = &[
+        ('\u{017F}', &['\u{0073}']),
+        ('\u{FB01}', &['\u{0066}', '\u{0069}']),
+        ('\u{2474}', &['\u{0028}', '\u{0031}', '\u{0029}']),
+        ('\u{3250}', &['\u{0050}', '\u{0054}', '\u{0045}']),
+    ];
+
+    fn compat_mapping(c: char) -> Option<&'static [char]> {
+        COMPAT_MAPPINGS
+            .iter()
+            .find(|&&(k, _)| k == c)
+            .map(|&(_, v)| v)
+            .or_else(|| mapping(c))
+    }
+
+    fn compose(a: char, b: char) -> Option<char> {
+        MAPPINGS
+            .iter()
+            .find(|&&(_, v)| v == &[a, b])
+            .map(|&(k, _)| k)
+    }
+
+    // U+0344 decomposes to U+0308 U+0301, but is excluded from
+    // composition.
+    fn excluded(c: char) -> bool {
+        c == '\u{0344}'
+    }
+
+    fn nfc(s: &str) -> String {
+        let mut chars = vec![];
+        for c in s.chars() {
+            full_canonical_decomposition(c, mapping, &mut chars);
+        }
+        canonical_reorder(&mut chars, ccc);
+        canonical_composition(&mut chars, ccc, compose, excluded);
+        chars.into_iter().collect()
+    }
+
+    fn nfkd(s: &str) -> String {
+        let mut chars = vec![];
+        for c in s.chars() {
+            full_compatibility_decomposition(c, compat_mapping, &mut chars);
+        }
+        canonical_reorder(&mut chars, ccc);
+        chars.into_iter().collect()
+    }
+
+    fn nfkc(s: &str) -> String {
+        let mut chars: Vec<char> = nfkd(s).chars().collect();
+        canonical_composition(&mut chars, ccc, compose, excluded);
+        chars.into_iter().collect()
+    }
+
+    // A small excerpt of DerivedNormalizationProps.txt.
+    fn nfc_qc(c: char) -> QuickCheckResult {
+        match c {
+            '\u{0301}' | '\u{0307}' | '\u{0308}' | '\u{0323}' => Maybe,
+            '\u{1161}'..='\u{1175}' | '\u{11A8}'..='\u{11C2}' => Maybe,
+            '\u{0344}' | '\u{212B}' => No,
+            _ => Yes,
+        }
+    }
+
+    fn nfd_qc(c: char) -> QuickCheckResult {
+        if mapping(c).is_some() || ('\u{AC00}' <= c && c <= '\u{D7A3}') {
+            No
+        } else {
+            Yes
+        }
+    }
+
+    fn decomp(c: char) -> Vec<char> {
+        let mut out = vec![];
+        full_canonical_decomposition(c, mapping, &mut out);
+        out
+    }
+
+    #[test]
+    fn recursive() {
+        assert_eq!(decomp('\u{1E69}'), vec!['s', '\u{0323}', '\u{0307}']);
+        assert_eq!(decomp('\u{1EDB}'), vec!['o', '\u{031B}', '\u{0301}']);
+        // ANGSTROM SIGN is a singleton mapping to a decomposable character.
+        assert_eq!(decomp('\u{212B}'), vec!['A', '\u{030A}']);
+    }
+
+    #[test]
+    fn no_mapping() {
+        assert_eq!(decomp('a'), vec!['a']);
+        let mut out = vec!['x'];
+        full_canonical_decomposition('a', |_| Some(&['a']), &mut out);
+        assert_eq!(out, vec!['x', 'a']);
+    }
+
+    #[test]
+    fn hangul() {
+        assert_eq!(
+            decomp('\u{D4DB}'),
+            vec!['\u{1111}', '\u{1171}', '\u{11B6}']);
+        assert_eq!(decomp('\u{AC00}'), vec!['\u{1100}', '\u{1161}']);
+    }
+
+    #[test]
+    fn reorder() {
+        let mut chars = vec!['s', '\u{0307}', '\u{0323}'];
+        canonical_reorder(&mut chars, ccc);
+        assert_eq!(chars, vec!['s', '\u{0323}', '\u{0307}']);
+
+        // Marks with the same class keep their order, and runs are never
+        // reordered across a starter.
+        let mut chars = vec![
+            '\u{0315}', '\u{0302}', '\u{0301}', '\u{031B}',
+            'a', '\u{0307}', '\u{0323}',
+        ];
+        canonical_reorder(&mut chars, ccc);
+        assert_eq!(chars, vec![
+            '\u{031B}', '\u{0302}', '\u{0301}', '\u{0315}',
+            'a', '\u{0323}', '\u{0307}',
+        ]);
+
+        let mut chars: Vec<char> = vec![];
+        canonical_reorder(&mut chars, ccc);
+        assert!(chars.is_empty());
+    }
+
+    #[test]
+    fn decomp_reorder() {
+        // The marks in the decomposition of U+1E69 must be interleaved
+        // with the marks that follow it.
+        let mut out = vec![];
+        for c in "\u{1E69}\u{0315}\u{031B}".chars() {
+            full_canonical_decomposition(c, mapping, &mut out);
+        }
+        canonical_reorder(&mut out, ccc);
+        assert_eq!(out, vec![
+            's', '\u{031B}', '\u{0323}', '\u{0307}', '\u{0315}',
+        ]);
+    }
+
+    #[test]
+    fn composition() {
+        assert_eq!(nfc("A\u{030A}"), "\u{00C5}");
+        assert_eq!(nfc("\u{212B}"), "\u{00C5}");
+        assert_eq!(nfc("s\u{0323}\u{0307}"), "\u{1E69}");
+        assert_eq!(nfc("s\u{0307}\u{0323}"), "\u{1E69}");
+        assert_eq!(nfc("o\u{031B}\u{0301}"), "\u{1EDB}");
+        assert_eq!(nfc("\u{1E69}\u{1EDB}"), "\u{1E69}\u{1EDB}");
+        assert_eq!(nfc("abc"), "abc");
+        assert_eq!(nfc(""), "");
+    }
+
+    #[test]
+    fn compose_blocked() {
+        // U+0301 is blocked from `a` by U+0302, which has the same class.
+        assert_eq!(nfc("a\u{0302}\u{0301}"), "a\u{0302}\u{0301}");
+        // A mark can skip over a mark with a lower class.
+        assert_eq!(nfc("a\u{0323}\u{0308}"), "\u{00E4}\u{0323}");
+        assert_eq!(nfc("S\u{0323}\u{030C}"), "\u{0160}\u{0323}");
+        // A starter can only combine with an adjacent starter.
+        assert_eq!(
+            nfc("\u{1111}\u{0323}\u{1171}"),
+            "\u{1111}\u{0323}\u{1171}");
+        // Mappings excluded from composition are never recomposed.
+        assert_eq!(nfc("\u{0344}"), "\u{0308}\u{0301}");
+    }
+
+    #[test]
+    fn compose_hangul() {
+        assert_eq!(nfc("\u{1111}\u{1171}\u{11B6}"), "\u{D4DB}");
+        assert_eq!(nfc("\u{D4CC}\u{11B6}"), "\u{D4DB}");
+        assert_eq!(nfc("\u{D4DB}"), "\u{D4DB}");
+        assert_eq!(
+            nfc("\u{1111}\u{1171}\u{1111}\u{1171}"),
+            "\u{D4CC}\u{D4CC}");
+    }
+
+    #[test]
+    fn compatibility() {
+        assert_eq!(nfkd("\u{FB01}"), "fi");
+        assert_eq!(nfkd("\u{2474}"), "(1)");
+        assert_eq!(nfkd("\u{1E9B}\u{0323}"), "s\u{0323}\u{0307}");
+        assert_eq!(nfkc("\u{1E9B}\u{0323}"), "\u{1E69}");
+        assert_eq!(nfkc("\u{00C5}\u{3250}"), "\u{00C5}PTE");
+        assert_eq!(nfkc("\u{D4DB}"), "\u{D4DB}");
+
+        // Canonical decomposition never follows compatibility mappings.
+        let mut out = vec![];
+        full_canonical_decomposition('\u{1E9B}', mapping, &mut out);
+        assert_eq!(out, vec!['\u{017F}', '\u{0307}']);
+    }
+
+    #[test]
+    fn quick_check_nfc() {
+        let qc = |s: &str| is_nfc_quick(s.chars(), ccc, nfc_qc);
+        assert_eq!(qc("abc"), Yes);
+        assert_eq!(qc(""), Yes);
+        assert_eq!(qc("\u{00C5}\u{D4DB}"), Yes);
+        assert_eq!(qc("a\u{0301}"), Maybe);
+        assert_eq!(qc("\u{1111}\u{1171}"), Maybe);
+        assert_eq!(qc("\u{212B}"), No);
+        // Marks that aren't in canonical order are never normalized.
+        assert_eq!(qc("a\u{0307}\u{0323}"), No);
+        assert_eq!(qc("a\u{0323}\u{0307}"), Maybe);
+    }
+
+    #[test]
+    fn quick_check_nfd() {
+        let qc = |s: &str| is_nfd_quick(s.chars(), ccc, nfd_qc);
+        assert_eq!(qc("abc"), Yes);
+        assert_eq!(qc("s\u{0323}\u{0307}"), Yes);
+        assert_eq!(qc("s\u{0307}\u{0323}"), No);
+        assert_eq!(qc("\u{1E69}"), No);
+        assert_eq!(qc("\u{D4DB}"), No);
+        assert_eq!(qc(&nfc("\u{1E69}")), No);
+    }
+}
@@ -0,0 +1,144 @@
+use name::symbolic_name_normalize;
+
+/// A table for resolving property names to their canonical names.
+///
+/// The table maps every alias of every property, normalized by
+/// `symbolic_name_normalize`, to the canonical (long) name of that property,
+/// e.g., `("gc", "General_Category")` and
+/// `("generalcategory", "General_Category")`. It must be sorted by the
+/// normalized alias. These are the aliases listed in `PropertyAliases.txt`.
+#[derive(Clone, Copy, Debug)]
+pub struct PropertyTable<'a> {
+    names: &'a [(&'a str, &'a str)],
+}
+
+impl<'a> PropertyTable<'a> {
+    /// Create a new property table from the given sorted list of
+    /// normalized aliases and canonical names.
+    pub fn new(names: &'a [(&'a str, &'a str)]) -> PropertyTable<'a> {
+        PropertyTable { names: names }
+    }
+
+    /// Return the canonical name of the property with the given name.
+    ///
+    /// The given name is matched loosely according to UAX44-LM3, e.g.,
+    /// `gc`, `General_Category` and `general category` all resolve to
+    /// `General_Category`. If no property has the given name, then this
+    /// returns `None`.
+    pub fn canonical(&self, name: &str) -> Option<&'a str> {
+        find(self.names, name)
+    }
+}
+
+/// A table for resolving property values to their canonical names.
+///
+/// The table maps the canonical name of each property to a list of the
+/// aliases of its values, in the same format as `PropertyTable`, e.g.,
+/// `("General_Category", &[("lu", "Uppercase_Letter"), ...])`. It must be
+/// sorted by the canonical property name. These are the aliases listed in
+/// `PropertyValueAliases.txt`.
+#[derive(Clone, Copy, Debug)]
+pub struct PropertyValueTable<'a> {
+    values: &'a [(&'a str, &'a [(&'a str, &'a str)])],
+}
+
+impl<'a> PropertyValueTable<'a> {
+    /// Create a new property value table from the given sorted list of
+    /// properties and their values.
+    pub fn new(
+        values: &'a [(&'a str, &'a [(&'a str, &'a str)])],
+    ) -> PropertyValueTable<'a> {
+        PropertyValueTable { values: values }
+    }
+
+    /// Return the canonical name of the given value of the property with the
+    /// given canonical name.
+    ///
+    /// The value is matched loosely according to UAX44-LM3, e.g., `Lu`,
+    /// `uppercase letter` and `Uppercase_Letter` all resolve to
+    /// `Uppercase_Letter` for `General_Category`. The property name must be
+    /// canonical, e.g., as returned by `PropertyTable::canonical`. If the
+    /// property has no such value, or if there is no such property, then
+    /// this returns `None`.
+    pub fn canonical(&self, property: &str, value: &str) -> Option<&'a str> {
+        let i = match self.values.binary_search_by_key(&property, |p| p.0) {
+            Err(_) => return None,
+            Ok(i) => i,
+        };
+        find(self.values[i].1, value)
+    }
+}
+
+/// Find the canonical name for the given name in a sorted table of
+/// normalized aliases.
+fn find<'a>(table: &'a [(&'a str, &'a str)], name: &str) -> Option<&'a str> {
+    let mut name = name.to_string();
+    symbolic_name_normalize(&mut name);
+    table
+        .binary_search_by_key(&&*name, |&(alias, _)| alias)
+        .ok()
+        .map(|i| table[i].1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PropertyTable, PropertyValueTable};
+
+    // Small excerpts of PropertyAliases.txt and PropertyValueAliases.txt.
+    const NAMES: &'static [(&'static str, &'static str)] = &[
+        ("alpha", "Alphabetic"),
+        ("alphabetic", "Alphabetic"),
+        ("gc", "General_Category"),
+        ("generalcategory", "General_Category"),
+        ("sc", "Script"),
+        ("script", "Script"),
+    ];
+
+    const VALUES: &'static [(
+        &'static str,
+        &'static [(&'static str, &'static str)],
+    )] = &[
+        ("General_Category", &[
+            ("l", "Letter"),
+            ("letter", "Letter"),
+            ("lu", "Uppercase_Letter"),
+            ("uppercaseletter", "Uppercase_Letter"),
+        ]),
+        ("Script", &[
+            ("greek", "Greek"),
+            ("grek", "Greek"),
+        ]),
+    ];
+
+    #[test]
+    fn property() {
+        let table = PropertyTable::new(NAMES);
+        assert_eq!(table.canonical("gc"), Some("General_Category"));
+        assert_eq!(table.canonical("GC"), Some("General_Category"));
+        assert_eq!(
+            table.canonical("general category"),
+            Some("General_Category"));
+        assert_eq!(
+            table.canonical("General_Category"),
+            Some("General_Category"));
+        assert_eq!(table.canonical("isAlpha"), Some("Alphabetic"));
+        assert_eq!(table.canonical("alphabet"), None);
+        assert_eq!(table.canonical(""), None);
+    }
+
+    #[test]
+    fn value() {
+        let table = PropertyValueTable::new(VALUES);
+        let gc = "General_Category";
+        assert_eq!(table.canonical(gc, "Lu"), Some("Uppercase_Letter"));
+        assert_eq!(
+            table.canonical(gc, "uppercase-letter"),
+            Some("Uppercase_Letter"));
+        assert_eq!(table.canonical(gc, "L"), Some("Letter"));
+        assert_eq!(table.canonical(gc, "Greek"), None);
+        assert_eq!(table.canonical("Script", "is_Greek"), Some("Greek"));
+        // The property name must be canonical.
+        assert_eq!(table.canonical("sc", "Greek"), None);
+        assert_eq!(table.canonical("Block", "Greek"), None);
+    }
+}
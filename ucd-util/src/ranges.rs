@@ -0,0 +1,81 @@
+use std::cmp::Ordering;
+
+/// Returns true if and only if the given codepoint is in one of the given
+/// ranges.
+///
+/// The ranges must be inclusive, sorted and non-overlapping, which is the
+/// format of the range tables emitted by `ucd-generate`, e.g.,
+/// `&[(0x41, 0x5A), (0x61, 0x7A)]`. Both `u32` and `char` tables are
+/// supported.
+///
+/// This runs in time logarithmic in the number of ranges.
+pub fn find_in_ranges<T: Copy + Ord>(ranges: &[(T, T)], cp: T) -> bool {
+    ranges
+        .binary_search_by(|&(start, end)| compare(start, end, cp))
+        .is_ok()
+}
+
+/// Return the value associated with the range containing the given
+/// codepoint.
+///
+/// The ranges must be inclusive, sorted and non-overlapping, which is the
+/// format of the tables emitted by `ucd-generate` for enumeration and
+/// numeric properties, e.g., `&[(0x30, 0x39, 0), (0x41, 0x5A, 1)]`. If no
+/// range contains the given codepoint, then this returns `None`.
+///
+/// This runs in time logarithmic in the number of ranges.
+pub fn find_in_range_values<T: Copy + Ord, V: Copy>(
+    ranges: &[(T, T, V)],
+    cp: T,
+) -> Option<V> {
+    ranges
+        .binary_search_by(|&(start, end, _)| compare(start, end, cp))
+        .ok()
+        .map(|i| ranges[i].2)
+}
+
+/// Compare the inclusive range `start..=end` with the given codepoint, such
+/// that the range is `Equal` if it contains the codepoint.
+fn compare<T: Ord>(start: T, end: T, cp: T) -> Ordering {
+    if end < cp {
+        Ordering::Less
+    } else if start > cp {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_in_range_values, find_in_ranges};
+
+    #[test]
+    fn ranges() {
+        let ranges: &[(u32, u32)] =
+            &[(0x41, 0x5A), (0x61, 0x61), (0xC0, 0xD6)];
+        for &cp in &[0x41, 0x50, 0x5A, 0x61, 0xC0, 0xD6] {
+            assert!(find_in_ranges(ranges, cp), "{:X}", cp);
+        }
+        for &cp in &[0, 0x40, 0x5B, 0x60, 0x62, 0xD7, 0x10FFFF] {
+            assert!(!find_in_ranges(ranges, cp), "{:X}", cp);
+        }
+        assert!(!find_in_ranges(&[], 0x41));
+
+        let ranges: &[(char, char)] = &[('a', 'z')];
+        assert!(find_in_ranges(ranges, 'q'));
+        assert!(!find_in_ranges(ranges, 'Q'));
+    }
+
+    #[test]
+    fn range_values() {
+        let ranges: &[(u32, u32, u8)] =
+            &[(0x30, 0x39, 0), (0x41, 0x5A, 1), (0x61, 0x7A, 2)];
+        assert_eq!(find_in_range_values(ranges, 0x30), Some(0));
+        assert_eq!(find_in_range_values(ranges, 0x5A), Some(1));
+        assert_eq!(find_in_range_values(ranges, 0x70), Some(2));
+        assert_eq!(find_in_range_values(ranges, 0x40), None);
+        assert_eq!(find_in_range_values(ranges, 0x7B), None);
+        assert_eq!(find_in_range_values::<u32, u8>(&[], 0x41), None);
+    }
+}
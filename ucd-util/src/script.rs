@@ -0,0 +1,196 @@
+// This implementation should correspond to the resolved script sets
+// described in UTS #39, section 5.1.
+
+/// The resolved script set of a character or a string.
+///
+/// Scripts are identified by the names used in the caller's tables, e.g.,
+/// `Latin` or `Latn`. Augmented scripts are identified by their ISO 15924
+/// codes, i.e., `Hanb`, `Jpan` and `Kore`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ResolvedScriptSet<'a> {
+    /// Every script, which is the resolved script set of characters like
+    /// digits and punctuation that are used by all scripts.
+    All,
+    /// The given scripts, in sorted order and without duplicates.
+    Scripts(Vec<&'a str>),
+}
+
+impl<'a> ResolvedScriptSet<'a> {
+    /// Returns true if and only if this set contains no scripts.
+    ///
+    /// A string whose resolved script set is empty is mixed-script.
+    pub fn is_empty(&self) -> bool {
+        match *self {
+            ResolvedScriptSet::All => false,
+            ResolvedScriptSet::Scripts(ref scripts) => scripts.is_empty(),
+        }
+    }
+
+    /// Returns true if and only if this set contains the given script.
+    pub fn contains(&self, script: &str) -> bool {
+        match *self {
+            ResolvedScriptSet::All => true,
+            ResolvedScriptSet::Scripts(ref scripts) => {
+                scripts.binary_search(&script).is_ok()
+            }
+        }
+    }
+
+    /// Return the intersection of this set and the given set.
+    pub fn intersect(
+        &self,
+        other: &ResolvedScriptSet<'a>,
+    ) -> ResolvedScriptSet<'a> {
+        use self::ResolvedScriptSet::*;
+
+        match (self, other) {
+            (&All, set) | (set, &All) => set.clone(),
+            (&Scripts(ref a), &Scripts(ref b)) => Scripts(
+                a.iter().cloned().filter(|s| b.binary_search(s).is_ok())
+                    .collect()),
+        }
+    }
+}
+
+/// Return the resolved script set of the given character.
+///
+/// `script` should return the `Script` property value of a character and
+/// `script_extensions` should return its `Script_Extensions` property value
+/// if it is listed in `ScriptExtensions.txt`, or `None` otherwise. Both may
+/// use either long or short script names, but must be consistent.
+///
+/// Characters whose scripts are only `Common` or `Inherited` have every
+/// script in their resolved script set. Otherwise, the set is augmented as
+/// described in UTS #39, e.g., a set containing `Han` also contains `Hanb`,
+/// `Jpan` and `Kore`.
+pub fn resolved_script_set<'a, F, G>(
+    c: char,
+    script: F,
+    script_extensions: G,
+) -> ResolvedScriptSet<'a>
+where F: Fn(char) -> &'a str,
+      G: Fn(char) -> Option<&'a [&'a str]>
+{
+    let mut scripts: Vec<&'a str> = match script_extensions(c) {
+        Some(scx) => scx.to_vec(),
+        None => vec![script(c)],
+    };
+    let is_any = |s: &str| match s {
+        "Common" | "Zyyy" | "Inherited" | "Zinh" => true,
+        _ => false,
+    };
+    if scripts.iter().all(|&s| is_any(s)) {
+        return ResolvedScriptSet::All;
+    }
+    let mut augmented = vec![];
+    for &s in &scripts {
+        augmented.extend_from_slice(match s {
+            "Han" | "Hani" => &["Hanb", "Jpan", "Kore"],
+            "Hiragana" | "Hira" | "Katakana" | "Kana" => &["Jpan"],
+            "Hangul" | "Hang" => &["Kore"],
+            "Bopomofo" | "Bopo" => &["Hanb"],
+            _ => &[],
+        });
+    }
+    scripts.extend(augmented);
+    scripts.sort();
+    scripts.dedup();
+    ResolvedScriptSet::Scripts(scripts)
+}
+
+/// Return the resolved script set of the given string.
+///
+/// This is the intersection of the resolved script sets of every character
+/// in the string, as computed by `resolved_script_set`. If it is empty, then
+/// the string is mixed-script. The resolved script set of the empty string
+/// is `All`.
+pub fn resolved_script_set_of_str<'a, F, G>(
+    s: &str,
+    script: F,
+    script_extensions: G,
+) -> ResolvedScriptSet<'a>
+where F: Fn(char) -> &'a str,
+      G: Fn(char) -> Option<&'a [&'a str]>
+{
+    let mut set = ResolvedScriptSet::All;
+    for c in s.chars() {
+        let resolved = resolved_script_set(c, &script, &script_extensions);
+        set = set.intersect(&resolved);
+        if set.is_empty() {
+            break;
+        }
+    }
+    set
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResolvedScriptSet::{self, *};
+    use super::{resolved_script_set, resolved_script_set_of_str};
+
+    // Small excerpts of Scripts.txt and ScriptExtensions.txt.
+    fn script(c: char) -> &'static str {
+        match c {
+            'a'..='z' | 'A'..='Z' => "Latin",
+            '\u{0391}'..='\u{03C9}' => "Greek",
+            '\u{0430}'..='\u{044F}' => "Cyrillic",
+            '\u{0301}' => "Inherited",
+            '\u{3041}'..='\u{3096}' => "Hiragana",
+            '\u{30A1}'..='\u{30FA}' => "Katakana",
+            '\u{4E00}'..='\u{9FFF}' => "Han",
+            '\u{AC00}'..='\u{D7A3}' => "Hangul",
+            _ => "Common",
+        }
+    }
+
+    fn script_extensions(c: char) -> Option<&'static [&'static str]> {
+        match c {
+            '\u{30FC}' => Some(&["Hiragana", "Katakana"]),
+            '\u{0483}' => Some(&["Cyrillic", "Old_Permic"]),
+            _ => None,
+        }
+    }
+
+    fn char_set(c: char) -> ResolvedScriptSet<'static> {
+        resolved_script_set(c, script, script_extensions)
+    }
+
+    fn str_set(s: &str) -> ResolvedScriptSet<'static> {
+        resolved_script_set_of_str(s, script, script_extensions)
+    }
+
+    #[test]
+    fn chars() {
+        assert_eq!(char_set('a'), Scripts(vec!["Latin"]));
+        assert_eq!(char_set('1'), All);
+        assert_eq!(char_set('\u{0301}'), All);
+        assert_eq!(
+            char_set('\u{4E00}'),
+            Scripts(vec!["Han", "Hanb", "Jpan", "Kore"]));
+        assert_eq!(
+            char_set('\u{30FC}'),
+            Scripts(vec!["Hiragana", "Jpan", "Katakana"]));
+        assert_eq!(
+            char_set('\u{0483}'),
+            Scripts(vec!["Cyrillic", "Old_Permic"]));
+    }
+
+    #[test]
+    fn strings() {
+        assert_eq!(str_set("Circle"), Scripts(vec!["Latin"]));
+        assert_eq!(str_set("1.5 a\u{0301}"), Scripts(vec!["Latin"]));
+        assert_eq!(str_set("123"), All);
+        assert_eq!(str_set(""), All);
+        // LATIN C, CYRILLIC i, GREEK r.
+        assert!(str_set("C\u{0456}r\u{03B1}").is_empty());
+        assert!(str_set("p\u{0430}ypal").is_empty());
+        // Japanese text mixes Han, Hiragana and Katakana.
+        let ja = str_set("\u{4E00}\u{3042}\u{30A2}\u{30FC}");
+        assert_eq!(ja, Scripts(vec!["Jpan"]));
+        assert!(ja.contains("Jpan"));
+        assert!(!ja.contains("Han"));
+        // Korean text mixes Han and Hangul.
+        assert_eq!(str_set("\u{4E00}\u{AC00}"), Scripts(vec!["Kore"]));
+        assert!(str_set("\u{AC00}\u{3042}").is_empty());
+    }
+}
@@ -0,0 +1,311 @@
+use normalization::{canonical_reorder, full_canonical_decomposition};
+use script::{
+    ResolvedScriptSet, resolved_script_set, resolved_script_set_of_str,
+};
+
+// This implementation should correspond to the security mechanisms
+// described in UTS #39.
+
+/// Append the skeleton of the given string to `out`.
+///
+/// The skeleton is `NFD(map(NFD(s)))`, where `map` replaces every character
+/// with its prototype in `confusables`. Two strings are confusable if and
+/// only if their skeletons are equal.
+///
+/// `confusables` should be the mappings listed in `confusables.txt`, sorted
+/// by character, e.g., `&[('\u{0430}', &['a']), ...]`. `mapping` and `ccc`
+/// are the canonical decomposition mapping and combining class lookups used
+/// by `full_canonical_decomposition` and `canonical_reorder`.
+///
+/// This implements the `skeleton` function defined in UTS #39, section 4.
+pub fn skeleton<'a, F, G>(
+    s: &str,
+    confusables: &[(char, &[char])],
+    mapping: F,
+    ccc: G,
+    out: &mut String,
+)
+where F: Fn(char) -> Option<&'a [char]>,
+      G: Fn(char) -> u8
+{
+    let nfd = |chars: &[char]| {
+        let mut decomposed = vec![];
+        for &c in chars {
+            full_canonical_decomposition(c, &mapping, &mut decomposed);
+        }
+        canonical_reorder(&mut decomposed, &ccc);
+        decomposed
+    };
+    let mut mapped = vec![];
+    for c in nfd(&s.chars().collect::<Vec<char>>()) {
+        match confusables.binary_search_by_key(&c, |&(k, _)| k) {
+            Ok(i) => mapped.extend_from_slice(confusables[i].1),
+            Err(_) => mapped.push(c),
+        }
+    }
+    out.extend(nfd(&mapped));
+}
+
+/// The restriction level of an identifier, as defined in UTS #39, section
+/// 5.2.
+///
+/// Levels are ordered from the most restrictive to the least restrictive,
+/// so that, e.g., `AsciiOnly < SingleScript`.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum RestrictionLevel {
+    /// Every character is in the ASCII range.
+    AsciiOnly,
+    /// The string has a non-empty resolved script set.
+    SingleScript,
+    /// The string is covered by Latin, Han, Hiragana and Katakana; by
+    /// Latin, Han and Bopomofo; or by Latin, Han and Hangul.
+    HighlyRestrictive,
+    /// The string is covered by Latin and one other recommended script,
+    /// except for Cyrillic and Greek.
+    ModeratelyRestrictive,
+    /// The string uses arbitrary scripts, but only characters in the
+    /// identifier profile.
+    MinimallyRestrictive,
+    /// The string contains characters outside of the identifier profile.
+    Unrestricted,
+}
+
+/// The sets of scripts that a highly restrictive string must be covered by,
+/// with every script given by its long and short names.
+const HIGHLY_RESTRICTIVE: &'static [&'static [(&'static str, &'static str)]] =
+    &[
+        &[
+            ("Latin", "Latn"), ("Han", "Hani"), ("Hiragana", "Hira"),
+            ("Katakana", "Kana"),
+        ],
+        &[("Latin", "Latn"), ("Han", "Hani"), ("Bopomofo", "Bopo")],
+        &[("Latin", "Latn"), ("Han", "Hani"), ("Hangul", "Hang")],
+    ];
+
+/// The scripts that a moderately restrictive string must not combine with
+/// Latin.
+const NOT_MODERATELY_RESTRICTIVE: &'static [(&'static str, &'static str)] =
+    &[("Cyrillic", "Cyrl"), ("Greek", "Grek")];
+
+/// Returns true if and only if the given string is mixed-script, i.e., its
+/// resolved script set is empty.
+///
+/// `script` and `script_extensions` are the lookups used by
+/// `resolved_script_set`.
+pub fn is_mixed_script<'a, F, G>(
+    s: &str,
+    script: F,
+    script_extensions: G,
+) -> bool
+where F: Fn(char) -> &'a str,
+      G: Fn(char) -> Option<&'a [&'a str]>
+{
+    resolved_script_set_of_str(s, script, script_extensions).is_empty()
+}
+
+/// Return the restriction level of the given identifier.
+///
+/// `script` and `script_extensions` are the lookups used by
+/// `resolved_script_set`. `allowed` should return true if and only if a
+/// character is in the identifier profile, e.g., if its `Identifier_Status`
+/// in `IdentifierStatus.txt` is `Allowed`. `recommended` should return true
+/// if and only if a script is one of the recommended scripts listed in UAX
+/// #31, Table 5, and must recognize the same script names as `script`.
+///
+/// This implements the algorithm in UTS #39, section 5.2.
+pub fn restriction_level<'a, F, G, H, R>(
+    s: &str,
+    script: F,
+    script_extensions: G,
+    allowed: H,
+    recommended: R,
+) -> RestrictionLevel
+where F: Fn(char) -> &'a str,
+      G: Fn(char) -> Option<&'a [&'a str]>,
+      H: Fn(char) -> bool,
+      R: Fn(&str) -> bool
+{
+    if !s.chars().all(&allowed) {
+        return RestrictionLevel::Unrestricted;
+    }
+    if s.chars().all(|c| c <= '\u{7E}') {
+        return RestrictionLevel::AsciiOnly;
+    }
+    let sets: Vec<ResolvedScriptSet> = s
+        .chars()
+        .map(|c| resolved_script_set(c, &script, &script_extensions))
+        .collect();
+    let all = sets
+        .iter()
+        .fold(ResolvedScriptSet::All, |all, set| all.intersect(set));
+    if !all.is_empty() {
+        return RestrictionLevel::SingleScript;
+    }
+    let covered = |scripts: &[(&str, &str)]| {
+        sets.iter().all(|set| intersects(set, scripts))
+    };
+    if HIGHLY_RESTRICTIVE.iter().any(|&scripts| covered(scripts)) {
+        return RestrictionLevel::HighlyRestrictive;
+    }
+    // Every character that isn't Latin must share one other script.
+    let latin = &[("Latin", "Latn")];
+    let other = sets
+        .iter()
+        .filter(|set| !intersects(set, latin))
+        .fold(ResolvedScriptSet::All, |other, set| other.intersect(set));
+    if let ResolvedScriptSet::Scripts(ref scripts) = other {
+        let moderate = scripts.iter().any(|&script| {
+            recommended(script)
+            && !is_one_of(script, NOT_MODERATELY_RESTRICTIVE)
+        });
+        if moderate {
+            return RestrictionLevel::ModeratelyRestrictive;
+        }
+    }
+    RestrictionLevel::MinimallyRestrictive
+}
+
+/// Returns true if and only if the given set contains one of the given
+/// scripts.
+fn intersects(set: &ResolvedScriptSet, scripts: &[(&str, &str)]) -> bool {
+    match *set {
+        ResolvedScriptSet::All => true,
+        ResolvedScriptSet::Scripts(ref names) => {
+            names.iter().any(|name| is_one_of(name, scripts))
+        }
+    }
+}
+
+/// Returns true if and only if the given name is the long or short name of
+/// one of the given scripts.
+fn is_one_of(name: &str, scripts: &[(&str, &str)]) -> bool {
+    scripts.iter().any(|&(long, short)| name == long || name == short)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RestrictionLevel::*;
+    use super::{is_mixed_script, restriction_level, skeleton};
+
+    // Small excerpts of UnicodeData.txt and confusables.txt.
+    const MAPPINGS: &'static [(char, &'static [char])] = &[
+        ('\u{00E9}', &['e', '\u{0301}']),
+        ('\u{0439}', &['\u{0438}', '\u{0306}']),
+    ];
+
+    const CONFUSABLES: &'static [(char, &'static [char])] = &[
+        ('0', &['O']),
+        ('1', &['l']),
+        ('m', &['r', 'n']),
+        ('\u{0430}', &['a']),
+        ('\u{0435}', &['e']),
+        ('\u{0438}', &['u']),
+    ];
+
+    fn mapping(c: char) -> Option<&'static [char]> {
+        MAPPINGS
+            .binary_search_by_key(&c, |&(k, _)| k)
+            .ok()
+            .map(|i| MAPPINGS[i].1)
+    }
+
+    fn ccc(c: char) -> u8 {
+        match c {
+            '\u{0301}' => 230,
+            '\u{0306}' => 230,
+            _ => 0,
+        }
+    }
+
+    fn skel(s: &str) -> String {
+        let mut out = String::new();
+        skeleton(s, CONFUSABLES, mapping, ccc, &mut out);
+        out
+    }
+
+    #[test]
+    fn skeletons() {
+        assert_eq!(skel("paypal"), "paypal");
+        assert_eq!(skel("p\u{0430}yp\u{0430}l"), "paypal");
+        assert_eq!(skel("modern"), "rnodern");
+        assert_eq!(skel("rnodern"), "rnodern");
+        assert_eq!(skel("g00g1e"), "gOOgle");
+        assert_eq!(skel("\u{00E9}"), "e\u{0301}");
+        assert_eq!(skel("\u{0435}\u{0301}"), "e\u{0301}");
+        // The mapped string is decomposed before it is mapped.
+        assert_eq!(skel("\u{0439}"), "u\u{0306}");
+        assert_eq!(skel(""), "");
+    }
+
+    #[test]
+    fn appends() {
+        let mut out = "x".to_string();
+        skeleton("1", CONFUSABLES, mapping, ccc, &mut out);
+        assert_eq!(out, "xl");
+    }
+
+    // Small excerpts of Scripts.txt, ScriptExtensions.txt and
+    // IdentifierStatus.txt.
+    fn script(c: char) -> &'static str {
+        match c {
+            'a'..='z' | 'A'..='Z' => "Latin",
+            '\u{00E9}' => "Latin",
+            '\u{0391}'..='\u{03C9}' => "Greek",
+            '\u{0430}'..='\u{044F}' => "Cyrillic",
+            '\u{0531}'..='\u{0587}' => "Armenian",
+            '\u{05D0}'..='\u{05EA}' => "Hebrew",
+            '\u{3041}'..='\u{3096}' => "Hiragana",
+            '\u{3105}'..='\u{312F}' => "Bopomofo",
+            '\u{4E00}'..='\u{9FFF}' => "Han",
+            '\u{AC00}'..='\u{D7A3}' => "Hangul",
+            _ => "Common",
+        }
+    }
+
+    fn script_extensions(c: char) -> Option<&'static [&'static str]> {
+        match c {
+            '\u{3001}' => Some(&["Bopomofo", "Han", "Hangul", "Hiragana"]),
+            _ => None,
+        }
+    }
+
+    fn allowed(c: char) -> bool {
+        c != '\u{2044}' && c != '\u{05F3}'
+    }
+
+    fn recommended(script: &str) -> bool {
+        script != "Hebrew"
+    }
+
+    #[test]
+    fn mixed_script() {
+        let mixed = |s| is_mixed_script(s, script, script_extensions);
+        assert!(!mixed("paypal"));
+        assert!(mixed("p\u{0430}ypal"));
+        assert!(!mixed("\u{4E00}\u{3001}\u{3042}"));
+        assert!(mixed("\u{4E00}\u{AC00}\u{3042}"));
+    }
+
+    #[test]
+    fn restriction_levels() {
+        let level = |s| {
+            restriction_level(
+                s, script, script_extensions, allowed, recommended)
+        };
+        assert_eq!(level(""), AsciiOnly);
+        assert_eq!(level("foo_1"), AsciiOnly);
+        assert_eq!(level("caf\u{00E9}"), SingleScript);
+        assert_eq!(level("\u{03B1}\u{03B2}"), SingleScript);
+        assert_eq!(level("a\u{4E00}\u{3042}"), HighlyRestrictive);
+        assert_eq!(level("a\u{4E00}\u{3105}"), HighlyRestrictive);
+        assert_eq!(level("a\u{AC00}\u{3001}"), HighlyRestrictive);
+        assert_eq!(level("a\u{0531}"), ModeratelyRestrictive);
+        assert_eq!(level("a\u{03B1}"), MinimallyRestrictive);
+        assert_eq!(level("a\u{0430}"), MinimallyRestrictive);
+        assert_eq!(level("a\u{05D0}"), MinimallyRestrictive);
+        assert_eq!(level("a\u{0531}\u{05D0}"), MinimallyRestrictive);
+        assert_eq!(level("a\u{2044}"), Unrestricted);
+        assert!(AsciiOnly < SingleScript);
+        assert!(MinimallyRestrictive < Unrestricted);
+    }
+}
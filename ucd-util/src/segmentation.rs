@@ -0,0 +1,875 @@
+// This implementation should correspond to the algorithms described in
+// UAX #29. Like normalization, the properties these algorithms need are not
+// included in this crate, so every function here is parameterized over
+// lookup functions that are typically backed by tables generated by
+// `ucd-generate`.
+
+/// The values of the `Grapheme_Cluster_Break` property.
+///
+/// These values are defined in UAX #29, Table 2. Characters missing from
+/// `GraphemeBreakProperty.txt` have the value `Other`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GraphemeClusterBreak {
+    /// `CR`, i.e., U+000D CARRIAGE RETURN.
+    CR,
+    /// `LF`, i.e., U+000A LINE FEED.
+    LF,
+    /// `Control`, e.g., most other control characters.
+    Control,
+    /// `Extend`, e.g., nonspacing marks.
+    Extend,
+    /// `ZWJ`, i.e., U+200D ZERO WIDTH JOINER.
+    ZWJ,
+    /// `Regional_Indicator`, i.e., U+1F1E6..U+1F1FF.
+    RegionalIndicator,
+    /// `Prepend`, e.g., Arabic number signs.
+    Prepend,
+    /// `SpacingMark`, e.g., most spacing combining marks.
+    SpacingMark,
+    /// `L`, i.e., Hangul leading consonants.
+    L,
+    /// `V`, i.e., Hangul vowels.
+    V,
+    /// `T`, i.e., Hangul trailing consonants.
+    T,
+    /// `LV`, i.e., Hangul syllables without a trailing consonant.
+    LV,
+    /// `LVT`, i.e., Hangul syllables with a trailing consonant.
+    LVT,
+    /// `Other`, i.e., every other character.
+    Other,
+}
+
+/// The definition of grapheme clusters to use when segmenting text.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GraphemeClusterMode {
+    /// Extended grapheme clusters, which are recommended for most purposes.
+    Extended,
+    /// Legacy grapheme clusters, which don't keep spacing marks with the
+    /// preceding character or prepended characters with the following
+    /// character. Regular expression engines use them for compatibility.
+    Legacy,
+}
+
+/// Return the grapheme cluster boundaries in the given text.
+///
+/// `mode` determines whether extended or legacy grapheme clusters are
+/// found. `gcb` should return the `Grapheme_Cluster_Break` property value of a
+/// character and `ext_pict` should return true for characters with the
+/// `Extended_Pictographic` property, as specified in `emoji-data.txt`.
+///
+/// The boundaries are returned as byte offsets in increasing order. Unless
+/// the text is empty, the first boundary is always `0` and the last is
+/// always `text.len()`, so every pair of adjacent boundaries delimits one
+/// grapheme cluster.
+///
+/// This implements the rules GB1 through GB999 described in UAX #29, except
+/// for GB9c, which requires the `Indic_Conjunct_Break` property. In legacy
+/// mode, GB9a and GB9b are not applied either.
+pub fn grapheme_boundaries<F, G>(
+    text: &str,
+    mode: GraphemeClusterMode,
+    gcb: F,
+    ext_pict: G,
+) -> Vec<usize>
+where F: Fn(char) -> GraphemeClusterBreak,
+      G: Fn(char) -> bool
+{
+    let boundaries =
+        grapheme_boundaries_chars(text.chars(), mode, gcb, ext_pict);
+    byte_offsets(text, boundaries)
+}
+
+/// Return the grapheme cluster boundaries in the given sequence of
+/// characters.
+///
+/// This is like `grapheme_boundaries`, except the boundaries are returned
+/// as indices into the sequence of characters.
+pub fn grapheme_boundaries_chars<I, F, G>(
+    chars: I,
+    mode: GraphemeClusterMode,
+    gcb: F,
+    ext_pict: G,
+) -> Vec<usize>
+where I: IntoIterator<Item=char>,
+      F: Fn(char) -> GraphemeClusterBreak,
+      G: Fn(char) -> bool
+{
+    let mut state = GraphemeState {
+        legacy: mode == GraphemeClusterMode::Legacy,
+        ..GraphemeState::default()
+    };
+    let mut boundaries = vec![];
+    let mut len = 0;
+    for (i, c) in chars.into_iter().enumerate() {
+        if state.next(gcb(c), ext_pict(c)) {
+            boundaries.push(i);
+        }
+        len = i + 1;
+    }
+    if len > 0 {
+        // GB2: Any ÷ eot
+        boundaries.push(len);
+    }
+    boundaries
+}
+
+/// Convert boundaries given as indices into the characters of the given text
+/// into byte offsets.
+fn byte_offsets(text: &str, boundaries: Vec<usize>) -> Vec<usize> {
+    let offsets: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+    boundaries
+        .into_iter()
+        .map(|i| offsets.get(i).map_or(text.len(), |&offset| offset))
+        .collect()
+}
+
+/// The state required to determine whether there is a grapheme cluster
+/// boundary before the next character.
+#[derive(Debug, Default)]
+struct GraphemeState {
+    /// The property value of the previous character, if there is one.
+    prev: Option<GraphemeClusterBreak>,
+    /// Whether the text so far ends with `ExtPict Extend*`.
+    pict: bool,
+    /// Whether the text so far ends with `ExtPict Extend* ZWJ`.
+    pict_zwj: bool,
+    /// Whether the text so far ends with an odd number of regional
+    /// indicators.
+    ri_odd: bool,
+    /// Whether legacy grapheme clusters are being found.
+    legacy: bool,
+}
+
+impl GraphemeState {
+    /// Advance the state by one character, and return true if and only if
+    /// there is a boundary before that character.
+    fn next(&mut self, cur: GraphemeClusterBreak, ext_pict: bool) -> bool {
+        use self::GraphemeClusterBreak::*;
+
+        let is_break = match self.prev {
+            // GB1: sot ÷ Any
+            None => true,
+            Some(prev) => match (prev, cur) {
+                // GB3
+                (CR, LF) => false,
+                // GB4, GB5
+                (Control, _) | (CR, _) | (LF, _) => true,
+                (_, Control) | (_, CR) | (_, LF) => true,
+                // GB6, GB7, GB8
+                (L, L) | (L, V) | (L, LV) | (L, LVT) => false,
+                (LV, V) | (LV, T) | (V, V) | (V, T) => false,
+                (LVT, T) | (T, T) => false,
+                // GB9
+                (_, Extend) | (_, ZWJ) => false,
+                // GB9a, GB9b
+                (_, SpacingMark) if !self.legacy => false,
+                (Prepend, _) if !self.legacy => false,
+                // GB11
+                (ZWJ, _) if self.pict_zwj && ext_pict => false,
+                // GB12, GB13
+                (RegionalIndicator, RegionalIndicator) => !self.ri_odd,
+                // GB999
+                _ => true,
+            },
+        };
+
+        self.pict_zwj = self.pict && cur == ZWJ;
+        self.pict = ext_pict || (self.pict && cur == Extend);
+        self.ri_odd = cur == RegionalIndicator && !self.ri_odd;
+        self.prev = Some(cur);
+        is_break
+    }
+}
+
+/// The values of the `Word_Break` property.
+///
+/// These values are defined in UAX #29, Table 3. Characters missing from
+/// `WordBreakProperty.txt` have the value `Other`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WordBreak {
+    /// `CR`, i.e., U+000D CARRIAGE RETURN.
+    CR,
+    /// `LF`, i.e., U+000A LINE FEED.
+    LF,
+    /// `Newline`, e.g., U+2028 LINE SEPARATOR.
+    Newline,
+    /// `Extend`, e.g., nonspacing marks.
+    Extend,
+    /// `ZWJ`, i.e., U+200D ZERO WIDTH JOINER.
+    ZWJ,
+    /// `Regional_Indicator`, i.e., U+1F1E6..U+1F1FF.
+    RegionalIndicator,
+    /// `Format`, e.g., U+00AD SOFT HYPHEN.
+    Format,
+    /// `Katakana`, e.g., Katakana letters.
+    Katakana,
+    /// `Hebrew_Letter`, i.e., Hebrew letters.
+    HebrewLetter,
+    /// `ALetter`, e.g., most other letters.
+    ALetter,
+    /// `Single_Quote`, i.e., U+0027 APOSTROPHE.
+    SingleQuote,
+    /// `Double_Quote`, i.e., U+0022 QUOTATION MARK.
+    DoubleQuote,
+    /// `MidNumLet`, e.g., U+002E FULL STOP.
+    MidNumLet,
+    /// `MidLetter`, e.g., U+003A COLON.
+    MidLetter,
+    /// `MidNum`, e.g., U+002C COMMA.
+    MidNum,
+    /// `Numeric`, e.g., decimal digits.
+    Numeric,
+    /// `ExtendNumLet`, e.g., U+005F LOW LINE.
+    ExtendNumLet,
+    /// `WSegSpace`, e.g., U+0020 SPACE.
+    WSegSpace,
+    /// `Other`, i.e., every other character.
+    Other,
+}
+
+/// Return the word boundaries in the given text.
+///
+/// `wb` should return the `Word_Break` property value of a character and
+/// `ext_pict` should return true for characters with the
+/// `Extended_Pictographic` property, as specified in `emoji-data.txt`.
+///
+/// The boundaries are returned as byte offsets in the same way as
+/// `grapheme_boundaries`. Note that the text between two boundaries is not
+/// necessarily a word, e.g., it may be whitespace or punctuation.
+///
+/// This implements the rules WB1 through WB999 described in UAX #29.
+pub fn word_boundaries<F, G>(text: &str, wb: F, ext_pict: G) -> Vec<usize>
+where F: Fn(char) -> WordBreak,
+      G: Fn(char) -> bool
+{
+    let boundaries = word_boundaries_chars(text.chars(), wb, ext_pict);
+    byte_offsets(text, boundaries)
+}
+
+/// Return the word boundaries in the given sequence of characters.
+///
+/// This is like `word_boundaries`, except the boundaries are returned as
+/// indices into the sequence of characters.
+pub fn word_boundaries_chars<I, F, G>(
+    chars: I,
+    wb: F,
+    ext_pict: G,
+) -> Vec<usize>
+where I: IntoIterator<Item=char>,
+      F: Fn(char) -> WordBreak,
+      G: Fn(char) -> bool
+{
+    use self::WordBreak::*;
+
+    // Unlike grapheme clusters, the word boundary rules look ahead and
+    // behind by more than one character, so the property values of every
+    // character are looked up first.
+    let props: Vec<(WordBreak, bool)> = chars
+        .into_iter()
+        .map(|c| (wb(c), ext_pict(c)))
+        .collect();
+    // The indices of the characters that aren't ignored by WB4, i.e., the
+    // characters that the rules after WB4 apply to.
+    let mut units: Vec<usize> = vec![];
+    let mut boundaries = vec![];
+    for (i, &(cur, pict)) in props.iter().enumerate() {
+        let prev = match i.checked_sub(1) {
+            // WB1: sot ÷ Any
+            None => {
+                units.push(i);
+                boundaries.push(i);
+                continue;
+            }
+            Some(j) => props[j].0,
+        };
+        // WB4: X (Extend | Format | ZWJ)* → X
+        match (prev, cur) {
+            (CR, _) | (LF, _) | (Newline, _) => {}
+            (_, Extend) | (_, Format) | (_, ZWJ) => continue,
+            _ => {}
+        }
+        units.push(i);
+        let is_break = match (prev, cur) {
+            // WB3
+            (CR, LF) => false,
+            // WB3a, WB3b
+            (Newline, _) | (CR, _) | (LF, _) => true,
+            (_, Newline) | (_, CR) | (_, LF) => true,
+            // WB3c
+            (ZWJ, _) if pict => false,
+            // WB3d
+            (WSegSpace, WSegSpace) => false,
+            _ => is_word_break(&props, &units),
+        };
+        if is_break {
+            boundaries.push(i);
+        }
+    }
+    if !props.is_empty() {
+        // WB2: Any ÷ eot
+        boundaries.push(props.len());
+    }
+    boundaries
+}
+
+/// Apply the rules WB5 through WB999 to the last character in `units`,
+/// which are indices into `props`, and return true if and only if there is
+/// a boundary before it.
+fn is_word_break(props: &[(WordBreak, bool)], units: &[usize]) -> bool {
+    use self::WordBreak::*;
+
+    fn is_ahletter(wb: WordBreak) -> bool {
+        wb == ALetter || wb == HebrewLetter
+    }
+    fn is_midnumletq(wb: WordBreak) -> bool {
+        wb == MidNumLet || wb == SingleQuote
+    }
+
+    let k = units.len() - 1;
+    let cur = props[units[k]].0;
+    let prev = props[units[k - 1]].0;
+    let before = if k >= 2 { Some(props[units[k - 2]].0) } else { None };
+    // The property value of the next character that isn't ignored by WB4.
+    let after = props[units[k] + 1..]
+        .iter()
+        .map(|&(wb, _)| wb)
+        .find(|&wb| wb != Extend && wb != Format && wb != ZWJ);
+
+    // WB5
+    if is_ahletter(prev) && is_ahletter(cur) {
+        return false;
+    }
+    // WB6, WB7
+    if (cur == MidLetter || is_midnumletq(cur))
+        && is_ahletter(prev)
+        && after.map_or(false, is_ahletter)
+    {
+        return false;
+    }
+    if is_ahletter(cur)
+        && (prev == MidLetter || is_midnumletq(prev))
+        && before.map_or(false, is_ahletter)
+    {
+        return false;
+    }
+    // WB7a, WB7b, WB7c
+    if prev == HebrewLetter && cur == SingleQuote {
+        return false;
+    }
+    if prev == HebrewLetter
+        && cur == DoubleQuote
+        && after == Some(HebrewLetter)
+    {
+        return false;
+    }
+    if before == Some(HebrewLetter)
+        && prev == DoubleQuote
+        && cur == HebrewLetter
+    {
+        return false;
+    }
+    // WB8, WB9, WB10
+    if (prev == Numeric || is_ahletter(prev))
+        && (cur == Numeric || is_ahletter(cur))
+    {
+        return false;
+    }
+    // WB11, WB12
+    if cur == Numeric
+        && (prev == MidNum || is_midnumletq(prev))
+        && before == Some(Numeric)
+    {
+        return false;
+    }
+    if prev == Numeric
+        && (cur == MidNum || is_midnumletq(cur))
+        && after == Some(Numeric)
+    {
+        return false;
+    }
+    // WB13
+    if prev == Katakana && cur == Katakana {
+        return false;
+    }
+    // WB13a, WB13b
+    if cur == ExtendNumLet
+        && (is_ahletter(prev)
+            || prev == Numeric
+            || prev == Katakana
+            || prev == ExtendNumLet)
+    {
+        return false;
+    }
+    if prev == ExtendNumLet
+        && (is_ahletter(cur) || cur == Numeric || cur == Katakana)
+    {
+        return false;
+    }
+    // WB15, WB16
+    if prev == RegionalIndicator && cur == RegionalIndicator {
+        let count = units[..k]
+            .iter()
+            .rev()
+            .take_while(|&&u| props[u].0 == RegionalIndicator)
+            .count();
+        return count % 2 == 0;
+    }
+    // WB999
+    true
+}
+
+/// The values of the `Sentence_Break` property.
+///
+/// These values are defined in UAX #29, Table 4. Characters missing from
+/// `SentenceBreakProperty.txt` have the value `Other`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SentenceBreak {
+    /// `CR`, i.e., U+000D CARRIAGE RETURN.
+    CR,
+    /// `LF`, i.e., U+000A LINE FEED.
+    LF,
+    /// `Extend`, e.g., nonspacing marks.
+    Extend,
+    /// `Sep`, e.g., U+2029 PARAGRAPH SEPARATOR.
+    Sep,
+    /// `Format`, e.g., U+00AD SOFT HYPHEN.
+    Format,
+    /// `Sp`, e.g., U+0020 SPACE.
+    Sp,
+    /// `Lower`, e.g., lowercase letters.
+    Lower,
+    /// `Upper`, e.g., uppercase letters.
+    Upper,
+    /// `OLetter`, e.g., letters without case.
+    OLetter,
+    /// `Numeric`, e.g., decimal digits.
+    Numeric,
+    /// `ATerm`, e.g., U+002E FULL STOP.
+    ATerm,
+    /// `SContinue`, e.g., U+002C COMMA.
+    SContinue,
+    /// `STerm`, e.g., U+0021 EXCLAMATION MARK.
+    STerm,
+    /// `Close`, e.g., closing brackets and quotation marks.
+    Close,
+    /// `Other`, i.e., every other character.
+    Other,
+}
+
+/// Return the sentence boundaries in the given text.
+///
+/// `sb` should return the `Sentence_Break` property value of a character.
+///
+/// The boundaries are returned as byte offsets in the same way as
+/// `grapheme_boundaries`.
+///
+/// This implements the rules SB1 through SB998 described in UAX #29.
+pub fn sentence_boundaries<F>(text: &str, sb: F) -> Vec<usize>
+where F: Fn(char) -> SentenceBreak
+{
+    let boundaries = sentence_boundaries_chars(text.chars(), sb);
+    byte_offsets(text, boundaries)
+}
+
+/// Return the sentence boundaries in the given sequence of characters.
+///
+/// This is like `sentence_boundaries`, except the boundaries are returned
+/// as indices into the sequence of characters.
+pub fn sentence_boundaries_chars<I, F>(chars: I, sb: F) -> Vec<usize>
+where I: IntoIterator<Item=char>,
+      F: Fn(char) -> SentenceBreak
+{
+    use self::SentenceBreak::*;
+
+    let props: Vec<SentenceBreak> = chars.into_iter().map(sb).collect();
+    // SB5: X (Extend | Format)* → X
+    //
+    // The indices of the characters that aren't ignored by SB5, i.e., the
+    // characters that the rules after SB5 apply to.
+    let units: Vec<usize> = (0..props.len())
+        .filter(|&i| {
+            i == 0
+            || is_para_sep(props[i - 1])
+            || (props[i] != Extend && props[i] != Format)
+        })
+        .collect();
+    let unit_props: Vec<SentenceBreak> =
+        units.iter().map(|&i| props[i]).collect();
+
+    let mut boundaries = vec![];
+    for (k, &i) in units.iter().enumerate() {
+        if k == 0 {
+            // SB1: sot ÷ Any
+            boundaries.push(i);
+            continue;
+        }
+        let is_break = match (props[i - 1], props[i]) {
+            // SB3
+            (CR, LF) => false,
+            // SB4
+            (prev, _) if is_para_sep(prev) => true,
+            _ => is_sentence_break(&unit_props, k),
+        };
+        if is_break {
+            boundaries.push(i);
+        }
+    }
+    if !props.is_empty() {
+        // SB2: Any ÷ eot
+        boundaries.push(props.len());
+    }
+    boundaries
+}
+
+/// Apply the rules SB6 through SB998 to the character at index `k` of
+/// `props`, which excludes the characters ignored by SB5, and return true
+/// if and only if there is a boundary before it.
+fn is_sentence_break(props: &[SentenceBreak], k: usize) -> bool {
+    use self::SentenceBreak::*;
+
+    let cur = props[k];
+    let prev = props[k - 1];
+    // SB6, SB7
+    if prev == ATerm && cur == Numeric {
+        return false;
+    }
+    if k >= 2
+        && (props[k - 2] == Upper || props[k - 2] == Lower)
+        && prev == ATerm
+        && cur == Upper
+    {
+        return false;
+    }
+
+    // Find the longest match of `SATerm Close* Sp*` that ends just before
+    // the current character. The rules after SB8 only apply if there is
+    // one.
+    let mut i = k;
+    while i > 0 && props[i - 1] == Sp {
+        i -= 1;
+    }
+    let has_sp = i < k;
+    while i > 0 && props[i - 1] == Close {
+        i -= 1;
+    }
+    let term = match i.checked_sub(1).map(|i| props[i]) {
+        Some(ATerm) => ATerm,
+        Some(STerm) => STerm,
+        // SB998
+        _ => return false,
+    };
+
+    // SB8
+    if term == ATerm {
+        let next = props[k..].iter().find(|&&sb| match sb {
+            OLetter | Upper | Lower | ATerm | STerm => true,
+            sb => is_para_sep(sb),
+        });
+        if next == Some(&Lower) {
+            return false;
+        }
+    }
+    // SB8a
+    if cur == SContinue || cur == ATerm || cur == STerm {
+        return false;
+    }
+    // SB9
+    if !has_sp && (cur == Close || cur == Sp || is_para_sep(cur)) {
+        return false;
+    }
+    // SB10
+    if cur == Sp || is_para_sep(cur) {
+        return false;
+    }
+    // SB11
+    true
+}
+
+/// Returns true if and only if the given value is one of the paragraph
+/// separators `Sep`, `CR` or `LF`.
+fn is_para_sep(sb: SentenceBreak) -> bool {
+    use self::SentenceBreak::*;
+
+    sb == Sep || sb == CR || sb == LF
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GraphemeClusterBreak::{self, *};
+    use super::{GraphemeClusterMode, SentenceBreak, WordBreak};
+    use super::{
+        grapheme_boundaries, grapheme_boundaries_chars, sentence_boundaries,
+        sentence_boundaries_chars, word_boundaries, word_boundaries_chars,
+    };
+
+    // A small excerpt of GraphemeBreakProperty.txt.
+    fn gcb(c: char) -> GraphemeClusterBreak {
+        match c {
+            '\r' => CR,
+            '\n' => LF,
+            '\u{0000}'..='\u{0009}' | '\u{000B}'..='\u{000C}' => Control,
+            '\u{0300}'..='\u{036F}' | '\u{1F3FB}'..='\u{1F3FF}' => Extend,
+            '\u{200D}' => ZWJ,
+            '\u{1F1E6}'..='\u{1F1FF}' => RegionalIndicator,
+            '\u{0600}'..='\u{0605}' => Prepend,
+            '\u{0903}' => SpacingMark,
+            '\u{1100}'..='\u{115F}' => L,
+            '\u{1160}'..='\u{11A7}' => V,
+            '\u{11A8}'..='\u{11FF}' => T,
+            '\u{AC00}' | '\u{D4CC}' => LV,
+            '\u{AC01}' | '\u{D4DB}' => LVT,
+            _ => Other,
+        }
+    }
+
+    // A small excerpt of emoji-data.txt.
+    fn ext_pict(c: char) -> bool {
+        match c {
+            '\u{00A9}' | '\u{1F468}'..='\u{1F469}' | '\u{2764}' => true,
+            _ => false,
+        }
+    }
+
+    fn clusters(text: &str) -> Vec<&str> {
+        clusters_mode(text, GraphemeClusterMode::Extended)
+    }
+
+    fn clusters_mode(text: &str, mode: GraphemeClusterMode) -> Vec<&str> {
+        let b = grapheme_boundaries(text, mode, gcb, ext_pict);
+        b.windows(2).map(|w| &text[w[0]..w[1]]).collect()
+    }
+
+    #[test]
+    fn basic() {
+        assert_eq!(clusters("abc"), vec!["a", "b", "c"]);
+        assert_eq!(
+            clusters("a\r\nb\n\r"),
+            vec!["a", "\r\n", "b", "\n", "\r"]);
+        assert_eq!(
+            clusters("a\u{0308}\u{0903}b"),
+            vec!["a\u{0308}\u{0903}", "b"]);
+        assert_eq!(
+            clusters("\u{0600}1\u{0600}\n"),
+            vec!["\u{0600}1", "\u{0600}", "\n"]);
+        assert_eq!(clusters("\u{0001}\u{0308}"), vec!["\u{0001}", "\u{0308}"]);
+        let extended = GraphemeClusterMode::Extended;
+        assert!(grapheme_boundaries("", extended, gcb, ext_pict).is_empty());
+    }
+
+    #[test]
+    fn legacy() {
+        let legacy = |text| clusters_mode(text, GraphemeClusterMode::Legacy);
+        assert_eq!(
+            legacy("a\u{0308}\u{0903}b"),
+            vec!["a\u{0308}", "\u{0903}", "b"]);
+        assert_eq!(legacy("\u{0600}1"), vec!["\u{0600}", "1"]);
+        // Every other rule still applies.
+        assert_eq!(legacy("\r\n"), vec!["\r\n"]);
+        assert_eq!(
+            legacy("\u{1111}\u{1171}\u{11B6}"),
+            vec!["\u{1111}\u{1171}\u{11B6}"]);
+        assert_eq!(
+            legacy("\u{1F468}\u{200D}\u{1F469}\u{1F1FA}\u{1F1F8}"),
+            vec!["\u{1F468}\u{200D}\u{1F469}", "\u{1F1FA}\u{1F1F8}"]);
+    }
+
+    #[test]
+    fn hangul() {
+        let text = "\u{1111}\u{1171}\u{11B6}\u{D4DB}\u{11B6}\u{AC00}\u{1161}";
+        assert_eq!(
+            clusters(text),
+            vec!["\u{1111}\u{1171}\u{11B6}", "\u{D4DB}\u{11B6}",
+                 "\u{AC00}\u{1161}"]);
+        assert_eq!(
+            clusters("\u{11B6}\u{1111}\u{D4DB}\u{1171}"),
+            vec!["\u{11B6}", "\u{1111}\u{D4DB}", "\u{1171}"]);
+    }
+
+    #[test]
+    fn emoji() {
+        // MAN ZWJ WOMAN, with skin tone modifiers.
+        assert_eq!(
+            clusters("\u{1F468}\u{1F3FB}\u{200D}\u{1F469}\u{1F3FF}a"),
+            vec!["\u{1F468}\u{1F3FB}\u{200D}\u{1F469}\u{1F3FF}", "a"]);
+        // A ZWJ only joins pictographs.
+        assert_eq!(
+            clusters("a\u{200D}\u{1F469}\u{1F468}\u{200D}b"),
+            vec!["a\u{200D}", "\u{1F469}", "\u{1F468}\u{200D}", "b"]);
+    }
+
+    #[test]
+    fn regional_indicators() {
+        // Flags are pairs of regional indicators.
+        assert_eq!(
+            clusters("\u{1F1FA}\u{1F1F8}\u{1F1EB}\u{1F1F7}\u{1F1E8}"),
+            vec!["\u{1F1FA}\u{1F1F8}", "\u{1F1EB}\u{1F1F7}", "\u{1F1E8}"]);
+        assert_eq!(
+            clusters("a\u{1F1FA}\u{1F1F8}\u{0308}\u{1F1EB}"),
+            vec!["a", "\u{1F1FA}\u{1F1F8}\u{0308}", "\u{1F1EB}"]);
+    }
+
+    #[test]
+    fn chars() {
+        let extended = GraphemeClusterMode::Extended;
+        let b = grapheme_boundaries_chars(
+            "e\u{0301}\u{1F1FA}\u{1F1F8}".chars(), extended, gcb, ext_pict);
+        assert_eq!(b, vec![0, 2, 4]);
+        let b = grapheme_boundaries_chars(vec![], extended, gcb, ext_pict);
+        assert!(b.is_empty());
+    }
+
+    // A small excerpt of WordBreakProperty.txt.
+    fn wb(c: char) -> WordBreak {
+        match c {
+            '\r' => WordBreak::CR,
+            '\n' => WordBreak::LF,
+            '\u{2028}' => WordBreak::Newline,
+            '\u{0300}'..='\u{036F}' => WordBreak::Extend,
+            '\u{200D}' => WordBreak::ZWJ,
+            '\u{00AD}' => WordBreak::Format,
+            '\u{1F1E6}'..='\u{1F1FF}' => WordBreak::RegionalIndicator,
+            '\u{30A0}'..='\u{30FF}' => WordBreak::Katakana,
+            '\u{05D0}'..='\u{05EA}' => WordBreak::HebrewLetter,
+            'a'..='z' | 'A'..='Z' => WordBreak::ALetter,
+            '\'' => WordBreak::SingleQuote,
+            '"' => WordBreak::DoubleQuote,
+            '.' => WordBreak::MidNumLet,
+            ':' => WordBreak::MidLetter,
+            ',' | ';' => WordBreak::MidNum,
+            '0'..='9' => WordBreak::Numeric,
+            '_' => WordBreak::ExtendNumLet,
+            ' ' => WordBreak::WSegSpace,
+            _ => WordBreak::Other,
+        }
+    }
+
+    fn words(text: &str) -> Vec<&str> {
+        let b = word_boundaries(text, wb, ext_pict);
+        b.windows(2).map(|w| &text[w[0]..w[1]]).collect()
+    }
+
+    #[test]
+    fn words_basic() {
+        assert_eq!(
+            words("The quick (\"brown\") fox"),
+            vec!["The", " ", "quick", " ", "(", "\"", "brown", "\"", ")",
+                 " ", "fox"]);
+        assert_eq!(words("a   b"), vec!["a", "   ", "b"]);
+        assert_eq!(words("a\r\n\r\nb"), vec!["a", "\r\n", "\r\n", "b"]);
+        assert_eq!(words("a\u{2028}b"), vec!["a", "\u{2028}", "b"]);
+        assert!(word_boundaries("", wb, ext_pict).is_empty());
+    }
+
+    #[test]
+    fn words_mid() {
+        assert_eq!(words("can't stop"), vec!["can't", " ", "stop"]);
+        assert_eq!(words("e.g. a:b"), vec!["e.g", ".", " ", "a:b"]);
+        assert_eq!(words("3.14,159;2"), vec!["3.14,159;2"]);
+        assert_eq!(words("3,a"), vec!["3", ",", "a"]);
+        assert_eq!(words("a1_b2"), vec!["a1_b2"]);
+        assert_eq!(words("\u{30A2}\u{30A2}_a"), vec!["\u{30A2}\u{30A2}_a"]);
+        assert_eq!(
+            words("\u{05D0}\"\u{05D1}\u{05D0}'"),
+            vec!["\u{05D0}\"\u{05D1}\u{05D0}'"]);
+        assert_eq!(words("a\"b"), vec!["a", "\"", "b"]);
+    }
+
+    #[test]
+    fn words_ignored() {
+        // Marks and format characters are ignored by the rules after WB4.
+        assert_eq!(
+            words("a\u{0301}\u{00AD}b'\u{0301}c."),
+            vec!["a\u{0301}\u{00AD}b'\u{0301}c", "."]);
+        // ... except at the start of text and after newlines.
+        assert_eq!(
+            words("\u{0301}a\n\u{0301}a"),
+            vec!["\u{0301}", "a", "\n", "\u{0301}", "a"]);
+        // A ZWJ joins a pictograph to anything.
+        assert_eq!(
+            words("a\u{200D}\u{1F468}\u{1F469}"),
+            vec!["a\u{200D}\u{1F468}", "\u{1F469}"]);
+    }
+
+    #[test]
+    fn words_regional_indicators() {
+        assert_eq!(
+            words("\u{1F1FA}\u{1F1F8}\u{0301}\u{1F1EB}\u{1F1F7}\u{1F1E8}"),
+            vec!["\u{1F1FA}\u{1F1F8}\u{0301}", "\u{1F1EB}\u{1F1F7}",
+                 "\u{1F1E8}"]);
+    }
+
+    #[test]
+    fn words_chars() {
+        let b = word_boundaries_chars("ab c".chars(), wb, ext_pict);
+        assert_eq!(b, vec![0, 2, 3, 4]);
+    }
+
+    // A small excerpt of SentenceBreakProperty.txt.
+    fn sb(c: char) -> SentenceBreak {
+        match c {
+            '\r' => SentenceBreak::CR,
+            '\n' => SentenceBreak::LF,
+            '\u{0300}'..='\u{036F}' => SentenceBreak::Extend,
+            '\u{2029}' => SentenceBreak::Sep,
+            '\u{00AD}' => SentenceBreak::Format,
+            ' ' | '\t' => SentenceBreak::Sp,
+            'a'..='z' => SentenceBreak::Lower,
+            'A'..='Z' => SentenceBreak::Upper,
+            '\u{05D0}'..='\u{05EA}' => SentenceBreak::OLetter,
+            '0'..='9' => SentenceBreak::Numeric,
+            '.' => SentenceBreak::ATerm,
+            ',' | ':' | ';' => SentenceBreak::SContinue,
+            '!' | '?' => SentenceBreak::STerm,
+            '"' | '\'' | '(' | ')' => SentenceBreak::Close,
+            _ => SentenceBreak::Other,
+        }
+    }
+
+    fn sentences(text: &str) -> Vec<&str> {
+        let b = sentence_boundaries(text, sb);
+        b.windows(2).map(|w| &text[w[0]..w[1]]).collect()
+    }
+
+    #[test]
+    fn sentences_basic() {
+        assert_eq!(
+            sentences("Hello there. How are you? Fine!!  OK"),
+            vec!["Hello there. ", "How are you? ", "Fine!!  ", "OK"]);
+        assert_eq!(
+            sentences("He said \"Stop.\" (Then left.) Done."),
+            vec!["He said \"Stop.\" ", "(Then left.) ", "Done."]);
+        assert_eq!(
+            sentences("Go!\r\nNow\u{2029}\u{2029}ok"),
+            vec!["Go!\r\n", "Now\u{2029}", "\u{2029}", "ok"]);
+        assert!(sentence_boundaries("", sb).is_empty());
+    }
+
+    #[test]
+    fn sentences_aterm() {
+        // SB6, SB7
+        assert_eq!(sentences("Pi is 3.14 ok."), vec!["Pi is 3.14 ok."]);
+        assert_eq!(sentences("The U.S.A. Army"), vec!["The U.S.A. ", "Army"]);
+        // SB8: a full stop followed by a lowercase word doesn't end a
+        // sentence.
+        assert_eq!(sentences("Mr. smith. Mr. Smith"), vec![
+            "Mr. smith. ", "Mr. ", "Smith",
+        ]);
+        assert_eq!(sentences("etc.) (and so on"), vec!["etc.) (and so on"]);
+        assert_eq!(
+            sentences("etc. \u{05D0} and"),
+            vec!["etc. ", "\u{05D0} and"]);
+        // SB8a
+        assert_eq!(sentences("Hi!, he said"), vec!["Hi!, he said"]);
+        assert_eq!(sentences("What?!  No"), vec!["What?!  ", "No"]);
+    }
+
+    #[test]
+    fn sentences_ignored() {
+        assert_eq!(
+            sentences("Yes.\u{0301}\u{00AD}) No"),
+            vec!["Yes.\u{0301}\u{00AD}) ", "No"]);
+        let b = sentence_boundaries_chars("A. B".chars(), sb);
+        assert_eq!(b, vec![0, 3, 4]);
+    }
+}
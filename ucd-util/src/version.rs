@@ -0,0 +1,153 @@
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use ranges::find_in_range_values;
+
+/// A version of the Unicode standard, e.g., `10.0.0`.
+///
+/// This is also the type of the values of the `Age` property, whose update
+/// version is always `0`. Versions are ordered by their components, so that
+/// older versions compare less than newer versions.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct UnicodeVersion(pub u8, pub u8, pub u8);
+
+impl UnicodeVersion {
+    /// Return the major version.
+    pub fn major(&self) -> u8 {
+        self.0
+    }
+
+    /// Return the minor version.
+    pub fn minor(&self) -> u8 {
+        self.1
+    }
+
+    /// Return the update version.
+    pub fn update(&self) -> u8 {
+        self.2
+    }
+}
+
+impl FromStr for UnicodeVersion {
+    type Err = ParseUnicodeVersionError;
+
+    /// Parse a version from one of the forms used by the UCD.
+    ///
+    /// Full versions such as `10.0.0`, `Age` values such as `10.0` and
+    /// `Age` value aliases such as `V10_0` are all accepted. The update
+    /// version is `0` when it is omitted.
+    fn from_str(s: &str) -> Result<UnicodeVersion, ParseUnicodeVersionError> {
+        let err = || ParseUnicodeVersionError { version: s.to_string() };
+        let (parts, max): (Vec<&str>, usize) = if s.starts_with('V') {
+            (s[1..].split('_').collect(), 2)
+        } else {
+            (s.split('.').collect(), 3)
+        };
+        if parts.len() < 2 || parts.len() > max {
+            return Err(err());
+        }
+        let mut nums = [0; 3];
+        for (i, part) in parts.iter().enumerate() {
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(err());
+            }
+            nums[i] = part.parse().map_err(|_| err())?;
+        }
+        Ok(UnicodeVersion(nums[0], nums[1], nums[2]))
+    }
+}
+
+impl fmt::Display for UnicodeVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.0, self.1, self.2)
+    }
+}
+
+/// An error that occurs when parsing a `UnicodeVersion`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseUnicodeVersionError {
+    version: String,
+}
+
+impl error::Error for ParseUnicodeVersionError {
+    fn description(&self) -> &str {
+        "invalid Unicode version"
+    }
+}
+
+impl fmt::Display for ParseUnicodeVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid Unicode version: '{}'", self.version)
+    }
+}
+
+/// Returns true if and only if the given codepoint was assigned in or
+/// before the given version of Unicode.
+///
+/// `ages` should be the ranges of codepoints and their `Age` values, as
+/// listed in `DerivedAge.txt`. The ranges must be sorted and
+/// non-overlapping. Codepoints missing from this table are unassigned.
+pub fn assigned_in(
+    cp: u32,
+    ages: &[(u32, u32, UnicodeVersion)],
+    version: UnicodeVersion,
+) -> bool {
+    find_in_range_values(ages, cp).map_or(false, |age| age <= version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UnicodeVersion, assigned_in};
+
+    fn parse(s: &str) -> Option<UnicodeVersion> {
+        s.parse().ok()
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(parse("10.0.0"), Some(UnicodeVersion(10, 0, 0)));
+        assert_eq!(parse("6.3.1"), Some(UnicodeVersion(6, 3, 1)));
+        assert_eq!(parse("10.0"), Some(UnicodeVersion(10, 0, 0)));
+        assert_eq!(parse("V10_0"), Some(UnicodeVersion(10, 0, 0)));
+        assert_eq!(parse("V1_1"), Some(UnicodeVersion(1, 1, 0)));
+
+        assert_eq!(parse("10"), None);
+        assert_eq!(parse("10.0.0.0"), None);
+        assert_eq!(parse("10.x"), None);
+        assert_eq!(parse("10..0"), None);
+        assert_eq!(parse("+10.0"), None);
+        assert_eq!(parse("V10.0"), None);
+        assert_eq!(parse("V10_0_0"), None);
+        assert_eq!(parse("256.0"), None);
+        assert_eq!(parse("Unassigned"), None);
+        assert_eq!(parse(""), None);
+    }
+
+    #[test]
+    fn display_and_order() {
+        let v = UnicodeVersion(10, 0, 0);
+        assert_eq!(v.to_string(), "10.0.0");
+        assert_eq!(parse(&v.to_string()), Some(v));
+        assert!(UnicodeVersion(6, 3, 0) < v);
+        assert!(UnicodeVersion(9, 0, 0) < UnicodeVersion(10, 0, 0));
+        assert!(UnicodeVersion(10, 0, 0) < UnicodeVersion(10, 0, 1));
+        assert_eq!((v.major(), v.minor(), v.update()), (10, 0, 0));
+    }
+
+    #[test]
+    fn assigned() {
+        // A small excerpt of DerivedAge.txt.
+        let ages = &[
+            (0x0041, 0x005A, UnicodeVersion(1, 1, 0)),
+            (0x20AC, 0x20AC, UnicodeVersion(2, 1, 0)),
+            (0x1F600, 0x1F600, UnicodeVersion(6, 1, 0)),
+        ];
+        let v6 = UnicodeVersion(6, 0, 0);
+        assert!(assigned_in(0x41, ages, v6));
+        assert!(assigned_in(0x20AC, ages, v6));
+        assert!(!assigned_in(0x1F600, ages, v6));
+        assert!(assigned_in(0x1F600, ages, UnicodeVersion(6, 1, 0)));
+        assert!(!assigned_in(0x40, ages, v6));
+    }
+}